@@ -0,0 +1,31 @@
+use solidity_verifiers::utils::{get_function_selector_for_nova_cyclefold_verifier, NovaProofJson};
+use std::fs;
+
+use folding_schemes::folding::nova::decider_eth::prepare_calldata;
+
+use crate::prove::read_proof;
+use crate::settings::{CalldataFormat, Cli};
+
+pub fn calldata(cli: Cli) {
+    let (proof, ivc_output) = read_proof(&cli);
+    let z_len = ivc_output.z_0.len();
+    let function_selector = get_function_selector_for_nova_cyclefold_verifier(z_len * 2 + 1);
+    let calldata = prepare_calldata(
+        function_selector,
+        ivc_output.i,
+        ivc_output.z_0,
+        ivc_output.z_i,
+        &ivc_output.U_i,
+        &ivc_output.u_i,
+        proof,
+    )
+    .unwrap();
+
+    let content = match cli.calldata_format {
+        CalldataFormat::Hex => "0x".to_string() + hex::encode(&calldata).as_str(),
+        CalldataFormat::Json => {
+            serde_json::to_string_pretty(&NovaProofJson::from_calldata(&calldata, z_len)).unwrap()
+        }
+    };
+    fs::write(&cli.out, content).unwrap();
+}
@@ -9,18 +9,28 @@ use ark_groth16::{Groth16, ProvingKey, VerifyingKey as G16VerifierKey};
 use ark_grumpkin::{constraints::GVar as GVar2, Projective as G2};
 use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
 use ark_serialize::CanonicalSerialize;
-use ark_std::{rand::rngs::OsRng, Zero};
-use std::{fs, time::Instant};
+use ark_std::{
+    rand::{rngs::OsRng, SeedableRng},
+    Zero,
+};
+use rand_chacha::ChaCha20Rng;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use solidity_verifiers::{
+    get_decider_template_for_cyclefold_decider, NovaCycleFoldVerifierKey, ProtocolVerifierKey,
+};
+use std::{fs, path::Path, time::Instant};
 
 use folding_schemes::{
     commitment::{
         kzg::{ProverKey as KZGProverKey, KZG},
         pedersen::Pedersen,
+        ptau::load_ptau_file,
         CommitmentScheme,
     },
     folding::nova::{
-        decider_eth::Decider as DeciderEth, decider_eth_circuit::DeciderEthCircuit, get_r1cs, Nova,
-        ProverParams, VerifierParams,
+        cyclefold::CF_PEDERSEN_LABEL, decider_eth::Decider as DeciderEth,
+        decider_eth_circuit::DeciderEthCircuit, get_r1cs, Nova, ProverParams, VerifierParams,
     },
     frontend::{circom::CircomFCircuit, FCircuit},
     transcript::poseidon::poseidon_canonical_config,
@@ -31,35 +41,112 @@ use crate::settings::{CircomConfig, Cli};
 
 pub fn gen_params(cli: Cli) {
     let config_path = cli.circom_config.expect("Circom config file is required");
-    let config: CircomConfig =
-        serde_json::from_reader(fs::File::open(&config_path).expect(&format!(
-            "file does not exist at {}",
-            config_path.clone().to_str().unwrap()
-        )))
-        .expect("Failed to parse config file");
+    let config = CircomConfig::from_file(&config_path);
     let f_circuit_params = (
         config.r1cs_path,
         config.wasm_path,
         config.state_len,
         config.external_inputs_len,
     );
-    let (fs_prover_params, kzg_vk, g16_pk, g16_vk) =
+    let (fs_prover_params, fs_verifier_params, kzg_vk, g16_pk, g16_vk) =
         init_ivc_and_decider_params::<CircomFCircuit<Fr>>(
             CircomFCircuit::new(f_circuit_params).unwrap(),
+            cli.ptau.as_deref(),
+            cli.seed,
         );
-    let cs_params_bytes = data_to_bytes(fs_prover_params.cs_params);
-    let cf_cs_params_bytes = data_to_bytes(fs_prover_params.cf_cs_params);
-    let g16_pk = data_to_bytes(g16_pk);
+    let pp_hash = fs_verifier_params.pp_hash.to_string();
+    let nova_cyclefold_vk = NovaCycleFoldVerifierKey::from((g16_vk, kzg_vk, config.state_len));
+    let mut nova_cyclefold_vk_bytes = vec![];
+    nova_cyclefold_vk
+        .serialize_protocol_verifier_key(&mut nova_cyclefold_vk_bytes)
+        .unwrap();
+    let decider_solidity_code = get_decider_template_for_cyclefold_decider(nova_cyclefold_vk);
+
+    let artifacts = [
+        (
+            "cs_params",
+            "cs_params.bin",
+            data_to_bytes(fs_prover_params.cs_params),
+        ),
+        (
+            "cf_cs_params",
+            "cf_cs_params.bin",
+            data_to_bytes(fs_prover_params.cf_cs_params),
+        ),
+        ("g16_pk", "g16_pk.bin", data_to_bytes(g16_pk)),
+        (
+            "verifier_params",
+            "verifier_params.bin",
+            data_to_bytes(fs_verifier_params),
+        ),
+        (
+            "nova_cyclefold_vk",
+            "nova_cyclefold_vk.bin",
+            nova_cyclefold_vk_bytes,
+        ),
+        (
+            "nova_cyclefold_verifier",
+            "nova_cyclefold_verifier.sol",
+            decider_solidity_code.into_bytes(),
+        ),
+    ];
     let out = cli.out;
-    // write cs_params_bytes to out/cs_params.bin
-    fs::write(out.join("cs_params.bin"), cs_params_bytes).unwrap();
-    // write cf_cs_params_bytes to out/cf_cs_params.bin
-    fs::write(out.join("cf_cs_params.bin"), cf_cs_params_bytes).unwrap();
-    // write g16_pk to out/g16_pk.bin
-    fs::write(out.join("g16_pk.bin"), g16_pk).unwrap();
+    fs::create_dir_all(&out).unwrap();
+    let manifest = Manifest {
+        circom_config: config_path.display().to_string(),
+        state_len: config.state_len,
+        external_inputs_len: config.external_inputs_len,
+        ptau: cli.ptau.map(|ptau| ptau.display().to_string()),
+        seed: cli.seed,
+        cf_pedersen_label: String::from_utf8_lossy(CF_PEDERSEN_LABEL).to_string(),
+        pp_hash,
+        artifacts: artifacts
+            .into_iter()
+            .map(|(name, file, bytes)| {
+                // write each artifact to out/<file>
+                fs::write(out.join(file), &bytes).unwrap();
+                Artifact {
+                    name: name.to_string(),
+                    file: file.to_string(),
+                    size: bytes.len(),
+                    sha256: hex::encode(Sha256::digest(&bytes)),
+                }
+            })
+            .collect(),
+    };
+    // write the manifest describing the artifacts to out/manifest.json
+    fs::write(
+        out.join("manifest.json"),
+        serde_json::to_string_pretty(&manifest).unwrap(),
+    )
+    .unwrap();
+}
+
+/// Describes the artifacts generated by `gen_params`, so that they can be checked against the
+/// inputs they were generated from.
+#[derive(Debug, Serialize)]
+struct Manifest {
+    circom_config: String,
+    state_len: usize,
+    external_inputs_len: usize,
+    ptau: Option<String>,
+    /// seed of the randomness used for the Groth16 setup, if any (insecure, test only)
+    seed: Option<u64>,
+    cf_pedersen_label: String,
+    /// digest of the Nova public parameters, see `ProverParams::pp_hash`
+    pp_hash: String,
+    artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Serialize)]
+struct Artifact {
+    name: String,
+    file: String,
+    size: usize,
+    sha256: String,
 }
 
-fn data_to_bytes<T: CanonicalSerialize>(data: T) -> Vec<u8> {
+pub(crate) fn data_to_bytes<T: CanonicalSerialize>(data: T) -> Vec<u8> {
     let mut bytes = vec![];
     data.serialize_compressed(&mut bytes).unwrap();
     bytes
@@ -67,16 +154,19 @@ fn data_to_bytes<T: CanonicalSerialize>(data: T) -> Vec<u8> {
 
 // This method computes the Nova's Prover & Verifier parameters for the example.
 // Warning: this method is only for testing purposes. For a real world use case those parameters
-// should be generated carefully (both the PoseidonConfig and the PedersenParams).
+// should be generated carefully (both the PoseidonConfig and the KZG params, which unless `ptau` is
+// given are generated from the `rng`). The CycleFold Pedersen params are derived from
+// `CF_PEDERSEN_LABEL`.
 #[allow(clippy::type_complexity)]
 fn init_nova_ivc_params<FC: FCircuit<Fr>>(
     F_circuit: FC,
+    ptau: Option<&Path>,
+    rng: &mut ChaCha20Rng,
 ) -> (
     ProverParams<G1, G2, KZG<'static, Bn254>, Pedersen<G2>>,
     VerifierParams<G1, G2>,
     KZGVerifierKey<Bn254>,
 ) {
-    let mut rng = OsRng;
     let poseidon_config = poseidon_canonical_config::<Fr>();
 
     // get the CM & CF_CM len
@@ -85,36 +175,61 @@ fn init_nova_ivc_params<FC: FCircuit<Fr>>(
     let cf_cs_len = cf_r1cs.A.n_rows;
 
     // let (pedersen_params, _) = Pedersen::<G1>::setup(&mut rng, cf_len).unwrap();
-    let (kzg_pk, kzg_vk): (KZGProverKey<G1>, KZGVerifierKey<Bn254>) =
-        KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
-    let (cf_pedersen_params, _) = Pedersen::<G2>::setup(&mut rng, cf_cs_len).unwrap();
+    let (kzg_pk, kzg_vk): (KZGProverKey<G1>, KZGVerifierKey<Bn254>) = match ptau {
+        Some(ptau) => load_ptau_file::<Bn254>(ptau, cs_len).unwrap(),
+        None => KZG::<Bn254>::setup(rng, cs_len).unwrap(),
+    };
+    let (cf_pedersen_params, _) =
+        Pedersen::<G2>::setup_from_label(CF_PEDERSEN_LABEL, cf_cs_len).unwrap();
 
     let fs_prover_params = ProverParams::<G1, G2, KZG<Bn254>, Pedersen<G2>> {
         poseidon_config: poseidon_config.clone(),
         cs_params: kzg_pk.clone(),
         cf_cs_params: cf_pedersen_params,
     };
+    let pp_hash = fs_prover_params.pp_hash(&r1cs, &cf_r1cs).unwrap();
     let fs_verifier_params = VerifierParams::<G1, G2> {
         poseidon_config: poseidon_config.clone(),
         r1cs,
         cf_r1cs,
+        pp_hash,
     };
     (fs_prover_params, fs_verifier_params, kzg_vk)
 }
 
-/// Initializes Nova parameters and DeciderEth parameters. Only for test purposes.
+/// Initializes Nova parameters and DeciderEth parameters. The randomness is sampled from the given
+/// `seed` if any, so that the parameters can be regenerated. Only for test purposes: anyone knowing
+/// the seed can recompute the Groth16 toxic waste and forge proofs. The seed is only accepted
+/// together with a `ptau`, so that the KZG params are never derived from it.
 #[allow(clippy::type_complexity)]
 fn init_ivc_and_decider_params<FC: FCircuit<Fr>>(
     f_circuit: FC,
+    ptau: Option<&Path>,
+    seed: Option<u64>,
 ) -> (
     ProverParams<G1, G2, KZG<'static, Bn254>, Pedersen<G2>>,
+    VerifierParams<G1, G2>,
     KZGVerifierKey<Bn254>,
     ProvingKey<Bn254>,
     G16VerifierKey<Bn254>,
 ) {
-    let mut rng = OsRng;
+    let mut rng = match seed {
+        Some(seed) => {
+            if ptau.is_none() {
+                panic!("--seed requires --ptau, the KZG params must not be derived from a seed");
+            }
+            eprintln!(
+                "WARNING: INSECURE, TEST ONLY. The Groth16 params are generated from the seed {}, \
+                 anyone knowing it can forge Decider proofs. Do not deploy these params.",
+                seed
+            );
+            ChaCha20Rng::seed_from_u64(seed)
+        }
+        None => ChaCha20Rng::from_rng(OsRng).unwrap(),
+    };
     let start = Instant::now();
-    let (fs_prover_params, _, kzg_vk) = init_nova_ivc_params::<FC>(f_circuit.clone());
+    let (fs_prover_params, fs_verifier_params, kzg_vk) =
+        init_nova_ivc_params::<FC>(f_circuit.clone(), ptau, &mut rng);
     println!("generated Nova folding params: {:?}", start.elapsed());
 
     pub type NOVA<FC> = Nova<G1, GVar, G2, GVar2, FC, KZG<'static, Bn254>, Pedersen<G2>>;
@@ -133,7 +248,7 @@ fn init_ivc_and_decider_params<FC: FCircuit<Fr>>(
         "generated G16 (Decider circuit) params: {:?}",
         start.elapsed()
     );
-    (fs_prover_params, kzg_vk, g16_pk, g16_vk)
+    (fs_prover_params, fs_verifier_params, kzg_vk, g16_pk, g16_vk)
 }
 
 fn main() {}
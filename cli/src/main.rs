@@ -9,10 +9,16 @@ use std::path::Path;
 #[cfg(not(target_arch = "wasm32"))]
 use std::{fs, io};
 
+#[cfg(not(target_arch = "wasm32"))]
+mod calldata;
 #[cfg(not(target_arch = "wasm32"))]
 mod gen_params;
 #[cfg(not(target_arch = "wasm32"))]
+mod prove;
+#[cfg(not(target_arch = "wasm32"))]
 mod settings;
+#[cfg(not(target_arch = "wasm32"))]
+mod verify;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn create_or_open_then_write<T: AsRef<[u8]>>(path: &Path, content: &T) -> Result<(), io::Error> {
@@ -33,6 +39,9 @@ fn main() {
     match cli.command {
         settings::Subcommand::GenVerifier => gen_solidity(cli),
         settings::Subcommand::GenParams => gen_params::gen_params(cli),
+        settings::Subcommand::Prove => prove::prove(cli),
+        settings::Subcommand::Verify => verify::verify(cli),
+        settings::Subcommand::Calldata => calldata::calldata(cli),
     }
 }
 
@@ -0,0 +1,209 @@
+#![allow(non_snake_case)]
+#![allow(non_camel_case_types)]
+#![allow(clippy::upper_case_acronyms)]
+use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as G1};
+use ark_groth16::{Groth16, ProvingKey};
+use ark_grumpkin::{constraints::GVar as GVar2, Projective as G2};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::rngs::OsRng;
+use serde_json::Value;
+use std::{fs, path::Path, str::FromStr, time::Instant};
+
+use folding_schemes::{
+    commitment::{
+        kzg::{ProverKey as KZGProverKey, KZG},
+        pedersen::{Params as PedersenParams, Pedersen},
+    },
+    folding::nova::{
+        decider_eth::{prepare_calldata, Decider as DeciderEth, Proof},
+        CommittedInstance, Nova, ProverParams,
+    },
+    frontend::circom::CircomFCircuit,
+    transcript::poseidon::poseidon_canonical_config,
+    Decider, FoldingScheme,
+};
+use solidity_verifiers::utils::{
+    get_formatted_calldata, get_function_selector_for_nova_cyclefold_verifier,
+};
+
+use crate::gen_params::data_to_bytes;
+use crate::settings::{CircomConfig, Cli};
+
+pub(crate) type NOVA =
+    Nova<G1, GVar, G2, GVar2, CircomFCircuit<Fr>, KZG<'static, Bn254>, Pedersen<G2>>;
+pub(crate) type DECIDER = DeciderEth<
+    G1,
+    GVar,
+    G2,
+    GVar2,
+    CircomFCircuit<Fr>,
+    KZG<'static, Bn254>,
+    Pedersen<G2>,
+    Groth16<Bn254>,
+    NOVA,
+>;
+
+pub(crate) type DECIDER_PROOF = Proof<G1, KZG<'static, Bn254>, Groth16<Bn254>>;
+
+/// The values of the last state of the IVC that, together with the Decider proof, are needed to
+/// verify it.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub(crate) struct IVCOutput {
+    pub i: Fr,
+    pub z_0: Vec<Fr>,
+    pub z_i: Vec<Fr>,
+    pub U_i: CommittedInstance<G1>,
+    pub u_i: CommittedInstance<G1>,
+}
+
+pub fn prove(cli: Cli) {
+    let config_path = cli.circom_config.expect("Circom config file is required");
+    let params_path = cli.params.expect("params directory is required");
+    let config = CircomConfig::from_file(&config_path);
+
+    let z_0 = read_field_elements(&cli.initial_state.expect("initial state file is required"))
+        .into_iter()
+        .flatten()
+        .collect::<Vec<Fr>>();
+    assert_eq!(
+        z_0.len(),
+        config.state_len,
+        "initial state length does not match the config's state_len"
+    );
+    let external_inputs = read_field_elements(
+        &cli.external_inputs
+            .expect("external inputs file is required"),
+    );
+    for (i, external_inputs_at_step) in external_inputs.iter().enumerate() {
+        assert_eq!(
+            external_inputs_at_step.len(),
+            config.external_inputs_len,
+            "external inputs length of step {} does not match the config's external_inputs_len",
+            i
+        );
+    }
+
+    let f_circuit = CircomFCircuit::<Fr>::new((
+        config.r1cs_path,
+        config.wasm_path,
+        config.state_len,
+        config.external_inputs_len,
+    ))
+    .unwrap();
+
+    let cs_params: KZGProverKey<'static, G1> = read_params(&params_path.join("cs_params.bin"));
+    let cf_cs_params: PedersenParams<G2> = read_params(&params_path.join("cf_cs_params.bin"));
+    let g16_pk: ProvingKey<Bn254> = read_params(&params_path.join("g16_pk.bin"));
+    let fs_prover_params = ProverParams::<G1, G2, KZG<Bn254>, Pedersen<G2>> {
+        poseidon_config: poseidon_canonical_config::<Fr>(),
+        cs_params,
+        cf_cs_params,
+    };
+
+    let mut nova = NOVA::init(&fs_prover_params, f_circuit, z_0).unwrap();
+    for (i, external_inputs_at_step) in external_inputs.into_iter().enumerate() {
+        let start = Instant::now();
+        nova.prove_step(OsRng, external_inputs_at_step).unwrap();
+        println!("Nova::prove_step {}: {:?}", i, start.elapsed());
+    }
+
+    let start = Instant::now();
+    let proof = DECIDER::prove((g16_pk, fs_prover_params.cs_params), OsRng, nova.clone()).unwrap();
+    println!("generated Decider proof: {:?}", start.elapsed());
+
+    let ivc_output = IVCOutput {
+        i: nova.i,
+        z_0: nova.z_0,
+        z_i: nova.z_i,
+        U_i: nova.U_i,
+        u_i: nova.u_i,
+    };
+    let function_selector =
+        get_function_selector_for_nova_cyclefold_verifier(ivc_output.z_0.len() * 2 + 1);
+    let calldata = prepare_calldata(
+        function_selector,
+        ivc_output.i,
+        ivc_output.z_0.clone(),
+        ivc_output.z_i.clone(),
+        &ivc_output.U_i,
+        &ivc_output.u_i,
+        proof.clone(),
+    )
+    .unwrap();
+
+    let out = cli.out;
+    fs::create_dir_all(&out).unwrap();
+    // write the Decider proof to out/proof.bin
+    fs::write(out.join("proof.bin"), data_to_bytes(proof)).unwrap();
+    // write the IVC's last state and instances to out/ivc_output.bin
+    fs::write(out.join("ivc_output.bin"), data_to_bytes(ivc_output)).unwrap();
+    // write the calldata to out/solidity-calldata.calldata, and its values (as decimal strings) to
+    // out/solidity-calldata.inputs
+    fs::write(out.join("solidity-calldata.calldata"), &calldata).unwrap();
+    fs::write(
+        out.join("solidity-calldata.inputs"),
+        get_formatted_calldata(calldata).join(",\n"),
+    )
+    .unwrap();
+}
+
+/// Reads the Decider proof and the IVC output written by `prove`.
+pub(crate) fn read_proof(cli: &Cli) -> (DECIDER_PROOF, IVCOutput) {
+    let proof = read_params(cli.proof.as_deref().expect("proof file is required"));
+    let ivc_output = read_params(
+        cli.ivc_output
+            .as_deref()
+            .expect("IVC output file is required"),
+    );
+    (proof, ivc_output)
+}
+
+pub(crate) fn read_params<T: CanonicalDeserialize>(path: &Path) -> T {
+    let bytes =
+        fs::read(path).unwrap_or_else(|_| panic!("file does not exist at {}", path.display()));
+    T::deserialize_compressed(bytes.as_slice())
+        .unwrap_or_else(|_| panic!("Failed to deserialize {}", path.display()))
+}
+
+/// Reads a list of vectors of field elements, given in decimal, from a CSV file (one vector per
+/// line) or from a JSON file (an array of arrays, or a single array for a single vector).
+fn read_field_elements(path: &Path) -> Vec<Vec<Fr>> {
+    let content = fs::read_to_string(path)
+        .unwrap_or_else(|_| panic!("file does not exist at {}", path.display()));
+    if path.extension().is_some_and(|ext| ext == "csv") {
+        return content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.split(',').map(parse_field_element).collect())
+            .collect();
+    }
+
+    let value: Value = serde_json::from_str(&content)
+        .unwrap_or_else(|_| panic!("Failed to parse {}", path.display()));
+    let rows = value.as_array().expect("expected a JSON array");
+    if rows.iter().all(Value::is_array) {
+        rows.iter()
+            .map(|row| {
+                row.as_array()
+                    .unwrap()
+                    .iter()
+                    .map(json_to_field_element)
+                    .collect()
+            })
+            .collect()
+    } else {
+        vec![rows.iter().map(json_to_field_element).collect()]
+    }
+}
+
+fn json_to_field_element(value: &Value) -> Fr {
+    match value {
+        Value::String(s) => parse_field_element(s),
+        Value::Number(n) => parse_field_element(&n.to_string()),
+        _ => panic!("expected a number or a decimal string, found {}", value),
+    }
+}
+
+fn parse_field_element(s: &str) -> Fr {
+    Fr::from_str(s.trim()).unwrap_or_else(|_| panic!("invalid field element: {}", s))
+}
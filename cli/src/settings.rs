@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use solidity_verifiers::{
     Groth16VerifierKey, KZG10VerifierKey, NovaCycleFoldVerifierKey, ProtocolVerifierKey,
 };
-use std::{env, fmt::Display, path::PathBuf};
+use std::{env, fmt::Display, fs, path::Path, path::PathBuf};
 
 fn get_default_out_path() -> PathBuf {
     let mut path = env::current_dir().unwrap();
@@ -16,6 +16,9 @@ fn get_default_out_path() -> PathBuf {
 pub(crate) enum Subcommand {
     GenVerifier,
     GenParams,
+    Prove,
+    Verify,
+    Calldata,
 }
 
 impl Display for Subcommand {
@@ -24,6 +27,14 @@ impl Display for Subcommand {
     }
 }
 
+#[derive(Debug, Copy, Clone, ValueEnum)]
+pub(crate) enum CalldataFormat {
+    /// The raw calldata, hex-encoded.
+    Hex,
+    /// The arguments of the Solidity verifier's function as a JSON object of hex strings.
+    Json,
+}
+
 #[derive(Debug, Copy, Clone, ValueEnum)]
 pub(crate) enum Protocol {
     Groth16,
@@ -67,6 +78,17 @@ pub struct CircomConfig {
     pub external_inputs_len: usize,
 }
 
+impl CircomConfig {
+    /// Reads the Circom config from the given JSON file.
+    pub fn from_file(path: &Path) -> Self {
+        serde_json::from_reader(
+            fs::File::open(path)
+                .unwrap_or_else(|_| panic!("file does not exist at {}", path.display())),
+        )
+        .expect("Failed to parse config file")
+    }
+}
+
 const ABOUT: &str = "A Command-Line Interface (CLI) tool to generate the Solidity smart contracts that verify proofs of Zero Knowledge cryptographic protocols.
 ";
 
@@ -137,4 +159,42 @@ pub(crate) struct Cli {
 
     #[arg(long)]
     pub circom_config: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Sets the path of the snarkjs Powers of Tau (`.ptau`) file from which the KZG parameters are
+    /// loaded by `gen-params`. If not set, the KZG parameters are generated from randomness, which is
+    /// only suitable for testing.
+    pub ptau: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Sets the seed of the randomness used by `gen-params` for the Groth16 setup, so that the same
+    /// parameters can be regenerated. INSECURE, only for testing: anyone knowing the seed can forge
+    /// proofs. Requires `ptau`. If not set, the randomness is sampled from the OS.
+    pub seed: Option<u64>,
+
+    #[arg(long)]
+    /// Sets the directory containing the parameters generated by `gen-params` (`cs_params.bin`,
+    /// `cf_cs_params.bin` and `g16_pk.bin`).
+    pub params: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Sets the path of the JSON or CSV file containing the initial state `z_0` of the IVC.
+    pub initial_state: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Sets the path of the JSON or CSV file containing the external inputs of each step of the
+    /// IVC, one step per row.
+    pub external_inputs: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Sets the path of the Decider proof (`proof.bin`) generated by `prove`.
+    pub proof: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Sets the path of the IVC's last state and instances (`ivc_output.bin`) generated by `prove`.
+    pub ivc_output: Option<PathBuf>,
+
+    /// Selects the format of the calldata generated by `calldata`.
+    #[arg(long, value_enum, default_value_t = CalldataFormat::Hex)]
+    pub calldata_format: CalldataFormat,
 }
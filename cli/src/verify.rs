@@ -0,0 +1,37 @@
+use solidity_verifiers::{NovaCycleFoldVerifierKey, ProtocolVerifierKey};
+use std::{fs, process};
+
+use folding_schemes::Decider;
+
+use crate::prove::{read_proof, DECIDER};
+use crate::settings::Cli;
+
+pub fn verify(cli: Cli) {
+    let protocol_vk_path = cli.protocol_vk.as_deref().expect("protocol_vk is required");
+    let protocol_vk = fs::read(protocol_vk_path)
+        .unwrap_or_else(|_| panic!("file does not exist at {}", protocol_vk_path.display()));
+    let nova_cyclefold_vk =
+        NovaCycleFoldVerifierKey::deserialize_protocol_verifier_key(protocol_vk.as_slice())
+            .expect("Failed to deserialize the NovaCycleFold verifier key");
+    let (proof, ivc_output) = read_proof(&cli);
+    assert_eq!(
+        ivc_output.z_0.len(),
+        nova_cyclefold_vk.z_len(),
+        "state length does not match the verifier key's z_len"
+    );
+
+    let verified = DECIDER::verify(
+        nova_cyclefold_vk.decider_vp(),
+        ivc_output.i,
+        ivc_output.z_0,
+        ivc_output.z_i,
+        &ivc_output.U_i,
+        &ivc_output.u_i,
+        &proof,
+    )
+    .unwrap_or_else(|e| panic!("Decider proof verification failed: {}", e));
+    println!("Decider proof verification: {}", verified);
+    if !verified {
+        process::exit(1);
+    }
+}
@@ -83,20 +83,22 @@ fn main() {
         NOVA,
     >;
 
+    let mut rng = rand::rngs::OsRng;
+
     // initialize the folding scheme engine, in our case we use Nova
     let mut nova = NOVA::init(&fs_prover_params, f_circuit.clone(), z_0).unwrap();
     // run n steps of the folding iteration
     for (i, external_inputs_at_step) in external_inputs.iter().enumerate() {
         let start = Instant::now();
-        nova.prove_step(external_inputs_at_step.clone()).unwrap();
+        nova.prove_step(&mut rng, external_inputs_at_step.clone())
+            .unwrap();
         println!("Nova::prove_step {}: {:?}", i, start.elapsed());
     }
 
-    let rng = rand::rngs::OsRng;
     let start = Instant::now();
     let proof = DECIDERETH_FCircuit::prove(
         (g16_pk, fs_prover_params.cs_params.clone()),
-        rng,
+        &mut rng,
         nova.clone(),
     )
     .unwrap();
@@ -189,6 +189,8 @@ fn main() {
         Pedersen<Projective2>,
     >;
 
+    let mut rng = rand::rngs::OsRng;
+
     println!("Initialize FoldingScheme");
     let mut folding_scheme = NOVA::init(&prover_params, F_circuit, initial_state.clone()).unwrap();
 
@@ -196,7 +198,7 @@ fn main() {
     for (i, external_inputs_at_step) in external_inputs.iter().enumerate() {
         let start = Instant::now();
         folding_scheme
-            .prove_step(external_inputs_at_step.clone())
+            .prove_step(&mut rng, external_inputs_at_step.clone())
             .unwrap();
         println!("Nova::prove_step {}: {:?}", i, start.elapsed());
     }
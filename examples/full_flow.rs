@@ -98,20 +98,21 @@ fn main() {
         NOVA,
     >;
 
+    let mut rng = rand::rngs::OsRng;
+
     // initialize the folding scheme engine, in our case we use Nova
     let mut nova = NOVA::init(&fs_prover_params, f_circuit, z_0).unwrap();
     // run n steps of the folding iteration
     for i in 0..n_steps {
         let start = Instant::now();
-        nova.prove_step(vec![]).unwrap();
+        nova.prove_step(&mut rng, vec![]).unwrap();
         println!("Nova::prove_step {}: {:?}", i, start.elapsed());
     }
 
-    let rng = rand::rngs::OsRng;
     let start = Instant::now();
     let proof = DECIDERETH_FCircuit::prove(
         (g16_pk, fs_prover_params.cs_params.clone()),
-        rng,
+        &mut rng,
         nova.clone(),
     )
     .unwrap();
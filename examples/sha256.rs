@@ -125,13 +125,15 @@ fn main() {
         Pedersen<Projective2>,
     >;
 
+    let mut rng = rand::rngs::OsRng;
+
     println!("Initialize FoldingScheme");
     let mut folding_scheme = NOVA::init(&prover_params, F_circuit, initial_state.clone()).unwrap();
 
     // compute a step of the IVC
     for i in 0..num_steps {
         let start = Instant::now();
-        folding_scheme.prove_step(vec![]).unwrap();
+        folding_scheme.prove_step(&mut rng, vec![]).unwrap();
         println!("Nova::prove_step {}: {:?}", i, start.elapsed());
     }
 
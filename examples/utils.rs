@@ -18,7 +18,8 @@ use folding_schemes::{
         CommitmentScheme,
     },
     folding::nova::{
-        decider_eth_circuit::DeciderEthCircuit, get_r1cs, Nova, ProverParams, VerifierParams,
+        cyclefold::CF_PEDERSEN_LABEL, decider_eth_circuit::DeciderEthCircuit, get_r1cs, Nova,
+        ProverParams, VerifierParams,
     },
     frontend::FCircuit,
     transcript::poseidon::poseidon_canonical_config,
@@ -27,7 +28,7 @@ use folding_schemes::{
 
 // This method computes the Nova's Prover & Verifier parameters for the example.
 // Warning: this method is only for testing purposes. For a real world use case those parameters
-// should be generated carefully (both the PoseidonConfig and the PedersenParams).
+// should be generated carefully (both the PoseidonConfig and the KZG params).
 #[allow(clippy::type_complexity)]
 pub(crate) fn init_nova_ivc_params<FC: FCircuit<Fr>>(
     F_circuit: FC,
@@ -47,17 +48,22 @@ pub(crate) fn init_nova_ivc_params<FC: FCircuit<Fr>>(
     // let (pedersen_params, _) = Pedersen::<G1>::setup(&mut rng, cf_len).unwrap();
     let (kzg_pk, kzg_vk): (KZGProverKey<G1>, KZGVerifierKey<Bn254>) =
         KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
-    let (cf_pedersen_params, _) = Pedersen::<G2>::setup(&mut rng, cf_cs_len).unwrap();
+    // the CycleFold Pedersen generators are derived by hashing to the curve, so they can be
+    // regenerated by anyone from the label
+    let (cf_pedersen_params, _) =
+        Pedersen::<G2>::setup_from_label(CF_PEDERSEN_LABEL, cf_cs_len).unwrap();
 
     let fs_prover_params = ProverParams::<G1, G2, KZG<Bn254>, Pedersen<G2>> {
         poseidon_config: poseidon_config.clone(),
         cs_params: kzg_pk.clone(),
         cf_cs_params: cf_pedersen_params,
     };
+    let pp_hash = fs_prover_params.pp_hash(&r1cs, &cf_r1cs).unwrap();
     let fs_verifier_params = VerifierParams::<G1, G2> {
         poseidon_config: poseidon_config.clone(),
         r1cs,
         cf_r1cs,
+        pp_hash,
     };
     (fs_prover_params, fs_verifier_params, kzg_vk)
 }
@@ -0,0 +1,35 @@
+use ark_bn254::{Fr, G1Projective as Projective};
+use ark_std::{UniformRand, Zero};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use folding_schemes::commitment::{pedersen::Pedersen, CommitmentScheme};
+
+// window size of the precomputed fixed-base tables
+const WINDOW: usize = 16;
+
+fn bench_pedersen_commit(c: &mut Criterion) {
+    let mut rng = ark_std::test_rng();
+    let mut group = c.benchmark_group("Pedersen commit");
+    group.sample_size(10);
+
+    for log_n in [16, 18, 20] {
+        let n = 1 << log_n;
+        let (params, _) = Pedersen::<Projective>::setup(&mut rng, n).unwrap();
+        let mut params_tables = params.clone();
+        params_tables.precompute(WINDOW).unwrap();
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(n)
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("msm", log_n), &v, |b, v| {
+            b.iter(|| Pedersen::<Projective>::commit(&params, v, &Fr::zero()).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("fixed-base tables", log_n), &v, |b, v| {
+            b.iter(|| Pedersen::<Projective>::commit(&params_tables, v, &Fr::zero()).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_pedersen_commit);
+criterion_main!(benches);
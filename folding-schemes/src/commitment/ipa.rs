@@ -64,6 +64,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         let p = PedersenParams::<C> {
             h: C::rand(&mut rng),
             generators,
+            tables: None,
         };
         Ok((p.clone(), p))
     }
@@ -102,6 +103,134 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         if !H && (!blind.is_zero()) {
             return Err(Error::BlindingNotZero);
         }
+
+        transcript.absorb_point(P)?;
+        let x = transcript.get_challenge(); // challenge value at which we evaluate
+        let s = transcript.get_challenge();
+        let U = C::generator().mul(s);
+
+        let b = powers_of(x, a.len());
+        let v = inner_prod(a, &b)?;
+
+        let proof = Self::prove_rounds(params, transcript, a, b, U, rng)?;
+        Ok((
+            proof, v,      // evaluation at challenge, v=p(x)
+            *blind, // blind factor
+        ))
+    }
+
+    fn prove_with_challenge(
+        _params: &Self::ProverParams,
+        _challenge: Self::ProverChallenge,
+        _a: &[C::ScalarField], // vector
+        _blind: &C::ScalarField,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Error> {
+        // not supported because the prover logic computes challenges as it advances on the logic
+        Err(Error::NotSupported("IPA::prove_with_challenge".to_string()))
+    }
+
+    fn verify(
+        params: &Self::VerifierParams,
+        transcript: &mut impl Transcript<C>,
+        P: &C, // commitment
+        proof: &Self::Proof,
+    ) -> Result<(), Error> {
+        let p = &proof.0;
+
+        transcript.absorb_point(P)?;
+        let x = transcript.get_challenge(); // challenge value at which we evaluate
+        let s = transcript.get_challenge();
+        let U = C::generator().mul(s);
+        let u = Self::get_rounds_challenges(transcript, p)?;
+        let challenge = (x, U, u);
+
+        Self::verify_with_challenge(params, challenge, P, proof)
+    }
+
+    fn verify_with_challenge(
+        params: &Self::VerifierParams,
+        challenge: Self::Challenge,
+        P: &C, // commitment
+        proof: &Self::Proof,
+    ) -> Result<(), Error> {
+        let (x, U, u) = challenge;
+        // b = <s, b_vec> = <s, [1, x, x^2, ..., x^d-1]>, computed in log time
+        Self::verify_rounds(params, P, proof, U, &u, |_| s_b_inner(&u, &x))
+    }
+}
+
+impl<C: CurveGroup, const H: bool> IPA<C, H> {
+    /// same as `setup`, but deriving the params from the given domain-separation label instead
+    /// of from randomness, see `PedersenParams::from_label`
+    pub fn setup_from_label(
+        label: &[u8],
+        len: usize,
+    ) -> Result<(PedersenParams<C>, PedersenParams<C>), Error> {
+        let p = PedersenParams::<C>::from_label(label, len);
+        Ok((p.clone(), p))
+    }
+
+    /// prove_inner_product proves that the vector `a` committed in `P` satisfies `<a, b> = v` for
+    /// the given public vector `b`. By setting `b` to the evaluations of `eq(r, x)` over the
+    /// boolean hypercube, the IPA can be used as a multilinear polynomial commitment, where
+    /// `v=a~(r)`. Returns the same tuple as `prove`: (proof, v, r=blinding factor).
+    pub fn prove_inner_product(
+        params: &PedersenParams<C>,
+        transcript: &mut impl Transcript<C>,
+        P: &C,                // commitment
+        a: &[C::ScalarField], // vector
+        b: &[C::ScalarField], // public vector
+        blind: &C::ScalarField,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<(Proof<C>, C::ScalarField, C::ScalarField), Error> {
+        if !a.len().is_power_of_two() {
+            return Err(Error::NotPowerOfTwo("a".to_string(), a.len()));
+        }
+        if !H && (!blind.is_zero()) {
+            return Err(Error::BlindingNotZero);
+        }
+        let v = inner_prod(a, b)?;
+
+        // v is chosen by the prover, so it is absorbed before getting the challenge s
+        transcript.absorb_point(P)?;
+        transcript.absorb(&v);
+        let s = transcript.get_challenge();
+        let U = C::generator().mul(s);
+
+        let proof = Self::prove_rounds(params, transcript, a, b.to_vec(), U, rng)?;
+        Ok((proof, v, *blind))
+    }
+
+    /// verify_inner_product verifies the proof generated by `prove_inner_product` for the given
+    /// commitment `P` and public vector `b`. Notice that the verifier computes `<s, b>` in linear
+    /// time, since `b` does not have the structure of the powers of a challenge.
+    pub fn verify_inner_product(
+        params: &PedersenParams<C>,
+        transcript: &mut impl Transcript<C>,
+        P: &C,                // commitment
+        b: &[C::ScalarField], // public vector
+        proof: &(Proof<C>, C::ScalarField, C::ScalarField),
+    ) -> Result<(), Error> {
+        transcript.absorb_point(P)?;
+        transcript.absorb(&proof.1);
+        let s = transcript.get_challenge();
+        let U = C::generator().mul(s);
+        let u = Self::get_rounds_challenges(transcript, &proof.0)?;
+
+        Self::verify_rounds(params, P, proof, U, &u, |s| inner_prod(s, b))
+    }
+
+    // runs the k rounds of the IPA prover over the vectors a and b, where U is the generator used
+    // to bind the inner product value
+    fn prove_rounds(
+        params: &PedersenParams<C>,
+        transcript: &mut impl Transcript<C>,
+        a: &[C::ScalarField],
+        b: Vec<C::ScalarField>,
+        U: C,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Proof<C>, Error> {
         let d = a.len();
         let k = (f64::from(d as u32).log2()) as usize;
 
@@ -124,14 +253,8 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
             r = vec![];
         }
 
-        transcript.absorb_point(P)?;
-        let x = transcript.get_challenge(); // challenge value at which we evaluate
-        let s = transcript.get_challenge();
-        let U = C::generator().mul(s);
-
         let mut a = a.to_owned();
-        let mut b = powers_of(x, d);
-        let v = inner_prod(&a, &b)?;
+        let mut b = b;
 
         let mut G = params.generators.clone();
 
@@ -192,65 +315,47 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
             return Err(Error::NotExpectedLength(G.len(), 1));
         }
 
-        Ok((
-            Proof {
-                a: a[0],
-                l: l.clone(),
-                r: r.clone(),
-                L,
-                R,
-            },
-            v,      // evaluation at challenge, v=p(x)
-            *blind, // blind factor
-        ))
-    }
-
-    fn prove_with_challenge(
-        _params: &Self::ProverParams,
-        _challenge: Self::ProverChallenge,
-        _a: &[C::ScalarField], // vector
-        _blind: &C::ScalarField,
-        _rng: Option<&mut dyn RngCore>,
-    ) -> Result<Self::Proof, Error> {
-        // not supported because the prover logic computes challenges as it advances on the logic
-        Err(Error::NotSupported("IPA::prove_with_challenge".to_string()))
+        Ok(Proof {
+            a: a[0],
+            l,
+            r,
+            L,
+            R,
+        })
     }
 
-    fn verify(
-        params: &Self::VerifierParams,
+    // absorbs the L, R values of the proof, returning the challenges u of each round
+    fn get_rounds_challenges(
         transcript: &mut impl Transcript<C>,
-        P: &C, // commitment
-        proof: &Self::Proof,
-    ) -> Result<(), Error> {
-        let (p, _r) = (proof.0.clone(), proof.1);
+        p: &Proof<C>,
+    ) -> Result<Vec<C::ScalarField>, Error> {
         let k = p.L.len();
-
-        transcript.absorb_point(P)?;
-        let x = transcript.get_challenge(); // challenge value at which we evaluate
-        let s = transcript.get_challenge();
-        let U = C::generator().mul(s);
+        if p.R.len() != k {
+            return Err(Error::CommitmentVerificationFail);
+        }
         let mut u: Vec<C::ScalarField> = vec![C::ScalarField::zero(); k];
         for i in (0..k).rev() {
             transcript.absorb_point(&p.L[i])?;
             transcript.absorb_point(&p.R[i])?;
             u[i] = transcript.get_challenge();
         }
-        let challenge = (x, U, u);
-
-        Self::verify_with_challenge(params, challenge, P, proof)
+        Ok(u)
     }
 
-    fn verify_with_challenge(
-        params: &Self::VerifierParams,
-        challenge: Self::Challenge,
+    // checks the IPA proof for the given challenges, where `compute_b` returns the value of
+    // `<s, b>` from the vector `s`
+    fn verify_rounds(
+        params: &PedersenParams<C>,
         P: &C, // commitment
-        proof: &Self::Proof,
+        proof: &(Proof<C>, C::ScalarField, C::ScalarField),
+        U: C,
+        u: &[C::ScalarField],
+        compute_b: impl FnOnce(&[C::ScalarField]) -> Result<C::ScalarField, Error>,
     ) -> Result<(), Error> {
         let (p, v, r) = (proof.0.clone(), proof.1, proof.2);
-        let (x, U, u) = challenge;
 
         let k = p.L.len();
-        if p.R.len() != k {
+        if p.R.len() != k || u.len() != k {
             return Err(Error::CommitmentVerificationFail);
         }
         if !H && (!r.is_zero()) {
@@ -263,7 +368,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
             return Err(Error::CommitmentVerificationFail);
         }
 
-        let P = *P + U.mul(v); // where v=p(x)
+        let P = *P + U.mul(v); // where v=<a, b>
 
         let mut q_0 = P;
         let mut r = r;
@@ -277,9 +382,8 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         }
 
         // compute b & G from s
-        let s = build_s(&u, &u_invs, k)?;
-        // b = <s, b_vec> = <s, [1, x, x^2, ..., x^d-1]>
-        let b = s_b_inner(&u, &x)?;
+        let s = build_s(u, &u_invs, k)?;
+        let b = compute_b(&s)?;
         let d: usize = 2_u64.pow(k as u32) as usize;
         if params.generators.len() < d {
             return Err(Error::PedersenParamsLen(params.generators.len(), d));
@@ -566,8 +670,11 @@ mod tests {
     use ark_std::UniformRand;
     use std::ops::Mul;
 
+    use ark_poly::{DenseMultilinearExtension, MultilinearExtension};
+
     use super::*;
     use crate::transcript::poseidon::{poseidon_canonical_config, PoseidonTranscript};
+    use crate::utils::virtual_polynomial::build_eq_x_r_vec;
 
     #[test]
     fn test_ipa() {
@@ -613,6 +720,74 @@ mod tests {
         IPA::<Projective, hiding>::verify(&params, &mut transcript_v, &cm, &proof).unwrap();
     }
 
+    #[test]
+    fn test_ipa_inner_product() {
+        test_ipa_inner_product_opt::<false>();
+        test_ipa_inner_product_opt::<true>();
+    }
+    fn test_ipa_inner_product_opt<const hiding: bool>() {
+        let mut rng = ark_std::test_rng();
+
+        const k: usize = 4;
+        const d: usize = 2_u64.pow(k as u32) as usize;
+
+        let (params, _) = IPA::<Projective, hiding>::setup(&mut rng, d).unwrap();
+
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let mut transcript_p = PoseidonTranscript::<Projective>::new(&poseidon_config);
+        let mut transcript_v = PoseidonTranscript::<Projective>::new(&poseidon_config);
+
+        let a: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(d)
+            .collect();
+        let r_blind: Fr = if hiding {
+            Fr::rand(&mut rng)
+        } else {
+            Fr::zero()
+        };
+        let cm = IPA::<Projective, hiding>::commit(&params, &a, &r_blind).unwrap();
+
+        // use b=eq(r, x), so that <a, b> is the evaluation of the multilinear extension of a at r
+        let r: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(k)
+            .collect();
+        let b = build_eq_x_r_vec(&r).unwrap();
+
+        let proof = IPA::<Projective, hiding>::prove_inner_product(
+            &params,
+            &mut transcript_p,
+            &cm,
+            &a,
+            &b,
+            &r_blind,
+            Some(&mut rng),
+        )
+        .unwrap();
+        let a_mle = DenseMultilinearExtension::from_evaluations_vec(k, a.clone());
+        assert_eq!(proof.1, a_mle.evaluate(&r).unwrap());
+
+        IPA::<Projective, hiding>::verify_inner_product(
+            &params,
+            &mut transcript_v,
+            &cm,
+            &b,
+            &proof,
+        )
+        .unwrap();
+
+        // the proof does not verify for a different b
+        let b_wrong = build_eq_x_r_vec(&[Fr::rand(&mut rng); k]).unwrap();
+        let mut transcript_v = PoseidonTranscript::<Projective>::new(&poseidon_config);
+        assert!(IPA::<Projective, hiding>::verify_inner_product(
+            &params,
+            &mut transcript_v,
+            &cm,
+            &b_wrong,
+            &proof,
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_ipa_gadget() {
         test_ipa_gadget_opt::<false>();
@@ -22,9 +22,9 @@ use core::marker::PhantomData;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::io::Read;
 
-use super::CommitmentScheme;
+use super::{BatchCommitmentScheme, CommitmentScheme};
 use crate::transcript::Transcript;
-use crate::utils::vec::poly_from_vec;
+use crate::utils::{powers_of, vec::poly_from_vec};
 use crate::Error;
 
 /// ProverKey defines a similar struct as in ark_poly_commit::kzg10::Powers, but instead of
@@ -245,6 +245,205 @@ where
     }
 }
 
+/// BatchProof is the proof of the opening of multiple KZG commitments, each one at its own point,
+/// following the version with two group elements of [BDFG20](https://eprint.iacr.org/2020/081.pdf)
+/// (Section 3), where each polynomial is opened at a single point.
+#[derive(Debug, Clone, Default, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BatchProof<C: CurveGroup> {
+    pub evals: Vec<C::ScalarField>,
+    pub W: C,
+    pub W_prime: C,
+}
+
+impl<'a, E, const H: bool> BatchCommitmentScheme<E::G1, H> for KZG<'a, E, H>
+where
+    E: Pairing,
+{
+    type BatchProof = BatchProof<E::G1>;
+
+    fn prove_batch(
+        params: &Self::ProverParams,
+        transcript: &mut impl Transcript<E::G1>,
+        cms: &[E::G1],
+        vs: &[Vec<E::ScalarField>],
+        points: &[E::ScalarField],
+    ) -> Result<Self::BatchProof, Error> {
+        if H {
+            return Err(Error::NotSupportedYet("hiding".to_string()));
+        }
+        check_batch_lengths(cms.len(), vs.len(), points.len())?;
+
+        // compute the witness polynomials q_i(X) = (p_i(X) - p_i(z_i)) / (X - z_i)
+        let (witness_polys, evals): (Vec<Vec<E::ScalarField>>, Vec<E::ScalarField>) = vs
+            .iter()
+            .zip(points)
+            .map(|(v, z)| divide_by_linear(v, *z))
+            .unzip();
+
+        transcript_absorb_batch(transcript, cms, points, &evals)?;
+        let gamma = transcript.get_challenge();
+        let gammas = powers_of(gamma, cms.len());
+
+        // h(X) = Σ γ^i·q_i(X)
+        let mut h = vec![];
+        for (q, gamma_i) in witness_polys.iter().zip(&gammas) {
+            add_scaled(&mut h, q, *gamma_i);
+        }
+        let W = commit_coeffs(params, &h)?;
+        transcript.absorb_point(&W)?;
+        let zeta = transcript.get_challenge();
+
+        // L(X) = Σ γ^i·Z_{T\{z_i}}(ζ)·(p_i(X) - y_i) - Z_T(ζ)·h(X), which vanishes at ζ
+        let (s, z_T) = batch_scalars(&gammas, points, zeta);
+        let mut L = vec![E::ScalarField::zero()];
+        for ((v, y), s_i) in vs.iter().zip(&evals).zip(&s) {
+            add_scaled(&mut L, v, *s_i);
+            L[0] -= *s_i * y;
+        }
+        add_scaled(&mut L, &h, -z_T);
+        let (W_prime_poly, _) = divide_by_linear(&L, zeta);
+        let W_prime = commit_coeffs(params, &W_prime_poly)?;
+
+        Ok(BatchProof { evals, W, W_prime })
+    }
+
+    fn verify_batch(
+        params: &Self::VerifierParams,
+        transcript: &mut impl Transcript<E::G1>,
+        cms: &[E::G1],
+        points: &[E::ScalarField],
+        proof: &Self::BatchProof,
+    ) -> Result<(), Error> {
+        if H {
+            return Err(Error::NotSupportedYet("hiding".to_string()));
+        }
+        check_batch_lengths(cms.len(), proof.evals.len(), points.len())?;
+
+        transcript_absorb_batch(transcript, cms, points, &proof.evals)?;
+        let gamma = transcript.get_challenge();
+        transcript.absorb_point(&proof.W)?;
+        let zeta = transcript.get_challenge();
+
+        // F = Σ γ^i·Z_{T\{z_i}}(ζ)·(C_i - y_i·G) - Z_T(ζ)·W
+        let (s, z_T) = batch_scalars(&powers_of(gamma, cms.len()), points, zeta);
+        let s_y: E::ScalarField = s.iter().zip(&proof.evals).map(|(s_i, y)| *s_i * y).sum();
+        let F =
+            E::G1::msm_unchecked(&E::G1::normalize_batch(cms), &s) - params.g * s_y - proof.W * z_T;
+
+        // F opens to 0 at ζ: e(F + ζ·W', [1]_2) == e(W', [τ]_2)
+        let check = E::multi_pairing(
+            [
+                (F + proof.W_prime * zeta).into_affine(),
+                (-proof.W_prime).into_affine(),
+            ],
+            [params.h, params.beta_h],
+        );
+        if !check.is_zero() {
+            return Err(Error::CommitmentVerificationFail);
+        }
+        Ok(())
+    }
+}
+
+fn check_batch_lengths(n_cms: usize, n_vs: usize, n_points: usize) -> Result<(), Error> {
+    if n_cms == 0 {
+        return Err(Error::Empty);
+    }
+    if n_cms != n_vs {
+        return Err(Error::NotSameLength(
+            "cms.len()".to_string(),
+            n_cms,
+            "vs.len()".to_string(),
+            n_vs,
+        ));
+    }
+    if n_cms != n_points {
+        return Err(Error::NotSameLength(
+            "cms.len()".to_string(),
+            n_cms,
+            "points.len()".to_string(),
+            n_points,
+        ));
+    }
+    Ok(())
+}
+
+fn transcript_absorb_batch<C: CurveGroup>(
+    transcript: &mut impl Transcript<C>,
+    cms: &[C],
+    points: &[C::ScalarField],
+    evals: &[C::ScalarField],
+) -> Result<(), Error> {
+    for cm in cms {
+        transcript.absorb_point(cm)?;
+    }
+    transcript.absorb_vec(points);
+    transcript.absorb_vec(evals);
+    Ok(())
+}
+
+/// returns the scalars γ^i·Z_{T\{z_i}}(ζ) together with Z_T(ζ), where Z_T(X) = Π_j (X - z_j)
+fn batch_scalars<F: PrimeField>(gammas: &[F], points: &[F], zeta: F) -> (Vec<F>, F) {
+    let diffs: Vec<F> = points.iter().map(|z| zeta - z).collect();
+    let s = gammas
+        .iter()
+        .enumerate()
+        .map(|(i, gamma_i)| {
+            let z_T_i: F = diffs
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, d)| *d)
+                .product();
+            *gamma_i * z_T_i
+        })
+        .collect();
+    (s, diffs.iter().product())
+}
+
+/// divides the polynomial p(X) (given by its coefficients) by (X - z), returning the quotient and
+/// the remainder, which is p(z)
+pub(crate) fn divide_by_linear<F: PrimeField>(p: &[F], z: F) -> (Vec<F>, F) {
+    if p.is_empty() {
+        return (vec![], F::zero());
+    }
+    let mut quotient = vec![F::zero(); p.len() - 1];
+    let mut remainder = p[p.len() - 1];
+    for i in (0..p.len() - 1).rev() {
+        quotient[i] = remainder;
+        remainder = p[i] + remainder * z;
+    }
+    (quotient, remainder)
+}
+
+/// a(X) += s·b(X)
+fn add_scaled<F: PrimeField>(a: &mut Vec<F>, b: &[F], s: F) {
+    if a.len() < b.len() {
+        a.resize(b.len(), F::zero());
+    }
+    for (a_i, b_i) in a.iter_mut().zip(b) {
+        *a_i += s * b_i;
+    }
+}
+
+fn commit_coeffs<C: CurveGroup>(
+    params: &ProverKey<C>,
+    coeffs: &[C::ScalarField],
+) -> Result<C, Error> {
+    if coeffs.len() > params.powers_of_g.len() {
+        return Err(Error::PolyCommitError(
+            ark_poly_commit::error::Error::TooManyCoefficients {
+                num_coefficients: coeffs.len(),
+                num_powers: params.powers_of_g.len(),
+            },
+        ));
+    }
+    Ok(C::msm_unchecked(
+        &params.powers_of_g[..coeffs.len()],
+        coeffs,
+    ))
+}
+
 fn check_degree_is_too_large(
     degree: usize,
     num_powers: usize,
@@ -283,7 +482,10 @@ mod tests {
     use ark_std::{test_rng, UniformRand};
 
     use super::*;
-    use crate::transcript::poseidon::{poseidon_canonical_config, PoseidonTranscript};
+    use crate::transcript::{
+        keccak::KeccakTranscript,
+        poseidon::{poseidon_canonical_config, PoseidonTranscript},
+    };
 
     #[test]
     fn test_kzg_commitment_scheme() {
@@ -304,4 +506,70 @@ mod tests {
         // verify the proof:
         KZG::<Bn254>::verify(&vk, transcript_v, &cm, &proof).unwrap();
     }
+
+    // the KZG challenge can also be computed with the Keccak transcript, so that it can be
+    // recomputed by an EVM verifier
+    #[test]
+    fn test_kzg_commitment_scheme_keccak_transcript() {
+        let mut rng = &mut test_rng();
+        let transcript_p = &mut KeccakTranscript::<G1>::new(&());
+        let transcript_v = &mut KeccakTranscript::<G1>::new(&());
+
+        let n = 10;
+        let (pk, vk): (ProverKey<G1>, VerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, n).unwrap();
+
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng)).take(n).collect();
+        let cm = KZG::<Bn254>::commit(&pk, &v, &Fr::zero()).unwrap();
+
+        let proof = KZG::<Bn254>::prove(&pk, transcript_p, &cm, &v, &Fr::zero(), None).unwrap();
+        KZG::<Bn254>::verify(&vk, transcript_v, &cm, &proof).unwrap();
+    }
+
+    #[test]
+    fn test_kzg_batch_opening() {
+        let mut rng = &mut test_rng();
+
+        let n = 16;
+        let (pk, vk): (ProverKey<G1>, VerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, n).unwrap();
+
+        // vectors of different lengths, each one opened at its own point
+        let vs: Vec<Vec<Fr>> = [n, n - 3, 1]
+            .iter()
+            .map(|len| {
+                std::iter::repeat_with(|| Fr::rand(rng))
+                    .take(*len)
+                    .collect()
+            })
+            .collect();
+        let points: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng)).take(3).collect();
+        let cms: Vec<G1> = vs
+            .iter()
+            .map(|v| KZG::<Bn254>::commit(&pk, v, &Fr::zero()).unwrap())
+            .collect();
+
+        let transcript_p = &mut KeccakTranscript::<G1>::new(&());
+        let proof = KZG::<Bn254>::prove_batch(&pk, transcript_p, &cms, &vs, &points).unwrap();
+        for ((v, z), eval) in vs.iter().zip(&points).zip(&proof.evals) {
+            assert_eq!(poly_from_vec(v.clone()).unwrap().evaluate(z), *eval);
+        }
+        let transcript_v = &mut KeccakTranscript::<G1>::new(&());
+        KZG::<Bn254>::verify_batch(&vk, transcript_v, &cms, &points, &proof).unwrap();
+
+        // a wrong evaluation is rejected
+        let mut wrong_proof = proof.clone();
+        wrong_proof.evals[1] += Fr::one();
+        let transcript_v = &mut KeccakTranscript::<G1>::new(&());
+        assert!(
+            KZG::<Bn254>::verify_batch(&vk, transcript_v, &cms, &points, &wrong_proof).is_err()
+        );
+
+        // and so are the swapped points
+        let swapped_points = vec![points[1], points[0], points[2]];
+        let transcript_v = &mut KeccakTranscript::<G1>::new(&());
+        assert!(
+            KZG::<Bn254>::verify_batch(&vk, transcript_v, &cms, &swapped_points, &proof).is_err()
+        );
+    }
 }
@@ -9,6 +9,8 @@ use crate::Error;
 pub mod ipa;
 pub mod kzg;
 pub mod pedersen;
+pub mod ptau;
+pub mod zeromorph;
 
 /// CommitmentScheme defines the vector commitment scheme trait. Where `H` indicates if to use the
 /// commitment in hiding mode or not.
@@ -66,6 +68,32 @@ pub trait CommitmentScheme<C: CurveGroup, const H: bool = false>: Clone + Debug
     ) -> Result<(), Error>;
 }
 
+/// BatchCommitmentScheme extends the CommitmentScheme trait with the opening of multiple
+/// commitments, each one at its own point, with a single proof.
+pub trait BatchCommitmentScheme<C: CurveGroup, const H: bool = false>:
+    CommitmentScheme<C, H>
+{
+    type BatchProof: Clone + Debug + CanonicalSerialize + CanonicalDeserialize;
+
+    /// proves the evaluations of the vectors `vs` (committed in `cms`) at the given points, where
+    /// the i-th vector is evaluated at the i-th point
+    fn prove_batch(
+        params: &Self::ProverParams,
+        transcript: &mut impl Transcript<C>,
+        cms: &[C],
+        vs: &[Vec<C::ScalarField>],
+        points: &[C::ScalarField],
+    ) -> Result<Self::BatchProof, Error>;
+
+    fn verify_batch(
+        params: &Self::VerifierParams,
+        transcript: &mut impl Transcript<C>,
+        cms: &[C],
+        points: &[C::ScalarField],
+        proof: &Self::BatchProof,
+    ) -> Result<(), Error>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +106,7 @@ mod tests {
     use super::ipa::IPA;
     use super::kzg::{ProverKey, KZG};
     use super::pedersen::Pedersen;
+    use super::zeromorph::Zeromorph;
     use crate::transcript::{
         poseidon::{poseidon_canonical_config, PoseidonTranscript},
         Transcript,
@@ -95,8 +124,9 @@ mod tests {
         // set a random challenge for the random linear combination
         let r = Fr::rand(rng);
 
-        // setup params for Pedersen & KZG
+        // setup params for Pedersen, KZG & Zeromorph
         let (pedersen_params, _) = Pedersen::<G1>::setup(&mut rng, n).unwrap();
+        let (zeromorph_pk, zeromorph_vk) = Zeromorph::<Bn254>::setup(&mut rng, n).unwrap();
         let (kzg_pk, kzg_vk): (ProverKey<G1>, VerifierKey<Bn254>) =
             KZG::<Bn254>::setup(rng, n).unwrap();
 
@@ -127,6 +157,15 @@ mod tests {
             &v_1,
             &v_2,
         );
+        // test with Zeromorph
+        test_homomorphic_property_using_Commitment_trait_opt::<G1, Zeromorph<Bn254>>(
+            &poseidon_config,
+            &zeromorph_pk,
+            &zeromorph_vk,
+            r,
+            &v_1,
+            &v_2,
+        );
     }
 
     fn test_homomorphic_property_using_Commitment_trait_opt<
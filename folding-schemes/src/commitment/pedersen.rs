@@ -1,11 +1,19 @@
-use ark_ec::CurveGroup;
-use ark_ff::Field;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField};
 use ark_r1cs_std::{boolean::Boolean, groups::GroupOpsBounds, prelude::CurveVar};
 use ark_relations::r1cs::SynthesisError;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::Zero;
 use ark_std::{rand::RngCore, UniformRand};
 use core::marker::PhantomData;
+use rayon::prelude::*;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
 
 use super::CommitmentScheme;
 use crate::transcript::Transcript;
@@ -19,10 +27,196 @@ pub struct Proof<C: CurveGroup> {
     pub r_u: C::ScalarField, // blind
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Params<C: CurveGroup> {
     pub h: C,
     pub generators: Vec<C::Affine>,
+    /// optional precomputed tables for the generators, used by `commit` when present. They are
+    /// not serialized, and can be recomputed with `Params::precompute` after loading the params.
+    pub tables: Option<FixedBaseTables<C>>,
+}
+
+impl<C: CurveGroup> Params<C> {
+    /// deterministically derives the params for `len` generators by hashing to the curve the
+    /// given domain-separation `label`, so that anyone can regenerate (and audit) them, and no
+    /// discrete-log relation between the generators is known. The generators are the points at
+    /// indexes `0..len.next_power_of_two()` of the label, and `h` is derived from `label || "/h"`.
+    pub fn from_label(label: &[u8], len: usize) -> Self {
+        let generators: Vec<C::Affine> = (0..len.next_power_of_two() as u64)
+            .into_par_iter()
+            .map(|i| hash_to_curve::<C>(label, i))
+            .collect();
+        Self {
+            h: hash_to_curve::<C>(&[label, b"/h"].concat(), 0).into_group(),
+            generators,
+            tables: None,
+        }
+    }
+
+    /// precomputes the fixed-base tables of the generators with the given window size (in bits, at
+    /// most `MAX_FIXED_BASE_WINDOW`), which speeds up the commitments at the cost of storing
+    /// `ceil(|F|/window)` points per generator.
+    pub fn precompute(&mut self, window: usize) -> Result<(), Error> {
+        self.tables = Some(FixedBaseTables::new(&self.generators, window)?);
+        Ok(())
+    }
+
+    // <g, v>, using the precomputed tables if they are available
+    fn msm(&self, v: &[C::ScalarField]) -> C {
+        match &self.tables {
+            Some(tables) => tables.msm(v),
+            // use msm_unchecked because the callers already ensured that lengths match
+            None => C::msm_unchecked(&self.generators[..v.len()], v),
+        }
+    }
+}
+
+/// Hashes the given `label` and `index` into a point of the prime-order subgroup of the curve,
+/// by try-and-increment: SHAKE256(label || index || counter) is interpreted as the x-coordinate
+/// (and the sign of y) of a point, increasing the counter until it is on the curve.
+pub fn hash_to_curve<C: CurveGroup>(label: &[u8], index: u64) -> C::Affine {
+    let mut bytes = vec![0u8; 2 * C::Affine::generator().compressed_size()];
+    for counter in 0u64.. {
+        let mut hasher = Shake256::default();
+        hasher.update(&(label.len() as u64).to_le_bytes());
+        hasher.update(label);
+        hasher.update(&index.to_le_bytes());
+        hasher.update(&counter.to_le_bytes());
+        XofReader::read(&mut hasher.finalize_xof(), &mut bytes);
+        if let Some(p) = C::Affine::from_random_bytes(&bytes) {
+            let p = p.clear_cofactor();
+            if !p.is_zero() {
+                return p;
+            }
+        }
+    }
+    unreachable!()
+}
+
+// the tables are not serialized, since they can be recomputed from the generators
+impl<C: CurveGroup> CanonicalSerialize for Params<C> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.h.serialize_with_mode(&mut writer, compress)?;
+        self.generators.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.h.serialized_size(compress) + self.generators.serialized_size(compress)
+    }
+}
+
+impl<C: CurveGroup> Valid for Params<C> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.h.check()?;
+        self.generators.check()
+    }
+}
+
+impl<C: CurveGroup> CanonicalDeserialize for Params<C> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            h: C::deserialize_with_mode(&mut reader, compress, validate)?,
+            generators: Vec::<C::Affine>::deserialize_with_mode(&mut reader, compress, validate)?,
+            tables: None,
+        })
+    }
+}
+
+/// maximum window size (in bits) of the fixed-base tables. Each rayon thread allocates
+/// `2^window - 1` buckets, and larger windows stop paying off once the bucket aggregation
+/// dominates the additions saved.
+pub const MAX_FIXED_BASE_WINDOW: usize = 16;
+
+/// FixedBaseTables contains, for each generator `g_i`, its shifted multiples `2^{c·j}·g_i` for
+/// `j` in `0..ceil(|F|/c)`, where `c` is the window size. With them, a commitment `<g, v>` becomes
+/// a single-window bucket MSM over the `c`-bit digits of `v`, skipping the doublings and the
+/// per-window bucket aggregations of a variable-base MSM.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FixedBaseTables<C: CurveGroup> {
+    pub window: usize,
+    pub n_windows: usize,
+    /// `bases[i * n_windows + j] = 2^{window·j}·g_i`
+    pub bases: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> FixedBaseTables<C> {
+    pub fn new(generators: &[C::Affine], window: usize) -> Result<Self, Error> {
+        // the buckets are indexed by the window digits, so bound the window to keep them in memory
+        if window == 0 || window > MAX_FIXED_BASE_WINDOW {
+            return Err(Error::OutOfBounds);
+        }
+        let n_windows = (C::ScalarField::MODULUS_BIT_SIZE as usize + window - 1) / window;
+        let bases: Vec<C> = generators
+            .par_iter()
+            .flat_map_iter(|g| {
+                let mut g_j = g.into_group();
+                (0..n_windows).map(move |_| {
+                    let current = g_j;
+                    for _ in 0..window {
+                        g_j.double_in_place();
+                    }
+                    current
+                })
+            })
+            .collect();
+        Ok(Self {
+            window,
+            n_windows,
+            bases: C::normalize_batch(&bases),
+        })
+    }
+
+    /// computes `<g, v>` from the precomputed tables
+    pub fn msm(&self, v: &[C::ScalarField]) -> C {
+        let n_buckets = (1 << self.window) - 1;
+        let chunk_size = ark_std::cmp::max(1, v.len() / rayon::current_num_threads());
+        v.par_chunks(chunk_size)
+            .enumerate()
+            .map(|(chunk_index, chunk)| {
+                let mut buckets = vec![C::zero(); n_buckets];
+                for (k, v_i) in chunk.iter().enumerate() {
+                    let i = chunk_index * chunk_size + k;
+                    let limbs = v_i.into_bigint();
+                    for j in 0..self.n_windows {
+                        let digit = get_window(limbs.as_ref(), j * self.window, self.window);
+                        if digit != 0 {
+                            buckets[digit - 1] += &self.bases[i * self.n_windows + j];
+                        }
+                    }
+                }
+                // sum_d d·bucket_d, computed with a running sum
+                let mut running_sum = C::zero();
+                let mut res = C::zero();
+                for bucket in buckets.into_iter().rev() {
+                    running_sum += bucket;
+                    res += running_sum;
+                }
+                res
+            })
+            .sum()
+    }
+}
+
+// returns the `window` bits of `limbs` starting at the bit `offset`
+fn get_window(limbs: &[u64], offset: usize, window: usize) -> usize {
+    let limb = offset / 64;
+    let shift = offset % 64;
+    if limb >= limbs.len() {
+        return 0;
+    }
+    let mut bits = limbs[limb] >> shift;
+    if shift + window > 64 && limb + 1 < limbs.len() {
+        bits |= limbs[limb + 1] << (64 - shift);
+    }
+    (bits & ((1 << window) - 1)) as usize
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -30,6 +224,15 @@ pub struct Pedersen<C: CurveGroup, const H: bool = false> {
     _c: PhantomData<C>,
 }
 
+impl<C: CurveGroup, const H: bool> Pedersen<C, H> {
+    /// same as `setup`, but deriving the params from the given domain-separation label instead
+    /// of from randomness, see `Params::from_label`
+    pub fn setup_from_label(label: &[u8], len: usize) -> Result<(Params<C>, Params<C>), Error> {
+        let p = Params::<C>::from_label(label, len);
+        Ok((p.clone(), p))
+    }
+}
+
 /// Implements the CommitmentScheme trait for Pedersen commitments
 impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
     type ProverParams = Params<C>;
@@ -48,6 +251,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
         let p = Params::<C> {
             h: C::rand(&mut rng),
             generators,
+            tables: None,
         };
         Ok((p.clone(), p))
     }
@@ -65,11 +269,10 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
         }
 
         // h⋅r + <g, v>
-        // use msm_unchecked because we already ensured at the if that lengths match
         if !H {
-            return Ok(C::msm_unchecked(&params.generators[..v.len()], v));
+            return Ok(params.msm(v));
         }
-        Ok(params.h.mul(r) + C::msm_unchecked(&params.generators[..v.len()], v))
+        Ok(params.h.mul(r) + params.msm(v))
     }
 
     fn prove(
@@ -85,8 +288,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
         let d = transcript.get_challenges(v.len());
 
         // R = h⋅r_1 + <g, d>
-        // use msm_unchecked because we already ensured at the if that lengths match
-        let mut R: C = C::msm_unchecked(&params.generators[..d.len()], &d);
+        let mut R: C = params.msm(&d);
         if H {
             R += params.h.mul(r1);
         }
@@ -158,8 +360,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
 
         // check that: R + cm⋅e == h⋅r_u + <g, u>
         let lhs = proof.R + cm.mul(e);
-        // use msm_unchecked because we already ensured at the if that lengths match
-        let mut rhs = C::msm_unchecked(&params.generators[..proof.u.len()], &proof.u);
+        let mut rhs = params.msm(&proof.u);
         if H {
             rhs += params.h.mul(proof.r_u);
         }
@@ -253,6 +454,77 @@ mod tests {
         Pedersen::<Projective, hiding>::verify(&params, &mut transcript_v, &cm, &proof).unwrap();
     }
 
+    #[test]
+    fn test_pedersen_fixed_base_tables() {
+        let mut rng = ark_std::test_rng();
+
+        let n: usize = 100;
+        let (params, _) = Pedersen::<Projective>::setup(&mut rng, n).unwrap();
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(n)
+            .collect();
+        let cm = Pedersen::<Projective>::commit(&params, &v, &Fr::zero()).unwrap();
+
+        for window in [1, 4, 7, 16] {
+            let mut params_tables = params.clone();
+            params_tables.precompute(window).unwrap();
+            // also for vectors shorter than the generators
+            for v in [&v[..], &v[..n / 3]] {
+                assert_eq!(
+                    Pedersen::<Projective>::commit(&params_tables, v, &Fr::zero()).unwrap(),
+                    Pedersen::<Projective>::commit(&params, v, &Fr::zero()).unwrap(),
+                );
+            }
+            assert_eq!(
+                Pedersen::<Projective>::commit(&params_tables, &v, &Fr::zero()).unwrap(),
+                cm
+            );
+
+            // the tables are not serialized
+            let mut bytes = vec![];
+            params_tables.serialize_compressed(&mut bytes).unwrap();
+            let params_deserialized =
+                Params::<Projective>::deserialize_compressed(&bytes[..]).unwrap();
+            assert_eq!(params_deserialized, params);
+        }
+
+        // out of range windows are rejected
+        for window in [0, MAX_FIXED_BASE_WINDOW + 1] {
+            assert!(params.clone().precompute(window).is_err());
+        }
+    }
+
+    #[test]
+    fn test_pedersen_from_label() {
+        let mut rng = ark_std::test_rng();
+        let n: usize = 10;
+        let (params, _) = Pedersen::<Projective>::setup_from_label(b"test", n).unwrap();
+        assert_eq!(params.generators.len(), n.next_power_of_two());
+
+        // the same label leads to the same params, and a different one to different params
+        assert_eq!(params, Params::<Projective>::from_label(b"test", n));
+        assert_ne!(params, Params::<Projective>::from_label(b"test2", n));
+        let mut points = params.generators.clone();
+        points.push(params.h.into_affine());
+        points.sort_by_key(|p| p.to_string());
+        points.dedup();
+        assert_eq!(points.len(), n.next_power_of_two() + 1);
+        assert!(points.iter().all(|p| p.is_on_curve() && !p.is_zero()));
+
+        // the params work as the randomly sampled ones
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(n)
+            .collect();
+        let cm = Pedersen::<Projective>::commit(&params, &v, &Fr::zero()).unwrap();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let mut transcript_p = PoseidonTranscript::<Projective>::new(&poseidon_config);
+        let mut transcript_v = PoseidonTranscript::<Projective>::new(&poseidon_config);
+        let proof =
+            Pedersen::<Projective>::prove(&params, &mut transcript_p, &cm, &v, &Fr::zero(), None)
+                .unwrap();
+        Pedersen::<Projective>::verify(&params, &mut transcript_v, &cm, &proof).unwrap();
+    }
+
     #[test]
     fn test_pedersen_circuit() {
         test_pedersen_circuit_opt::<false>();
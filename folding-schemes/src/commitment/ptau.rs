@@ -0,0 +1,530 @@
+/// Loaders of the KZG structured reference string (SRS) from the transcripts of public Powers of
+/// Tau ceremonies, so that the KZG ProverKey & VerifierKey do not come from the toxic waste of an
+/// RNG (as in `KZG::setup`) but from a ceremony in which at least one participant was honest.
+///
+/// Supported formats:
+/// - snarkjs' `.ptau` files
+/// - the `challenge` & `response` files of the BN254 Perpetual Powers of Tau ceremony
+///   (https://github.com/privacy-scaling-explorations/perpetualpowersoftau)
+///
+/// All the loaded points are checked to be on the curve and in the prime order subgroup, and the
+/// G1 powers are checked to be consistent with the G2 ones. Notice that the contributions to the
+/// ceremony are not verified here, the transcript should be verified with the ceremony's tooling.
+use ark_ec::{pairing::Pairing, AffineRepr, VariableBaseMSM};
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_poly_commit::kzg10::VerifierKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use ark_std::{borrow::Cow, UniformRand, Zero};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use super::kzg::ProverKey;
+use crate::Error;
+
+/// size of the (blake2b) hash at the beginning of the Perpetual Powers of Tau challenge & response
+/// files
+const POWERSOFTAU_HASH_SIZE: u64 = 64;
+
+/// load_ptau builds the KZG ProverKey & VerifierKey for vectors of the given length from a snarkjs
+/// `.ptau` file. As in `KZG::setup`, the length is rounded up to the next power of two.
+pub fn load_ptau<E: Pairing, R: Read + Seek>(
+    mut reader: R,
+    len: usize,
+) -> Result<(ProverKey<'static, E::G1>, VerifierKey<E>), Error> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != *b"ptau" {
+        return Err(Error::InvalidPtau("wrong magic bytes".to_string()));
+    }
+    let _version = read_u32(&mut reader)?;
+    let n_sections = read_u32(&mut reader)?;
+
+    // map from the section type to its (position, size) in the file
+    let mut sections = HashMap::new();
+    for _ in 0..n_sections {
+        let section_type = read_u32(&mut reader)?;
+        let section_size = read_u64(&mut reader)?;
+        let position = reader.stream_position()?;
+        sections.insert(section_type, (position, section_size));
+        reader.seek(SeekFrom::Current(section_size as i64))?;
+    }
+
+    // section 1: header
+    seek_section(&mut reader, &sections, 1)?;
+    let n8 = read_u32(&mut reader)? as usize;
+    let modulus = E::BaseField::MODULUS.to_bytes_le();
+    if n8 != modulus.len() {
+        return Err(Error::InvalidPtau(format!(
+            "field elements of {} bytes, expected {}",
+            n8,
+            modulus.len()
+        )));
+    }
+    let mut q = vec![0u8; n8];
+    reader.read_exact(&mut q)?;
+    if q != modulus {
+        return Err(Error::InvalidPtau(
+            "the file is for a different curve".to_string(),
+        ));
+    }
+    let power = read_u32(&mut reader)? as usize;
+    if power >= usize::BITS as usize - 1 {
+        return Err(Error::InvalidPtau(format!("invalid power {}", power)));
+    }
+    let n_g1 = (1 << (power + 1)) - 1;
+    let n_g2 = 1 << power;
+
+    let len = len.next_power_of_two();
+    if n_g1 < len + 1 {
+        return Err(Error::PtauParamsLen(n_g1, len + 1));
+    }
+
+    // ptau files store the coordinates in Montgomery form (x*R), with R = 2^(8*n8)
+    let r_inv = E::BaseField::from(2_u64)
+        .pow([8 * n8 as u64])
+        .inverse()
+        .ok_or(Error::OutOfBounds)?;
+
+    // section 2: tau^i * G1, for i in [0, 2^(power+1)-1)
+    let g1_size = E::G1Affine::zero().uncompressed_size();
+    let section_size = seek_section(&mut reader, &sections, 2)?;
+    check_section_size(2, section_size, n_g1 * g1_size)?;
+    let powers_of_g = read_points(&mut reader, len + 1, g1_size, |bytes| {
+        decode_ptau_point(bytes, &r_inv)
+    })?;
+
+    // section 3: tau^i * G2, for i in [0, 2^power)
+    let g2_size = E::G2Affine::zero().uncompressed_size();
+    let section_size = seek_section(&mut reader, &sections, 3)?;
+    check_section_size(3, section_size, n_g2 * g2_size)?;
+    let powers_of_h = read_points(&mut reader, 2, g2_size, |bytes| {
+        decode_ptau_point(bytes, &r_inv)
+    })?;
+
+    build_kzg_params::<E>(powers_of_g, &powers_of_h)
+}
+
+/// load_ptau_file reads the given snarkjs `.ptau` file, check [`load_ptau`].
+pub fn load_ptau_file<E: Pairing>(
+    path: impl AsRef<Path>,
+    len: usize,
+) -> Result<(ProverKey<'static, E::G1>, VerifierKey<E>), Error> {
+    load_ptau::<E, _>(BufReader::new(File::open(path)?), len)
+}
+
+/// load_powersoftau_challenge builds the KZG ProverKey & VerifierKey for vectors of the given
+/// length from a Perpetual Powers of Tau `challenge` file (which contains uncompressed points),
+/// where `power` is the power of the ceremony (ie. 28 for the main BN254 ceremony). As in
+/// `KZG::setup`, the length is rounded up to the next power of two.
+pub fn load_powersoftau_challenge<E: Pairing, R: Read>(
+    reader: R,
+    power: usize,
+    len: usize,
+) -> Result<(ProverKey<'static, E::G1>, VerifierKey<E>), Error> {
+    load_powersoftau::<E, R>(reader, power, len, Compress::No)
+}
+
+/// load_powersoftau_response builds the KZG ProverKey & VerifierKey for vectors of the given length
+/// from a Perpetual Powers of Tau `response` file (which contains compressed points), where `power`
+/// is the power of the ceremony. As in `KZG::setup`, the length is rounded up to the next power of
+/// two.
+pub fn load_powersoftau_response<E: Pairing, R: Read>(
+    reader: R,
+    power: usize,
+    len: usize,
+) -> Result<(ProverKey<'static, E::G1>, VerifierKey<E>), Error> {
+    load_powersoftau::<E, R>(reader, power, len, Compress::Yes)
+}
+
+fn load_powersoftau<E: Pairing, R: Read>(
+    mut reader: R,
+    power: usize,
+    len: usize,
+    compress: Compress,
+) -> Result<(ProverKey<'static, E::G1>, VerifierKey<E>), Error> {
+    if power >= usize::BITS as usize - 1 {
+        return Err(Error::InvalidPtau(format!("invalid power {}", power)));
+    }
+    let n_g1 = (1 << (power + 1)) - 1;
+
+    let len = len.next_power_of_two();
+    if n_g1 < len + 1 {
+        return Err(Error::PtauParamsLen(n_g1, len + 1));
+    }
+
+    // the file starts with the hash of the previous contribution, followed by the tau powers in G1
+    // and then the tau powers in G2 (the rest of the file is not needed for KZG)
+    skip(&mut reader, POWERSOFTAU_HASH_SIZE)?;
+    let g1_size = E::G1Affine::zero().serialized_size(compress);
+    let powers_of_g = read_points(&mut reader, len + 1, g1_size, |bytes| {
+        decode_powersoftau_point(bytes, compress)
+    })?;
+    skip(&mut reader, ((n_g1 - len - 1) * g1_size) as u64)?;
+    let g2_size = E::G2Affine::zero().serialized_size(compress);
+    let powers_of_h = read_points(&mut reader, 2, g2_size, |bytes| {
+        decode_powersoftau_point(bytes, compress)
+    })?;
+
+    build_kzg_params::<E>(powers_of_g, &powers_of_h)
+}
+
+/// builds the KZG params from the powers of tau in G1 and the first two powers of tau in G2,
+/// checking that they are consistent, ie. that all the G1 powers are consecutive powers of the
+/// same tau as the G2 ones.
+fn build_kzg_params<E: Pairing>(
+    powers_of_g: Vec<E::G1Affine>,
+    powers_of_h: &[E::G2Affine],
+) -> Result<(ProverKey<'static, E::G1>, VerifierKey<E>), Error> {
+    let (g, h, beta_h) = (powers_of_g[0], powers_of_h[0], powers_of_h[1]);
+    if g != E::G1Affine::generator() || h != E::G2Affine::generator() {
+        return Err(Error::InvalidPtau(
+            "the first powers are not the generators".to_string(),
+        ));
+    }
+    // with tau=0 all the higher powers are the point at infinity, which would pass the check below
+    if beta_h.is_zero() || powers_of_g.iter().any(|p| p.is_zero()) {
+        return Err(Error::InvalidPtau(
+            "the powers contain the point at infinity".to_string(),
+        ));
+    }
+    // g_{i+1} = tau*g_i for all i, checked at once with a random linear combination:
+    // e(sum_i r_i*g_{i+1}, G2) == e(sum_i r_i*g_i, tau*G2)
+    let mut rng = rand::rngs::OsRng;
+    let r: Vec<E::ScalarField> = std::iter::repeat_with(|| E::ScalarField::rand(&mut rng))
+        .take(powers_of_g.len() - 1)
+        .collect();
+    let lhs = E::G1::msm_unchecked(&powers_of_g[1..], &r);
+    let rhs = E::G1::msm_unchecked(&powers_of_g[..powers_of_g.len() - 1], &r);
+    if E::pairing(lhs, h) != E::pairing(rhs, beta_h) {
+        return Err(Error::InvalidPtau(
+            "the G1 and G2 powers are not consistent".to_string(),
+        ));
+    }
+
+    let vk = VerifierKey {
+        g,
+        // gamma_g is only used by hiding commitments, which our KZG does not support, and the
+        // ceremonies do not provide it
+        gamma_g: E::G1Affine::zero(),
+        h,
+        beta_h,
+        prepared_h: h.into(),
+        prepared_beta_h: beta_h.into(),
+    };
+    let pk = ProverKey::<E::G1> {
+        powers_of_g: Cow::Owned(powers_of_g),
+    };
+    Ok((pk, vk))
+}
+
+/// decodes a point from the ptau encoding, where the coordinates are little-endian in Montgomery
+/// form (for G2, in the order x.c0, x.c1, y.c0, y.c1), and the point at infinity is all zeroes.
+fn decode_ptau_point<P: AffineRepr, F: PrimeField>(bytes: &[u8], r_inv: &F) -> Result<P, Error> {
+    if bytes.iter().all(|b| *b == 0) {
+        return Ok(P::zero());
+    }
+    let n8 = F::MODULUS.to_bytes_le().len();
+    let mut point_bytes = Vec::with_capacity(bytes.len());
+    for coordinate in bytes.chunks(n8) {
+        let c = F::deserialize_uncompressed(coordinate)? * r_inv;
+        c.serialize_uncompressed(&mut point_bytes)?;
+    }
+    Ok(P::deserialize_with_mode(
+        &point_bytes[..],
+        Compress::No,
+        Validate::Yes,
+    )?)
+}
+
+/// decodes a point from the big-endian encoding of the Perpetual Powers of Tau (for G2, in the
+/// order x.c1, x.c0, y.c1, y.c0), whose flags are in the two most significant bits of the first
+/// byte: 0x40 for the point at infinity, and, in the compressed form, 0x80 when y is the
+/// lexicographically largest. Once reversed into little-endian, those flags match arkworks' ones.
+fn decode_powersoftau_point<P: AffineRepr>(bytes: &[u8], compress: Compress) -> Result<P, Error> {
+    let mut point_bytes = bytes.to_vec();
+    match compress {
+        Compress::Yes => point_bytes.reverse(),
+        Compress::No => {
+            if bytes[0] & 0x80 != 0 {
+                return Err(Error::InvalidPtau(
+                    "unexpected compressed point".to_string(),
+                ));
+            }
+            if bytes[0] & 0x40 != 0 {
+                return Ok(P::zero());
+            }
+            let (x, y) = point_bytes.split_at_mut(bytes.len() / 2);
+            x.reverse();
+            y.reverse();
+        }
+    }
+    Ok(P::deserialize_with_mode(
+        &point_bytes[..],
+        compress,
+        Validate::Yes,
+    )?)
+}
+
+/// reads n points of point_size bytes each, decoding (and validating) them in parallel
+fn read_points<P: AffineRepr, R: Read>(
+    reader: &mut R,
+    n: usize,
+    point_size: usize,
+    decode: impl Fn(&[u8]) -> Result<P, Error> + Sync,
+) -> Result<Vec<P>, Error> {
+    let mut bytes = vec![0u8; n * point_size];
+    reader.read_exact(&mut bytes)?;
+    bytes
+        .par_chunks(point_size)
+        .map(decode)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn seek_section<R: Read + Seek>(
+    reader: &mut R,
+    sections: &HashMap<u32, (u64, u64)>,
+    section_type: u32,
+) -> Result<usize, Error> {
+    let (position, size) = sections
+        .get(&section_type)
+        .ok_or_else(|| Error::InvalidPtau(format!("missing section {}", section_type)))?;
+    reader.seek(SeekFrom::Start(*position))?;
+    Ok(*size as usize)
+}
+
+fn check_section_size(section_type: u32, size: usize, expected: usize) -> Result<(), Error> {
+    if size != expected {
+        return Err(Error::InvalidPtau(format!(
+            "section {} has {} bytes, expected {}",
+            section_type, size, expected
+        )));
+    }
+    Ok(())
+}
+
+fn skip<R: Read>(reader: &mut R, n: u64) -> Result<(), Error> {
+    let skipped = io::copy(&mut reader.by_ref().take(n), &mut io::sink())?;
+    if skipped != n {
+        return Err(Error::IOError(io::ErrorKind::UnexpectedEof.into()));
+    }
+    Ok(())
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, Error> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, Error> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::{Bn254, Fq, Fr, G1Affine, G1Projective as G1, G2Affine};
+    use ark_ec::CurveGroup;
+    use ark_std::{test_rng, UniformRand};
+    use std::io::{Cursor, Write};
+
+    use super::*;
+    use crate::commitment::{kzg::KZG, CommitmentScheme};
+    use crate::transcript::{
+        poseidon::{poseidon_canonical_config, PoseidonTranscript},
+        Transcript,
+    };
+
+    const POWER: usize = 3;
+
+    fn powers_of_tau(tau: Fr) -> (Vec<G1Affine>, Vec<G2Affine>) {
+        let n_g1 = (1 << (POWER + 1)) - 1;
+        let n_g2 = 1 << POWER;
+        let mut t = Fr::from(1_u32);
+        let mut g1 = vec![];
+        let mut g2 = vec![];
+        for i in 0..n_g1 {
+            g1.push((G1Affine::generator() * t).into_affine());
+            if i < n_g2 {
+                g2.push((G2Affine::generator() * t).into_affine());
+            }
+            t *= tau;
+        }
+        (g1, g2)
+    }
+
+    // encodes the points into the ptau format, converting the coordinates to Montgomery form
+    fn ptau_points<P: AffineRepr>(points: &[P]) -> Vec<u8> {
+        let r = Fq::from(2_u64).pow([256]);
+        let mut out = vec![];
+        for p in points {
+            let mut bytes = vec![];
+            p.serialize_uncompressed(&mut bytes).unwrap();
+            for coordinate in bytes.chunks(32) {
+                let c = Fq::deserialize_uncompressed(coordinate).unwrap() * r;
+                c.serialize_uncompressed(&mut out).unwrap();
+            }
+        }
+        out
+    }
+
+    fn ptau_file(g1: &[G1Affine], g2: &[G2Affine]) -> Vec<u8> {
+        let mut header = vec![];
+        header.write_all(&32_u32.to_le_bytes()).unwrap();
+        header.write_all(&Fq::MODULUS.to_bytes_le()).unwrap();
+        header.write_all(&(POWER as u32).to_le_bytes()).unwrap();
+        header.write_all(&(POWER as u32).to_le_bytes()).unwrap();
+
+        let mut file = vec![];
+        file.write_all(b"ptau").unwrap();
+        file.write_all(&1_u32.to_le_bytes()).unwrap();
+        file.write_all(&3_u32.to_le_bytes()).unwrap();
+        for (section_type, data) in [(1_u32, header), (2, ptau_points(g1)), (3, ptau_points(g2))] {
+            file.write_all(&section_type.to_le_bytes()).unwrap();
+            file.write_all(&(data.len() as u64).to_le_bytes()).unwrap();
+            file.write_all(&data).unwrap();
+        }
+        file
+    }
+
+    // encodes the points into the Perpetual Powers of Tau format, which is the arkworks encoding
+    // reversed (coordinate by coordinate in the uncompressed case)
+    fn powersoftau_points<P: AffineRepr>(points: &[P], compress: Compress) -> Vec<u8> {
+        let mut out = vec![];
+        for p in points {
+            let mut bytes = vec![];
+            p.serialize_with_mode(&mut bytes, compress).unwrap();
+            match compress {
+                Compress::Yes => bytes.reverse(),
+                Compress::No => {
+                    let half = bytes.len() / 2;
+                    bytes[..half].reverse();
+                    bytes[half..].reverse();
+                }
+            }
+            out.extend(bytes);
+        }
+        out
+    }
+
+    fn powersoftau_file(g1: &[G1Affine], g2: &[G2Affine], compress: Compress) -> Vec<u8> {
+        let mut file = vec![0u8; POWERSOFTAU_HASH_SIZE as usize];
+        file.extend(powersoftau_points(g1, compress));
+        file.extend(powersoftau_points(g2, compress));
+        // the alpha & beta powers, which are not used
+        file.extend(powersoftau_points(&g1[..1 << POWER], compress));
+        file
+    }
+
+    fn check_params(
+        pk: &ProverKey<G1>,
+        vk: &VerifierKey<Bn254>,
+        g1: &[G1Affine],
+        g2: &[G2Affine],
+        len: usize,
+    ) {
+        assert_eq!(pk.powers_of_g.to_vec(), g1[..=len.next_power_of_two()]);
+        assert_eq!(vk.g, g1[0]);
+        assert_eq!(vk.h, g2[0]);
+        assert_eq!(vk.beta_h, g2[1]);
+
+        // the loaded params work with the KZG scheme
+        let mut rng = test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let transcript_p = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        let transcript_v = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(len)
+            .collect();
+        let cm = KZG::<Bn254>::commit(pk, &v, &Fr::zero()).unwrap();
+        let proof = KZG::<Bn254>::prove(pk, transcript_p, &cm, &v, &Fr::zero(), None).unwrap();
+        KZG::<Bn254>::verify(vk, transcript_v, &cm, &proof).unwrap();
+    }
+
+    #[test]
+    fn test_load_ptau() {
+        let mut rng = test_rng();
+        let (g1, g2) = powers_of_tau(Fr::rand(&mut rng));
+        let file = ptau_file(&g1, &g2);
+
+        let len = 5;
+        let (pk, vk) = load_ptau::<Bn254, _>(Cursor::new(&file), len).unwrap();
+        check_params(&pk, &vk, &g1, &g2, len);
+
+        // the file does not contain enough powers
+        assert!(matches!(
+            load_ptau::<Bn254, _>(Cursor::new(&file), 9),
+            Err(Error::PtauParamsLen(15, 17))
+        ));
+
+        // a point that is not on the curve must be rejected
+        let mut corrupted = file.clone();
+        // the G1 points start after the file header (12 bytes), the header section (12 + 44 bytes)
+        // and the G1 section header (12 bytes)
+        let g1_section = 80;
+        corrupted[g1_section + 64] ^= 1;
+        assert!(load_ptau::<Bn254, _>(Cursor::new(&corrupted), len).is_err());
+
+        // a valid point that is not the expected higher power must be rejected too
+        let mut corrupted_g1 = g1.clone();
+        corrupted_g1[len] = (corrupted_g1[len] + G1Affine::generator()).into_affine();
+        let corrupted = ptau_file(&corrupted_g1, &g2);
+        assert!(matches!(
+            load_ptau::<Bn254, _>(Cursor::new(&corrupted), len),
+            Err(Error::InvalidPtau(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_powersoftau() {
+        let mut rng = test_rng();
+        let (g1, g2) = powers_of_tau(Fr::rand(&mut rng));
+        let len = 5;
+
+        let challenge = powersoftau_file(&g1, &g2, Compress::No);
+        let (pk, vk) =
+            load_powersoftau_challenge::<Bn254, _>(Cursor::new(&challenge), POWER, len).unwrap();
+        check_params(&pk, &vk, &g1, &g2, len);
+
+        let response = powersoftau_file(&g1, &g2, Compress::Yes);
+        let (pk, vk) =
+            load_powersoftau_response::<Bn254, _>(Cursor::new(&response), POWER, len).unwrap();
+        check_params(&pk, &vk, &g1, &g2, len);
+
+        // G1 & G2 powers from different taus are rejected
+        let (_, other_g2) = powers_of_tau(Fr::rand(&mut rng));
+        let response = powersoftau_file(&g1, &other_g2, Compress::Yes);
+        assert!(load_powersoftau_response::<Bn254, _>(Cursor::new(&response), POWER, len).is_err());
+    }
+
+    #[test]
+    fn test_reject_zero_tau() {
+        // tau=0 gives consistent G1 & G2 powers, but all of them (except the generators) are the
+        // point at infinity
+        let (g1, g2) = powers_of_tau(Fr::zero());
+        let len = 5;
+        assert!(matches!(
+            load_ptau::<Bn254, _>(Cursor::new(&ptau_file(&g1, &g2)), len),
+            Err(Error::InvalidPtau(_))
+        ));
+        let response = powersoftau_file(&g1, &g2, Compress::Yes);
+        assert!(matches!(
+            load_powersoftau_response::<Bn254, _>(Cursor::new(&response), POWER, len),
+            Err(Error::InvalidPtau(_))
+        ));
+
+        // and so does a single zero G1 power, even when beta_h is not zero
+        let (mut g1, g2) = powers_of_tau(Fr::from(2_u32));
+        g1[len] = G1Affine::zero();
+        assert!(matches!(
+            load_ptau::<Bn254, _>(Cursor::new(&ptau_file(&g1, &g2)), len),
+            Err(Error::InvalidPtau(_))
+        ));
+    }
+}
@@ -0,0 +1,512 @@
+/// Zeromorph multilinear polynomial commitment scheme, following
+/// [Zeromorph](https://eprint.iacr.org/2023/917.pdf) over the KZG commitments.
+///
+/// The multilinear polynomial is given by its evaluations over the boolean hypercube (in the same
+/// order as in `utils::mle`, where the first variable is the least significant bit of the index),
+/// and its commitment is the KZG commitment to the univariate polynomial which has those
+/// evaluations as coefficients. This means that the commitments are the same as the ones computed
+/// by `KZG::commit` for the same vector, so a vector committed with KZG (ie. the witness `W`) can
+/// be opened at a point in F^n without re-committing to it.
+///
+/// The degree of the batched quotient `q̂` is checked as described in the paper: with an SRS of
+/// maximum degree `N_max`, the prover also commits to `X^{N_max-2^n+1}·q̂(X)`, which can only be
+/// done when `deg(q̂) < 2^n`, and the verifier checks it against `[τ^{N_max-2^n+1}]_2`. So the
+/// ProverKey can have more powers than `2^n` (ie. loaded from a Powers of Tau ceremony), as long as
+/// the VerifierKey contains the matching power of tau in G2.
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, PrimeField};
+use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+use ark_std::{log2, One, Zero};
+use core::marker::PhantomData;
+
+use super::{
+    kzg::{divide_by_linear, ProverKey, KZG},
+    CommitmentScheme,
+};
+use crate::transcript::Transcript;
+use crate::Error;
+
+/// VerifierKey contains the KZG VerifierKey together with the number of variables of the
+/// multilinear polynomials that can be opened and the power of tau in G2 used in the degree check.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerifierKey<E: Pairing> {
+    pub kzg_vk: KZGVerifierKey<E>,
+    pub n_vars: usize,
+    /// [τ^{N_max-2^n+1}]_2, where N_max is the maximum degree supported by the ProverKey
+    pub shift_h: E::G2Affine,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Proof<C: CurveGroup> {
+    /// evaluation of the multilinear polynomial at the opening point
+    pub eval: C::ScalarField,
+    /// commitments to the univariate polynomials of the quotients q_k, for k in [0, n)
+    pub q_k: Vec<C>,
+    /// commitment to the batched and degree-lifted quotient
+    pub q_hat: C,
+    /// commitment to the batched quotient shifted by X^{N_max-2^n+1}, for the degree check
+    pub q_hat_shift: C,
+    /// KZG opening proof of the batched polynomial ζ_x + z·Z_x at x
+    pub pi: C,
+}
+
+/// Zeromorph implements the CommitmentScheme trait for the Zeromorph multilinear polynomial
+/// commitment scheme.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Zeromorph<'a, E: Pairing, const H: bool = false> {
+    _a: PhantomData<&'a ()>,
+    _e: PhantomData<E>,
+}
+
+impl<'a, E, const H: bool> CommitmentScheme<E::G1, H> for Zeromorph<'a, E, H>
+where
+    E: Pairing,
+{
+    type ProverParams = ProverKey<'a, E::G1>;
+    type VerifierParams = VerifierKey<E>;
+    type Proof = Proof<E::G1>;
+    // (point, y, x, z)
+    type ProverChallenge = (
+        Vec<E::ScalarField>,
+        E::ScalarField,
+        E::ScalarField,
+        E::ScalarField,
+    );
+    type Challenge = (
+        Vec<E::ScalarField>,
+        E::ScalarField,
+        E::ScalarField,
+        E::ScalarField,
+    );
+
+    /// setup returns the tuple (ProverKey, VerifierKey) for multilinear polynomials of
+    /// `log2(len)` variables. As in KZG, for real world deployments the setup must be computed
+    /// through a MPC ceremony.
+    fn setup(
+        rng: impl RngCore,
+        len: usize,
+    ) -> Result<(Self::ProverParams, Self::VerifierParams), Error> {
+        let len = len.next_power_of_two();
+        let (pk, kzg_vk) = KZG::<E>::setup(rng, len)?;
+        // KZG::setup returns len+1 powers, so N_max = len and the degree check uses [τ]_2
+        let vk = VerifierKey {
+            shift_h: kzg_vk.beta_h,
+            kzg_vk,
+            n_vars: log2(len) as usize,
+        };
+        Ok((pk, vk))
+    }
+
+    /// commit returns the KZG commitment to the evaluations of the multilinear polynomial over the
+    /// boolean hypercube.
+    fn commit(
+        params: &Self::ProverParams,
+        v: &[E::ScalarField],
+        blind: &E::ScalarField,
+    ) -> Result<E::G1, Error> {
+        KZG::<E, H>::commit(params, v, blind)
+    }
+
+    /// prove opens the multilinear polynomial at a point in F^n obtained from the transcript,
+    /// where n is log2 of the length of `v` (rounded up).
+    fn prove(
+        params: &Self::ProverParams,
+        transcript: &mut impl Transcript<E::G1>,
+        cm: &E::G1,
+        v: &[E::ScalarField],
+        blind: &E::ScalarField,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Error> {
+        if !blind.is_zero() || H {
+            return Err(Error::NotSupportedYet("hiding".to_string()));
+        }
+        transcript.absorb_point(cm)?;
+        let point = transcript.get_challenges(log2(v.len()) as usize);
+        Self::prove_evaluation(params, transcript, cm, v, &point)
+    }
+
+    fn prove_with_challenge(
+        _params: &Self::ProverParams,
+        _challenge: Self::ProverChallenge,
+        _v: &[E::ScalarField],
+        _blind: &E::ScalarField,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Error> {
+        // not supported because the challenges y, x, z depend on the prover's commitments
+        Err(Error::NotSupported(
+            "Zeromorph::prove_with_challenge".to_string(),
+        ))
+    }
+
+    fn verify(
+        params: &Self::VerifierParams,
+        transcript: &mut impl Transcript<E::G1>,
+        cm: &E::G1,
+        proof: &Self::Proof,
+    ) -> Result<(), Error> {
+        transcript.absorb_point(cm)?;
+        let point = transcript.get_challenges(params.n_vars);
+        Self::verify_evaluation(params, transcript, cm, &point, proof)
+    }
+
+    fn verify_with_challenge(
+        params: &Self::VerifierParams,
+        challenge: Self::Challenge,
+        cm: &E::G1,
+        proof: &Self::Proof,
+    ) -> Result<(), Error> {
+        if H {
+            return Err(Error::NotSupportedYet("hiding".to_string()));
+        }
+        let (point, y, x, z) = challenge;
+        let n = point.len();
+        if n != params.n_vars {
+            return Err(Error::NotSameLength(
+                "point.len()".to_string(),
+                n,
+                "n_vars".to_string(),
+                params.n_vars,
+            ));
+        }
+        if proof.q_k.len() != n {
+            return Err(Error::NotSameLength(
+                "proof.q_k.len()".to_string(),
+                proof.q_k.len(),
+                "n_vars".to_string(),
+                n,
+            ));
+        }
+
+        // C = C_ζ + z·C_Z, where
+        //   C_ζ = C_q̂ - Σ_k y^k·x^{2^n-2^k}·C_{q_k}
+        //   C_Z = C_f - v·Φ_n(x)·[1]_1 - Σ_k (x^{2^k}·Φ_{n-k-1}(x^{2^{k+1}}) - u_k·Φ_{n-k}(x^{2^k}))·C_{q_k}
+        let scalars = quotients_scalars(&point, y, x, z);
+        let mut c = proof.q_hat + *cm * z
+            - params.kzg_vk.g * (z * proof.eval * phi(x, n))
+            - E::G1::msm_unchecked(&E::G1::normalize_batch(&proof.q_k), &scalars);
+
+        // C opens to 0 at x: e(C + x·π, [1]_2) == e(π, [τ]_2)
+        c += proof.pi * x;
+        let check = E::multi_pairing(
+            [c.into_affine(), (-proof.pi).into_affine()],
+            [params.kzg_vk.h, params.kzg_vk.beta_h],
+        );
+        if !check.is_zero() {
+            return Err(Error::CommitmentVerificationFail);
+        }
+
+        // degree check of q̂: e(C_q̂, [τ^{N_max-2^n+1}]_2) == e(C_q̂_shift, [1]_2)
+        let check = E::multi_pairing(
+            [
+                proof.q_hat.into_affine(),
+                (-proof.q_hat_shift).into_affine(),
+            ],
+            [params.shift_h, params.kzg_vk.h],
+        );
+        if !check.is_zero() {
+            return Err(Error::CommitmentVerificationFail);
+        }
+        Ok(())
+    }
+}
+
+impl<'a, E: Pairing, const H: bool> Zeromorph<'a, E, H> {
+    /// prove_evaluation proves the evaluation of the multilinear polynomial (given by its
+    /// evaluations `v` over the boolean hypercube) committed in `cm` at the given point, which
+    /// allows to open at points computed elsewhere (ie. the sum-check point).
+    pub fn prove_evaluation(
+        params: &ProverKey<'a, E::G1>,
+        transcript: &mut impl Transcript<E::G1>,
+        cm: &E::G1,
+        v: &[E::ScalarField],
+        point: &[E::ScalarField],
+    ) -> Result<Proof<E::G1>, Error> {
+        if H {
+            return Err(Error::NotSupportedYet("hiding".to_string()));
+        }
+        let n = point.len();
+        let N = 1 << n;
+        if v.len() > N {
+            return Err(Error::NotExpectedLength(v.len(), N));
+        }
+        if params.powers_of_g.len() < N {
+            return Err(Error::NotExpectedLength(params.powers_of_g.len(), N));
+        }
+
+        // compute the quotients q_k such that f(X) - f(u) = Σ_k (X_k - u_k)·q_k(X_0, ..., X_{k-1}),
+        // by fixing the variables from the last one to the first one
+        let mut f = v.to_vec();
+        f.resize(N, E::ScalarField::zero());
+        let mut q_k = vec![vec![]; n];
+        for k in (0..n).rev() {
+            let (low, high) = f.split_at(1 << k);
+            let q: Vec<E::ScalarField> = high.iter().zip(low).map(|(h, l)| *h - l).collect();
+            f = low.iter().zip(&q).map(|(l, q)| *l + point[k] * q).collect();
+            q_k[k] = q;
+        }
+        let eval = f[0];
+
+        transcript.absorb_point(cm)?;
+        transcript.absorb_vec(point);
+        transcript.absorb(&eval);
+
+        let q_k_cms = q_k.iter().map(|q| msm(params, q)).collect::<Vec<E::G1>>();
+        for c in &q_k_cms {
+            transcript.absorb_point(c)?;
+        }
+        let y = transcript.get_challenge();
+
+        // q̂(X) = Σ_k y^k·X^{2^n-2^k}·U_k(q_k)(X)
+        let mut q_hat = vec![E::ScalarField::zero(); N];
+        let mut y_k = E::ScalarField::one();
+        for (k, q) in q_k.iter().enumerate() {
+            let offset = N - (1 << k);
+            for (i, q_i) in q.iter().enumerate() {
+                q_hat[offset + i] += y_k * q_i;
+            }
+            y_k *= y;
+        }
+        let q_hat_cm = msm(params, &q_hat);
+        // X^{N_max-2^n+1}·q̂(X), where N_max+1 is the number of powers in the ProverKey
+        let shift = params.powers_of_g.len() - N;
+        let q_hat_shift_cm = E::G1::msm_unchecked(&params.powers_of_g[shift..shift + N], &q_hat);
+        transcript.absorb_point(&q_hat_cm)?;
+        let x = transcript.get_challenge();
+        let z = transcript.get_challenge();
+
+        // g(X) = ζ_x(X) + z·Z_x(X), which vanishes at x, where
+        //   ζ_x(X) = q̂(X) - Σ_k y^k·x^{2^n-2^k}·U_k(q_k)(X)
+        //   Z_x(X) = U_n(f)(X) - v·Φ_n(x) - Σ_k (x^{2^k}·Φ_{n-k-1}(x^{2^{k+1}}) - u_k·Φ_{n-k}(x^{2^k}))·U_k(q_k)(X)
+        let mut g = q_hat;
+        for (g_i, f_i) in g.iter_mut().zip(v) {
+            *g_i += z * f_i;
+        }
+        g[0] -= z * eval * phi(x, n);
+        let scalars = quotients_scalars(point, y, x, z);
+        for (q, s) in q_k.iter().zip(scalars) {
+            for (g_i, q_i) in g.iter_mut().zip(q) {
+                *g_i -= s * q_i;
+            }
+        }
+
+        // π(X) = g(X) / (X - x)
+        let pi = msm(params, &divide_by_linear(&g, x).0);
+
+        Ok(Proof {
+            eval,
+            q_k: q_k_cms,
+            q_hat: q_hat_cm,
+            q_hat_shift: q_hat_shift_cm,
+            pi,
+        })
+    }
+
+    /// verify_evaluation verifies the proof generated by `prove_evaluation` for the commitment `cm`
+    /// and the given point.
+    pub fn verify_evaluation(
+        params: &VerifierKey<E>,
+        transcript: &mut impl Transcript<E::G1>,
+        cm: &E::G1,
+        point: &[E::ScalarField],
+        proof: &Proof<E::G1>,
+    ) -> Result<(), Error> {
+        transcript.absorb_point(cm)?;
+        transcript.absorb_vec(point);
+        transcript.absorb(&proof.eval);
+        for c in &proof.q_k {
+            transcript.absorb_point(c)?;
+        }
+        let y = transcript.get_challenge();
+        transcript.absorb_point(&proof.q_hat)?;
+        let x = transcript.get_challenge();
+        let z = transcript.get_challenge();
+
+        <Self as CommitmentScheme<E::G1, H>>::verify_with_challenge(
+            params,
+            (point.to_vec(), y, x, z),
+            cm,
+            proof,
+        )
+    }
+}
+
+/// returns, for each k in [0, n), the scalar multiplying U_k(q_k) in ζ_x + z·Z_x (with the sign
+/// flipped):
+/// y^k·x^{2^n-2^k} + z·(x^{2^k}·Φ_{n-k-1}(x^{2^{k+1}}) - u_k·Φ_{n-k}(x^{2^k}))
+fn quotients_scalars<F: PrimeField>(point: &[F], y: F, x: F, z: F) -> Vec<F> {
+    let n = point.len();
+    let N: u64 = 1 << n;
+    let mut scalars = Vec::with_capacity(n);
+    let mut y_k = F::one();
+    let mut x_2k = x; // x^{2^k}
+    for (k, u_k) in point.iter().enumerate() {
+        let x_2k1 = x_2k.square(); // x^{2^{k+1}}
+        let s = y_k * x.pow([N - (1 << k)])
+            + z * (x_2k * phi(x_2k1, n - k - 1) - *u_k * phi(x_2k, n - k));
+        scalars.push(s);
+        y_k *= y;
+        x_2k = x_2k1;
+    }
+    scalars
+}
+
+/// Φ_m(x) = Σ_{i<2^m} x^i = Π_{j<m} (1 + x^{2^j})
+fn phi<F: PrimeField>(x: F, m: usize) -> F {
+    let mut r = F::one();
+    let mut x_2j = x;
+    for _ in 0..m {
+        r *= F::one() + x_2j;
+        x_2j.square_in_place();
+    }
+    r
+}
+
+fn msm<C: CurveGroup>(params: &ProverKey<C>, coeffs: &[C::ScalarField]) -> C {
+    C::msm_unchecked(&params.powers_of_g[..coeffs.len()], coeffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::{Bn254, Fr, G1Affine, G1Projective as G1, G2Affine};
+    use ark_ec::AffineRepr;
+    use ark_poly::{DenseMultilinearExtension, MultilinearExtension};
+    use ark_std::{borrow::Cow, test_rng, UniformRand};
+
+    use super::*;
+    use crate::transcript::{
+        poseidon::{poseidon_canonical_config, PoseidonTranscript},
+        Transcript,
+    };
+
+    #[test]
+    fn test_zeromorph_evaluation() {
+        let mut rng = &mut test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let n_vars = 5;
+        let (pk, vk) = Zeromorph::<Bn254>::setup(&mut rng, 1 << n_vars).unwrap();
+
+        // a vector shorter than 2^n_vars is padded with zeroes
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng))
+            .take((1 << n_vars) - 3)
+            .collect();
+        let point: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng))
+            .take(n_vars)
+            .collect();
+
+        // the commitment is the same as the KZG one
+        let cm = Zeromorph::<Bn254>::commit(&pk, &v, &Fr::zero()).unwrap();
+        assert_eq!(cm, KZG::<Bn254>::commit(&pk, &v, &Fr::zero()).unwrap());
+
+        let transcript_p = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        let proof =
+            Zeromorph::<Bn254>::prove_evaluation(&pk, transcript_p, &cm, &v, &point).unwrap();
+
+        // the evaluation matches the one of the MLE
+        let mut v_padded = v.clone();
+        v_padded.resize(1 << n_vars, Fr::zero());
+        let mle = DenseMultilinearExtension::from_evaluations_vec(n_vars, v_padded);
+        assert_eq!(proof.eval, mle.evaluate(&point).unwrap());
+
+        let transcript_v = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        Zeromorph::<Bn254>::verify_evaluation(&vk, transcript_v, &cm, &point, &proof).unwrap();
+
+        // a wrong evaluation is rejected
+        let mut wrong_proof = proof.clone();
+        wrong_proof.eval += Fr::one();
+        let transcript_v = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        assert!(Zeromorph::<Bn254>::verify_evaluation(
+            &vk,
+            transcript_v,
+            &cm,
+            &point,
+            &wrong_proof
+        )
+        .is_err());
+
+        // and so is the proof for a different point
+        let mut other_point = point.clone();
+        other_point[0] += Fr::one();
+        let transcript_v = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        assert!(Zeromorph::<Bn254>::verify_evaluation(
+            &vk,
+            transcript_v,
+            &cm,
+            &other_point,
+            &proof
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_zeromorph_longer_srs() {
+        let mut rng = &mut test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let n_vars = 3;
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng))
+            .take(1 << n_vars)
+            .collect();
+        let point: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng))
+            .take(n_vars)
+            .collect();
+
+        // SRS with more powers than 2^n, as the ones of a ceremony
+        let tau = Fr::rand(rng);
+        let n_powers = 1 << (n_vars + 2);
+        let g = G1Affine::generator();
+        let h = G2Affine::generator();
+        let pk = ProverKey::<G1> {
+            powers_of_g: Cow::Owned(
+                (0..n_powers)
+                    .map(|i| (g * tau.pow([i as u64])).into_affine())
+                    .collect(),
+            ),
+        };
+        let beta_h = (h * tau).into_affine();
+        let kzg_vk = KZGVerifierKey {
+            g,
+            gamma_g: G1Affine::zero(),
+            h,
+            beta_h,
+            prepared_h: h.into(),
+            prepared_beta_h: beta_h.into(),
+        };
+        let vk = VerifierKey::<Bn254> {
+            kzg_vk,
+            n_vars,
+            shift_h: (h * tau.pow([(n_powers - (1 << n_vars)) as u64])).into_affine(),
+        };
+
+        let cm = Zeromorph::<Bn254>::commit(&pk, &v, &Fr::zero()).unwrap();
+        let transcript_p = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        let proof =
+            Zeromorph::<Bn254>::prove_evaluation(&pk, transcript_p, &cm, &v, &point).unwrap();
+        let transcript_v = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        Zeromorph::<Bn254>::verify_evaluation(&vk, transcript_v, &cm, &point, &proof).unwrap();
+
+        // a proof whose q̂ is not shifted by the right power of tau fails the degree check
+        let mut wrong_proof = proof.clone();
+        wrong_proof.q_hat_shift = proof.q_hat;
+        let transcript_v = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        assert!(Zeromorph::<Bn254>::verify_evaluation(
+            &vk,
+            transcript_v,
+            &cm,
+            &point,
+            &wrong_proof
+        )
+        .is_err());
+
+        // a ProverKey with less than 2^n powers is rejected
+        let short_pk = ProverKey::<G1> {
+            powers_of_g: Cow::Owned(pk.powers_of_g[..(1 << n_vars) - 1].to_vec()),
+        };
+        let transcript_p = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        assert!(
+            Zeromorph::<Bn254>::prove_evaluation(&short_pk, transcript_p, &cm, &v, &point).is_err()
+        );
+    }
+}
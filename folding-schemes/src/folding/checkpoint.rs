@@ -0,0 +1,104 @@
+/// Checkpointing of long-running IVC provers: the current state of a FoldingScheme can be written
+/// into any `Write` and restored later (eg. after the process got preempted) from a `Read`.
+///
+/// A checkpoint consists of a [`CheckpointHeader`] followed by the scheme-specific state (step
+/// number, initial & current state, running & incoming instances and witnesses, and the CycleFold
+/// running instance). The header binds the checkpoint to the circuits being folded and to the
+/// parameters in use, so that restoring it with a different F circuit or different parameters is
+/// detected instead of silently producing proofs that will not verify.
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, Write};
+use ark_std::Zero;
+
+use crate::frontend::FCircuit;
+use crate::{Error, FoldingScheme};
+
+/// version of the checkpoint format, bumped each time the serialized layout changes
+pub const CHECKPOINT_VERSION: u32 = 2;
+
+/// Header prepended to each checkpoint.
+#[derive(Debug, Clone, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct CheckpointHeader<F: PrimeField> {
+    pub version: u32,
+    /// IVC step at which the checkpoint was taken
+    pub step: F,
+    /// hash of the AugmentedFCircuit (which includes the F circuit) and CycleFold circuit shapes,
+    /// of the commitment scheme parameters and of the Poseidon config
+    pub params_hash: F,
+}
+
+/// Checkpoint is implemented by the folding schemes whose state can be snapshotted and resumed.
+/// Implementors only need to provide the digests and the (de)serialization of their state, the
+/// header handling and the consistency checks are shared.
+pub trait Checkpoint<C1: CurveGroup, C2: CurveGroup, FC>: FoldingScheme<C1, C2, FC>
+where
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    C2::BaseField: PrimeField,
+    FC: FCircuit<C1::ScalarField>,
+{
+    /// returns the hash of the constraint systems (of the AugmentedFCircuit and of the CycleFold
+    /// circuit) and of the parameters used by the scheme
+    fn params_hash(&self) -> Result<C1::ScalarField, Error>;
+
+    /// returns the current IVC step
+    fn step(&self) -> C1::ScalarField;
+
+    /// serializes the state of the scheme, without the parameters nor the constraint systems
+    fn serialize_state<W: Write>(&self, writer: W) -> Result<(), Error>;
+
+    /// overwrites the state of the scheme with the one read from the given reader
+    fn deserialize_state<R: Read>(&mut self, reader: R) -> Result<(), Error>;
+
+    fn checkpoint_header(&self) -> Result<CheckpointHeader<C1::ScalarField>, Error> {
+        Ok(CheckpointHeader {
+            version: CHECKPOINT_VERSION,
+            step: self.step(),
+            params_hash: self.params_hash()?,
+        })
+    }
+
+    /// writes the header and the current state of the scheme into the given writer
+    fn write_checkpoint<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        self.checkpoint_header()?
+            .serialize_compressed(&mut writer)?;
+        self.serialize_state(&mut writer)
+    }
+
+    /// restores a scheme from a checkpoint previously written with `write_checkpoint`. The given
+    /// prover params and step circuit must match the ones used when the checkpoint was taken,
+    /// otherwise `Error::CheckpointMismatch` is returned.
+    fn resume_from_checkpoint<R: Read>(
+        pp: &Self::ProverParam,
+        step_circuit: FC,
+        mut reader: R,
+    ) -> Result<Self, Error> {
+        let header = CheckpointHeader::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        if header.version != CHECKPOINT_VERSION {
+            return Err(Error::CheckpointMismatch(format!(
+                "checkpoint version {} (expected {})",
+                header.version, CHECKPOINT_VERSION
+            )));
+        }
+
+        // initialize the scheme with a dummy z_0, which is replaced by the one of the checkpoint
+        let state_len = step_circuit.state_len();
+        let mut folding_scheme =
+            Self::init(pp, step_circuit, vec![C1::ScalarField::zero(); state_len])?;
+        if folding_scheme.params_hash()? != header.params_hash {
+            return Err(Error::CheckpointMismatch("params hash".to_string()));
+        }
+
+        folding_scheme.deserialize_state(&mut reader)?;
+        if folding_scheme.step() != header.step {
+            return Err(Error::CheckpointMismatch("step".to_string()));
+        }
+        if folding_scheme.state().len() != state_len {
+            return Err(Error::NotExpectedLength(
+                folding_scheme.state().len(),
+                state_len,
+            ));
+        }
+        Ok(folding_scheme)
+    }
+}
@@ -12,7 +12,7 @@ use ark_ec::{CurveGroup, Group};
 /// - Uses `folding-schemes`' own `TranscriptVar` trait and `PoseidonTranscriptVar` struct
 /// - API made closer to gadgets found in `folding-schemes`
 use ark_ff::PrimeField;
-use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial};
+use ark_poly::univariate::DensePolynomial;
 use ark_r1cs_std::{
     alloc::{AllocVar, AllocationMode},
     eq::EqGadget,
@@ -94,13 +94,15 @@ where
             let mut proofs =
                 Vec::<DensePolynomialVar<C::ScalarField>>::with_capacity(cp.proofs.len());
             for proof in cp.proofs.iter() {
-                let poly = DensePolynomial::from_coefficients_slice(&proof.coeffs);
-                let proof = DensePolynomialVar::<C::ScalarField>::new_variable(
+                // allocate the coefficients directly instead of going through `DensePolynomial`,
+                // which would trim the trailing zero coefficients and thus change the shape of
+                // the circuit depending on the proof values
+                let coeffs = Vec::<FpVar<C::ScalarField>>::new_variable(
                     cs.clone(),
-                    || Ok(poly),
+                    || Ok(proof.coeffs.clone()),
                     mode,
                 )?;
-                proofs.push(proof);
+                proofs.push(DensePolynomialVar { coeffs });
             }
             Ok(Self { proofs, claim })
         })
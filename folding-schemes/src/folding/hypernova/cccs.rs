@@ -1,5 +1,6 @@
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::One;
 use ark_std::Zero;
 use std::sync::Arc;
@@ -7,10 +8,8 @@ use std::sync::Arc;
 use ark_std::rand::Rng;
 
 use crate::ccs::CCS;
-use crate::commitment::{
-    pedersen::{Params as PedersenParams, Pedersen},
-    CommitmentScheme,
-};
+use crate::commitment::CommitmentScheme;
+use crate::folding::circuits::nonnative::affine::nonnative_affine_to_field_elements;
 use crate::utils::hypercube::BooleanHypercube;
 use crate::utils::mle::dense_vec_to_dense_mle;
 use crate::utils::vec::mat_vec_mul;
@@ -18,14 +17,26 @@ use crate::utils::virtual_polynomial::{build_eq_x_r_vec, VirtualPolynomial};
 use crate::Error;
 
 /// Witness for the LCCCS & CCCS, containing the w vector, and the r_w used as randomness in the Pedersen commitment.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Witness<F: PrimeField> {
     pub w: Vec<F>,
     pub r_w: F, // randomness used in the Pedersen commitment of w
 }
 
+impl<F: PrimeField> Witness<F> {
+    pub fn new(w: Vec<F>) -> Self {
+        // note: at the current version, we don't use the blinding factors and we set them to 0
+        // always.
+        Self { w, r_w: F::zero() }
+    }
+    /// returns a dummy witness (all zeroes) with the length corresponding to the given CCS
+    pub fn dummy(ccs: &CCS<F>) -> Self {
+        Self::new(vec![F::zero(); ccs.n - ccs.l - 1])
+    }
+}
+
 /// Committed CCS instance
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct CCCS<C: CurveGroup> {
     // Commitment to witness
     pub C: C,
@@ -34,10 +45,10 @@ pub struct CCCS<C: CurveGroup> {
 }
 
 impl<F: PrimeField> CCS<F> {
-    pub fn to_cccs<R: Rng, C: CurveGroup>(
+    pub fn to_cccs<R: Rng, C: CurveGroup, CS: CommitmentScheme<C, H>, const H: bool>(
         &self,
         rng: &mut R,
-        pedersen_params: &PedersenParams<C>,
+        cs_params: &CS::ProverParams,
         z: &[C::ScalarField],
     ) -> Result<(CCCS<C>, Witness<C::ScalarField>), Error>
     where
@@ -45,8 +56,13 @@ impl<F: PrimeField> CCS<F> {
         C: CurveGroup<ScalarField = F>,
     {
         let w: Vec<C::ScalarField> = z[(1 + self.l)..].to_vec();
-        let r_w = C::ScalarField::rand(rng);
-        let C = Pedersen::<C, true>::commit(pedersen_params, &w, &r_w)?;
+        // if the commitment scheme is set to be hiding, set the random blinding parameter
+        let r_w = if H {
+            C::ScalarField::rand(rng)
+        } else {
+            C::ScalarField::zero()
+        };
+        let C = CS::commit(cs_params, &w, &r_w)?;
 
         Ok((
             CCCS::<C> {
@@ -92,19 +108,36 @@ impl<F: PrimeField> CCS<F> {
 }
 
 impl<C: CurveGroup> CCCS<C> {
+    pub fn dummy(l: usize) -> CCCS<C> {
+        CCCS::<C> {
+            C: C::zero(),
+            x: vec![C::ScalarField::zero(); l],
+        }
+    }
+
     /// Perform the check of the CCCS instance described at section 4.1
-    pub fn check_relation(
+    pub fn check_relation<CS: CommitmentScheme<C, H>, const H: bool>(
         &self,
-        pedersen_params: &PedersenParams<C>,
+        cs_params: &CS::ProverParams,
         ccs: &CCS<C::ScalarField>,
         w: &Witness<C::ScalarField>,
     ) -> Result<(), Error> {
         // check that C is the commitment of w. Notice that this is not verifying a Pedersen
         // opening, but checking that the commitment comes from committing to the witness.
-        if self.C != Pedersen::<C, true>::commit(pedersen_params, &w.w, &w.r_w)? {
+        if self.C != CS::commit(cs_params, &w.w, &w.r_w)? {
             return Err(Error::NotSatisfied);
         }
 
+        self.check_ccs_relation(ccs, w)
+    }
+
+    /// Checks that the CCCS relation is satisfied by the witness, without checking that `C` is
+    /// the commitment to `w`.
+    pub fn check_ccs_relation(
+        &self,
+        ccs: &CCS<C::ScalarField>,
+        w: &Witness<C::ScalarField>,
+    ) -> Result<(), Error> {
         // check CCCS relation
         let z: Vec<C::ScalarField> =
             [vec![C::ScalarField::one()], self.x.clone(), w.w.to_vec()].concat();
@@ -122,6 +155,18 @@ impl<C: CurveGroup> CCCS<C> {
     }
 }
 
+impl<C: CurveGroup> CCCS<C>
+where
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    /// Returns the CCCS as a vector of field elements, where the commitment is represented by the
+    /// limbs of its non-native coordinates. Compatible with `CCCSVar::to_constraint_field`.
+    pub fn to_field_elements(&self) -> Result<Vec<C::ScalarField>, Error> {
+        let (C_x, C_y) = nonnative_affine_to_field_elements::<C>(self.C)?;
+        Ok([C_x, C_y, self.x.clone()].concat())
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
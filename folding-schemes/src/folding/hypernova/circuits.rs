@@ -1,24 +1,43 @@
-/// Implementation of [HyperNova](https://eprint.iacr.org/2023/573.pdf) NIMFS verifier circuit
-use ark_crypto_primitives::sponge::Absorb;
+/// Implementation of [HyperNova](https://eprint.iacr.org/2023/573.pdf) circuits
+use ark_crypto_primitives::crh::{
+    poseidon::constraints::{CRHGadget, CRHParametersVar},
+    CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
 use ark_ec::{CurveGroup, Group};
 use ark_ff::PrimeField;
 use ark_r1cs_std::{
     alloc::{AllocVar, AllocationMode},
+    boolean::Boolean,
     eq::EqGadget,
     fields::{fp::FpVar, FieldVar},
+    groups::GroupOpsBounds,
+    prelude::CurveVar,
+    R1CSVar, ToConstraintFieldGadget,
 };
-use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, Namespace, SynthesisError};
+use ark_std::{fmt::Debug, One, Zero};
 use core::{borrow::Borrow, marker::PhantomData};
 
 use super::{cccs::CCCS, lcccs::LCCCS, nimfs::Proof};
+use crate::ccs::{r1cs::R1CS, CCS};
+use crate::constants::N_BITS_RO;
 use crate::folding::circuits::{
-    nonnative::affine::NonNativeAffineVar,
+    nonnative::{affine::NonNativeAffineVar, uint::NonNativeUintVar},
     sum_check::{IOPProofVar, SumCheckVerifierGadget, VPAuxInfoVar},
     utils::EqEvalGadget,
-    CF1,
+    CF1, CF2,
+};
+use crate::folding::nova::{
+    cyclefold::{
+        CycleFoldChallengeGadget, CycleFoldCommittedInstanceVar, NIFSFullGadget, CF_IO_LEN,
+    },
+    get_r1cs_from_cs, CommittedInstance,
 };
+use crate::frontend::FCircuit;
+use crate::transcript::{poseidon::PoseidonTranscriptVar, TranscriptVar};
 use crate::utils::virtual_polynomial::VPAuxInfo;
-use crate::{ccs::CCS, transcript::TranscriptVar};
+use crate::Error;
 
 /// Committed CCS instance
 #[derive(Debug, Clone)]
@@ -53,6 +72,18 @@ where
     }
 }
 
+impl<C> ToConstraintFieldGadget<CF1<C>> for CCCSVar<C>
+where
+    C: CurveGroup,
+    <C as ark_ec::CurveGroup>::BaseField: PrimeField,
+{
+    /// Returns the CCCS as a vector of FpVars, compatible with the native
+    /// `CCCS::to_field_elements`.
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<CF1<C>>>, SynthesisError> {
+        Ok([self.C.to_constraint_field()?, self.x.clone()].concat())
+    }
+}
+
 /// Linearized Committed CCS instance
 #[derive(Debug, Clone)]
 pub struct LCCCSVar<C: CurveGroup>
@@ -97,6 +128,53 @@ where
     }
 }
 
+impl<C> ToConstraintFieldGadget<CF1<C>> for LCCCSVar<C>
+where
+    C: CurveGroup,
+    <C as ark_ec::CurveGroup>::BaseField: PrimeField,
+{
+    /// Returns the LCCCS as a vector of FpVars, compatible with the native
+    /// `LCCCS::to_field_elements`.
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<CF1<C>>>, SynthesisError> {
+        Ok([
+            self.C.to_constraint_field()?,
+            vec![self.u.clone()],
+            self.x.clone(),
+            self.r_x.clone(),
+            self.v.clone(),
+        ]
+        .concat())
+    }
+}
+
+impl<C> LCCCSVar<C>
+where
+    C: CurveGroup,
+    <C as Group>::ScalarField: Absorb,
+    <C as ark_ec::CurveGroup>::BaseField: ark_ff::PrimeField,
+{
+    /// hash implements the LCCCS hash compatible with the native implementation from
+    /// LCCCS.hash.
+    /// Returns `H(i, z_0, z_i, U_i)`, where `i` can be `i` but also `i+1`, and `U` is the LCCCS.
+    /// Additionally it returns the vector of the field elements from the self parameters, so they
+    /// can be reused in other gadgets avoiding recalculating (reconstraining) them.
+    #[allow(clippy::type_complexity)]
+    pub fn hash(
+        self,
+        crh_params: &CRHParametersVar<CF1<C>>,
+        i: FpVar<CF1<C>>,
+        z_0: Vec<FpVar<CF1<C>>>,
+        z_i: Vec<FpVar<CF1<C>>>,
+    ) -> Result<(FpVar<CF1<C>>, Vec<FpVar<CF1<C>>>), SynthesisError> {
+        let U_vec = self.to_constraint_field()?;
+        let input = [vec![i], z_0, z_i, U_vec.clone()].concat();
+        Ok((
+            CRHGadget::<C::ScalarField>::evaluate(crh_params, &input)?,
+            U_vec,
+        ))
+    }
+}
+
 /// ProofVar defines a multifolding proof
 #[derive(Debug)]
 pub struct ProofVar<C: CurveGroup> {
@@ -153,6 +231,11 @@ impl<C: CurveGroup> NIMFSGadget<C>
 where
     <C as CurveGroup>::BaseField: PrimeField,
 {
+    /// Runs (in-circuit) the NIMFS.V, which outputs the new folded LCCCS instance together with
+    /// the rho_bits, which will be used in other parts of the AugmentedFCircuit. Notice that the
+    /// commitment `C` of the returned LCCCS is not folded, since its folding is delegated to the
+    /// CycleFold circuit.
+    #[allow(clippy::type_complexity)]
     pub fn verify(
         cs: ConstraintSystemRef<CF1<C>>,
         // only used the CCS params, not the matrices
@@ -162,7 +245,15 @@ where
         running_instances: &[LCCCSVar<C>],
         new_instances: &[CCCSVar<C>],
         proof: ProofVar<C>,
-    ) -> Result<LCCCSVar<C>, SynthesisError> {
+    ) -> Result<(LCCCSVar<C>, Vec<Boolean<CF1<C>>>), SynthesisError> {
+        // absorb the instances that are being folded
+        for lcccs in running_instances.iter() {
+            transcript.absorb_vec(&lcccs.to_constraint_field()?)?;
+        }
+        for cccs in new_instances.iter() {
+            transcript.absorb_vec(&cccs.to_constraint_field()?)?;
+        }
+
         // get the challenges
         let gamma_scalar_raw = C::ScalarField::from_le_bytes_mod_order(b"gamma");
         let gamma_scalar: FpVar<CF1<C>> =
@@ -181,7 +272,7 @@ where
             num_variables: ccs.s,
             phantom: PhantomData::<C::ScalarField>,
         };
-        let vp_aux_info = VPAuxInfoVar::<CF1<C>>::new_witness(cs.clone(), || Ok(vp_aux_info_raw))?;
+        let vp_aux_info = VPAuxInfoVar::<CF1<C>>::new_constant(cs.clone(), vp_aux_info_raw)?;
 
         // sumcheck
         // first, compute the expected sumcheck sum: \sum gamma^j v_j
@@ -189,8 +280,8 @@ where
         let mut gamma_j = FpVar::<C::ScalarField>::one();
         for running_instance in running_instances.iter() {
             for j in 0..running_instance.v.len() {
-                gamma_j *= gamma.clone();
                 sum_v_j_gamma += running_instance.v[j].clone() * gamma_j.clone();
+                gamma_j *= gamma.clone();
             }
         }
 
@@ -198,6 +289,9 @@ where
         let (e_vars, r_vars) =
             SumCheckVerifierGadget::<C>::verify(&proof.sc_proof, &vp_aux_info, &mut transcript)?;
 
+        // check that the claimed sum of the sumcheck matches the expected \sum gamma^j v_j
+        e_vars[0].enforce_equal(&sum_v_j_gamma)?;
+
         // extract the randomness from the sumcheck
         let r_x_prime = r_vars.clone();
 
@@ -221,16 +315,21 @@ where
         let rho_scalar_raw = C::ScalarField::from_le_bytes_mod_order(b"rho");
         let rho_scalar: FpVar<CF1<C>> = FpVar::<CF1<C>>::new_constant(cs.clone(), rho_scalar_raw)?;
         transcript.absorb(rho_scalar)?;
-        let rho: FpVar<CF1<C>> = transcript.get_challenge()?;
-
-        // return the folded instance
-        Self::fold(
-            running_instances,
-            new_instances,
-            proof.sigmas_thetas,
-            r_x_prime,
-            rho,
-        )
+        let rho_bits: Vec<Boolean<CF1<C>>> = transcript.get_challenge_nbits(N_BITS_RO)?;
+        let rho = Boolean::le_bits_to_fp_var(&rho_bits)?;
+
+        // return the folded instance, together with the rho_bits so they can be reused in other
+        // parts of the AugmentedFCircuit
+        Ok((
+            Self::fold(
+                running_instances,
+                new_instances,
+                proof.sigmas_thetas,
+                r_x_prime,
+                rho,
+            )?,
+            rho_bits,
+        ))
     }
 
     #[allow(clippy::type_complexity)]
@@ -286,7 +385,8 @@ where
         }
 
         Ok(LCCCSVar::<C> {
-            C: lcccs[0].C.clone(), // WIP this will come from the cyclefold circuit
+            // C is not folded here, its folding is delegated to the CycleFold circuit
+            C: NonNativeAffineVar::new_constant(ConstraintSystemRef::None, C::zero())?,
             u: u_folded,
             x: x_folded,
             r_x: r_x_prime,
@@ -345,6 +445,383 @@ fn compute_c_gadget<F: PrimeField>(
     Ok(c)
 }
 
+/// `AugmentedFCircuit` enhances the original step function `F`, so that it can be used in
+/// recursive arguments. The method `AugmentedFCircuit::generate_constraints` implements the
+/// constraints of F' of the HyperNova+CycleFold IVC, where the NIMFS verifier is run in-circuit
+/// and the folding of the commitments is delegated to the `CycleFoldCircuit`.
+/// At each step it folds `mu` LCCCS instances (the running instance `U_i` and `mu-1` extra ones
+/// `Us`) and `nu` CCCS instances (the incoming instance `u_i` and `nu-1` extra ones `us`).
+#[derive(Debug, Clone)]
+pub struct AugmentedFCircuit<
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+> where
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    pub _c2: PhantomData<C2>,
+    pub _gc2: PhantomData<GC2>,
+    pub poseidon_config: PoseidonConfig<CF1<C1>>,
+    pub ccs: CCS<C1::ScalarField>, // CCS of the AugmentedFCircuit
+    pub mu: usize,                 // number of LCCCS instances folded at each step
+    pub nu: usize,                 // number of CCCS instances folded at each step
+    pub i: Option<CF1<C1>>,
+    pub i_usize: Option<usize>,
+    pub z_0: Option<Vec<C1::ScalarField>>,
+    pub z_i: Option<Vec<C1::ScalarField>>,
+    pub external_inputs: Option<Vec<C1::ScalarField>>,
+    pub u_i_C: Option<C1>, // u_i.C
+    pub U_i: Option<LCCCS<C1>>,
+    pub Us: Option<Vec<LCCCS<C1>>>, // other running instances, of length mu-1
+    pub us: Option<Vec<CCCS<C1>>>,  // other incoming instances, of length nu-1
+    pub U_i1_C: Option<C1>,         // U_{i+1}.C
+    // intermediate values of the Horner evaluation of U_{i+1}.C = \sum_j rho^j * C_j, where
+    // C_stars[0] = C_{mu+nu-2} + rho * C_{mu+nu-1}
+    pub C_stars: Option<Vec<C1>>,
+    pub F: FC,              // F circuit
+    pub x: Option<CF1<C1>>, // public input (u_{i+1}.x[0])
+    pub nimfs_proof: Option<Proof<C1>>,
+
+    // cyclefold verifier on C1
+    // Here cf_u_i_cmW and cf_cmT contain the values of each of the mu+nu-1 CycleFold circuits,
+    // which compute the random linear combination of the commitments C
+    pub cf_u_i_cmW: Option<Vec<C2>>,           // input
+    pub cf_U_i: Option<CommittedInstance<C2>>, // input, RelaxedR1CS CycleFold instance
+    pub cf_x: Option<CF1<C1>>,                 // public input (u_{i+1}.x[1])
+    pub cf_cmT: Option<Vec<C2>>,
+}
+
+impl<C1, C2, GC2, FC> AugmentedFCircuit<C1, C2, GC2, FC>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    pub fn default(
+        poseidon_config: &PoseidonConfig<CF1<C1>>,
+        F_circuit: FC,
+        mu: usize,
+        nu: usize,
+        ccs: CCS<C1::ScalarField>,
+    ) -> Self {
+        Self {
+            _c2: PhantomData,
+            _gc2: PhantomData,
+            poseidon_config: poseidon_config.clone(),
+            ccs,
+            mu,
+            nu,
+            i: None,
+            i_usize: None,
+            z_0: None,
+            z_i: None,
+            external_inputs: None,
+            u_i_C: None,
+            U_i: None,
+            Us: None,
+            us: None,
+            U_i1_C: None,
+            C_stars: None,
+            F: F_circuit,
+            x: None,
+            nimfs_proof: None,
+            cf_u_i_cmW: None,
+            cf_U_i: None,
+            cf_x: None,
+            cf_cmT: None,
+        }
+    }
+
+    /// Returns an empty AugmentedFCircuit. If the CCS of the AugmentedFCircuit is not provided,
+    /// it is computed through `upper_bound_ccs`.
+    pub fn empty(
+        poseidon_config: &PoseidonConfig<CF1<C1>>,
+        F_circuit: FC,
+        mu: usize,
+        nu: usize,
+        ccs: Option<CCS<C1::ScalarField>>,
+    ) -> Result<Self, Error> {
+        if mu == 0 || nu == 0 {
+            return Err(Error::NotSupported(
+                "mu and nu must be at least 1".to_string(),
+            ));
+        }
+        let initial_ccs = CCS {
+            // m, n values are overwritten by `upper_bound_ccs`, we set s, l >= 1 so the NIMFS
+            // gadget can be built for the placeholder CCS
+            m: 0,
+            n: 0,
+            l: 2, // io_len
+            s: 1,
+            s_prime: 1,
+            ..CCS::from_r1cs(R1CS::empty())
+        };
+        let mut augmented_f_circuit =
+            Self::default(poseidon_config, F_circuit, mu, nu, initial_ccs);
+        augmented_f_circuit.ccs = match ccs {
+            Some(ccs) => ccs,
+            None => augmented_f_circuit.upper_bound_ccs()?,
+        };
+        Ok(augmented_f_circuit)
+    }
+
+    /// This method computes the CCS parameters. This is used because there is a circular
+    /// dependency between the AugmentedFCircuit CCS and the CCS parameters m & n & s & s'.
+    /// For a stable FCircuit circuit, the CCS parameters can be computed in advance and can be
+    /// fed in as parameter for the AugmentedFCircuit::empty() method to avoid computing them
+    /// there.
+    pub fn upper_bound_ccs(&self) -> Result<CCS<C1::ScalarField>, Error> {
+        let mut augmented_f_circuit = self.clone();
+        let mut ccs = self.ccs.clone();
+        // the NIMFS gadget depends on the CCS parameters (s, l), which at the same time depend
+        // on the size of the AugmentedFCircuit, so we iterate until they stabilize
+        loop {
+            augmented_f_circuit.ccs = ccs.clone();
+            let r1cs = get_r1cs_from_cs::<CF1<C1>>(augmented_f_circuit.clone())?;
+            let new_ccs = CCS::from_r1cs(r1cs);
+            if new_ccs.s == ccs.s && new_ccs.l == ccs.l {
+                return Ok(new_ccs);
+            }
+            ccs = new_ccs;
+        }
+    }
+}
+
+impl<C1, C2, GC2, FC> ConstraintSynthesizer<CF1<C1>> for AugmentedFCircuit<C1, C2, GC2, FC>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<CF1<C1>>) -> Result<(), SynthesisError> {
+        let i = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self.i.unwrap_or_else(CF1::<C1>::zero))
+        })?;
+        let z_0 = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .z_0
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.state_len()]))
+        })?;
+        let z_i = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .z_i
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.state_len()]))
+        })?;
+        let external_inputs = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .external_inputs
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.external_inputs_len()]))
+        })?;
+
+        let U_dummy = LCCCS::<C1>::dummy(self.ccs.l, self.ccs.t, self.ccs.s);
+        let u_dummy = CCCS::<C1>::dummy(self.ccs.l);
+        let U_i =
+            LCCCSVar::<C1>::new_witness(cs.clone(), || Ok(self.U_i.unwrap_or(U_dummy.clone())))?;
+        let Us = Vec::<LCCCSVar<C1>>::new_witness(cs.clone(), || {
+            Ok(self.Us.unwrap_or(vec![U_dummy.clone(); self.mu - 1]))
+        })?;
+        let us = Vec::<CCCSVar<C1>>::new_witness(cs.clone(), || {
+            Ok(self.us.unwrap_or(vec![u_dummy.clone(); self.nu - 1]))
+        })?;
+        let U_i1_C = NonNativeAffineVar::new_witness(cs.clone(), || {
+            Ok(self.U_i1_C.unwrap_or_else(C1::zero))
+        })?;
+        let C_stars = Vec::<NonNativeAffineVar<C1>>::new_witness(cs.clone(), || {
+            Ok(self
+                .C_stars
+                .unwrap_or(vec![C1::zero(); self.mu + self.nu - 2]))
+        })?;
+        let nimfs_proof_dummy = Proof::<C1>::dummy(&self.ccs, self.mu, self.nu);
+        let nimfs_proof = ProofVar::<C1>::new_witness(cs.clone(), || {
+            Ok(self.nimfs_proof.unwrap_or(nimfs_proof_dummy))
+        })?;
+
+        let cf_u_dummy = CommittedInstance::dummy(CF_IO_LEN);
+        let cf_U_i = CycleFoldCommittedInstanceVar::<C2, GC2>::new_witness(cs.clone(), || {
+            Ok(self.cf_U_i.unwrap_or(cf_u_dummy.clone()))
+        })?;
+        let cf_u_i_cmW = Vec::<GC2>::new_witness(cs.clone(), || {
+            Ok(self
+                .cf_u_i_cmW
+                .unwrap_or(vec![C2::zero(); self.mu + self.nu - 1]))
+        })?;
+        let cf_cmT = Vec::<GC2>::new_witness(cs.clone(), || {
+            Ok(self
+                .cf_cmT
+                .unwrap_or(vec![C2::zero(); self.mu + self.nu - 1]))
+        })?;
+
+        let crh_params = CRHParametersVar::<C1::ScalarField>::new_constant(
+            cs.clone(),
+            self.poseidon_config.clone(),
+        )?;
+
+        // get z_{i+1} from the F circuit
+        let i_usize = self.i_usize.unwrap_or(0);
+        let z_i1 =
+            self.F
+                .generate_step_constraints(cs.clone(), i_usize, z_i.clone(), external_inputs)?;
+
+        let is_basecase = i.is_zero()?;
+
+        // Primary Part
+        // P.1. Compute u_i.x
+        // u_i.x[0] = H(i, z_0, z_i, U_i)
+        let (u_i_x, _) = U_i
+            .clone()
+            .hash(&crh_params, i.clone(), z_0.clone(), z_i.clone())?;
+        // u_i.x[1] = H(cf_U_i)
+        let (cf_u_i_x, cf_U_i_vec) = cf_U_i.clone().hash(&crh_params)?;
+
+        // P.2. Construct u_i
+        let u_i = CCCSVar::<C1> {
+            // u_i.C is provided by the prover as witness
+            C: NonNativeAffineVar::new_witness(cs.clone(), || {
+                Ok(self.u_i_C.unwrap_or(C1::zero()))
+            })?,
+            // u_i.x is computed in step 1
+            x: vec![u_i_x, cf_u_i_x],
+        };
+
+        // P.3. NIMFS.verify, obtains U_{i+1} by folding [U_i, Us] & [u_i, us].
+        // Notice that NIMFSGadget::verify does not fold the commitments C. We set `U_i1.C` to the
+        // unconstrained witness `U_i1_C`, its correctness is checked on the other curve.
+        let all_Us = [vec![U_i.clone()], Us].concat();
+        let all_us = [vec![u_i.clone()], us].concat();
+        let transcript = PoseidonTranscriptVar::<CF1<C1>>::new(cs.clone(), &self.poseidon_config);
+        let (mut U_i1, rho_bits) = NIMFSGadget::<C1>::verify(
+            cs.clone(),
+            &self.ccs,
+            transcript,
+            &all_Us,
+            &all_us,
+            nimfs_proof,
+        )?;
+        U_i1.C = U_i1_C;
+
+        // P.4.a compute and check the first output of F'
+        // Base case: u_{i+1}.x[0] == H((1, z_0, z_{i+1}, U_{\bot})
+        // Non-base case: u_{i+1}.x[0] == H((i+1, z_0, z_{i+1}, U_{i+1})
+        let (u_i1_x, _) = U_i1.clone().hash(
+            &crh_params,
+            i + FpVar::<CF1<C1>>::one(),
+            z_0.clone(),
+            z_i1.clone(),
+        )?;
+        let (u_i1_x_base, _) = LCCCSVar::new_constant(cs.clone(), U_dummy)?.hash(
+            &crh_params,
+            FpVar::<CF1<C1>>::one(),
+            z_0.clone(),
+            z_i1.clone(),
+        )?;
+        let x = FpVar::new_input(cs.clone(), || Ok(self.x.unwrap_or(u_i1_x_base.value()?)))?;
+        x.enforce_equal(&is_basecase.select(&u_i1_x_base, &u_i1_x)?)?;
+
+        // convert rho_bits to a `NonNativeFieldVar`
+        let rho_nonnat = {
+            let mut bits = rho_bits;
+            bits.resize(C1::BaseField::MODULUS_BIT_SIZE as usize, Boolean::FALSE);
+            NonNativeUintVar::from(&bits)
+        };
+
+        // CycleFold part
+        // The commitment U_{i+1}.C = \sum_j rho^j * C_j, where C_j are the commitments of
+        // [U_i, Us, u_i, us], is computed through a chain of CycleFold circuits following
+        // Horner's method: the first circuit computes C_{n-2} + rho * C_{n-1}, and each of the
+        // next ones computes p3 = p1 + rho * p2, being p1 the commitment C_j and p2 the output of
+        // the previous circuit. The output of the last circuit is U_{i+1}.C.
+        let Cs = all_Us
+            .iter()
+            .map(|U| U.C.clone())
+            .chain(all_us.iter().map(|u| u.C.clone()))
+            .collect::<Vec<_>>();
+        let n = Cs.len();
+        let p1s = Cs[..n - 1].iter().rev().cloned().collect::<Vec<_>>();
+        let p2s = [vec![Cs[n - 1].clone()], C_stars.clone()].concat();
+        let p3s = [C_stars, vec![U_i1.C.clone()]].concat();
+
+        let mut cf_U_i1 = cf_U_i;
+        let mut cf_U_i1_vec = cf_U_i_vec;
+        for j in 0..n - 1 {
+            // C.1. Construct `cf_u_i`, ensuring that it has as public inputs the coordinates of
+            // the points p1, p2, p3
+            let cf_u_i = CycleFoldCommittedInstanceVar {
+                // cf_u_i.cmE = 0
+                cmE: GC2::zero(),
+                // cf_u_i.u = 1
+                u: NonNativeUintVar::new_constant(cs.clone(), C1::BaseField::one())?,
+                // cf_u_i.cmW is provided by the prover as witness
+                cmW: cf_u_i_cmW[j].clone(),
+                x: vec![
+                    rho_nonnat.clone(),
+                    p1s[j].x.clone(),
+                    p1s[j].y.clone(),
+                    p2s[j].x.clone(),
+                    p2s[j].y.clone(),
+                    p3s[j].x.clone(),
+                    p3s[j].y.clone(),
+                ],
+            };
+
+            // C.2. nifs.verify, obtains cf_U_{i+1} by folding cf_u_i & cf_U_i.
+            // compute cf_r = H(cf_u_i, cf_U_i, cf_cmT)
+            // cf_r_bits is denoted by rho* in the paper.
+            let cf_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_gadget(
+                cs.clone(),
+                &self.poseidon_config,
+                cf_U_i1_vec,
+                cf_u_i.clone(),
+                cf_cmT[j].clone(),
+            )?;
+            // Convert cf_r_bits to a `NonNativeFieldVar`
+            let cf_r_nonnat = {
+                let mut bits = cf_r_bits.clone();
+                bits.resize(C1::BaseField::MODULUS_BIT_SIZE as usize, Boolean::FALSE);
+                NonNativeUintVar::from(&bits)
+            };
+            // Fold cf_u_i & cf_U_i into cf_U_{i+1}
+            cf_U_i1 = NIFSFullGadget::<C2, GC2>::fold_committed_instance(
+                cf_r_bits,
+                cf_r_nonnat,
+                cf_cmT[j].clone(),
+                cf_U_i1,
+                cf_u_i,
+            )?;
+            cf_U_i1_vec = cf_U_i1.to_constraint_field()?;
+        }
+
+        // Back to Primary Part
+        // P.4.b compute and check the second output of F'
+        // Base case: u_{i+1}.x[1] == H(cf_U_{\bot})
+        // Non-base case: u_{i+1}.x[1] == H(cf_U_{i+1})
+        let (cf_u_i1_x, _) = cf_U_i1.hash(&crh_params)?;
+        let (cf_u_i1_x_base, _) =
+            CycleFoldCommittedInstanceVar::new_constant(cs.clone(), cf_u_dummy)?
+                .hash(&crh_params)?;
+        let cf_x = FpVar::new_input(cs.clone(), || {
+            Ok(self.cf_x.unwrap_or(cf_u_i1_x_base.value()?))
+        })?;
+        cf_x.enforce_equal(&is_basecase.select(&cf_u_i1_x_base, &cf_u_i1_x)?)?;
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ark_pallas::{Fr, Projective};
@@ -399,13 +876,25 @@ mod tests {
         // Create the LCCCS instances out of z_lcccs
         let mut lcccs_instances = Vec::new();
         for z_i in z_lcccs.iter() {
-            let (inst, _) = ccs.to_lcccs(&mut rng, &pedersen_params, z_i).unwrap();
+            let (inst, _) = ccs
+                .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                    &mut rng,
+                    &pedersen_params,
+                    z_i,
+                )
+                .unwrap();
             lcccs_instances.push(inst);
         }
         // Create the CCCS instance out of z_cccs
         let mut cccs_instances = Vec::new();
         for z_i in z_cccs.iter() {
-            let (inst, _) = ccs.to_cccs(&mut rng, &pedersen_params, z_i).unwrap();
+            let (inst, _) = ccs
+                .to_cccs::<_, Projective, Pedersen<Projective, true>, true>(
+                    &mut rng,
+                    &pedersen_params,
+                    z_i,
+                )
+                .unwrap();
             cccs_instances.push(inst);
         }
 
@@ -491,7 +980,13 @@ mod tests {
         let mut lcccs_instances = Vec::new();
         let mut w_lcccs = Vec::new();
         for z_i in z_lcccs.iter() {
-            let (running_instance, w) = ccs.to_lcccs(&mut rng, &pedersen_params, z_i).unwrap();
+            let (running_instance, w) = ccs
+                .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                    &mut rng,
+                    &pedersen_params,
+                    z_i,
+                )
+                .unwrap();
             lcccs_instances.push(running_instance);
             w_lcccs.push(w);
         }
@@ -499,7 +994,13 @@ mod tests {
         let mut cccs_instances = Vec::new();
         let mut w_cccs = Vec::new();
         for z_i in z_cccs.iter() {
-            let (new_instance, w) = ccs.to_cccs(&mut rng, &pedersen_params, z_i).unwrap();
+            let (new_instance, w) = ccs
+                .to_cccs::<_, Projective, Pedersen<Projective, true>, true>(
+                    &mut rng,
+                    &pedersen_params,
+                    z_i,
+                )
+                .unwrap();
             cccs_instances.push(new_instance);
             w_cccs.push(w);
         }
@@ -538,7 +1039,11 @@ mod tests {
 
         // Check that the folded LCCCS instance is a valid instance with respect to the folded witness
         folded_lcccs
-            .check_relation(&pedersen_params, &ccs, &folded_witness)
+            .check_relation::<Pedersen<Projective, true>, true>(
+                &pedersen_params,
+                &ccs,
+                &folded_witness,
+            )
             .unwrap();
 
         // allocate circuit inputs
@@ -553,7 +1058,7 @@ mod tests {
             ProofVar::<Projective>::new_witness(cs.clone(), || Ok(proof.clone())).unwrap();
         let transcriptVar = PoseidonTranscriptVar::<Fr>::new(cs.clone(), &poseidon_config);
 
-        let folded_lcccsVar = NIMFSGadget::<Projective>::verify(
+        let (folded_lcccsVar, _) = NIMFSGadget::<Projective>::verify(
             cs.clone(),
             &ccs,
             transcriptVar,
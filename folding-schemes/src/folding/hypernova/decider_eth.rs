@@ -0,0 +1,348 @@
+/// This file implements the onchain (Ethereum's EVM) decider for HyperNova+CycleFold.
+use ark_bn254::Bn254;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::Groth16;
+use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+use ark_snark::SNARK;
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::{One, Zero};
+use core::marker::PhantomData;
+
+pub use super::decider_eth_circuit::{DeciderEthCircuit, KZGChallengesGadget};
+use super::HyperNova;
+use crate::commitment::{
+    kzg::{Proof as KZGProof, KZG},
+    pedersen::Params as PedersenParams,
+    CommitmentScheme,
+};
+use crate::folding::circuits::{nonnative::affine::NonNativeAffineVar, CF2};
+use crate::folding::nova::decider_eth::{point2_to_eth_format, point_to_eth_format};
+use crate::frontend::FCircuit;
+use crate::Error;
+use crate::{Decider as DeciderTrait, FoldingScheme};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Proof<C1, CS1, S>
+where
+    C1: CurveGroup,
+    CS1: CommitmentScheme<C1, ProverChallenge = C1::ScalarField, Challenge = C1::ScalarField>,
+    S: SNARK<C1::ScalarField>,
+{
+    pub snark_proof: S::Proof,
+    // openings of the commitments U_i.C and u_i.C
+    pub kzg_proofs: [CS1::Proof; 2],
+    // the KZG challenges are provided by the prover, but in-circuit they are checked to match
+    // the in-circuit computed computed ones.
+    pub kzg_challenges: [C1::ScalarField; 2],
+}
+
+/// Onchain Decider for HyperNova, for ethereum use cases. Instead of doing a last fold, the
+/// circuit checks directly both the running LCCCS instance and the incoming CCCS instance, so the
+/// verifier only needs their commitments (the rest of the instances' values are witnesses of the
+/// circuit, bound through the hash checked against u_i.x).
+#[derive(Clone, Debug)]
+pub struct Decider<C1, GC1, C2, GC2, FC, CS1, CS2, S, FS> {
+    _c1: PhantomData<C1>,
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    _fc: PhantomData<FC>,
+    _cs1: PhantomData<CS1>,
+    _cs2: PhantomData<CS2>,
+    _s: PhantomData<S>,
+    _fs: PhantomData<FS>,
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2, S, FS> DeciderTrait<C1, C2, FC, FS>
+    for Decider<C1, GC1, C2, GC2, FC, CS1, CS2, S, FS>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<
+        C1,
+        ProverChallenge = C1::ScalarField,
+        Challenge = C1::ScalarField,
+        Proof = KZGProof<C1>,
+    >, // KZG commitment, where challenge is C1::Fr elem
+    // enforce that the CS2 is Pedersen commitment scheme, since we're at Ethereum's EVM decider
+    CS2: CommitmentScheme<C2, ProverParams = PedersenParams<C2>>,
+    S: SNARK<C1::ScalarField>,
+    FS: FoldingScheme<C1, C2, FC>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'b> &'b GC2: GroupOpsBounds<'b, C2, GC2>,
+    // constrain FS into HyperNova, since this is a Decider specifically for HyperNova
+    HyperNova<C1, GC1, C2, GC2, FC, CS1, CS2>: From<FS>,
+{
+    type ProverParam = (S::ProvingKey, CS1::ProverParams);
+    type Proof = Proof<C1, CS1, S>;
+    type VerifierParam = (S::VerifyingKey, CS1::VerifierParams);
+    type PublicInput = Vec<C1::ScalarField>;
+    type CommittedInstanceWithWitness = ();
+    /// the commitments of the running LCCCS instance (U_i.C) and of the incoming CCCS instance
+    /// (u_i.C)
+    type CommittedInstance = C1;
+
+    fn prove(
+        pp: Self::ProverParam,
+        mut rng: impl RngCore + CryptoRng,
+        folding_scheme: FS,
+    ) -> Result<Self::Proof, Error> {
+        let (snark_pk, cs_pk): (S::ProvingKey, CS1::ProverParams) = pp;
+
+        let circuit = DeciderEthCircuit::<C1, GC1, C2, GC2, CS1, CS2>::from_hypernova::<FC>(
+            folding_scheme.into(),
+        )?;
+
+        let snark_proof = S::prove(&snark_pk, circuit.clone(), &mut rng)
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let W_i = circuit.W_i.ok_or(Error::MissingValue("W_i".to_string()))?;
+        let w_i = circuit.w_i.ok_or(Error::MissingValue("w_i".to_string()))?;
+
+        // get the challenges that have been already computed when preparing the circuit inputs in
+        // the above `from_hypernova` call
+        let kzg_challenges = circuit
+            .kzg_challenges
+            .ok_or(Error::MissingValue("kzg_challenges".to_string()))?;
+
+        // generate KZG proofs
+        let U_C_proof = CS1::prove_with_challenge(
+            &cs_pk,
+            kzg_challenges[0],
+            &W_i.w,
+            &C1::ScalarField::zero(),
+            None,
+        )?;
+        let u_C_proof = CS1::prove_with_challenge(
+            &cs_pk,
+            kzg_challenges[1],
+            &w_i.w,
+            &C1::ScalarField::zero(),
+            None,
+        )?;
+
+        Ok(Self::Proof {
+            snark_proof,
+            kzg_proofs: [U_C_proof, u_C_proof],
+            kzg_challenges: [kzg_challenges[0], kzg_challenges[1]],
+        })
+    }
+
+    fn verify(
+        vp: Self::VerifierParam,
+        i: C1::ScalarField,
+        z_0: Vec<C1::ScalarField>,
+        z_i: Vec<C1::ScalarField>,
+        running_instance: &Self::CommittedInstance,
+        incoming_instance: &Self::CommittedInstance,
+        proof: &Self::Proof,
+    ) -> Result<bool, Error> {
+        if i <= C1::ScalarField::one() {
+            return Err(Error::NotEnoughSteps);
+        }
+
+        let (snark_vk, cs_vk): (S::VerifyingKey, CS1::VerifierParams) = vp;
+
+        let (U_C_x, U_C_y) = NonNativeAffineVar::inputize(*running_instance)?;
+        let (u_C_x, u_C_y) = NonNativeAffineVar::inputize(*incoming_instance)?;
+
+        let public_input: Vec<C1::ScalarField> = vec![
+            vec![i],
+            z_0,
+            z_i,
+            U_C_x,
+            U_C_y,
+            u_C_x,
+            u_C_y,
+            proof.kzg_challenges.to_vec(),
+            vec![
+                proof.kzg_proofs[0].eval, // eval of U_i.C
+                proof.kzg_proofs[1].eval, // eval of u_i.C
+            ],
+        ]
+        .concat();
+
+        let snark_v = S::verify(&snark_vk, &public_input, &proof.snark_proof)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        if !snark_v {
+            return Err(Error::SNARKVerificationFail);
+        }
+
+        // we're at the Ethereum EVM case, so the CS1 is KZG commitments
+        CS1::verify_with_challenge(
+            &cs_vk,
+            proof.kzg_challenges[0],
+            running_instance,
+            &proof.kzg_proofs[0],
+        )?;
+        CS1::verify_with_challenge(
+            &cs_vk,
+            proof.kzg_challenges[1],
+            incoming_instance,
+            &proof.kzg_proofs[1],
+        )?;
+
+        Ok(true)
+    }
+}
+
+/// Prepares solidity calldata for calling the HyperNovaDecider contract
+pub fn prepare_calldata(
+    function_signature_check: [u8; 4],
+    i: ark_bn254::Fr,
+    z_0: Vec<ark_bn254::Fr>,
+    z_i: Vec<ark_bn254::Fr>,
+    running_instance: &ark_bn254::G1Projective,
+    incoming_instance: &ark_bn254::G1Projective,
+    proof: Proof<ark_bn254::G1Projective, KZG<'static, Bn254>, Groth16<Bn254>>,
+) -> Result<Vec<u8>, Error> {
+    Ok(vec![
+        function_signature_check.to_vec(),
+        i.into_bigint().to_bytes_be(), // i
+        z_0.iter()
+            .flat_map(|v| v.into_bigint().to_bytes_be())
+            .collect::<Vec<u8>>(), // z_0
+        z_i.iter()
+            .flat_map(|v| v.into_bigint().to_bytes_be())
+            .collect::<Vec<u8>>(), // z_i
+        point_to_eth_format(running_instance.into_affine())?, // U_i_C
+        point_to_eth_format(incoming_instance.into_affine())?, // u_i_C
+        point_to_eth_format(proof.snark_proof.a)?, // pA
+        point2_to_eth_format(proof.snark_proof.b)?, // pB
+        point_to_eth_format(proof.snark_proof.c)?, // pC
+        proof.kzg_challenges[0].into_bigint().to_bytes_be(), // challenge_U
+        proof.kzg_challenges[1].into_bigint().to_bytes_be(), // challenge_u
+        proof.kzg_proofs[0].eval.into_bigint().to_bytes_be(), // eval U
+        proof.kzg_proofs[1].eval.into_bigint().to_bytes_be(), // eval u
+        point_to_eth_format(proof.kzg_proofs[0].proof.into_affine())?, // U kzg_proof
+        point_to_eth_format(proof.kzg_proofs[1].proof.into_affine())?, // u kzg_proof
+    ]
+    .concat())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as Projective};
+    use ark_groth16::Groth16;
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+    use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
+    use std::time::Instant;
+
+    use crate::commitment::kzg::{ProverKey as KZGProverKey, KZG};
+    use crate::commitment::pedersen::Pedersen;
+    use crate::folding::hypernova::{get_cs_params_len, ProverParams};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_canonical_config;
+
+    #[test]
+    fn test_decider() {
+        // use HyperNova as FoldingScheme
+        type HN = HyperNova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            KZG<'static, Bn254>,
+            Pedersen<Projective2>,
+        >;
+        type DECIDER = Decider<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            KZG<'static, Bn254>,
+            Pedersen<Projective2>,
+            Groth16<Bn254>, // here we define the Snark to use in the decider
+            HN,             // here we define the FoldingScheme to use
+        >;
+
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let z_0 = vec![Fr::from(3_u32)];
+
+        let (cs_len, cf_cs_len) = get_cs_params_len::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+        >(&poseidon_config, F_circuit, 1, 1)
+        .unwrap();
+        let start = Instant::now();
+        let (kzg_pk, kzg_vk): (KZGProverKey<Projective>, KZGVerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        println!("generated KZG params, {:?}", start.elapsed());
+
+        let prover_params =
+            ProverParams::<Projective, Projective2, KZG<Bn254>, Pedersen<Projective2>> {
+                poseidon_config: poseidon_config.clone(),
+                cs_params: kzg_pk.clone(),
+                cf_cs_params: cf_pedersen_params,
+                ccs: None,
+                mu: 1,
+                nu: 1,
+            };
+
+        let start = Instant::now();
+        let mut hypernova = HN::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        println!("HyperNova initialized, {:?}", start.elapsed());
+        let start = Instant::now();
+        hypernova.prove_step(&mut rng, vec![]).unwrap();
+        println!("prove_step, {:?}", start.elapsed());
+        hypernova.prove_step(&mut rng, vec![]).unwrap(); // do a 2nd step
+
+        // generate Groth16 setup
+        let circuit = DeciderEthCircuit::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            KZG<Bn254>,
+            Pedersen<Projective2>,
+        >::from_hypernova::<CubicFCircuit<Fr>>(hypernova.clone())
+        .unwrap();
+        let mut rng = rand::rngs::OsRng;
+
+        let start = Instant::now();
+        let (g16_pk, g16_vk) =
+            Groth16::<Bn254>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+        println!("Groth16 setup, {:?}", start.elapsed());
+
+        // decider proof generation
+        let start = Instant::now();
+        let decider_pp = (g16_pk, kzg_pk);
+        let proof = DECIDER::prove(decider_pp, rng, hypernova.clone()).unwrap();
+        println!("Decider prove, {:?}", start.elapsed());
+
+        // decider proof verification
+        let start = Instant::now();
+        let decider_vp = (g16_vk, kzg_vk);
+        let verified = DECIDER::verify(
+            decider_vp,
+            hypernova.i,
+            hypernova.z_0,
+            hypernova.z_i,
+            &hypernova.U_i.C,
+            &hypernova.u_i.C,
+            &proof,
+        )
+        .unwrap();
+        assert!(verified);
+        println!("Decider verify, {:?}", start.elapsed());
+    }
+}
@@ -0,0 +1,594 @@
+/// This file implements the onchain (Ethereum's EVM) decider circuit for HyperNova+CycleFold. For
+/// non-ethereum use cases, other more efficient approaches can be used.
+use ark_crypto_primitives::crh::poseidon::constraints::CRHParametersVar;
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::PrimeField;
+use ark_poly::{EvaluationDomain, Polynomial, Radix2EvaluationDomain};
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    groups::GroupOpsBounds,
+    prelude::CurveVar,
+    ToConstraintFieldGadget,
+};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_std::Zero;
+use core::marker::PhantomData;
+
+use super::{
+    cccs::{Witness, CCCS},
+    circuits::{CCCSVar, LCCCSVar},
+    lcccs::LCCCS,
+    HyperNova,
+};
+use crate::ccs::{r1cs::R1CS, CCS};
+use crate::commitment::{pedersen::Params as PedersenParams, CommitmentScheme};
+use crate::folding::circuits::{
+    nonnative::affine::{nonnative_affine_to_field_elements, NonNativeAffineVar},
+    CF1, CF2,
+};
+use crate::folding::nova::{CommittedInstance, Witness as NovaWitness};
+use crate::frontend::FCircuit;
+use crate::transcript::{
+    poseidon::{PoseidonTranscript, PoseidonTranscriptVar},
+    Transcript, TranscriptVar,
+};
+use crate::utils::{
+    gadgets::{MatrixGadget, SparseMatrixVar, VectorGadget},
+    vec::poly_from_vec,
+};
+use crate::Error;
+
+/// Circuit that implements the in-circuit checks needed for the HyperNova's onchain (Ethereum's
+/// EVM) verification.
+#[derive(Clone, Debug)]
+pub struct DeciderEthCircuit<C1, GC1, C2, GC2, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    _c1: PhantomData<C1>,
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    _cs1: PhantomData<CS1>,
+    _cs2: PhantomData<CS2>,
+
+    /// E vector's length of the CycleFold instance witness
+    pub cf_E_len: usize,
+    /// CCS of the Augmented Function circuit
+    pub ccs: CCS<C1::ScalarField>,
+    /// R1CS of the CycleFold circuit
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+    /// CycleFold PedersenParams over C2
+    pub cf_pedersen_params: PedersenParams<C2>,
+    pub poseidon_config: PoseidonConfig<CF1<C1>>,
+    pub i: Option<CF1<C1>>,
+    /// initial state
+    pub z_0: Option<Vec<C1::ScalarField>>,
+    /// current i-th state
+    pub z_i: Option<Vec<C1::ScalarField>>,
+    /// HyperNova instances
+    pub U_i: Option<LCCCS<C1>>,
+    pub W_i: Option<Witness<C1::ScalarField>>,
+    pub u_i: Option<CCCS<C1>>,
+    pub w_i: Option<Witness<C1::ScalarField>>,
+    /// CycleFold running instance
+    pub cf_U_i: Option<CommittedInstance<C2>>,
+    pub cf_W_i: Option<NovaWitness<C2>>,
+
+    /// KZG challenges, for the commitments U_i.C and u_i.C
+    pub kzg_challenges: Option<Vec<C1::ScalarField>>,
+    /// evaluations of the polynomials of W_i.w and w_i.w at the KZG challenges
+    pub kzg_evaluations: Option<Vec<C1::ScalarField>>,
+}
+
+impl<C1, GC1, C2, GC2, CS1, CS2> DeciderEthCircuit<C1, GC1, C2, GC2, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    CS1: CommitmentScheme<C1>,
+    // enforce that the CS2 is Pedersen commitment scheme, since we're at Ethereum's EVM decider
+    CS2: CommitmentScheme<C2, ProverParams = PedersenParams<C2>>,
+    <C1 as Group>::ScalarField: Absorb,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+{
+    pub fn from_hypernova<FC: FCircuit<C1::ScalarField>>(
+        hypernova: HyperNova<C1, GC1, C2, GC2, FC, CS1, CS2>,
+    ) -> Result<Self, Error> {
+        // compute the KZG challenges used as inputs in the circuit
+        let kzg_challenges = KZGChallengesGadget::<C1>::get_challenges_native(
+            &hypernova.poseidon_config,
+            &[hypernova.U_i.C, hypernova.u_i.C],
+        )?;
+
+        // get KZG evals
+        let kzg_evaluations = [&hypernova.W_i.w, &hypernova.w_i.w]
+            .iter()
+            .zip(kzg_challenges.iter())
+            .map(|(w, c)| Ok(poly_from_vec(w.to_vec())?.evaluate(c)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self {
+            _c1: PhantomData,
+            _gc1: PhantomData,
+            _c2: PhantomData,
+            _gc2: PhantomData,
+            _cs1: PhantomData,
+            _cs2: PhantomData,
+
+            cf_E_len: hypernova.cf_W_i.E.len(),
+            ccs: hypernova.ccs,
+            cf_r1cs: hypernova.cf_r1cs,
+            cf_pedersen_params: hypernova.cf_cs_params,
+            poseidon_config: hypernova.poseidon_config,
+            i: Some(hypernova.i),
+            z_0: Some(hypernova.z_0),
+            z_i: Some(hypernova.z_i),
+            U_i: Some(hypernova.U_i),
+            W_i: Some(hypernova.W_i),
+            u_i: Some(hypernova.u_i),
+            w_i: Some(hypernova.w_i),
+            cf_U_i: Some(hypernova.cf_U_i),
+            cf_W_i: Some(hypernova.cf_W_i),
+            kzg_challenges: Some(kzg_challenges),
+            kzg_evaluations: Some(kzg_evaluations),
+        })
+    }
+}
+
+impl<C1, GC1, C2, GC2, CS1, CS2> ConstraintSynthesizer<CF1<C1>>
+    for DeciderEthCircuit<C1, GC1, C2, GC2, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>>,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'b> &'b GC2: GroupOpsBounds<'b, C2, GC2>,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<CF1<C1>>) -> Result<(), SynthesisError> {
+        let U_dummy_native = LCCCS::<C1>::dummy(self.ccs.l, self.ccs.t, self.ccs.s);
+        let u_dummy_native = CCCS::<C1>::dummy(self.ccs.l);
+        let w_dummy_native = Witness::<C1::ScalarField>::dummy(&self.ccs);
+
+        let U_i_native = self.U_i.unwrap_or(U_dummy_native);
+        let u_i_native = self.u_i.unwrap_or(u_dummy_native);
+
+        // public inputs
+        let i =
+            FpVar::<CF1<C1>>::new_input(cs.clone(), || Ok(self.i.unwrap_or_else(CF1::<C1>::zero)))?;
+        let z_0 = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
+            Ok(self.z_0.unwrap_or(vec![CF1::<C1>::zero()]))
+        })?;
+        let z_i = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
+            Ok(self.z_i.unwrap_or(vec![CF1::<C1>::zero()]))
+        })?;
+        // the commitments of U_i & u_i are public inputs, since their openings at the KZG
+        // challenges are verified outside of the circuit
+        let U_i_C = NonNativeAffineVar::<C1>::new_input(cs.clone(), || Ok(U_i_native.C))?;
+        let u_i_C = NonNativeAffineVar::<C1>::new_input(cs.clone(), || Ok(u_i_native.C))?;
+        let kzg_challenges = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
+            Ok(self.kzg_challenges.unwrap_or(vec![CF1::<C1>::zero(); 2]))
+        })?;
+        let kzg_evaluations = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
+            Ok(self.kzg_evaluations.unwrap_or(vec![CF1::<C1>::zero(); 2]))
+        })?;
+
+        // witnesses
+        let U_i = LCCCSVar::<C1> {
+            C: U_i_C,
+            u: FpVar::new_witness(cs.clone(), || Ok(U_i_native.u))?,
+            x: Vec::new_witness(cs.clone(), || Ok(U_i_native.x.clone()))?,
+            r_x: Vec::new_witness(cs.clone(), || Ok(U_i_native.r_x.clone()))?,
+            v: Vec::new_witness(cs.clone(), || Ok(U_i_native.v.clone()))?,
+        };
+        let u_i = CCCSVar::<C1> {
+            C: u_i_C,
+            x: Vec::new_witness(cs.clone(), || Ok(u_i_native.x.clone()))?,
+        };
+        let W_i = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self.W_i.unwrap_or(w_dummy_native.clone()).w)
+        })?;
+        let w_i = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self.w_i.unwrap_or(w_dummy_native.clone()).w)
+        })?;
+
+        let crh_params = CRHParametersVar::<C1::ScalarField>::new_constant(
+            cs.clone(),
+            self.poseidon_config.clone(),
+        )?;
+
+        let M = self
+            .ccs
+            .M
+            .iter()
+            .map(|M_j| SparseMatrixVar::<_, _, FpVar<CF1<C1>>>::new_constant(cs.clone(), M_j))
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        // 1. check LCCCS relation of U_i: v_j == \tilde{M_j z}(r_x), where z = (u, x, W_i)
+        let z_U: Vec<FpVar<CF1<C1>>> = [vec![U_i.u.clone()], U_i.x.clone(), W_i.clone()].concat();
+        let eq_r_x = eq_evals_gadget(&U_i.r_x);
+        for (M_j, v_j) in M.iter().zip(U_i.v.iter()) {
+            let M_j_z = M_j.mul_vector(&z_U)?;
+            let computed_v_j: FpVar<CF1<C1>> =
+                M_j_z.iter().zip(eq_r_x.iter()).map(|(a, b)| a * b).sum();
+            computed_v_j.enforce_equal(v_j)?;
+        }
+
+        // 2. check CCCS relation of u_i: \sum_i c_i * \prod_{j \in S_i} M_j z == 0, where
+        // z = (1, x, w_i)
+        let z_u: Vec<FpVar<CF1<C1>>> = [vec![FpVar::one()], u_i.x.clone(), w_i.clone()].concat();
+        let M_z_u = M
+            .iter()
+            .map(|M_j| M_j.mul_vector(&z_u))
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+        let mut sum = vec![FpVar::<CF1<C1>>::zero(); self.ccs.m];
+        for (c_i, S_i) in self.ccs.c.iter().zip(self.ccs.S.iter()) {
+            let mut prod = vec![FpVar::<CF1<C1>>::constant(*c_i); self.ccs.m];
+            for j in S_i {
+                prod = prod.hadamard(&M_z_u[*j])?;
+            }
+            sum = sum.add(&prod)?;
+        }
+        for sum_k in sum.iter() {
+            sum_k.enforce_equal(&FpVar::zero())?;
+        }
+
+        // 3.a u_i.x[0] == H(i, z_0, z_i, U_i)
+        let (u_i_x, _) = U_i
+            .clone()
+            .hash(&crh_params, i.clone(), z_0.clone(), z_i.clone())?;
+        (u_i.x[0]).enforce_equal(&u_i_x)?;
+
+        #[cfg(feature = "light-test")]
+        println!("[WARNING]: Running with the 'light-test' feature, skipping the big part of the DeciderEthCircuit.\n           Only for testing purposes.");
+
+        // The following two checks (and their respective allocations) are disabled for normal
+        // tests since they take several millions of constraints and would take several minutes
+        // (and RAM) to run the test. It is active by default, and not active only when
+        // 'light-test' feature is used.
+        #[cfg(not(feature = "light-test"))]
+        {
+            // imports here instead of at the top of the file, so we avoid having multiple
+            // `#[cfg(not(test))]`
+            use crate::commitment::pedersen::PedersenGadget;
+            use crate::folding::circuits::nonnative::uint::NonNativeUintVar;
+            use crate::folding::nova::{
+                cyclefold::{CycleFoldCommittedInstanceVar, CF_IO_LEN},
+                decider_eth_circuit::{CycleFoldWitnessVar, R1CSVar, RelaxedR1CSGadget},
+            };
+            use ark_r1cs_std::{boolean::Boolean, ToBitsGadget};
+
+            let cf_u_dummy_native = CommittedInstance::<C2>::dummy(CF_IO_LEN);
+            let w_dummy_native = NovaWitness::<C2>::new(
+                vec![C2::ScalarField::zero(); self.cf_r1cs.A.n_cols - 1 - self.cf_r1cs.l],
+                self.cf_E_len,
+            );
+            let cf_U_i = CycleFoldCommittedInstanceVar::<C2, GC2>::new_witness(cs.clone(), || {
+                Ok(self.cf_U_i.unwrap_or_else(|| cf_u_dummy_native.clone()))
+            })?;
+            let cf_W_i = CycleFoldWitnessVar::<C2>::new_witness(cs.clone(), || {
+                Ok(self.cf_W_i.unwrap_or(w_dummy_native.clone()))
+            })?;
+
+            // 3.b u_i.x[1] == H(cf_U_i)
+            let (cf_u_i_x, _) = cf_U_i.clone().hash(&crh_params)?;
+            (u_i.x[1]).enforce_equal(&cf_u_i_x)?;
+
+            // 4. check Pedersen commitments of cf_U_i.{cmE, cmW}
+            let H = GC2::new_constant(cs.clone(), self.cf_pedersen_params.h)?;
+            let G = Vec::<GC2>::new_constant(cs.clone(), self.cf_pedersen_params.generators)?;
+            let cf_W_i_E_bits: Result<Vec<Vec<Boolean<CF1<C1>>>>, SynthesisError> =
+                cf_W_i.E.iter().map(|E_i| E_i.to_bits_le()).collect();
+            let cf_W_i_W_bits: Result<Vec<Vec<Boolean<CF1<C1>>>>, SynthesisError> =
+                cf_W_i.W.iter().map(|W_i| W_i.to_bits_le()).collect();
+
+            let computed_cmE = PedersenGadget::<C2, GC2>::commit(
+                H.clone(),
+                G.clone(),
+                cf_W_i_E_bits?,
+                cf_W_i.rE.to_bits_le()?,
+            )?;
+            cf_U_i.cmE.enforce_equal(&computed_cmE)?;
+            let computed_cmW =
+                PedersenGadget::<C2, GC2>::commit(H, G, cf_W_i_W_bits?, cf_W_i.rW.to_bits_le()?)?;
+            cf_U_i.cmW.enforce_equal(&computed_cmW)?;
+
+            let cf_r1cs =
+                R1CSVar::<C1::BaseField, CF1<C1>, NonNativeUintVar<CF1<C1>>>::new_witness(
+                    cs.clone(),
+                    || Ok(self.cf_r1cs.clone()),
+                )?;
+
+            // 5. check RelaxedR1CS of cf_U_i
+            let cf_z_U = [vec![cf_U_i.u.clone()], cf_U_i.x.to_vec(), cf_W_i.W.to_vec()].concat();
+            RelaxedR1CSGadget::check_nonnative(cf_r1cs, cf_W_i.E, cf_U_i.u.clone(), cf_z_U)?;
+        }
+
+        // 6. check KZG challenges
+        let incircuit_challenges = KZGChallengesGadget::<C1>::get_challenges_gadget(
+            cs.clone(),
+            &self.poseidon_config,
+            &[U_i.C.clone(), u_i.C.clone()],
+        )?;
+        incircuit_challenges.enforce_equal(&kzg_challenges)?;
+
+        // 7. check that the KZG evaluations match the polynomials of W_i & w_i at the challenges,
+        // so that the witnesses used in the checks 1 & 2 are the ones committed in U_i.C & u_i.C
+        for ((w, challenge), eval) in [W_i, w_i]
+            .into_iter()
+            .zip(incircuit_challenges)
+            .zip(kzg_evaluations.iter())
+        {
+            let incircuit_eval = evaluate_gadget::<CF1<C1>>(w, challenge)?;
+            incircuit_eval.enforce_equal(eval)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the evaluations of eq(r, y) for all the y \in {0,1}^{|r|}, where the bits of y are
+/// taken in little-endian, following the variables order used by `dense_vec_to_dense_mle`.
+fn eq_evals_gadget<F: PrimeField>(r: &[FpVar<F>]) -> Vec<FpVar<F>> {
+    let mut evals = vec![FpVar::<F>::one()];
+    for r_i in r.iter() {
+        let (lo, hi): (Vec<FpVar<F>>, Vec<FpVar<F>>) = evals
+            .iter()
+            .map(|e| {
+                let e_r_i = e * r_i;
+                (e - &e_r_i, e_r_i)
+            })
+            .unzip();
+        evals = [lo, hi].concat();
+    }
+    evals
+}
+
+/// Returns the evaluation at the given point of the polynomial interpolated from the given vector
+/// over the smallest radix-2 domain that fits it, as done by `poly_from_vec`. The evaluation is
+/// computed through the barycentric form of the Lagrange basis, which avoids interpolating the
+/// polynomial in-circuit:
+/// p(X) = (X^n - 1)/n * \sum_k v_k * w^k / (X - w^k)
+pub(crate) fn evaluate_gadget<F: PrimeField>(
+    v: Vec<FpVar<F>>,
+    point: FpVar<F>,
+) -> Result<FpVar<F>, SynthesisError> {
+    let domain = Radix2EvaluationDomain::<F>::new(v.len()).ok_or(SynthesisError::Unsatisfiable)?;
+
+    let mut point_n = point.clone();
+    for _ in 0..domain.log_size_of_group {
+        point_n.square_in_place()?;
+    }
+
+    let mut sum = FpVar::<F>::zero();
+    for (v_k, w_k) in v.iter().zip(domain.elements()) {
+        sum += v_k * (point.clone() - w_k).inverse()? * w_k;
+    }
+    Ok(sum * (point_n - F::one()) * domain.size_inv)
+}
+
+/// Gadget that computes the KZG challenges for the given commitments, also offers the rust native
+/// implementation compatible with the gadget.
+pub struct KZGChallengesGadget<C: CurveGroup> {
+    _c: PhantomData<C>,
+}
+impl<C> KZGChallengesGadget<C>
+where
+    C: CurveGroup,
+    C::ScalarField: PrimeField,
+    <C as CurveGroup>::BaseField: PrimeField,
+    C::ScalarField: Absorb,
+{
+    pub fn get_challenges_native(
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        cms: &[C],
+    ) -> Result<Vec<C::ScalarField>, Error> {
+        let transcript = &mut PoseidonTranscript::<C>::new(poseidon_config);
+        cms.iter()
+            .map(|cm| {
+                let (cm_x_limbs, cm_y_limbs) = nonnative_affine_to_field_elements(*cm)?;
+                transcript.absorb_vec(&cm_x_limbs);
+                transcript.absorb_vec(&cm_y_limbs);
+                Ok(transcript.get_challenge())
+            })
+            .collect()
+    }
+    // compatible with the native get_challenges_native
+    pub fn get_challenges_gadget(
+        cs: ConstraintSystemRef<C::ScalarField>,
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        cms: &[NonNativeAffineVar<C>],
+    ) -> Result<Vec<FpVar<C::ScalarField>>, SynthesisError> {
+        let mut transcript = PoseidonTranscriptVar::<CF1<C>>::new(cs.clone(), poseidon_config);
+        cms.iter()
+            .map(|cm| {
+                transcript.absorb_vec(&cm.to_constraint_field()?)?;
+                transcript.get_challenge()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_pallas::{constraints::GVar, Fr, Projective};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::{One, UniformRand};
+    use ark_vesta::{constraints::GVar as GVar2, Projective as Projective2};
+
+    use crate::commitment::pedersen::Pedersen;
+    use crate::folding::hypernova::{get_cs_params_len, ProverParams, VerifierParams};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_canonical_config;
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_decider_circuit() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let z_0 = vec![Fr::from(3_u32)];
+
+        // get the CS & CF_CS len
+        let (cs_len, cf_cs_len) = get_cs_params_len::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+        >(&poseidon_config, F_circuit, 1, 1)
+        .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config: poseidon_config.clone(),
+                cs_params: pedersen_params,
+                cf_cs_params: cf_pedersen_params,
+                ccs: None,
+                mu: 1,
+                nu: 1,
+            };
+
+        type HN = HyperNova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >;
+
+        // generate a HyperNova instance and do two steps of it, so the running instance is not
+        // the dummy one
+        let mut hypernova = HN::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        hypernova.prove_step(&mut rng, vec![]).unwrap();
+        hypernova.prove_step(&mut rng, vec![]).unwrap();
+        let ivc_v = hypernova.clone();
+        let verifier_params = VerifierParams::<Projective, Projective2> {
+            poseidon_config: poseidon_config.clone(),
+            ccs: ivc_v.clone().ccs,
+            cf_r1cs: ivc_v.clone().cf_r1cs,
+        };
+        let (running_instance, incoming_instance, cyclefold_instance) = ivc_v.instances();
+        HN::verify(
+            verifier_params,
+            z_0,
+            ivc_v.z_i,
+            Fr::from(2_u32),
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+        )
+        .unwrap();
+
+        // load the DeciderEthCircuit from the generated HyperNova instance
+        let decider_circuit = DeciderEthCircuit::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >::from_hypernova(hypernova)
+        .unwrap();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+
+        // generate the constraints and check that are satisfied by the inputs
+        decider_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    // checks that the gadget and native implementations of the challenges computation match
+    #[test]
+    fn test_kzg_challenges_gadget() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let cms = vec![Projective::rand(&mut rng), Projective::rand(&mut rng)];
+
+        // compute the challenges natively
+        let challenges =
+            KZGChallengesGadget::<Projective>::get_challenges_native(&poseidon_config, &cms)
+                .unwrap();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let cmsVar =
+            Vec::<NonNativeAffineVar<Projective>>::new_witness(cs.clone(), || Ok(cms)).unwrap();
+        let challengesVar = KZGChallengesGadget::<Projective>::get_challenges_gadget(
+            cs.clone(),
+            &poseidon_config,
+            &cmsVar,
+        )
+        .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // check that the natively computed and in-circuit computed challenges match
+        assert_eq!(challengesVar.value().unwrap(), challenges);
+    }
+
+    #[test]
+    fn test_evaluate_gadget() {
+        let mut rng = ark_std::test_rng();
+        // use a length which is not a power of two, to check the padding of the domain
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(5000)
+            .collect();
+        let point = Fr::rand(&mut rng);
+        let eval = poly_from_vec(v.clone()).unwrap().evaluate(&point);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let vVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(v)).unwrap();
+        let pointVar = FpVar::<Fr>::new_witness(cs.clone(), || Ok(point)).unwrap();
+
+        let evalVar = evaluate_gadget::<Fr>(vVar, pointVar).unwrap();
+        assert_eq!(evalVar.value().unwrap(), eval);
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_eq_evals_gadget() {
+        let mut rng = ark_std::test_rng();
+        let r: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(4)
+            .collect();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let rVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(r.clone())).unwrap();
+        let evals = eq_evals_gadget(&rVar);
+
+        for (y, eval) in evals.iter().enumerate() {
+            // eq(r, y) = \prod_i (r_i * y_i + (1 - r_i) * (1 - y_i))
+            let expected = r.iter().enumerate().fold(Fr::one(), |acc, (i, r_i)| {
+                if (y >> i) & 1 == 1 {
+                    acc * r_i
+                } else {
+                    acc * (Fr::one() - r_i)
+                }
+            });
+            assert_eq!(eval.value().unwrap(), expected);
+        }
+    }
+}
@@ -1,22 +1,24 @@
-use ark_ec::CurveGroup;
+use ark_crypto_primitives::{
+    crh::{poseidon::CRH, CRHScheme},
+    sponge::{poseidon::PoseidonConfig, Absorb},
+};
+use ark_ec::{CurveGroup, Group};
 use ark_ff::PrimeField;
 use ark_poly::DenseMultilinearExtension;
 use ark_poly::MultilinearExtension;
-
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::rand::Rng;
 
 use super::cccs::Witness;
 use crate::ccs::CCS;
-use crate::commitment::{
-    pedersen::{Params as PedersenParams, Pedersen},
-    CommitmentScheme,
-};
+use crate::commitment::CommitmentScheme;
+use crate::folding::circuits::nonnative::affine::nonnative_affine_to_field_elements;
 use crate::utils::mle::dense_vec_to_dense_mle;
 use crate::utils::vec::mat_vec_mul;
 use crate::Error;
 
 /// Linearized Committed CCS instance
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct LCCCS<C: CurveGroup> {
     // Commitment to witness
     pub C: C,
@@ -31,10 +33,10 @@ pub struct LCCCS<C: CurveGroup> {
 }
 
 impl<F: PrimeField> CCS<F> {
-    pub fn to_lcccs<R: Rng, C: CurveGroup>(
+    pub fn to_lcccs<R: Rng, C: CurveGroup, CS: CommitmentScheme<C, H>, const H: bool>(
         &self,
         rng: &mut R,
-        pedersen_params: &PedersenParams<C>,
+        cs_params: &CS::ProverParams,
         z: &[C::ScalarField],
     ) -> Result<(LCCCS<C>, Witness<C::ScalarField>), Error>
     where
@@ -42,8 +44,13 @@ impl<F: PrimeField> CCS<F> {
         C: CurveGroup<ScalarField = F>,
     {
         let w: Vec<C::ScalarField> = z[(1 + self.l)..].to_vec();
-        let r_w = C::ScalarField::rand(rng);
-        let C = Pedersen::<C, true>::commit(pedersen_params, &w, &r_w)?;
+        // if the commitment scheme is set to be hiding, set the random blinding parameter
+        let r_w = if H {
+            C::ScalarField::rand(rng)
+        } else {
+            C::ScalarField::zero()
+        };
+        let C = CS::commit(cs_params, &w, &r_w)?;
 
         let r_x: Vec<C::ScalarField> = (0..self.s).map(|_| C::ScalarField::rand(rng)).collect();
 
@@ -73,19 +80,40 @@ impl<F: PrimeField> CCS<F> {
 }
 
 impl<C: CurveGroup> LCCCS<C> {
+    /// returns a dummy LCCCS, which is satisfied by the dummy (all zeroes) witness
+    pub fn dummy(l: usize, t: usize, s: usize) -> LCCCS<C> {
+        LCCCS::<C> {
+            C: C::zero(),
+            u: C::ScalarField::zero(),
+            x: vec![C::ScalarField::zero(); l],
+            r_x: vec![C::ScalarField::zero(); s],
+            v: vec![C::ScalarField::zero(); t],
+        }
+    }
+
     /// Perform the check of the LCCCS instance described at section 4.2
-    pub fn check_relation(
+    pub fn check_relation<CS: CommitmentScheme<C, H>, const H: bool>(
         &self,
-        pedersen_params: &PedersenParams<C>,
+        cs_params: &CS::ProverParams,
         ccs: &CCS<C::ScalarField>,
         w: &Witness<C::ScalarField>,
     ) -> Result<(), Error> {
         // check that C is the commitment of w. Notice that this is not verifying a Pedersen
         // opening, but checking that the Commitment comes from committing to the witness.
-        if self.C != Pedersen::<C, true>::commit(pedersen_params, &w.w, &w.r_w)? {
+        if self.C != CS::commit(cs_params, &w.w, &w.r_w)? {
             return Err(Error::NotSatisfied);
         }
 
+        self.check_ccs_relation(ccs, w)
+    }
+
+    /// Checks that the LCCCS relation is satisfied by the witness, without checking that `C` is
+    /// the commitment to `w`.
+    pub fn check_ccs_relation(
+        &self,
+        ccs: &CCS<C::ScalarField>,
+        w: &Witness<C::ScalarField>,
+    ) -> Result<(), Error> {
         // check CCS relation
         let z: Vec<C::ScalarField> = [vec![self.u], self.x.clone(), w.w.to_vec()].concat();
 
@@ -104,6 +132,44 @@ impl<C: CurveGroup> LCCCS<C> {
     }
 }
 
+impl<C: CurveGroup> LCCCS<C>
+where
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    /// Returns the LCCCS as a vector of field elements, where the commitment is represented by the
+    /// limbs of its non-native coordinates. Compatible with `LCCCSVar::to_constraint_field`.
+    pub fn to_field_elements(&self) -> Result<Vec<C::ScalarField>, Error> {
+        let (C_x, C_y) = nonnative_affine_to_field_elements::<C>(self.C)?;
+        Ok([
+            C_x,
+            C_y,
+            vec![self.u],
+            self.x.clone(),
+            self.r_x.clone(),
+            self.v.clone(),
+        ]
+        .concat())
+    }
+
+    /// hash implements the committed instance hash compatible with the gadget implemented in
+    /// hypernova/circuits.rs::LCCCSVar.hash.
+    /// Returns `H(i, z_0, z_i, U_i)`, where `i` can be `i` but also `i+1`, and `U_i` is the LCCCS.
+    pub fn hash(
+        &self,
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        i: C::ScalarField,
+        z_0: Vec<C::ScalarField>,
+        z_i: Vec<C::ScalarField>,
+    ) -> Result<C::ScalarField, Error> {
+        CRH::<C::ScalarField>::evaluate(
+            poseidon_config,
+            [vec![i], z_0, z_i, self.to_field_elements()?].concat(),
+        )
+        .map_err(|e| Error::Other(e.to_string()))
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use ark_pallas::{Fr, Projective};
@@ -118,6 +184,7 @@ pub mod tests {
         r1cs::R1CS,
         tests::{get_test_ccs, get_test_z},
     };
+    use crate::commitment::pedersen::Pedersen;
     use crate::utils::hypercube::BooleanHypercube;
     use crate::utils::virtual_polynomial::{build_eq_x_r_vec, VirtualPolynomial};
 
@@ -159,7 +226,13 @@ pub mod tests {
         let (pedersen_params, _) =
             Pedersen::<Projective>::setup(&mut rng, ccs.n - ccs.l - 1).unwrap();
 
-        let (lcccs, _) = ccs.to_lcccs(&mut rng, &pedersen_params, &z).unwrap();
+        let (lcccs, _) = ccs
+            .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                &mut rng,
+                &pedersen_params,
+                &z,
+            )
+            .unwrap();
         // with our test vector coming from R1CS, v should have length 3
         assert_eq!(lcccs.v.len(), 3);
 
@@ -191,7 +264,13 @@ pub mod tests {
         let (pedersen_params, _) =
             Pedersen::<Projective>::setup(&mut rng, ccs.n - ccs.l - 1).unwrap();
         // Compute v_j with the right z
-        let (lcccs, _) = ccs.to_lcccs(&mut rng, &pedersen_params, &z).unwrap();
+        let (lcccs, _) = ccs
+            .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                &mut rng,
+                &pedersen_params,
+                &z,
+            )
+            .unwrap();
         // with our test vector coming from R1CS, v should have length 3
         assert_eq!(lcccs.v.len(), 3);
 
@@ -1,6 +1,1029 @@
 /// Implements the scheme described in [HyperNova](https://eprint.iacr.org/2023/573.pdf)
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, Write};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::{fmt::Debug, One, Zero};
+use core::marker::PhantomData;
+
 pub mod cccs;
 pub mod circuits;
+pub mod decider_eth;
+pub mod decider_eth_circuit;
 pub mod lcccs;
 pub mod nimfs;
 pub mod utils;
+
+use cccs::{Witness, CCCS};
+use circuits::AugmentedFCircuit;
+use lcccs::LCCCS;
+use nimfs::NIMFS;
+
+use crate::ccs::{
+    r1cs::{extract_w_x, R1CS},
+    CCS,
+};
+use crate::commitment::CommitmentScheme;
+use crate::folding::checkpoint::Checkpoint;
+use crate::folding::circuits::CF2;
+use crate::folding::nova::{
+    cyclefold::{fold_cyclefold_circuit, CycleFoldCircuit},
+    get_cm_coordinates, get_r1cs_from_cs,
+    traits::NovaR1CS,
+    CommittedInstance, Witness as NovaWitness,
+};
+use crate::frontend::FCircuit;
+use crate::transcript::{poseidon::PoseidonTranscript, Transcript};
+use crate::utils::{hash_bytes, serialize_poseidon_config};
+use crate::Error;
+use crate::FoldingScheme;
+
+#[derive(Debug, Clone)]
+pub struct ProverParams<C1, C2, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    pub cs_params: CS1::ProverParams,
+    pub cf_cs_params: CS2::ProverParams,
+    // if ccs is set, it will be used, if not, it will be computed at runtime
+    pub ccs: Option<CCS<C1::ScalarField>>,
+    // number of LCCCS (mu) and CCCS (nu) instances folded at each IVC step, including the
+    // running instance U_i and the incoming instance u_i
+    pub mu: usize,
+    pub nu: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifierParams<C1: CurveGroup, C2: CurveGroup> {
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    pub ccs: CCS<C1::ScalarField>,
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+}
+
+/// Implements HyperNova+CycleFold's IVC, described in
+/// [HyperNova](https://eprint.iacr.org/2023/573.pdf) and
+/// [CycleFold](https://eprint.iacr.org/2023/1192.pdf), following the FoldingScheme trait
+#[derive(Clone, Debug)]
+pub struct HyperNova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    /// CCS of the Augmented Function circuit
+    pub ccs: CCS<C1::ScalarField>,
+    /// R1CS of the CycleFold circuit
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    /// CommitmentScheme::ProverParams over C1
+    pub cs_params: CS1::ProverParams,
+    /// CycleFold CommitmentScheme::ProverParams, over C2
+    pub cf_cs_params: CS2::ProverParams,
+    /// F circuit, the circuit that is being folded
+    pub F: FC,
+    /// number of LCCCS instances folded at each step
+    pub mu: usize,
+    /// number of CCCS instances folded at each step
+    pub nu: usize,
+    pub i: C1::ScalarField,
+    /// initial state
+    pub z_0: Vec<C1::ScalarField>,
+    /// current i-th state
+    pub z_i: Vec<C1::ScalarField>,
+    /// HyperNova instances
+    pub W_i: Witness<C1::ScalarField>,
+    pub U_i: LCCCS<C1>,
+    pub w_i: Witness<C1::ScalarField>,
+    pub u_i: CCCS<C1>,
+
+    /// CycleFold running instance
+    pub cf_W_i: NovaWitness<C2>,
+    pub cf_U_i: CommittedInstance<C2>,
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> HyperNova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    /// Returns a new running instance (LCCCS) of the AugmentedFCircuit, obtained from the base
+    /// case step of an IVC starting at the given `state`. It can be used as one of the `mu-1`
+    /// other running instances of `prove_step_with_instances`.
+    pub fn new_running_instance(
+        &self,
+        mut rng: impl RngCore,
+        state: Vec<C1::ScalarField>,
+        external_inputs: Vec<C1::ScalarField>,
+    ) -> Result<(LCCCS<C1>, Witness<C1::ScalarField>), Error> {
+        let z = self.new_instance_z(state, external_inputs)?;
+        self.ccs
+            .to_lcccs::<_, C1, CS1, false>(&mut rng, &self.cs_params, &z)
+    }
+
+    /// Returns a new incoming instance (CCCS) of the AugmentedFCircuit, obtained from the base
+    /// case step of an IVC starting at the given `state`. It can be used as one of the `nu-1`
+    /// other incoming instances of `prove_step_with_instances`.
+    pub fn new_incoming_instance(
+        &self,
+        mut rng: impl RngCore,
+        state: Vec<C1::ScalarField>,
+        external_inputs: Vec<C1::ScalarField>,
+    ) -> Result<(CCCS<C1>, Witness<C1::ScalarField>), Error> {
+        let z = self.new_instance_z(state, external_inputs)?;
+        self.ccs
+            .to_cccs::<_, C1, CS1, false>(&mut rng, &self.cs_params, &z)
+    }
+
+    // returns the z vector, ie. (1, x, w), of the AugmentedFCircuit for the base case step
+    // starting at the given state
+    fn new_instance_z(
+        &self,
+        state: Vec<C1::ScalarField>,
+        external_inputs: Vec<C1::ScalarField>,
+    ) -> Result<Vec<C1::ScalarField>, Error> {
+        if state.len() != self.F.state_len() {
+            return Err(Error::NotSameLength(
+                "state.len()".to_string(),
+                state.len(),
+                "F.state_len()".to_string(),
+                self.F.state_len(),
+            ));
+        }
+        if external_inputs.len() != self.F.external_inputs_len() {
+            return Err(Error::NotSameLength(
+                "F.external_inputs_len()".to_string(),
+                self.F.external_inputs_len(),
+                "external_inputs.len()".to_string(),
+                external_inputs.len(),
+            ));
+        }
+
+        // the public inputs x are left unset, so that the circuit computes them for the base case
+        let augmented_f_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
+            i: Some(C1::ScalarField::zero()),
+            i_usize: Some(0),
+            z_0: Some(state.clone()),
+            z_i: Some(state),
+            external_inputs: Some(external_inputs),
+            ..AugmentedFCircuit::default(
+                &self.poseidon_config,
+                self.F.clone(),
+                self.mu,
+                self.nu,
+                self.ccs.clone(),
+            )
+        };
+
+        let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+        augmented_f_circuit.generate_constraints(cs.clone())?;
+        let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+        let (w, x) = extract_w_x::<C1::ScalarField>(&cs);
+        Ok([vec![C1::ScalarField::one()], x, w].concat())
+    }
+
+    /// Implements IVC.P of HyperNova+CycleFold, folding at each step the running instance U_i
+    /// and the incoming instance u_i together with the given `other_instances`, which are
+    /// `mu-1` running instances and `nu-1` incoming instances of the AugmentedFCircuit (see
+    /// `new_running_instance` and `new_incoming_instance`). If `other_instances` is `None`,
+    /// copies of U_i and u_i are folded in their place.
+    /// At the base case (i=0) there is no folding, so `other_instances` must be `None`.
+    #[allow(clippy::type_complexity)]
+    pub fn prove_step_with_instances(
+        &mut self,
+        _rng: impl RngCore + CryptoRng,
+        external_inputs: Vec<C1::ScalarField>,
+        other_instances: Option<(
+            Vec<(LCCCS<C1>, Witness<C1::ScalarField>)>,
+            Vec<(CCCS<C1>, Witness<C1::ScalarField>)>,
+        )>,
+    ) -> Result<(), Error> {
+        let augmented_f_circuit: AugmentedFCircuit<C1, C2, GC2, FC>;
+
+        if self.z_i.len() != self.F.state_len() {
+            return Err(Error::NotSameLength(
+                "z_i.len()".to_string(),
+                self.z_i.len(),
+                "F.state_len()".to_string(),
+                self.F.state_len(),
+            ));
+        }
+        if external_inputs.len() != self.F.external_inputs_len() {
+            return Err(Error::NotSameLength(
+                "F.external_inputs_len()".to_string(),
+                self.F.external_inputs_len(),
+                "external_inputs.len()".to_string(),
+                external_inputs.len(),
+            ));
+        }
+
+        if self.i > C1::ScalarField::from_le_bytes_mod_order(&usize::MAX.to_le_bytes()) {
+            return Err(Error::MaxStep);
+        }
+
+        #[cfg(target_pointer_width = "64")]
+        let i_usize: usize = {
+            let mut i_bytes: [u8; 8] = [0; 8];
+            i_bytes.copy_from_slice(&self.i.into_bigint().to_bytes_le()[..8]);
+            usize::from_le_bytes(i_bytes)
+        };
+        #[cfg(target_pointer_width = "32")]
+        let i_usize: usize = {
+            let mut i_bytes: [u8; 4] = [0; 4];
+            i_bytes.copy_from_slice(&self.i.into_bigint().to_bytes_le()[..4]);
+            usize::from_le_bytes(i_bytes)
+        };
+
+        // other running (Us) and incoming (us) instances to be folded together with U_i & u_i
+        let (Us, Ws, us, ws) = match other_instances {
+            Some((lcccs, cccs)) => {
+                if self.i == C1::ScalarField::zero() {
+                    return Err(Error::NotSupported(
+                        "other instances can not be folded at the base case (i=0)".to_string(),
+                    ));
+                }
+                if lcccs.len() != self.mu - 1 {
+                    return Err(Error::NotSameLength(
+                        "other_instances.0.len()".to_string(),
+                        lcccs.len(),
+                        "mu - 1".to_string(),
+                        self.mu - 1,
+                    ));
+                }
+                if cccs.len() != self.nu - 1 {
+                    return Err(Error::NotSameLength(
+                        "other_instances.1.len()".to_string(),
+                        cccs.len(),
+                        "nu - 1".to_string(),
+                        self.nu - 1,
+                    ));
+                }
+                let (Us, Ws): (Vec<LCCCS<C1>>, Vec<Witness<C1::ScalarField>>) =
+                    lcccs.into_iter().unzip();
+                let (us, ws): (Vec<CCCS<C1>>, Vec<Witness<C1::ScalarField>>) =
+                    cccs.into_iter().unzip();
+                (Us, Ws, us, ws)
+            }
+            None => (
+                vec![self.U_i.clone(); self.mu - 1],
+                vec![self.W_i.clone(); self.mu - 1],
+                vec![self.u_i.clone(); self.nu - 1],
+                vec![self.w_i.clone(); self.nu - 1],
+            ),
+        };
+
+        let z_i1 = self
+            .F
+            .step_native(i_usize, self.z_i.clone(), external_inputs.clone())?;
+
+        // u_{i+1}.x[0] = H(i+1, z_0, z_{i+1}, U_{i+1})
+        let u_i1_x: C1::ScalarField;
+        // u_{i+1}.x[1] = H(cf_U_{i+1})
+        let cf_u_i1_x: C1::ScalarField;
+        let U_i1: LCCCS<C1>;
+        let W_i1: Witness<C1::ScalarField>;
+
+        if self.i == C1::ScalarField::zero() {
+            // base case: the running instance U_{i+1} is the dummy LCCCS, so there is no
+            // multi-folding to be done
+            W_i1 = Witness::<C1::ScalarField>::dummy(&self.ccs);
+            U_i1 = LCCCS::<C1>::dummy(self.ccs.l, self.ccs.t, self.ccs.s);
+
+            u_i1_x = U_i1.hash(
+                &self.poseidon_config,
+                C1::ScalarField::one(),
+                self.z_0.clone(),
+                z_i1.clone(),
+            )?;
+            cf_u_i1_x = self.cf_U_i.hash_cyclefold(&self.poseidon_config)?;
+
+            augmented_f_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
+                _c2: PhantomData,
+                _gc2: PhantomData,
+                poseidon_config: self.poseidon_config.clone(),
+                ccs: self.ccs.clone(),
+                mu: self.mu,
+                nu: self.nu,
+                i: Some(C1::ScalarField::zero()),
+                i_usize: Some(0),
+                z_0: Some(self.z_0.clone()),
+                z_i: Some(self.z_i.clone()),
+                external_inputs: Some(external_inputs.clone()),
+                u_i_C: Some(self.u_i.C),
+                U_i: Some(self.U_i.clone()),
+                Us: Some(Us),
+                us: Some(us),
+                U_i1_C: Some(U_i1.C),
+                C_stars: None,
+                F: self.F.clone(),
+                x: Some(u_i1_x),
+                nimfs_proof: None,
+
+                // cyclefold values
+                cf_u_i_cmW: None,
+                cf_U_i: None,
+                cf_x: Some(cf_u_i1_x),
+                cf_cmT: None,
+            };
+        } else {
+            let mut transcript_p: PoseidonTranscript<C1> =
+                PoseidonTranscript::<C1>::new(&self.poseidon_config);
+            let (nimfs_proof, rho_bits);
+            (nimfs_proof, U_i1, W_i1, rho_bits) = NIMFS::<C1, PoseidonTranscript<C1>>::prove(
+                &mut transcript_p,
+                &self.ccs,
+                &[vec![self.U_i.clone()], Us.clone()].concat(),
+                &[vec![self.u_i.clone()], us.clone()].concat(),
+                &[vec![self.W_i.clone()], Ws].concat(),
+                &[vec![self.w_i.clone()], ws].concat(),
+            )?;
+
+            // sanity check: check the folded instance relation
+            #[cfg(test)]
+            U_i1.check_relation::<CS1, false>(&self.cs_params, &self.ccs, &W_i1)?;
+
+            u_i1_x = U_i1.hash(
+                &self.poseidon_config,
+                self.i + C1::ScalarField::one(),
+                self.z_0.clone(),
+                z_i1.clone(),
+            )?;
+
+            let rho = C1::ScalarField::from_bigint(BigInteger::from_bits_le(&rho_bits))
+                .ok_or(Error::OutOfBounds)?;
+            let rho_Fq = C1::BaseField::from_bigint(BigInteger::from_bits_le(&rho_bits))
+                .ok_or(Error::OutOfBounds)?;
+
+            // CycleFold part:
+            // U_{i+1}.C = \sum_j rho^j * C_j, where C_j are the commitments of [U_i, Us, u_i, us],
+            // is computed by a chain of CycleFold circuits following Horner's method, where the
+            // j-th one computes p3 = p1 + rho * p2, being p2 the output of the previous one
+            let Cs: Vec<C1> = [
+                vec![self.U_i.C],
+                Us.iter().map(|U| U.C).collect(),
+                vec![self.u_i.C],
+                us.iter().map(|u| u.C).collect(),
+            ]
+            .concat();
+            let n = Cs.len();
+            let p1s: Vec<C1> = Cs[..n - 1].iter().rev().cloned().collect();
+            let mut p2s: Vec<C1> = vec![Cs[n - 1]];
+            for p1 in p1s.iter() {
+                p2s.push(*p1 + p2s[p2s.len() - 1] * rho);
+            }
+            // p2s = [C_{n-1}, C_stars, U_{i+1}.C]
+            let p3s: Vec<C1> = p2s[1..].to_vec();
+            let C_stars: Vec<C1> = p2s[1..n - 1].to_vec();
+
+            #[cfg(test)]
+            assert_eq!(p3s[n - 2], U_i1.C);
+
+            let mut cf_u_i_cmW: Vec<C2> = vec![];
+            let mut cf_cmT: Vec<C2> = vec![];
+            let mut cf_W_i1 = self.cf_W_i.clone();
+            let mut cf_U_i1 = self.cf_U_i.clone();
+            for j in 0..n - 1 {
+                // get the vector used as public inputs 'x' in the CycleFold circuit
+                let cf_u_i_x = [
+                    vec![rho_Fq],
+                    get_cm_coordinates(&p1s[j]),
+                    get_cm_coordinates(&p2s[j]),
+                    get_cm_coordinates(&p3s[j]),
+                ]
+                .concat();
+
+                let cf_circuit = CycleFoldCircuit::<C1, GC1> {
+                    _gc: PhantomData,
+                    r_bits: Some(rho_bits.clone()),
+                    p1: Some(p1s[j]),
+                    p2: Some(p2s[j]),
+                    x: Some(cf_u_i_x.clone()),
+                };
+
+                let (_cf_w_i, cf_u_i, cf_W_i1_j, cf_U_i1_j, cf_cmT_j, _) =
+                    fold_cyclefold_circuit::<C1, GC1, C2, GC2, CS2>(
+                        &self.poseidon_config,
+                        self.cf_r1cs.clone(),
+                        self.cf_cs_params.clone(),
+                        cf_W_i1, // CycleFold running instance witness
+                        cf_U_i1, // CycleFold running instance
+                        cf_u_i_x,
+                        cf_circuit,
+                    )?;
+
+                #[cfg(test)]
+                self.cf_r1cs.check_instance_relation(&_cf_w_i, &cf_u_i)?;
+
+                cf_u_i_cmW.push(cf_u_i.cmW);
+                cf_cmT.push(cf_cmT_j);
+                cf_W_i1 = cf_W_i1_j;
+                cf_U_i1 = cf_U_i1_j;
+            }
+
+            cf_u_i1_x = cf_U_i1.hash_cyclefold(&self.poseidon_config)?;
+
+            augmented_f_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
+                _c2: PhantomData,
+                _gc2: PhantomData,
+                poseidon_config: self.poseidon_config.clone(),
+                ccs: self.ccs.clone(),
+                mu: self.mu,
+                nu: self.nu,
+                i: Some(self.i),
+                i_usize: Some(i_usize),
+                z_0: Some(self.z_0.clone()),
+                z_i: Some(self.z_i.clone()),
+                external_inputs: Some(external_inputs.clone()),
+                u_i_C: Some(self.u_i.C),
+                U_i: Some(self.U_i.clone()),
+                Us: Some(Us),
+                us: Some(us),
+                U_i1_C: Some(U_i1.C),
+                C_stars: Some(C_stars),
+                F: self.F.clone(),
+                x: Some(u_i1_x),
+                nimfs_proof: Some(nimfs_proof),
+
+                // cyclefold values
+                cf_u_i_cmW: Some(cf_u_i_cmW),
+                cf_U_i: Some(self.cf_U_i.clone()),
+                cf_x: Some(cf_u_i1_x),
+                cf_cmT: Some(cf_cmT),
+            };
+
+            self.cf_W_i = cf_W_i1;
+            self.cf_U_i = cf_U_i1;
+
+            #[cfg(test)]
+            self.cf_r1cs
+                .check_relaxed_instance_relation(&self.cf_W_i, &self.cf_U_i)?;
+        }
+
+        let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+
+        augmented_f_circuit.generate_constraints(cs.clone())?;
+
+        #[cfg(test)]
+        assert!(cs.is_satisfied().unwrap());
+
+        let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+        let (w_i1, x_i1) = extract_w_x::<C1::ScalarField>(&cs);
+        if x_i1[0] != u_i1_x || x_i1[1] != cf_u_i1_x {
+            return Err(Error::NotEqual);
+        }
+
+        #[cfg(test)]
+        if x_i1.len() != 2 {
+            return Err(Error::NotExpectedLength(x_i1.len(), 2));
+        }
+
+        // set values for next iteration
+        self.i += C1::ScalarField::one();
+        self.z_i = z_i1;
+        self.w_i = Witness::<C1::ScalarField>::new(w_i1);
+        self.u_i = CCCS::<C1> {
+            C: CS1::commit(&self.cs_params, &self.w_i.w, &self.w_i.r_w)?,
+            x: x_i1,
+        };
+        self.W_i = W_i1;
+        self.U_i = U_i1;
+
+        #[cfg(test)]
+        {
+            // check the new LCCCS instance relation
+            self.U_i
+                .check_relation::<CS1, false>(&self.cs_params, &self.ccs, &self.W_i)?;
+            // check the new CCCS instance relation
+            self.u_i
+                .check_relation::<CS1, false>(&self.cs_params, &self.ccs, &self.w_i)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> FoldingScheme<C1, C2, FC>
+    for HyperNova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    type PreprocessorParam = (Self::ProverParam, FC);
+    type ProverParam = ProverParams<C1, C2, CS1, CS2>;
+    type VerifierParam = VerifierParams<C1, C2>;
+    type RunningInstance = (LCCCS<C1>, Witness<C1::ScalarField>);
+    type IncomingInstance = (CCCS<C1>, Witness<C1::ScalarField>);
+    type CFInstance = (CommittedInstance<C2>, NovaWitness<C2>);
+
+    fn preprocess(
+        prep_param: &Self::PreprocessorParam,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        let (prover_params, F_circuit) = prep_param;
+
+        let augmented_f_circuit = AugmentedFCircuit::<C1, C2, GC2, FC>::empty(
+            &prover_params.poseidon_config,
+            F_circuit.clone(),
+            prover_params.mu,
+            prover_params.nu,
+            prover_params.ccs.clone(),
+        )?;
+        let cf_circuit = CycleFoldCircuit::<C1, GC1>::empty();
+        let cf_r1cs = get_r1cs_from_cs::<C2::ScalarField>(cf_circuit)?;
+
+        // if the ccs was not provided, set the computed one into the prover params, so it does
+        // not need to be computed again at init
+        let prover_params = ProverParams::<C1, C2, CS1, CS2> {
+            ccs: Some(augmented_f_circuit.ccs.clone()),
+            ..prover_params.clone()
+        };
+        let verifier_params = VerifierParams::<C1, C2> {
+            poseidon_config: prover_params.poseidon_config.clone(),
+            ccs: augmented_f_circuit.ccs,
+            cf_r1cs,
+        };
+        Ok((prover_params, verifier_params))
+    }
+
+    /// Initializes the HyperNova+CycleFold's IVC for the given parameters and initial state `z_0`.
+    fn init(pp: &Self::ProverParam, F: FC, z_0: Vec<C1::ScalarField>) -> Result<Self, Error> {
+        // prepare the HyperNova setup (obtain the CCS of the AugmentedFCircuit)
+        let augmented_f_circuit = AugmentedFCircuit::<C1, C2, GC2, FC>::empty(
+            &pp.poseidon_config,
+            F.clone(),
+            pp.mu,
+            pp.nu,
+            pp.ccs.clone(),
+        )?;
+        let ccs = augmented_f_circuit.ccs.clone();
+
+        let cf_circuit = CycleFoldCircuit::<C1, GC1>::empty();
+        let cf_r1cs = get_r1cs_from_cs::<C2::ScalarField>(cf_circuit)?;
+
+        // setup the dummy instances
+        let W_dummy = Witness::<C1::ScalarField>::dummy(&ccs);
+        let U_dummy = LCCCS::<C1>::dummy(ccs.l, ccs.t, ccs.s);
+        let w_dummy = W_dummy.clone();
+        let u_dummy = CCCS::<C1>::dummy(ccs.l);
+        let (cf_w_dummy, cf_u_dummy) = cf_r1cs.dummy_instance();
+
+        // W_dummy=W_0 is a 'dummy witness', all zeroes, but with the size corresponding to the
+        // CCS that we're working with.
+        Ok(Self {
+            _gc1: PhantomData,
+            _c2: PhantomData,
+            _gc2: PhantomData,
+            ccs,
+            cf_r1cs,
+            poseidon_config: pp.poseidon_config.clone(),
+            cs_params: pp.cs_params.clone(),
+            cf_cs_params: pp.cf_cs_params.clone(),
+            F,
+            mu: pp.mu,
+            nu: pp.nu,
+            i: C1::ScalarField::zero(),
+            z_0: z_0.clone(),
+            z_i: z_0,
+            W_i: W_dummy,
+            U_i: U_dummy,
+            w_i: w_dummy,
+            u_i: u_dummy,
+            // cyclefold running instance
+            cf_W_i: cf_w_dummy,
+            cf_U_i: cf_u_dummy,
+        })
+    }
+
+    /// Implements IVC.P of HyperNova+CycleFold. When mu > 1 or nu > 1, the running and incoming
+    /// instances are folded together with copies of themselves, use
+    /// `HyperNova::prove_step_with_instances` to fold other instances at each step.
+    fn prove_step(
+        &mut self,
+        rng: impl RngCore + CryptoRng,
+        external_inputs: Vec<C1::ScalarField>,
+    ) -> Result<(), Error> {
+        self.prove_step_with_instances(rng, external_inputs, None)
+    }
+
+    fn state(&self) -> Vec<C1::ScalarField> {
+        self.z_i.clone()
+    }
+    fn instances(
+        &self,
+    ) -> (
+        Self::RunningInstance,
+        Self::IncomingInstance,
+        Self::CFInstance,
+    ) {
+        (
+            (self.U_i.clone(), self.W_i.clone()),
+            (self.u_i.clone(), self.w_i.clone()),
+            (self.cf_U_i.clone(), self.cf_W_i.clone()),
+        )
+    }
+
+    /// Implements IVC.V of HyperNova+CycleFold. Notice that this method does not include the
+    /// commitments verification, which is done in the Decider.
+    fn verify(
+        vp: Self::VerifierParam,
+        z_0: Vec<C1::ScalarField>, // initial state
+        z_i: Vec<C1::ScalarField>, // last state
+        num_steps: C1::ScalarField,
+        running_instance: Self::RunningInstance,
+        incoming_instance: Self::IncomingInstance,
+        cyclefold_instance: Self::CFInstance,
+    ) -> Result<(), Error> {
+        let (U_i, W_i) = running_instance;
+        let (u_i, w_i) = incoming_instance;
+        let (cf_U_i, cf_W_i) = cyclefold_instance;
+
+        if u_i.x.len() != 2 || U_i.x.len() != 2 {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check that u_i's output points to the running instance
+        // u_i.X[0] == H(i, z_0, z_i, U_i)
+        let expected_u_i_x = U_i.hash(&vp.poseidon_config, num_steps, z_0, z_i.clone())?;
+        if expected_u_i_x != u_i.x[0] {
+            return Err(Error::IVCVerificationFail);
+        }
+        // u_i.X[1] == H(cf_U_i)
+        let expected_cf_u_i_x = cf_U_i.hash_cyclefold(&vp.poseidon_config)?;
+        if expected_cf_u_i_x != u_i.x[1] {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check CCCS satisfiability
+        u_i.check_ccs_relation(&vp.ccs, &w_i)?;
+        // check LCCCS satisfiability
+        U_i.check_ccs_relation(&vp.ccs, &W_i)?;
+
+        // check CycleFold RelaxedR1CS satisfiability
+        vp.cf_r1cs
+            .check_relaxed_instance_relation(&cf_W_i, &cf_U_i)?;
+
+        Ok(())
+    }
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> Checkpoint<C1, C2, FC>
+    for HyperNova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    fn params_hash(&self) -> Result<C1::ScalarField, Error> {
+        let mut bytes = vec![];
+        self.ccs.serialize_compressed(&mut bytes)?;
+        self.cf_r1cs.serialize_compressed(&mut bytes)?;
+        self.cs_params.serialize_compressed(&mut bytes)?;
+        self.cf_cs_params.serialize_compressed(&mut bytes)?;
+        serialize_poseidon_config(&self.poseidon_config, &mut bytes)?;
+        hash_bytes(&self.poseidon_config, &bytes)
+    }
+
+    fn step(&self) -> C1::ScalarField {
+        self.i
+    }
+
+    fn serialize_state<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        self.i.serialize_compressed(&mut writer)?;
+        self.z_0.serialize_compressed(&mut writer)?;
+        self.z_i.serialize_compressed(&mut writer)?;
+        self.W_i.serialize_compressed(&mut writer)?;
+        self.U_i.serialize_compressed(&mut writer)?;
+        self.w_i.serialize_compressed(&mut writer)?;
+        self.u_i.serialize_compressed(&mut writer)?;
+        self.cf_W_i.serialize_compressed(&mut writer)?;
+        self.cf_U_i.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    fn deserialize_state<R: Read>(&mut self, mut reader: R) -> Result<(), Error> {
+        self.i = C1::ScalarField::deserialize_compressed(&mut reader)?;
+        self.z_0 = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.z_i = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.W_i = Witness::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.U_i = LCCCS::<C1>::deserialize_compressed(&mut reader)?;
+        self.w_i = Witness::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.u_i = CCCS::<C1>::deserialize_compressed(&mut reader)?;
+        self.cf_W_i = NovaWitness::<C2>::deserialize_compressed(&mut reader)?;
+        self.cf_U_i = CommittedInstance::<C2>::deserialize_compressed(&mut reader)?;
+        Ok(())
+    }
+}
+
+/// helper method to get the commitment scheme params length for both the AugmentedFCircuit and the
+/// CycleFold circuit
+pub fn get_cs_params_len<C1, GC1, C2, GC2, FC>(
+    poseidon_config: &PoseidonConfig<C1::ScalarField>,
+    F_circuit: FC,
+    mu: usize,
+    nu: usize,
+) -> Result<(usize, usize), Error>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    let ccs =
+        AugmentedFCircuit::<C1, C2, GC2, FC>::empty(poseidon_config, F_circuit, mu, nu, None)?.ccs;
+    let cf_r1cs = get_r1cs_from_cs::<C2::ScalarField>(CycleFoldCircuit::<C1, GC1>::empty())?;
+    // the witness committed in the CCCS & LCCCS instances is z without the (1, x) part
+    Ok((ccs.n - ccs.l - 1, cf_r1cs.A.n_rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::kzg::{ProverKey as KZGProverKey, KZG};
+    use ark_bn254::{constraints::GVar, Bn254, Fq, Fr, G1Projective as Projective};
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+    use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
+
+    use crate::commitment::pedersen::Pedersen;
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_canonical_config;
+
+    /// This test tests the HyperNova+CycleFold IVC, and by consequence it is also testing the
+    /// AugmentedFCircuit
+    #[test]
+    fn test_ivc() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+
+        // compute the CCS of the AugmentedFCircuit, to obtain the needed commitment params length
+        let ccs = AugmentedFCircuit::<Projective, Projective2, GVar2, CubicFCircuit<Fr>>::empty(
+            &poseidon_config,
+            F_circuit,
+            1,
+            1,
+            None,
+        )
+        .unwrap()
+        .ccs;
+        let cf_r1cs =
+            get_r1cs_from_cs::<Fq>(CycleFoldCircuit::<Projective, GVar>::empty()).unwrap();
+        let cs_len = ccs.n - ccs.l - 1;
+        let cf_cs_len = cf_r1cs.A.n_rows;
+
+        let (kzg_pk, _): (KZGProverKey<Projective>, KZGVerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        // run the test using Pedersen commitments on both sides of the curve cycle
+        test_ivc_opt::<Pedersen<Projective>, Pedersen<Projective2>>(
+            poseidon_config.clone(),
+            pedersen_params,
+            cf_pedersen_params.clone(),
+            F_circuit,
+            1,
+            1,
+        );
+        // run the test using KZG for the commitments on the main curve, and Pedersen for the
+        // commitments on the secondary curve
+        test_ivc_opt::<KZG<Bn254>, Pedersen<Projective2>>(
+            poseidon_config,
+            kzg_pk,
+            cf_pedersen_params,
+            F_circuit,
+            1,
+            1,
+        );
+    }
+
+    /// Tests the HyperNova+CycleFold IVC folding mu > 1 LCCCS and nu > 1 CCCS instances at each
+    /// IVC step
+    #[test]
+    fn test_ivc_multi_instances() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (mu, nu) = (2, 3);
+
+        let (cs_len, cf_cs_len) = get_cs_params_len::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+        >(&poseidon_config, F_circuit, mu, nu)
+        .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        test_ivc_opt::<Pedersen<Projective>, Pedersen<Projective2>>(
+            poseidon_config,
+            pedersen_params,
+            cf_pedersen_params,
+            F_circuit,
+            mu,
+            nu,
+        );
+    }
+
+    // test_ivc allowing to choose the CommitmentSchemes
+    fn test_ivc_opt<CS1: CommitmentScheme<Projective>, CS2: CommitmentScheme<Projective2>>(
+        poseidon_config: PoseidonConfig<Fr>,
+        cs_params: CS1::ProverParams,
+        cf_cs_params: CS2::ProverParams,
+        F_circuit: CubicFCircuit<Fr>,
+        mu: usize,
+        nu: usize,
+    ) {
+        let mut rng = ark_std::test_rng();
+        type HN<CS1, CS2> =
+            HyperNova<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>, CS1, CS2>;
+
+        let prover_params = ProverParams::<Projective, Projective2, CS1, CS2> {
+            poseidon_config: poseidon_config.clone(),
+            cs_params,
+            cf_cs_params,
+            ccs: None,
+            mu,
+            nu,
+        };
+        let (prover_params, verifier_params) = HN::preprocess(&(prover_params, F_circuit)).unwrap();
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut hypernova = HN::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+
+        let num_steps: usize = 4;
+        for i in 0..num_steps {
+            if i % 2 == 0 {
+                // U_i and u_i are folded together with copies of themselves
+                hypernova.prove_step(&mut rng, vec![]).unwrap();
+            } else {
+                // U_i and u_i are folded together with other instances, computed from
+                // different states
+                let Us = (0..mu - 1)
+                    .map(|j| {
+                        hypernova
+                            .new_running_instance(&mut rng, vec![Fr::from(j as u32 + 10)], vec![])
+                            .unwrap()
+                    })
+                    .collect();
+                let us = (0..nu - 1)
+                    .map(|j| {
+                        hypernova
+                            .new_incoming_instance(&mut rng, vec![Fr::from(j as u32 + 20)], vec![])
+                            .unwrap()
+                    })
+                    .collect();
+                hypernova
+                    .prove_step_with_instances(&mut rng, vec![], Some((Us, us)))
+                    .unwrap();
+            }
+        }
+        assert_eq!(Fr::from(num_steps as u32), hypernova.i);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = hypernova.instances();
+        HN::verify(
+            verifier_params,
+            z_0,
+            hypernova.z_i,
+            hypernova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+        )
+        .unwrap();
+    }
+
+    /// Tests that a HyperNova IVC can be checkpointed and resumed, and that the resumed IVC
+    /// keeps producing valid proofs
+    #[test]
+    fn test_checkpoint() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+
+        type HN = HyperNova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >;
+        let (cs_len, cf_cs_len) = get_cs_params_len::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+        >(&poseidon_config, F_circuit, 1, 1)
+        .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        let prover_params = ProverParams::<Projective, Projective2, _, _> {
+            poseidon_config,
+            cs_params: pedersen_params,
+            cf_cs_params: cf_pedersen_params,
+            ccs: None,
+            mu: 1,
+            nu: 1,
+        };
+        let (prover_params, verifier_params) = HN::preprocess(&(prover_params, F_circuit)).unwrap();
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut hypernova = HN::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        hypernova.prove_step(&mut rng, vec![]).unwrap();
+        hypernova.prove_step(&mut rng, vec![]).unwrap();
+
+        let mut checkpoint = vec![];
+        hypernova.write_checkpoint(&mut checkpoint).unwrap();
+        let mut hypernova =
+            HN::resume_from_checkpoint(&prover_params, F_circuit, checkpoint.as_slice()).unwrap();
+        hypernova.prove_step(&mut rng, vec![]).unwrap();
+        assert_eq!(Fr::from(3_u32), hypernova.i);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = hypernova.instances();
+        HN::verify(
+            verifier_params,
+            z_0,
+            hypernova.z_i,
+            hypernova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+        )
+        .unwrap();
+
+        // a checkpoint taken with a different number of folded instances is rejected
+        let (prover_params_multi, _) = HN::preprocess(&(
+            ProverParams {
+                ccs: None,
+                nu: 2,
+                ..prover_params
+            },
+            F_circuit,
+        ))
+        .unwrap();
+        let res =
+            HN::resume_from_checkpoint(&prover_params_multi, F_circuit, checkpoint.as_slice());
+        assert!(matches!(res, Err(Error::CheckpointMismatch(_))));
+    }
+}
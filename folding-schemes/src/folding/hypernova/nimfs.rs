@@ -1,6 +1,6 @@
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::{CurveGroup, Group};
-use ark_ff::{Field, PrimeField};
+use ark_ff::{BigInteger, Field, PrimeField};
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::{DenseUVPolynomial, Polynomial};
 use ark_std::{One, Zero};
@@ -9,9 +9,10 @@ use super::cccs::{Witness, CCCS};
 use super::lcccs::LCCCS;
 use super::utils::{compute_c, compute_g, compute_sigmas_thetas};
 use crate::ccs::CCS;
+use crate::constants::N_BITS_RO;
 use crate::transcript::Transcript;
 use crate::utils::hypercube::BooleanHypercube;
-use crate::utils::sum_check::structs::IOPProof as SumCheckProof;
+use crate::utils::sum_check::structs::{IOPProof as SumCheckProof, IOPProverMessage};
 use crate::utils::sum_check::{IOPSumCheck, SumCheck};
 use crate::utils::virtual_polynomial::VPAuxInfo;
 use crate::Error;
@@ -26,6 +27,33 @@ pub struct Proof<C: CurveGroup> {
     pub sigmas_thetas: SigmasThetas<C::ScalarField>,
 }
 
+impl<C: CurveGroup> Proof<C> {
+    /// returns a dummy proof (all zeroes) for folding `mu` LCCCS and `nu` CCCS instances of the
+    /// given CCS, used for the base case of the IVC, where the instances are not folded but the
+    /// circuit still needs a proof of the same shape.
+    pub fn dummy(ccs: &CCS<C::ScalarField>, mu: usize, nu: usize) -> Self {
+        // the sum-check univariate polynomials have degree ccs.d + 1, so we set ccs.d + 2
+        // coefficients in order to obtain a circuit of the same shape as with a real proof
+        let sc_proof = SumCheckProof::<C::ScalarField> {
+            point: vec![C::ScalarField::zero(); ccs.s],
+            proofs: vec![
+                IOPProverMessage {
+                    coeffs: vec![C::ScalarField::zero(); ccs.d + 2],
+                };
+                ccs.s
+            ],
+        };
+        let sigmas_thetas = SigmasThetas(
+            vec![vec![C::ScalarField::zero(); ccs.t]; mu],
+            vec![vec![C::ScalarField::zero(); ccs.t]; nu],
+        );
+        Self {
+            sc_proof,
+            sigmas_thetas,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SigmasThetas<F: PrimeField>(pub Vec<Vec<F>>, pub Vec<Vec<F>>);
 
@@ -40,6 +68,7 @@ pub struct NIMFS<C: CurveGroup, T: Transcript<C>> {
 impl<C: CurveGroup, T: Transcript<C>> NIMFS<C, T>
 where
     <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
 {
     pub fn fold(
         lcccs: &[LCCCS<C>],
@@ -145,10 +174,27 @@ where
         }
     }
 
+    /// Absorbs the LCCCS and CCCS instances that are being folded into the transcript.
+    fn absorb_instances(
+        transcript: &mut impl Transcript<C>,
+        running_instances: &[LCCCS<C>],
+        new_instances: &[CCCS<C>],
+    ) -> Result<(), Error> {
+        for lcccs in running_instances.iter() {
+            transcript.absorb_vec(&lcccs.to_field_elements()?);
+        }
+        for cccs in new_instances.iter() {
+            transcript.absorb_vec(&cccs.to_field_elements()?);
+        }
+        Ok(())
+    }
+
     /// Performs the multifolding prover. Given μ LCCCS instances and ν CCS instances, fold them
     /// into a single LCCCS instance. Since this is the prover, also fold their witness.
-    /// Returns the final folded LCCCS, the folded witness, and the multifolding proof, which
-    /// contains the sumcheck proof and the helper sumcheck claim sigmas and thetas.
+    /// Returns the final folded LCCCS, the folded witness, the multifolding proof, which
+    /// contains the sumcheck proof and the helper sumcheck claim sigmas and thetas, and the bits
+    /// of the folding challenge rho, which are used to fold the commitments in the CycleFold
+    /// circuit.
     #[allow(clippy::type_complexity)]
     pub fn prove(
         transcript: &mut impl Transcript<C>,
@@ -157,9 +203,7 @@ where
         new_instances: &[CCCS<C>],
         w_lcccs: &[Witness<C::ScalarField>],
         w_cccs: &[Witness<C::ScalarField>],
-    ) -> Result<(Proof<C>, LCCCS<C>, Witness<C::ScalarField>), Error> {
-        // TODO appends to transcript
-
+    ) -> Result<(Proof<C>, LCCCS<C>, Witness<C::ScalarField>, Vec<bool>), Error> {
         if running_instances.is_empty() {
             return Err(Error::Empty);
         }
@@ -167,6 +211,8 @@ where
             return Err(Error::Empty);
         }
 
+        Self::absorb_instances(transcript, running_instances, new_instances)?;
+
         // construct the LCCCS z vector from the relaxation factor, public IO and witness
         // XXX this deserves its own function in LCCCS
         let mut z_lcccs = Vec::new();
@@ -249,7 +295,9 @@ where
         // Step 6: Get the folding challenge
         let rho_scalar = C::ScalarField::from_le_bytes_mod_order(b"rho");
         transcript.absorb(&rho_scalar);
-        let rho: C::ScalarField = transcript.get_challenge();
+        let rho_bits: Vec<bool> = transcript.get_challenge_nbits(N_BITS_RO);
+        let rho: C::ScalarField = C::ScalarField::from_bigint(BigInteger::from_bits_le(&rho_bits))
+            .ok_or(Error::OutOfBounds)?;
 
         // Step 7: Create the folded instance
         let folded_lcccs = Self::fold(
@@ -270,6 +318,7 @@ where
             },
             folded_lcccs,
             folded_witness,
+            rho_bits,
         ))
     }
 
@@ -283,8 +332,6 @@ where
         new_instances: &[CCCS<C>],
         proof: Proof<C>,
     ) -> Result<LCCCS<C>, Error> {
-        // TODO appends to transcript
-
         if running_instances.is_empty() {
             return Err(Error::Empty);
         }
@@ -292,6 +339,8 @@ where
             return Err(Error::Empty);
         }
 
+        Self::absorb_instances(transcript, running_instances, new_instances)?;
+
         // Step 1: Get some challenges
         let gamma_scalar = C::ScalarField::from_le_bytes_mod_order(b"gamma");
         transcript.absorb(&gamma_scalar);
@@ -359,7 +408,9 @@ where
         // Step 6: Get the folding challenge
         let rho_scalar = C::ScalarField::from_le_bytes_mod_order(b"rho");
         transcript.absorb(&rho_scalar);
-        let rho: C::ScalarField = transcript.get_challenge();
+        let rho_bits: Vec<bool> = transcript.get_challenge_nbits(N_BITS_RO);
+        let rho: C::ScalarField = C::ScalarField::from_bigint(BigInteger::from_bits_le(&rho_bits))
+            .ok_or(Error::OutOfBounds)?;
 
         // Step 7: Compute the folded instance
         Ok(Self::fold(
@@ -376,6 +427,7 @@ where
 pub mod tests {
     use super::*;
     use crate::ccs::tests::{get_test_ccs, get_test_z};
+    use crate::transcript::keccak::KeccakTranscript;
     use crate::transcript::poseidon::poseidon_canonical_config;
     use crate::transcript::poseidon::PoseidonTranscript;
     use ark_std::test_rng;
@@ -401,11 +453,26 @@ pub mod tests {
         let (pedersen_params, _) =
             Pedersen::<Projective>::setup(&mut rng, ccs.n - ccs.l - 1).unwrap();
 
-        let (lcccs, w1) = ccs.to_lcccs(&mut rng, &pedersen_params, &z1).unwrap();
-        let (cccs, w2) = ccs.to_cccs(&mut rng, &pedersen_params, &z2).unwrap();
+        let (lcccs, w1) = ccs
+            .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                &mut rng,
+                &pedersen_params,
+                &z1,
+            )
+            .unwrap();
+        let (cccs, w2) = ccs
+            .to_cccs::<_, Projective, Pedersen<Projective, true>, true>(
+                &mut rng,
+                &pedersen_params,
+                &z2,
+            )
+            .unwrap();
 
-        lcccs.check_relation(&pedersen_params, &ccs, &w1).unwrap();
-        cccs.check_relation(&pedersen_params, &ccs, &w2).unwrap();
+        lcccs
+            .check_relation::<Pedersen<Projective, true>, true>(&pedersen_params, &ccs, &w1)
+            .unwrap();
+        cccs.check_relation::<Pedersen<Projective, true>, true>(&pedersen_params, &ccs, &w2)
+            .unwrap();
 
         let mut rng = test_rng();
         let rho = Fr::rand(&mut rng);
@@ -423,7 +490,7 @@ pub mod tests {
 
         // check lcccs relation
         folded
-            .check_relation(&pedersen_params, &ccs, &w_folded)
+            .check_relation::<Pedersen<Projective, true>, true>(&pedersen_params, &ccs, &w_folded)
             .unwrap();
     }
 
@@ -443,9 +510,21 @@ pub mod tests {
         let z_2 = get_test_z(4);
 
         // Create the LCCCS instance out of z_1
-        let (running_instance, w1) = ccs.to_lcccs(&mut rng, &pedersen_params, &z_1).unwrap();
+        let (running_instance, w1) = ccs
+            .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                &mut rng,
+                &pedersen_params,
+                &z_1,
+            )
+            .unwrap();
         // Create the CCCS instance out of z_2
-        let (new_instance, w2) = ccs.to_cccs(&mut rng, &pedersen_params, &z_2).unwrap();
+        let (new_instance, w2) = ccs
+            .to_cccs::<_, Projective, Pedersen<Projective, true>, true>(
+                &mut rng,
+                &pedersen_params,
+                &z_2,
+            )
+            .unwrap();
 
         // Prover's transcript
         let poseidon_config = poseidon_canonical_config::<Fr>();
@@ -454,7 +533,7 @@ pub mod tests {
         transcript_p.absorb(&Fr::from_le_bytes_mod_order(b"init init"));
 
         // Run the prover side of the multifolding
-        let (proof, folded_lcccs, folded_witness) =
+        let (proof, folded_lcccs, folded_witness, _) =
             NIMFS::<Projective, PoseidonTranscript<Projective>>::prove(
                 &mut transcript_p,
                 &ccs,
@@ -483,7 +562,75 @@ pub mod tests {
 
         // Check that the folded LCCCS instance is a valid instance with respect to the folded witness
         folded_lcccs
-            .check_relation(&pedersen_params, &ccs, &folded_witness)
+            .check_relation::<Pedersen<Projective, true>, true>(
+                &pedersen_params,
+                &ccs,
+                &folded_witness,
+            )
+            .unwrap();
+    }
+
+    /// Perform multifolding of an LCCCS instance with a CCCS instance using the Keccak transcript
+    #[test]
+    pub fn test_basic_multifolding_keccak() {
+        let mut rng = test_rng();
+
+        let ccs = get_test_ccs::<Fr>();
+        let (pedersen_params, _) =
+            Pedersen::<Projective>::setup(&mut rng, ccs.n - ccs.l - 1).unwrap();
+
+        let z_1 = get_test_z(3);
+        let z_2 = get_test_z(4);
+        let (running_instance, w1) = ccs
+            .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                &mut rng,
+                &pedersen_params,
+                &z_1,
+            )
+            .unwrap();
+        let (new_instance, w2) = ccs
+            .to_cccs::<_, Projective, Pedersen<Projective, true>, true>(
+                &mut rng,
+                &pedersen_params,
+                &z_2,
+            )
+            .unwrap();
+
+        // Prover's transcript
+        let mut transcript_p = KeccakTranscript::<Projective>::new(&());
+        transcript_p.absorb(&Fr::from_le_bytes_mod_order(b"init init"));
+
+        let (proof, folded_lcccs, folded_witness, _) =
+            NIMFS::<Projective, KeccakTranscript<Projective>>::prove(
+                &mut transcript_p,
+                &ccs,
+                &[running_instance.clone()],
+                &[new_instance.clone()],
+                &[w1],
+                &[w2],
+            )
+            .unwrap();
+
+        // Verifier's transcript
+        let mut transcript_v = KeccakTranscript::<Projective>::new(&());
+        transcript_v.absorb(&Fr::from_le_bytes_mod_order(b"init init"));
+
+        let folded_lcccs_v = NIMFS::<Projective, KeccakTranscript<Projective>>::verify(
+            &mut transcript_v,
+            &ccs,
+            &[running_instance.clone()],
+            &[new_instance.clone()],
+            proof,
+        )
+        .unwrap();
+        assert_eq!(folded_lcccs, folded_lcccs_v);
+
+        folded_lcccs
+            .check_relation::<Pedersen<Projective, true>, true>(
+                &pedersen_params,
+                &ccs,
+                &folded_witness,
+            )
             .unwrap();
     }
 
@@ -499,8 +646,13 @@ pub mod tests {
 
         // LCCCS witness
         let z_1 = get_test_z(2);
-        let (mut running_instance, mut w1) =
-            ccs.to_lcccs(&mut rng, &pedersen_params, &z_1).unwrap();
+        let (mut running_instance, mut w1) = ccs
+            .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                &mut rng,
+                &pedersen_params,
+                &z_1,
+            )
+            .unwrap();
 
         let poseidon_config = poseidon_canonical_config::<Fr>();
 
@@ -520,10 +672,16 @@ pub mod tests {
             let z_2 = get_test_z(i);
             println!("z_2 {:?}", z_2); // DBG
 
-            let (new_instance, w2) = ccs.to_cccs(&mut rng, &pedersen_params, &z_2).unwrap();
+            let (new_instance, w2) = ccs
+                .to_cccs::<_, Projective, Pedersen<Projective, true>, true>(
+                    &mut rng,
+                    &pedersen_params,
+                    &z_2,
+                )
+                .unwrap();
 
             // run the prover side of the multifolding
-            let (proof, folded_lcccs, folded_witness) =
+            let (proof, folded_lcccs, folded_witness, _) =
                 NIMFS::<Projective, PoseidonTranscript<Projective>>::prove(
                     &mut transcript_p,
                     &ccs,
@@ -548,7 +706,11 @@ pub mod tests {
             // check that the folded instance with the folded witness holds the LCCCS relation
             println!("check_relation {}", i);
             folded_lcccs
-                .check_relation(&pedersen_params, &ccs, &folded_witness)
+                .check_relation::<Pedersen<Projective, true>, true>(
+                    &pedersen_params,
+                    &ccs,
+                    &folded_witness,
+                )
                 .unwrap();
 
             running_instance = folded_lcccs;
@@ -585,7 +747,13 @@ pub mod tests {
         let mut lcccs_instances = Vec::new();
         let mut w_lcccs = Vec::new();
         for z_i in z_lcccs.iter() {
-            let (running_instance, w) = ccs.to_lcccs(&mut rng, &pedersen_params, z_i).unwrap();
+            let (running_instance, w) = ccs
+                .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                    &mut rng,
+                    &pedersen_params,
+                    z_i,
+                )
+                .unwrap();
             lcccs_instances.push(running_instance);
             w_lcccs.push(w);
         }
@@ -593,7 +761,13 @@ pub mod tests {
         let mut cccs_instances = Vec::new();
         let mut w_cccs = Vec::new();
         for z_i in z_cccs.iter() {
-            let (new_instance, w) = ccs.to_cccs(&mut rng, &pedersen_params, z_i).unwrap();
+            let (new_instance, w) = ccs
+                .to_cccs::<_, Projective, Pedersen<Projective, true>, true>(
+                    &mut rng,
+                    &pedersen_params,
+                    z_i,
+                )
+                .unwrap();
             cccs_instances.push(new_instance);
             w_cccs.push(w);
         }
@@ -605,7 +779,7 @@ pub mod tests {
         transcript_p.absorb(&Fr::from_le_bytes_mod_order(b"init init"));
 
         // Run the prover side of the multifolding
-        let (proof, folded_lcccs, folded_witness) =
+        let (proof, folded_lcccs, folded_witness, _) =
             NIMFS::<Projective, PoseidonTranscript<Projective>>::prove(
                 &mut transcript_p,
                 &ccs,
@@ -634,7 +808,11 @@ pub mod tests {
 
         // Check that the folded LCCCS instance is a valid instance with respect to the folded witness
         folded_lcccs
-            .check_relation(&pedersen_params, &ccs, &folded_witness)
+            .check_relation::<Pedersen<Projective, true>, true>(
+                &pedersen_params,
+                &ccs,
+                &folded_witness,
+            )
             .unwrap();
     }
 
@@ -683,7 +861,13 @@ pub mod tests {
             let mut lcccs_instances = Vec::new();
             let mut w_lcccs = Vec::new();
             for z_i in z_lcccs.iter() {
-                let (running_instance, w) = ccs.to_lcccs(&mut rng, &pedersen_params, z_i).unwrap();
+                let (running_instance, w) = ccs
+                    .to_lcccs::<_, Projective, Pedersen<Projective, true>, true>(
+                        &mut rng,
+                        &pedersen_params,
+                        z_i,
+                    )
+                    .unwrap();
                 lcccs_instances.push(running_instance);
                 w_lcccs.push(w);
             }
@@ -691,13 +875,19 @@ pub mod tests {
             let mut cccs_instances = Vec::new();
             let mut w_cccs = Vec::new();
             for z_i in z_cccs.iter() {
-                let (new_instance, w) = ccs.to_cccs(&mut rng, &pedersen_params, z_i).unwrap();
+                let (new_instance, w) = ccs
+                    .to_cccs::<_, Projective, Pedersen<Projective, true>, true>(
+                        &mut rng,
+                        &pedersen_params,
+                        z_i,
+                    )
+                    .unwrap();
                 cccs_instances.push(new_instance);
                 w_cccs.push(w);
             }
 
             // Run the prover side of the multifolding
-            let (proof, folded_lcccs, folded_witness) =
+            let (proof, folded_lcccs, folded_witness, _) =
                 NIMFS::<Projective, PoseidonTranscript<Projective>>::prove(
                     &mut transcript_p,
                     &ccs,
@@ -722,7 +912,11 @@ pub mod tests {
 
             // Check that the folded LCCCS instance is a valid instance with respect to the folded witness
             folded_lcccs
-                .check_relation(&pedersen_params, &ccs, &folded_witness)
+                .check_relation::<Pedersen<Projective, true>, true>(
+                    &pedersen_params,
+                    &ccs,
+                    &folded_witness,
+                )
                 .unwrap();
         }
     }
@@ -1,4 +1,6 @@
+pub mod checkpoint;
 pub mod circuits;
 pub mod hypernova;
 pub mod nova;
 pub mod protogalaxy;
+pub mod supernova;
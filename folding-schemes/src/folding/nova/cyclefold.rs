@@ -10,8 +10,8 @@ use ark_crypto_primitives::{
         Absorb, CryptographicSponge,
     },
 };
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_ff::{Field, PrimeField, ToConstraintField};
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ff::{BigInteger, Field, PrimeField, ToConstraintField};
 use ark_r1cs_std::{
     alloc::{AllocVar, AllocationMode},
     boolean::Boolean,
@@ -21,12 +21,16 @@ use ark_r1cs_std::{
     prelude::CurveVar,
     ToConstraintFieldGadget,
 };
-use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, Namespace, SynthesisError};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, Namespace, SynthesisError,
+};
 use ark_std::fmt::Debug;
 use ark_std::{One, Zero};
 use core::{borrow::Borrow, marker::PhantomData};
 
-use super::CommittedInstance;
+use super::{nifs::NIFS, CommittedInstance, Witness};
+use crate::ccs::r1cs::{extract_w_x, R1CS};
+use crate::commitment::CommitmentScheme;
 use crate::constants::N_BITS_RO;
 use crate::folding::circuits::{nonnative::uint::NonNativeUintVar, CF2};
 use crate::Error;
@@ -34,6 +38,10 @@ use crate::Error;
 // public inputs length for the CycleFoldCircuit: |[r, p1.x,y, p2.x,y, p3.x,y]|
 pub const CF_IO_LEN: usize = 7;
 
+/// Domain-separation label from which the CycleFold Pedersen generators are derived with
+/// `Pedersen::setup_from_label`, so that they can be regenerated by anyone from the label.
+pub const CF_PEDERSEN_LABEL: &[u8] = b"sonobe/nova/cyclefold/pedersen";
+
 /// CycleFoldCommittedInstanceVar is the CycleFold CommittedInstance representation in the Nova
 /// circuit.
 #[derive(Debug, Clone)]
@@ -365,6 +373,87 @@ where
     }
 }
 
+/// Folds the given cyclefold circuit and its instances. This is a method shared by the different
+/// folding schemes (eg. Nova, HyperNova) that use CycleFold to delegate the elliptic curve
+/// operations of their commitments.
+#[allow(clippy::type_complexity)]
+pub fn fold_cyclefold_circuit<C1, GC1, C2, GC2, CS2>(
+    poseidon_config: &PoseidonConfig<C1::ScalarField>,
+    cf_r1cs: R1CS<C2::ScalarField>,
+    cf_cs_params: CS2::ProverParams,
+    cf_W_i: Witness<C2>,           // witness of the running instance
+    cf_U_i: CommittedInstance<C2>, // running instance
+    cf_u_i_x: Vec<C2::ScalarField>,
+    cf_circuit: CycleFoldCircuit<C1, GC1>,
+) -> Result<
+    (
+        Witness<C2>,
+        CommittedInstance<C2>, // u_i
+        Witness<C2>,           // W_i1
+        CommittedInstance<C2>, // U_i1
+        C2,                    // cmT
+        C2::ScalarField,       // r_Fq
+    ),
+    Error,
+>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    let cs2 = ConstraintSystem::<C1::BaseField>::new_ref();
+    cf_circuit.generate_constraints(cs2.clone())?;
+
+    let cs2 = cs2.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+    let (cf_w_i, cf_x_i) = extract_w_x::<C1::BaseField>(&cs2);
+    if cf_x_i != cf_u_i_x {
+        return Err(Error::NotEqual);
+    }
+
+    #[cfg(test)]
+    if cf_x_i.len() != CF_IO_LEN {
+        return Err(Error::NotExpectedLength(cf_x_i.len(), CF_IO_LEN));
+    }
+
+    // fold cyclefold instances
+    let cf_w_i = Witness::<C2>::new(cf_w_i.clone(), cf_r1cs.A.n_rows);
+    let cf_u_i: CommittedInstance<C2> =
+        cf_w_i.commit::<CS2, false>(&cf_cs_params, cf_x_i.clone())?;
+
+    // compute T* and cmT* for CycleFoldCircuit
+    let (cf_T, cf_cmT) = NIFS::<C2, CS2>::compute_cyclefold_cmT(
+        &cf_cs_params,
+        &cf_r1cs,
+        &cf_w_i,
+        &cf_u_i,
+        &cf_W_i,
+        &cf_U_i,
+    )?;
+
+    let cf_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_native(
+        poseidon_config,
+        cf_U_i.clone(),
+        cf_u_i.clone(),
+        cf_cmT,
+    )?;
+    let cf_r_Fq = C1::BaseField::from_bigint(BigInteger::from_bits_le(&cf_r_bits))
+        .ok_or(Error::OutOfBounds)?;
+
+    let (cf_W_i1, cf_U_i1) = NIFS::<C2, CS2>::fold_instances(
+        cf_r_Fq, &cf_W_i, &cf_U_i, &cf_w_i, &cf_u_i, &cf_T, cf_cmT,
+    )?;
+    Ok((cf_w_i, cf_u_i, cf_W_i1, cf_U_i1, cf_cmT, cf_r_Fq))
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -0,0 +1,331 @@
+/// This file implements an offchain decider for Nova+CycleFold, which does not require a trusted
+/// setup. The last running instance is folded with the last incoming instance, and the Relaxed
+/// R1CS relation of the resulting instance and of the CycleFold running instance is proven with
+/// the Spartan-style argument implemented in `super::spartan`.
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::{One, Zero};
+use core::marker::PhantomData;
+
+use super::{
+    circuits::ChallengeGadget,
+    nifs::NIFS,
+    spartan::{Spartan, SpartanProof},
+    CommittedInstance, Nova, VerifierParams,
+};
+use crate::commitment::{pedersen::Params as PedersenParams, CommitmentScheme};
+use crate::folding::circuits::CF2;
+use crate::frontend::FCircuit;
+use crate::transcript::{poseidon::PoseidonTranscript, Transcript};
+use crate::Error;
+use crate::{Decider as DeciderTrait, FoldingScheme};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Proof<C1: CurveGroup, C2: CurveGroup> {
+    // cmT is the value for the last fold, U_{i+1}=NIFS.V(r, U_i, u_i, cmT), where r is recomputed
+    // by the verifier
+    pub cmT: C1,
+    pub spartan_proof: SpartanProof<C1>,
+    // CycleFold running instance, its hash is checked against u_i.x[1]
+    pub cf_U_i: CommittedInstance<C2>,
+    pub cf_spartan_proof: SpartanProof<C2>,
+}
+
+/// Offchain Decider, based on a Spartan-style sum-check argument and the IPA, with transparent
+/// setup. Since the commitments are opened through the IPA, both CommitmentSchemes must use
+/// Pedersen parameters (ie. Pedersen or IPA).
+#[derive(Clone, Debug)]
+pub struct Decider<C1, GC1, C2, GC2, FC, CS1, CS2, FS> {
+    _c1: PhantomData<C1>,
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    _fc: PhantomData<FC>,
+    _cs1: PhantomData<CS1>,
+    _cs2: PhantomData<CS2>,
+    _fs: PhantomData<FS>,
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2, FS> DeciderTrait<C1, C2, FC, FS>
+    for Decider<C1, GC1, C2, GC2, FC, CS1, CS2, FS>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1, ProverParams = PedersenParams<C1>>,
+    CS2: CommitmentScheme<C2, ProverParams = PedersenParams<C2>>,
+    FS: FoldingScheme<C1, C2, FC>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'b> &'b GC2: GroupOpsBounds<'b, C2, GC2>,
+    // constrain FS into Nova, since this is a Decider specifically for Nova
+    Nova<C1, GC1, C2, GC2, FC, CS1, CS2>: From<FS>,
+{
+    /// (params over C1, params over C2, poseidon config used for the transcript over C2)
+    type ProverParam = (
+        PedersenParams<C1>,
+        PedersenParams<C2>,
+        PoseidonConfig<C2::ScalarField>,
+    );
+    type Proof = Proof<C1, C2>;
+    /// (Nova's VerifierParams, params over C1, params over C2, poseidon config used for the
+    /// transcript over C2)
+    type VerifierParam = (
+        VerifierParams<C1, C2>,
+        PedersenParams<C1>,
+        PedersenParams<C2>,
+        PoseidonConfig<C2::ScalarField>,
+    );
+    type PublicInput = Vec<C1::ScalarField>;
+    type CommittedInstanceWithWitness = ();
+    type CommittedInstance = CommittedInstance<C1>;
+
+    fn prove(
+        pp: Self::ProverParam,
+        _rng: impl RngCore + CryptoRng,
+        folding_scheme: FS,
+    ) -> Result<Self::Proof, Error> {
+        let (cs_params, cf_cs_params, cf_poseidon_config) = pp;
+        let nova: Nova<C1, GC1, C2, GC2, FC, CS1, CS2> = folding_scheme.into();
+
+        // compute the U_{i+1}, W_{i+1}
+        let (T, cmT) = NIFS::<C1, CS1>::compute_cmT(
+            &cs_params, &nova.r1cs, &nova.w_i, &nova.u_i, &nova.W_i, &nova.U_i,
+        )?;
+        let r = get_challenge(
+            &nova.poseidon_config,
+            nova.pp_hash,
+            &nova.U_i,
+            &nova.u_i,
+            cmT,
+        )?;
+        let (W_i1, U_i1) = NIFS::<C1, CS1>::fold_instances(
+            r, &nova.W_i, &nova.U_i, &nova.w_i, &nova.u_i, &T, cmT,
+        )?;
+
+        let mut transcript = PoseidonTranscript::<C1>::new(&nova.poseidon_config);
+        let spartan_proof = Spartan::<C1, PoseidonTranscript<C1>>::prove(
+            &cs_params,
+            &mut transcript,
+            &nova.r1cs,
+            &W_i1,
+            &U_i1,
+        )?;
+
+        let mut cf_transcript = PoseidonTranscript::<C2>::new(&cf_poseidon_config);
+        let cf_spartan_proof = Spartan::<C2, PoseidonTranscript<C2>>::prove(
+            &cf_cs_params,
+            &mut cf_transcript,
+            &nova.cf_r1cs,
+            &nova.cf_W_i,
+            &nova.cf_U_i,
+        )?;
+
+        Ok(Self::Proof {
+            cmT,
+            spartan_proof,
+            cf_U_i: nova.cf_U_i,
+            cf_spartan_proof,
+        })
+    }
+
+    fn verify(
+        vp: Self::VerifierParam,
+        i: C1::ScalarField,
+        z_0: Vec<C1::ScalarField>,
+        z_i: Vec<C1::ScalarField>,
+        running_instance: &Self::CommittedInstance,
+        incoming_instance: &Self::CommittedInstance,
+        proof: &Self::Proof,
+    ) -> Result<bool, Error> {
+        if i.is_zero() {
+            return Err(Error::NotEnoughSteps);
+        }
+        let (nova_vp, cs_params, cf_cs_params, cf_poseidon_config) = vp;
+        let (U_i, u_i) = (running_instance, incoming_instance);
+
+        if u_i.x.len() != 2 || U_i.x.len() != 2 {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check that u_i's output points to the running instance
+        // u_i.X[0] == H(pp_hash, i, z_0, z_i, U_i)
+        let expected_u_i_x = U_i.hash(&nova_vp.poseidon_config, nova_vp.pp_hash, i, z_0, z_i)?;
+        if expected_u_i_x != u_i.x[0] {
+            return Err(Error::IVCVerificationFail);
+        }
+        // u_i.X[1] == H(cf_U_i)
+        let expected_cf_u_i_x = proof.cf_U_i.hash_cyclefold(&nova_vp.poseidon_config)?;
+        if expected_cf_u_i_x != u_i.x[1] {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check u_i.cmE==0, u_i.u==1 (=u_i is a un-relaxed instance)
+        if !u_i.cmE.is_zero() || !u_i.u.is_one() {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // compute U = U_{d+1}= NIFS.V(U_d, u_d, cmT)
+        let r = get_challenge(
+            &nova_vp.poseidon_config,
+            nova_vp.pp_hash,
+            U_i,
+            u_i,
+            proof.cmT,
+        )?;
+        let U = NIFS::<C1, CS1>::verify(r, U_i, u_i, &proof.cmT);
+
+        let mut transcript = PoseidonTranscript::<C1>::new(&nova_vp.poseidon_config);
+        Spartan::<C1, PoseidonTranscript<C1>>::verify(
+            &cs_params,
+            &mut transcript,
+            &nova_vp.r1cs,
+            &U,
+            &proof.spartan_proof,
+        )?;
+
+        let mut cf_transcript = PoseidonTranscript::<C2>::new(&cf_poseidon_config);
+        Spartan::<C2, PoseidonTranscript<C2>>::verify(
+            &cf_cs_params,
+            &mut cf_transcript,
+            &nova_vp.cf_r1cs,
+            &proof.cf_U_i,
+            &proof.cf_spartan_proof,
+        )?;
+
+        Ok(true)
+    }
+}
+
+// returns the challenge r used to fold U_i and u_i, as computed in the AugmentedFCircuit
+fn get_challenge<C: CurveGroup>(
+    poseidon_config: &PoseidonConfig<C::ScalarField>,
+    pp_hash: C::ScalarField,
+    U_i: &CommittedInstance<C>,
+    u_i: &CommittedInstance<C>,
+    cmT: C,
+) -> Result<C::ScalarField, Error>
+where
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    let r_bits = ChallengeGadget::<C>::get_challenge_native(
+        poseidon_config,
+        pp_hash,
+        U_i.clone(),
+        u_i.clone(),
+        cmT,
+    )?;
+    C::ScalarField::from_bigint(BigInteger::from_bits_le(&r_bits)).ok_or(Error::OutOfBounds)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bn254::{constraints::GVar, Fq, Fr, G1Projective as Projective};
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+
+    use crate::commitment::pedersen::Pedersen;
+    use crate::folding::nova::{get_cs_params_len, ProverParams};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_canonical_config;
+
+    #[test]
+    fn test_decider() {
+        // use Nova as FoldingScheme
+        type NOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >;
+        type DECIDER = Decider<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+            NOVA, // here we define the FoldingScheme to use
+        >;
+
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let cf_poseidon_config = poseidon_canonical_config::<Fq>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let z_0 = vec![Fr::from(3_u32)];
+
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config: poseidon_config.clone(),
+                cs_params: pedersen_params.clone(),
+                cf_cs_params: cf_pedersen_params.clone(),
+            };
+        let (_, nova_vp) = NOVA::preprocess(&(prover_params.clone(), F_circuit)).unwrap();
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        nova.prove_step(&mut rng, vec![]).unwrap();
+        nova.prove_step(&mut rng, vec![]).unwrap(); // do a 2nd step
+
+        // decider proof generation
+        let decider_pp = (
+            pedersen_params.clone(),
+            cf_pedersen_params.clone(),
+            cf_poseidon_config.clone(),
+        );
+        let proof = DECIDER::prove(decider_pp, &mut rng, nova.clone()).unwrap();
+
+        // decider proof verification
+        let decider_vp = (
+            nova_vp,
+            pedersen_params,
+            cf_pedersen_params,
+            cf_poseidon_config,
+        );
+        let verified = DECIDER::verify(
+            decider_vp.clone(),
+            nova.i,
+            nova.z_0.clone(),
+            nova.z_i.clone(),
+            &nova.U_i,
+            &nova.u_i,
+            &proof,
+        )
+        .unwrap();
+        assert!(verified);
+
+        // the proof does not verify for a wrong last state
+        assert!(DECIDER::verify(
+            decider_vp,
+            nova.i,
+            nova.z_0,
+            vec![Fr::from(4_u32)],
+            &nova.U_i,
+            &nova.u_i,
+            &proof,
+        )
+        .is_err());
+    }
+}
@@ -5,6 +5,7 @@ use ark_ec::{AffineRepr, CurveGroup, Group};
 use ark_ff::{BigInteger, PrimeField};
 use ark_groth16::Groth16;
 use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_snark::SNARK;
 use ark_std::rand::{CryptoRng, RngCore};
 use ark_std::{One, Zero};
@@ -13,24 +14,27 @@ use core::marker::PhantomData;
 pub use super::decider_eth_circuit::{DeciderEthCircuit, KZGChallengesGadget};
 use super::{nifs::NIFS, CommittedInstance, Nova};
 use crate::commitment::{
-    kzg::{Proof as KZGProof, KZG},
+    kzg::{BatchProof as KZGBatchProof, KZG},
     pedersen::Params as PedersenParams,
-    CommitmentScheme,
+    BatchCommitmentScheme, CommitmentScheme,
 };
 use crate::folding::circuits::{nonnative::affine::NonNativeAffineVar, CF2};
 use crate::frontend::FCircuit;
+use crate::transcript::{keccak::KeccakTranscript, Transcript};
 use crate::Error;
 use crate::{Decider as DeciderTrait, FoldingScheme};
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Proof<C1, CS1, S>
 where
     C1: CurveGroup,
-    CS1: CommitmentScheme<C1, ProverChallenge = C1::ScalarField, Challenge = C1::ScalarField>,
+    CS1: BatchCommitmentScheme<C1, ProverChallenge = C1::ScalarField, Challenge = C1::ScalarField>,
     S: SNARK<C1::ScalarField>,
 {
     pub snark_proof: S::Proof,
-    pub kzg_proofs: [CS1::Proof; 2],
+    // batch opening of U.cmW & U.cmE at the KZG challenges, whose challenges are computed with
+    // the Keccak transcript so that they can be recomputed by the EVM verifier
+    pub kzg_proof: CS1::BatchProof,
     // cmT and r are values for the last fold, U_{i+1}=NIFS.V(r, U_i, u_i, cmT), and they are
     // checked in-circuit
     pub cmT: C1,
@@ -62,11 +66,11 @@ where
     GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
     GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
     FC: FCircuit<C1::ScalarField>,
-    CS1: CommitmentScheme<
+    CS1: BatchCommitmentScheme<
         C1,
         ProverChallenge = C1::ScalarField,
         Challenge = C1::ScalarField,
-        Proof = KZGProof<C1>,
+        BatchProof = KZGBatchProof<C1>,
     >, // KZG commitment, where challenge is C1::Fr elem
     // enforce that the CS2 is Pedersen commitment scheme, since we're at Ethereum's EVM decider
     CS2: CommitmentScheme<C2, ProverParams = PedersenParams<C2>>,
@@ -115,25 +119,21 @@ where
             .kzg_c_E
             .ok_or(Error::MissingValue("kzg_c_E".to_string()))?;
 
-        // generate KZG proofs
-        let U_cmW_proof = CS1::prove_with_challenge(
+        // generate the KZG batch proof of U_{i+1}.cmW & U_{i+1}.cmE
+        let U_i1 = circuit
+            .U_i1
+            .ok_or(Error::MissingValue("U_i1".to_string()))?;
+        let kzg_proof = CS1::prove_batch(
             &cs_pk,
-            challenge_W,
-            &W_i1.W,
-            &C1::ScalarField::zero(),
-            None,
-        )?;
-        let U_cmE_proof = CS1::prove_with_challenge(
-            &cs_pk,
-            challenge_E,
-            &W_i1.E,
-            &C1::ScalarField::zero(),
-            None,
+            &mut KeccakTranscript::<C1>::new(&()),
+            &[U_i1.cmW, U_i1.cmE],
+            &[W_i1.W, W_i1.E],
+            &[challenge_W, challenge_E],
         )?;
 
         Ok(Self::Proof {
             snark_proof,
-            kzg_proofs: [U_cmW_proof, U_cmE_proof],
+            kzg_proof,
             cmT,
             r: r_Fr,
             kzg_challenges: [challenge_W, challenge_E],
@@ -173,10 +173,7 @@ where
             cmW_x,
             cmW_y,
             proof.kzg_challenges.to_vec(),
-            vec![
-                proof.kzg_proofs[0].eval, // eval_W
-                proof.kzg_proofs[1].eval, // eval_E
-            ],
+            proof.kzg_proof.evals.clone(), // [eval_W, eval_E]
             cmT_x,
             cmT_y,
             vec![proof.r],
@@ -190,17 +187,12 @@ where
         }
 
         // we're at the Ethereum EVM case, so the CS1 is KZG commitments
-        CS1::verify_with_challenge(
-            &cs_vk,
-            proof.kzg_challenges[0],
-            &U.cmW,
-            &proof.kzg_proofs[0],
-        )?;
-        CS1::verify_with_challenge(
+        CS1::verify_batch(
             &cs_vk,
-            proof.kzg_challenges[1],
-            &U.cmE,
-            &proof.kzg_proofs[1],
+            &mut KeccakTranscript::<C1>::new(&()),
+            &[U.cmW, U.cmE],
+            &proof.kzg_challenges,
+            &proof.kzg_proof,
         )?;
 
         Ok(true)
@@ -248,10 +240,10 @@ pub fn prepare_calldata(
         point_to_eth_format(proof.snark_proof.c)?, // pC
         proof.kzg_challenges[0].into_bigint().to_bytes_be(), // challenge_W
         proof.kzg_challenges[1].into_bigint().to_bytes_be(), // challenge_E
-        proof.kzg_proofs[0].eval.into_bigint().to_bytes_be(), // eval W
-        proof.kzg_proofs[1].eval.into_bigint().to_bytes_be(), // eval E
-        point_to_eth_format(proof.kzg_proofs[0].proof.into_affine())?, // W kzg_proof
-        point_to_eth_format(proof.kzg_proofs[1].proof.into_affine())?, // E kzg_proof
+        proof.kzg_proof.evals[0].into_bigint().to_bytes_be(), // eval W
+        proof.kzg_proof.evals[1].into_bigint().to_bytes_be(), // eval E
+        point_to_eth_format(proof.kzg_proof.W.into_affine())?, // kzg batch proof W
+        point_to_eth_format(proof.kzg_proof.W_prime.into_affine())?, // kzg batch proof W'
     ]
     .concat())
 }
@@ -347,9 +339,9 @@ pub mod tests {
         let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
         println!("Nova initialized, {:?}", start.elapsed());
         let start = Instant::now();
-        nova.prove_step(vec![]).unwrap();
+        nova.prove_step(&mut rng, vec![]).unwrap();
         println!("prove_step, {:?}", start.elapsed());
-        nova.prove_step(vec![]).unwrap(); // do a 2nd step
+        nova.prove_step(&mut rng, vec![]).unwrap(); // do a 2nd step
 
         // generate Groth16 setup
         let circuit = DeciderEthCircuit::<
@@ -223,6 +223,9 @@ where
     /// CycleFold PedersenParams over C2
     pub cf_pedersen_params: PedersenParams<C2>,
     pub poseidon_config: PoseidonConfig<CF1<C1>>,
+    /// digest of the public parameters of the Nova instance. It is hardcoded into the circuit, so
+    /// the decider's verifying key is bound to it.
+    pub pp_hash: CF1<C1>,
     pub i: Option<CF1<C1>>,
     /// initial state
     pub z_0: Option<Vec<C1::ScalarField>>,
@@ -273,6 +276,7 @@ where
         )?;
         let r_bits = ChallengeGadget::<C1>::get_challenge_native(
             &nova.poseidon_config,
+            nova.pp_hash,
             nova.U_i.clone(),
             nova.u_i.clone(),
             cmT,
@@ -317,6 +321,7 @@ where
             cf_r1cs: nova.cf_r1cs,
             cf_pedersen_params: nova.cf_cs_params,
             poseidon_config: nova.poseidon_config,
+            pp_hash: nova.pp_hash,
             i: Some(nova.i),
             z_0: Some(nova.z_0),
             z_i: Some(nova.z_i),
@@ -360,6 +365,7 @@ where
                 Ok(self.r1cs.clone())
             })?;
 
+        let pp_hash = FpVar::<CF1<C1>>::new_constant(cs.clone(), self.pp_hash)?;
         let i =
             FpVar::<CF1<C1>>::new_input(cs.clone(), || Ok(self.i.unwrap_or_else(CF1::<C1>::zero)))?;
         let z_0 = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
@@ -420,10 +426,14 @@ where
         u_i.cmE.y.enforce_equal_unaligned(&zero)?;
         (u_i.u.is_one()?).enforce_equal(&Boolean::TRUE)?;
 
-        // 3.a u_i.x[0] == H(i, z_0, z_i, U_i)
-        let (u_i_x, U_i_vec) =
-            U_i.clone()
-                .hash(&crh_params, i.clone(), z_0.clone(), z_i.clone())?;
+        // 3.a u_i.x[0] == H(pp_hash, i, z_0, z_i, U_i)
+        let (u_i_x, U_i_vec) = U_i.clone().hash(
+            &crh_params,
+            pp_hash.clone(),
+            i.clone(),
+            z_0.clone(),
+            z_i.clone(),
+        )?;
         (u_i.x[0]).enforce_equal(&u_i_x)?;
 
         #[cfg(feature = "light-test")]
@@ -512,6 +522,7 @@ where
         let r_bits = ChallengeGadget::<C1>::get_challenge_gadget(
             cs.clone(),
             &self.poseidon_config,
+            pp_hash,
             U_i_vec,
             u_i.clone(),
             cmT.clone(),
@@ -802,12 +813,13 @@ pub mod tests {
 
         // generate a Nova instance and do a step of it
         let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
-        nova.prove_step(vec![]).unwrap();
+        nova.prove_step(&mut rng, vec![]).unwrap();
         let ivc_v = nova.clone();
         let verifier_params = VerifierParams::<Projective, Projective2> {
             poseidon_config: poseidon_config.clone(),
             r1cs: ivc_v.clone().r1cs,
             cf_r1cs: ivc_v.clone().cf_r1cs,
+            pp_hash: ivc_v.pp_hash,
         };
         let (running_instance, incoming_instance, cyclefold_instance) = ivc_v.instances();
         NOVA::verify(
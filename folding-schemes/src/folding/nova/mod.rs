@@ -8,9 +8,13 @@ use ark_ec::{AffineRepr, CurveGroup, Group};
 use ark_ff::{BigInteger, Field, PrimeField, ToConstraintField};
 use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
 use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::fmt::Debug;
-use ark_std::{One, Zero};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::{One, UniformRand, Zero};
 use core::marker::PhantomData;
 use std::usize;
 
@@ -23,26 +27,29 @@ use crate::folding::circuits::{
     CF2,
 };
 use crate::frontend::FCircuit;
-use crate::utils::vec::is_zero_vec;
+use crate::utils::{
+    deserialize_poseidon_config, hash_bytes, serialize_poseidon_config, vec::is_zero_vec,
+};
 use crate::Error;
 use crate::FoldingScheme;
 
 pub mod circuits;
 pub mod cyclefold;
+pub mod decider;
 pub mod decider_eth;
 pub mod decider_eth_circuit;
 pub mod nifs;
+pub mod pcd;
 pub mod serialize;
+pub mod spartan;
 pub mod traits;
+pub mod zk;
 
 use circuits::{AugmentedFCircuit, ChallengeGadget};
-use cyclefold::{CycleFoldChallengeGadget, CycleFoldCircuit};
+use cyclefold::{fold_cyclefold_circuit, CycleFoldCircuit};
 use nifs::NIFS;
 use traits::NovaR1CS;
 
-#[cfg(test)]
-use cyclefold::CF_IO_LEN;
-
 #[derive(Debug, Clone, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct CommittedInstance<C: CurveGroup> {
     pub cmE: C,
@@ -69,11 +76,13 @@ where
 {
     /// hash implements the committed instance hash compatible with the gadget implemented in
     /// nova/circuits.rs::CommittedInstanceVar.hash.
-    /// Returns `H(i, z_0, z_i, U_i)`, where `i` can be `i` but also `i+1`, and `U_i` is the
-    /// `CommittedInstance`.
+    /// Returns `H(pp_hash, i, z_0, z_i, U_i)`, where `i` can be `i` but also `i+1`, `U_i` is the
+    /// `CommittedInstance` and `pp_hash` is the digest of the public parameters (see
+    /// `ProverParams::pp_hash`).
     pub fn hash(
         &self,
         poseidon_config: &PoseidonConfig<C::ScalarField>,
+        pp_hash: C::ScalarField,
         i: C::ScalarField,
         z_0: Vec<C::ScalarField>,
         z_i: Vec<C::ScalarField>,
@@ -84,7 +93,7 @@ where
         CRH::<C::ScalarField>::evaluate(
             poseidon_config,
             vec![
-                vec![i],
+                vec![pp_hash, i],
                 z_0,
                 z_i,
                 vec![self.u],
@@ -172,7 +181,17 @@ where
             rW: C::ScalarField::zero(),
         }
     }
-    pub fn commit<CS: CommitmentScheme<C>>(
+    /// same as `new` but sampling a random blinding factor for W, to be used with the commitment
+    /// scheme in hiding mode. E is zero for a fresh (un-relaxed) witness, so rE is kept as 0.
+    pub fn new_with_blinding(w: Vec<C::ScalarField>, e_len: usize, mut rng: impl RngCore) -> Self {
+        Self {
+            E: vec![C::ScalarField::zero(); e_len],
+            rE: C::ScalarField::zero(),
+            W: w,
+            rW: C::ScalarField::rand(&mut rng),
+        }
+    }
+    pub fn commit<CS: CommitmentScheme<C, H>, const H: bool>(
         &self,
         params: &CS::ProverParams,
         x: Vec<C::ScalarField>,
@@ -192,11 +211,11 @@ where
 }
 
 #[derive(Debug, Clone)]
-pub struct ProverParams<C1, C2, CS1, CS2>
+pub struct ProverParams<C1, C2, CS1, CS2, const H: bool = false>
 where
     C1: CurveGroup,
     C2: CurveGroup,
-    CS1: CommitmentScheme<C1>,
+    CS1: CommitmentScheme<C1, H>,
     CS2: CommitmentScheme<C2>,
 {
     pub poseidon_config: PoseidonConfig<C1::ScalarField>,
@@ -204,24 +223,99 @@ where
     pub cf_cs_params: CS2::ProverParams,
 }
 
+impl<C1, C2, CS1, CS2, const H: bool> ProverParams<C1, C2, CS1, CS2, H>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    CS1: CommitmentScheme<C1, H>,
+    CS2: CommitmentScheme<C2>,
+    C1::ScalarField: Absorb,
+{
+    /// returns the digest of the public parameters: the R1CS of the AugmentedFCircuit and of the
+    /// CycleFold circuit, the commitment schemes parameters and the Poseidon config. It is
+    /// absorbed into the hash of the IVC instances and into the folding challenges, so that a
+    /// proof generated for some parameters can not be verified with different ones.
+    pub fn pp_hash(
+        &self,
+        r1cs: &R1CS<C1::ScalarField>,
+        cf_r1cs: &R1CS<C2::ScalarField>,
+    ) -> Result<C1::ScalarField, Error> {
+        let mut bytes = vec![];
+        r1cs.serialize_compressed(&mut bytes)?;
+        cf_r1cs.serialize_compressed(&mut bytes)?;
+        self.cs_params.serialize_compressed(&mut bytes)?;
+        self.cf_cs_params.serialize_compressed(&mut bytes)?;
+        serialize_poseidon_config(&self.poseidon_config, &mut bytes)?;
+        hash_bytes(&self.poseidon_config, &bytes)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct VerifierParams<C1: CurveGroup, C2: CurveGroup> {
     pub poseidon_config: PoseidonConfig<C1::ScalarField>,
     pub r1cs: R1CS<C1::ScalarField>,
     pub cf_r1cs: R1CS<C2::ScalarField>,
+    /// digest of the public parameters, computed by `ProverParams::pp_hash`
+    pub pp_hash: C1::ScalarField,
+}
+
+impl<C1: CurveGroup, C2: CurveGroup> Valid for VerifierParams<C1, C2> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.r1cs.check()?;
+        self.cf_r1cs.check()?;
+        self.pp_hash.check()
+    }
+}
+impl<C1: CurveGroup, C2: CurveGroup> CanonicalSerialize for VerifierParams<C1, C2> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_poseidon_config(&self.poseidon_config, &mut writer)
+            .map_err(|_| SerializationError::InvalidData)?;
+        self.r1cs.serialize_with_mode(&mut writer, compress)?;
+        self.cf_r1cs.serialize_with_mode(&mut writer, compress)?;
+        self.pp_hash.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        let mut poseidon_config_bytes = vec![];
+        serialize_poseidon_config(&self.poseidon_config, &mut poseidon_config_bytes).unwrap();
+        poseidon_config_bytes.len()
+            + self.r1cs.serialized_size(compress)
+            + self.cf_r1cs.serialized_size(compress)
+            + self.pp_hash.serialized_size(compress)
+    }
+}
+impl<C1: CurveGroup, C2: CurveGroup> CanonicalDeserialize for VerifierParams<C1, C2> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            poseidon_config: deserialize_poseidon_config(&mut reader)?,
+            r1cs: R1CS::deserialize_with_mode(&mut reader, compress, validate)?,
+            cf_r1cs: R1CS::deserialize_with_mode(&mut reader, compress, validate)?,
+            pp_hash: C1::ScalarField::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
 }
 
 /// Implements Nova+CycleFold's IVC, described in [Nova](https://eprint.iacr.org/2021/370.pdf) and
 /// [CycleFold](https://eprint.iacr.org/2023/1192.pdf), following the FoldingScheme trait
+/// The `H` const generic specifies whether the commitments over C1 are hiding, which is needed
+/// to obtain zero-knowledge IVC proofs (see the `zk` module).
 #[derive(Clone, Debug)]
-pub struct Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
+pub struct Nova<C1, GC1, C2, GC2, FC, CS1, CS2, const H: bool = false>
 where
     C1: CurveGroup,
     GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
     C2: CurveGroup,
     GC2: CurveVar<C2, CF2<C2>>,
     FC: FCircuit<C1::ScalarField>,
-    CS1: CommitmentScheme<C1>,
+    CS1: CommitmentScheme<C1, H>,
     CS2: CommitmentScheme<C2>,
 {
     _gc1: PhantomData<GC1>,
@@ -236,6 +330,8 @@ where
     pub cs_params: CS1::ProverParams,
     /// CycleFold CommitmentScheme::ProverParams, over C2
     pub cf_cs_params: CS2::ProverParams,
+    /// digest of the public parameters, see `ProverParams::pp_hash`
+    pub pp_hash: C1::ScalarField,
     /// F circuit, the circuit that is being folded
     pub F: FC,
     pub i: C1::ScalarField,
@@ -254,15 +350,15 @@ where
     pub cf_U_i: CommittedInstance<C2>,
 }
 
-impl<C1, GC1, C2, GC2, FC, CS1, CS2> FoldingScheme<C1, C2, FC>
-    for Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
+impl<C1, GC1, C2, GC2, FC, CS1, CS2, const H: bool> FoldingScheme<C1, C2, FC>
+    for Nova<C1, GC1, C2, GC2, FC, CS1, CS2, H>
 where
     C1: CurveGroup,
     GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
     C2: CurveGroup,
     GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
     FC: FCircuit<C1::ScalarField>,
-    CS1: CommitmentScheme<C1>,
+    CS1: CommitmentScheme<C1, H>,
     CS2: CommitmentScheme<C2>,
     <C1 as CurveGroup>::BaseField: PrimeField,
     <C2 as CurveGroup>::BaseField: PrimeField,
@@ -273,10 +369,11 @@ where
     for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
 {
     type PreprocessorParam = (Self::ProverParam, FC);
-    type ProverParam = ProverParams<C1, C2, CS1, CS2>;
+    type ProverParam = ProverParams<C1, C2, CS1, CS2, H>;
     type VerifierParam = VerifierParams<C1, C2>;
-    type CommittedInstanceWithWitness = (CommittedInstance<C1>, Witness<C1>);
-    type CFCommittedInstanceWithWitness = (CommittedInstance<C2>, Witness<C2>);
+    type RunningInstance = (CommittedInstance<C1>, Witness<C1>);
+    type IncomingInstance = (CommittedInstance<C1>, Witness<C1>);
+    type CFInstance = (CommittedInstance<C2>, Witness<C2>);
 
     fn preprocess(
         prep_param: &Self::PreprocessorParam,
@@ -286,10 +383,12 @@ where
         let (r1cs, cf_r1cs) =
             get_r1cs::<C1, GC1, C2, GC2, FC>(&prover_params.poseidon_config, F_circuit.clone())?;
 
+        let pp_hash = prover_params.pp_hash(&r1cs, &cf_r1cs)?;
         let verifier_params = VerifierParams::<C1, C2> {
             poseidon_config: prover_params.poseidon_config.clone(),
             r1cs,
             cf_r1cs,
+            pp_hash,
         };
         Ok((prover_params.clone(), verifier_params))
     }
@@ -314,6 +413,8 @@ where
         let cs2 = cs2.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
         let cf_r1cs = extract_r1cs::<C1::BaseField>(&cs2);
 
+        let pp_hash = pp.pp_hash(&r1cs, &cf_r1cs)?;
+
         // setup the dummy instances
         let (w_dummy, u_dummy) = r1cs.dummy_instance();
         let (cf_w_dummy, cf_u_dummy) = cf_r1cs.dummy_instance();
@@ -329,6 +430,7 @@ where
             poseidon_config: pp.poseidon_config.clone(),
             cs_params: pp.cs_params.clone(),
             cf_cs_params: pp.cf_cs_params.clone(),
+            pp_hash,
             F,
             i: C1::ScalarField::zero(),
             z_0: z_0.clone(),
@@ -343,8 +445,13 @@ where
         })
     }
 
-    /// Implements IVC.P of Nova+CycleFold
-    fn prove_step(&mut self, external_inputs: Vec<C1::ScalarField>) -> Result<(), Error> {
+    /// Implements IVC.P of Nova+CycleFold. In hiding mode, `rng` is used to sample the blinding
+    /// factors of the incoming witness and of cm(T).
+    fn prove_step(
+        &mut self,
+        mut rng: impl RngCore + CryptoRng,
+        external_inputs: Vec<C1::ScalarField>,
+    ) -> Result<(), Error> {
         let augmented_F_circuit: AugmentedFCircuit<C1, C2, GC2, FC>;
 
         if self.z_i.len() != self.F.state_len() {
@@ -386,11 +493,12 @@ where
             .step_native(i_usize, self.z_i.clone(), external_inputs.clone())?;
 
         // compute T and cmT for AugmentedFCircuit
-        let (T, cmT) = self.compute_cmT()?;
+        let (T, rT, cmT) = self.compute_cmT(&mut rng)?;
 
         // r_bits is the r used to the RLC of the F' instances
         let r_bits = ChallengeGadget::<C1>::get_challenge_native(
             &self.poseidon_config,
+            self.pp_hash,
             self.U_i.clone(),
             self.u_i.clone(),
             cmT,
@@ -401,14 +509,14 @@ where
             .ok_or(Error::OutOfBounds)?;
 
         // fold Nova instances
-        let (W_i1, U_i1): (Witness<C1>, CommittedInstance<C1>) = NIFS::<C1, CS1>::fold_instances(
-            r_Fr, &self.W_i, &self.U_i, &self.w_i, &self.u_i, &T, cmT,
-        )?;
+        let W_i1 = NIFS::<C1, CS1, H>::fold_witness(r_Fr, &self.W_i, &self.w_i, &T, rT)?;
+        let U_i1 = NIFS::<C1, CS1, H>::fold_committed_instance(r_Fr, &self.U_i, &self.u_i, &cmT);
 
         // folded instance output (public input, x)
-        // u_{i+1}.x[0] = H(i+1, z_0, z_{i+1}, U_{i+1})
+        // u_{i+1}.x[0] = H(pp_hash, i+1, z_0, z_{i+1}, U_{i+1})
         let u_i1_x = U_i1.hash(
             &self.poseidon_config,
+            self.pp_hash,
             self.i + C1::ScalarField::one(),
             self.z_0.clone(),
             z_i1.clone(),
@@ -422,6 +530,7 @@ where
             augmented_F_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
                 _gc2: PhantomData,
                 poseidon_config: self.poseidon_config.clone(),
+                pp_hash: Some(self.pp_hash),
                 i: Some(C1::ScalarField::zero()), // = i=0
                 i_usize: Some(0),
                 z_0: Some(self.z_0.clone()), // = z_i
@@ -443,7 +552,7 @@ where
             };
 
             #[cfg(test)]
-            NIFS::<C1, CS1>::verify_folded_instance(r_Fr, &self.U_i, &self.u_i, &U_i1, &cmT)?;
+            NIFS::<C1, CS1, H>::verify_folded_instance(r_Fr, &self.U_i, &self.u_i, &U_i1, &cmT)?;
         } else {
             // CycleFold part:
             // get the vector used as public inputs 'x' in the CycleFold circuit
@@ -480,21 +589,34 @@ where
             };
 
             // fold self.cf_U_i + cfW_U -> folded running with cfW
-            let (_cfW_w_i, cfW_u_i, cfW_W_i1, cfW_U_i1, cfW_cmT, _) = self.fold_cyclefold_circuit(
-                self.cf_W_i.clone(), // CycleFold running instance witness
-                self.cf_U_i.clone(), // CycleFold running instance
-                cfW_u_i_x,
-                cfW_circuit,
-            )?;
+            let (_cfW_w_i, cfW_u_i, cfW_W_i1, cfW_U_i1, cfW_cmT, _) =
+                fold_cyclefold_circuit::<C1, GC1, C2, GC2, CS2>(
+                    &self.poseidon_config,
+                    self.cf_r1cs.clone(),
+                    self.cf_cs_params.clone(),
+                    self.cf_W_i.clone(), // CycleFold running instance witness
+                    self.cf_U_i.clone(), // CycleFold running instance
+                    cfW_u_i_x,
+                    cfW_circuit,
+                )?;
             // fold [the output from folding self.cf_U_i + cfW_U] + cfE_U = folded_running_with_cfW + cfE
             let (_cfE_w_i, cfE_u_i, cf_W_i1, cf_U_i1, cf_cmT, _) =
-                self.fold_cyclefold_circuit(cfW_W_i1, cfW_U_i1.clone(), cfE_u_i_x, cfE_circuit)?;
+                fold_cyclefold_circuit::<C1, GC1, C2, GC2, CS2>(
+                    &self.poseidon_config,
+                    self.cf_r1cs.clone(),
+                    self.cf_cs_params.clone(),
+                    cfW_W_i1,
+                    cfW_U_i1.clone(),
+                    cfE_u_i_x,
+                    cfE_circuit,
+                )?;
 
             cf_u_i1_x = cf_U_i1.hash_cyclefold(&self.poseidon_config)?;
 
             augmented_F_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
                 _gc2: PhantomData,
                 poseidon_config: self.poseidon_config.clone(),
+                pp_hash: Some(self.pp_hash),
                 i: Some(self.i),
                 i_usize: Some(i_usize),
                 z_0: Some(self.z_0.clone()),
@@ -549,8 +671,12 @@ where
         // set values for next iteration
         self.i += C1::ScalarField::one();
         self.z_i = z_i1;
-        self.w_i = Witness::<C1>::new(w_i1, self.r1cs.A.n_rows);
-        self.u_i = self.w_i.commit::<CS1>(&self.cs_params, x_i1)?;
+        self.w_i = if H {
+            Witness::<C1>::new_with_blinding(w_i1, self.r1cs.A.n_rows, &mut rng)
+        } else {
+            Witness::<C1>::new(w_i1, self.r1cs.A.n_rows)
+        };
+        self.u_i = self.w_i.commit::<CS1, H>(&self.cs_params, x_i1)?;
         self.W_i = W_i1;
         self.U_i = U_i1;
 
@@ -570,9 +696,9 @@ where
     fn instances(
         &self,
     ) -> (
-        Self::CommittedInstanceWithWitness,
-        Self::CommittedInstanceWithWitness,
-        Self::CFCommittedInstanceWithWitness,
+        Self::RunningInstance,
+        Self::IncomingInstance,
+        Self::CFInstance,
     ) {
         (
             (self.U_i.clone(), self.W_i.clone()),
@@ -587,9 +713,9 @@ where
         z_0: Vec<C1::ScalarField>, // initial state
         z_i: Vec<C1::ScalarField>, // last state
         num_steps: C1::ScalarField,
-        running_instance: Self::CommittedInstanceWithWitness,
-        incoming_instance: Self::CommittedInstanceWithWitness,
-        cyclefold_instance: Self::CFCommittedInstanceWithWitness,
+        running_instance: Self::RunningInstance,
+        incoming_instance: Self::IncomingInstance,
+        cyclefold_instance: Self::CFInstance,
     ) -> Result<(), Error> {
         let (U_i, W_i) = running_instance;
         let (u_i, w_i) = incoming_instance;
@@ -600,8 +726,9 @@ where
         }
 
         // check that u_i's output points to the running instance
-        // u_i.X[0] == H(i, z_0, z_i, U_i)
-        let expected_u_i_x = U_i.hash(&vp.poseidon_config, num_steps, z_0, z_i.clone())?;
+        // u_i.X[0] == H(pp_hash, i, z_0, z_i, U_i)
+        let expected_u_i_x =
+            U_i.hash(&vp.poseidon_config, vp.pp_hash, num_steps, z_0, z_i.clone())?;
         if expected_u_i_x != u_i.x[0] {
             return Err(Error::IVCVerificationFail);
         }
@@ -629,121 +756,48 @@ where
     }
 }
 
-impl<C1, GC1, C2, GC2, FC, CS1, CS2> Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
+impl<C1, GC1, C2, GC2, FC, CS1, CS2, const H: bool> Nova<C1, GC1, C2, GC2, FC, CS1, CS2, H>
 where
     C1: CurveGroup,
     GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
     C2: CurveGroup,
     GC2: CurveVar<C2, CF2<C2>>,
     FC: FCircuit<C1::ScalarField>,
-    CS1: CommitmentScheme<C1>,
+    CS1: CommitmentScheme<C1, H>,
     CS2: CommitmentScheme<C2>,
     <C2 as CurveGroup>::BaseField: PrimeField,
     <C1 as Group>::ScalarField: Absorb,
     <C2 as Group>::ScalarField: Absorb,
     C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
 {
-    // computes T and cmT for the AugmentedFCircuit
-    fn compute_cmT(&self) -> Result<(Vec<C1::ScalarField>, C1), Error> {
-        NIFS::<C1, CS1>::compute_cmT(
+    // computes T, its blinding factor rT and cmT for the AugmentedFCircuit. In hiding mode cm(T)
+    // is blinded with a random rT, otherwise rT=0.
+    fn compute_cmT(
+        &self,
+        mut rng: impl RngCore,
+    ) -> Result<(Vec<C1::ScalarField>, C1::ScalarField, C1), Error> {
+        if H {
+            let rT = C1::ScalarField::rand(&mut rng);
+            let (T, cmT) = NIFS::<C1, CS1, H>::compute_cmT_with_blind(
+                &self.cs_params,
+                &self.r1cs,
+                &self.w_i,
+                &self.u_i,
+                &self.W_i,
+                &self.U_i,
+                rT,
+            )?;
+            return Ok((T, rT, cmT));
+        }
+        let (T, cmT) = NIFS::<C1, CS1, H>::compute_cmT(
             &self.cs_params,
             &self.r1cs,
             &self.w_i,
             &self.u_i,
             &self.W_i,
             &self.U_i,
-        )
-    }
-    // computes T* and cmT* for the CycleFoldCircuit
-    fn compute_cf_cmT(
-        &self,
-        cf_w_i: &Witness<C2>,
-        cf_u_i: &CommittedInstance<C2>,
-        cf_W_i: &Witness<C2>,
-        cf_U_i: &CommittedInstance<C2>,
-    ) -> Result<(Vec<C2::ScalarField>, C2), Error> {
-        NIFS::<C2, CS2>::compute_cyclefold_cmT(
-            &self.cf_cs_params,
-            &self.cf_r1cs,
-            cf_w_i,
-            cf_u_i,
-            cf_W_i,
-            cf_U_i,
-        )
-    }
-}
-
-impl<C1, GC1, C2, GC2, FC, CS1, CS2> Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
-where
-    C1: CurveGroup,
-    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
-    C2: CurveGroup,
-    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
-    FC: FCircuit<C1::ScalarField>,
-    CS1: CommitmentScheme<C1>,
-    CS2: CommitmentScheme<C2>,
-    <C1 as CurveGroup>::BaseField: PrimeField,
-    <C2 as CurveGroup>::BaseField: PrimeField,
-    <C1 as Group>::ScalarField: Absorb,
-    <C2 as Group>::ScalarField: Absorb,
-    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
-    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
-    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
-{
-    // folds the given cyclefold circuit and its instances
-    #[allow(clippy::type_complexity)]
-    fn fold_cyclefold_circuit(
-        &self,
-        cf_W_i: Witness<C2>,           // witness of the running instance
-        cf_U_i: CommittedInstance<C2>, // running instance
-        cf_u_i_x: Vec<C2::ScalarField>,
-        cf_circuit: CycleFoldCircuit<C1, GC1>,
-    ) -> Result<
-        (
-            Witness<C2>,
-            CommittedInstance<C2>, // u_i
-            Witness<C2>,           // W_i1
-            CommittedInstance<C2>, // U_i1
-            C2,                    // cmT
-            C2::ScalarField,       // r_Fq
-        ),
-        Error,
-    > {
-        let cs2 = ConstraintSystem::<C1::BaseField>::new_ref();
-        cf_circuit.generate_constraints(cs2.clone())?;
-
-        let cs2 = cs2.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
-        let (cf_w_i, cf_x_i) = extract_w_x::<C1::BaseField>(&cs2);
-        if cf_x_i != cf_u_i_x {
-            return Err(Error::NotEqual);
-        }
-
-        #[cfg(test)]
-        if cf_x_i.len() != CF_IO_LEN {
-            return Err(Error::NotExpectedLength(cf_x_i.len(), CF_IO_LEN));
-        }
-
-        // fold cyclefold instances
-        let cf_w_i = Witness::<C2>::new(cf_w_i.clone(), self.cf_r1cs.A.n_rows);
-        let cf_u_i: CommittedInstance<C2> =
-            cf_w_i.commit::<CS2>(&self.cf_cs_params, cf_x_i.clone())?;
-
-        // compute T* and cmT* for CycleFoldCircuit
-        let (cf_T, cf_cmT) = self.compute_cf_cmT(&cf_w_i, &cf_u_i, &cf_W_i, &cf_U_i)?;
-
-        let cf_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_native(
-            &self.poseidon_config,
-            cf_U_i.clone(),
-            cf_u_i.clone(),
-            cf_cmT,
-        )?;
-        let cf_r_Fq = C1::BaseField::from_bigint(BigInteger::from_bits_le(&cf_r_bits))
-            .ok_or(Error::OutOfBounds)?;
-
-        let (cf_W_i1, cf_U_i1) = NIFS::<C2, CS2>::fold_instances(
-            cf_r_Fq, &cf_W_i, &cf_U_i, &cf_w_i, &cf_u_i, &cf_T, cf_cmT,
         )?;
-        Ok((cf_w_i, cf_u_i, cf_W_i1, cf_U_i1, cf_cmT, cf_r_Fq))
+        Ok((T, C1::ScalarField::zero(), cmT))
     }
 }
 
@@ -876,6 +930,7 @@ pub mod tests {
         cf_cs_params: CS2::ProverParams,
         F_circuit: CubicFCircuit<Fr>,
     ) {
+        let mut rng = ark_std::test_rng();
         type NOVA<CS1, CS2> =
             Nova<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>, CS1, CS2>;
 
@@ -890,7 +945,7 @@ pub mod tests {
 
         let num_steps: usize = 3;
         for _ in 0..num_steps {
-            nova.prove_step(vec![]).unwrap();
+            nova.prove_step(&mut rng, vec![]).unwrap();
         }
         assert_eq!(Fr::from(num_steps as u32), nova.i);
 
@@ -898,10 +953,49 @@ pub mod tests {
             poseidon_config,
             r1cs: nova.clone().r1cs,
             cf_r1cs: nova.clone().cf_r1cs,
+            pp_hash: nova.pp_hash,
         };
         let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
         NOVA::<CS1, CS2>::verify(
-            verifier_params,
+            verifier_params.clone(),
+            z_0.clone(),
+            nova.z_i.clone(),
+            nova.i,
+            running_instance.clone(),
+            incoming_instance.clone(),
+            cyclefold_instance.clone(),
+        )
+        .unwrap();
+
+        // the IVC proof verifies with the deserialized verifier params
+        let mut verifier_params_bytes = vec![];
+        verifier_params
+            .serialize_compressed(&mut verifier_params_bytes)
+            .unwrap();
+        let deserialized_verifier_params =
+            VerifierParams::<Projective, Projective2>::deserialize_compressed(
+                verifier_params_bytes.as_slice(),
+            )
+            .unwrap();
+        assert_eq!(deserialized_verifier_params.r1cs, verifier_params.r1cs);
+        NOVA::<CS1, CS2>::verify(
+            deserialized_verifier_params,
+            z_0.clone(),
+            nova.z_i.clone(),
+            nova.i,
+            running_instance.clone(),
+            incoming_instance.clone(),
+            cyclefold_instance.clone(),
+        )
+        .unwrap();
+
+        // the IVC proof does not verify under a different pp_hash
+        let wrong_verifier_params = VerifierParams {
+            pp_hash: nova.pp_hash + Fr::one(),
+            ..verifier_params
+        };
+        assert!(NOVA::<CS1, CS2>::verify(
+            wrong_verifier_params,
             z_0,
             nova.z_i,
             nova.i,
@@ -909,6 +1003,6 @@ pub mod tests {
             incoming_instance,
             cyclefold_instance,
         )
-        .unwrap();
+        .is_err());
     }
 }
@@ -0,0 +1,1195 @@
+/// Implements a proof-carrying-data (PCD) mode for Nova+CycleFold, where the IVC steps are proven
+/// in a binary tree instead of sequentially, so that the proving can be split across workers and
+/// the results merged.
+///
+/// Each node of the tree executes one step of the F circuit, and merges the proofs of its (up to)
+/// two children: the left child covers the steps `[a, i)`, the node the step `i` and the right
+/// child the steps `[i+1, b)`, so the resulting proof covers `[a, b)`. The node circuit folds the
+/// incoming instance of each child into its running instance, and then folds both resulting
+/// running instances into a single one (Relaxed R1CS-Relaxed R1CS folding). Likewise, the
+/// CycleFold running instances of both children are folded together, and the CycleFold instances
+/// of the node's elliptic curve operations are folded into the result.
+///
+/// A missing child is represented by an empty proof (`i_start == i_end`) made of dummy instances,
+/// so that leaves and inner nodes use the same circuit.
+use ark_crypto_primitives::{
+    crh::{
+        poseidon::{
+            constraints::{CRHGadget, CRHParametersVar},
+            CRH,
+        },
+        CRHScheme, CRHSchemeGadget,
+    },
+    sponge::{poseidon::PoseidonConfig, Absorb},
+};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    groups::GroupOpsBounds,
+    prelude::CurveVar,
+    R1CSVar, ToConstraintFieldGadget,
+};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, SynthesisError,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{One, Zero};
+use core::marker::PhantomData;
+
+use super::{
+    circuits::{ChallengeGadget, CommittedInstanceVar, NIFSGadget},
+    cyclefold::{
+        fold_cyclefold_circuit, CycleFoldChallengeGadget, CycleFoldCircuit,
+        CycleFoldCommittedInstanceVar, NIFSFullGadget, CF_IO_LEN,
+    },
+    get_cm_coordinates, get_r1cs_from_cs,
+    nifs::NIFS,
+    traits::NovaR1CS,
+    CommittedInstance, ProverParams, VerifierParams, Witness,
+};
+use crate::ccs::r1cs::{extract_w_x, R1CS};
+use crate::commitment::CommitmentScheme;
+use crate::folding::circuits::{
+    nonnative::{
+        affine::{nonnative_affine_to_field_elements, NonNativeAffineVar},
+        uint::NonNativeUintVar,
+    },
+    CF1, CF2,
+};
+use crate::frontend::FCircuit;
+use crate::Error;
+
+/// number of CycleFold circuits folded at each node: cmW & cmE for the fold of each child, and
+/// cmW & two for cmE (which contains the r^2 term) for the fold of the two children
+pub const PCD_CF_CIRCUITS: usize = 7;
+
+impl<C: CurveGroup> CommittedInstance<C>
+where
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    /// hash_pcd implements the committed instance hash of the PCD nodes, compatible with the
+    /// gadget `CommittedInstanceVar::hash_pcd`.
+    /// Returns `H(pp_hash, i_start, i_end, z_start, z_end, U)`, where `[i_start, i_end)` is the
+    /// range of steps covered by the proof.
+    pub fn hash_pcd(
+        &self,
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        pp_hash: C::ScalarField,
+        i_start: C::ScalarField,
+        i_end: C::ScalarField,
+        z_start: Vec<C::ScalarField>,
+        z_end: Vec<C::ScalarField>,
+    ) -> Result<C::ScalarField, Error> {
+        let (cmE_x, cmE_y) = nonnative_affine_to_field_elements::<C>(self.cmE)?;
+        let (cmW_x, cmW_y) = nonnative_affine_to_field_elements::<C>(self.cmW)?;
+
+        CRH::<C::ScalarField>::evaluate(
+            poseidon_config,
+            [
+                vec![pp_hash, i_start, i_end],
+                z_start,
+                z_end,
+                vec![self.u],
+                self.x.clone(),
+                cmE_x,
+                cmE_y,
+                cmW_x,
+                cmW_y,
+            ]
+            .concat(),
+        )
+        .map_err(|e| Error::Other(e.to_string()))
+    }
+}
+
+impl<C> CommittedInstanceVar<C>
+where
+    C: CurveGroup,
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    /// hash_pcd implements the committed instance hash compatible with the native implementation
+    /// from CommittedInstance.hash_pcd.
+    /// Additionally it returns the vector of the field elements from the self parameters, so they
+    /// can be reused in other gadgets.
+    #[allow(clippy::type_complexity)]
+    pub fn hash_pcd(
+        self,
+        crh_params: &CRHParametersVar<CF1<C>>,
+        pp_hash: FpVar<CF1<C>>,
+        i_start: FpVar<CF1<C>>,
+        i_end: FpVar<CF1<C>>,
+        z_start: Vec<FpVar<CF1<C>>>,
+        z_end: Vec<FpVar<CF1<C>>>,
+    ) -> Result<(FpVar<CF1<C>>, Vec<FpVar<CF1<C>>>), SynthesisError> {
+        let U_vec = to_field_elements_gadget(&self)?;
+        let input = [vec![pp_hash, i_start, i_end], z_start, z_end, U_vec.clone()].concat();
+        Ok((CRHGadget::evaluate(crh_params, &input)?, U_vec))
+    }
+}
+
+// returns the field elements of a CommittedInstanceVar, in the order used by the challenge gadget
+fn to_field_elements_gadget<C: CurveGroup>(
+    ci: &CommittedInstanceVar<C>,
+) -> Result<Vec<FpVar<CF1<C>>>, SynthesisError>
+where
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    Ok([
+        vec![ci.u.clone()],
+        ci.x.clone(),
+        ci.cmE.to_constraint_field()?,
+        ci.cmW.to_constraint_field()?,
+    ]
+    .concat())
+}
+
+/// Folds two CycleFold running instances (both relaxed), computing
+/// `cmE = ci1.cmE + r * (cmT + r * ci2.cmE)`, `cmW = ci1.cmW + r * ci2.cmW`, `u = ci1.u + r * ci2.u`
+/// and `x = ci1.x + r * ci2.x`. Compatible with the native `NIFS::fold_committed_instance`.
+fn fold_relaxed_cyclefold_instances<C, GC>(
+    // assumes that r_bits is equal to r_nonnat just that in a different format
+    r_bits: Vec<Boolean<CF2<C>>>,
+    r_nonnat: NonNativeUintVar<CF2<C>>,
+    cmT: GC,
+    ci1: CycleFoldCommittedInstanceVar<C, GC>,
+    ci2: CycleFoldCommittedInstanceVar<C, GC>,
+) -> Result<CycleFoldCommittedInstanceVar<C, GC>, SynthesisError>
+where
+    C: CurveGroup,
+    GC: CurveVar<C, CF2<C>>,
+    <C as CurveGroup>::BaseField: PrimeField,
+    for<'a> &'a GC: GroupOpsBounds<'a, C, GC>,
+{
+    Ok(CycleFoldCommittedInstanceVar {
+        cmE: (ci2.cmE.scalar_mul_le(r_bits.iter())? + cmT).scalar_mul_le(r_bits.iter())? + ci1.cmE,
+        cmW: ci1.cmW + ci2.cmW.scalar_mul_le(r_bits.iter())?,
+        u: ci1
+            .u
+            .add_no_align(&r_nonnat.mul_no_align(&ci2.u)?)
+            .modulo::<C::ScalarField>()?,
+        x: ci1
+            .x
+            .iter()
+            .zip(ci2.x)
+            .map(|(a, b)| {
+                a.add_no_align(&r_nonnat.mul_no_align(&b)?)
+                    .modulo::<C::ScalarField>()
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    })
+}
+
+// converts the challenge bits into a NonNativeUintVar over the base field of C
+fn bits_to_nonnative<C: CurveGroup>(r_bits: &[Boolean<CF1<C>>]) -> NonNativeUintVar<CF1<C>>
+where
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    let mut bits = r_bits.to_vec();
+    bits.resize(C::BaseField::MODULUS_BIT_SIZE as usize, Boolean::FALSE);
+    NonNativeUintVar::from(&bits)
+}
+
+/// PCDProof attests that `z_end` is the result of applying the F circuit to `z_start` for the
+/// steps `[i_start, i_end)`. It can be sent to another worker to be merged into a bigger proof.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PCDProof<C1: CurveGroup, C2: CurveGroup> {
+    pub i_start: usize,
+    pub i_end: usize,
+    pub z_start: Vec<C1::ScalarField>,
+    pub z_end: Vec<C1::ScalarField>,
+    /// running instance
+    pub W: Witness<C1>,
+    pub U: CommittedInstance<C1>,
+    /// incoming instance, output of the node circuit
+    pub w: Witness<C1>,
+    pub u: CommittedInstance<C1>,
+    /// CycleFold running instance
+    pub cf_W: Witness<C2>,
+    pub cf_U: CommittedInstance<C2>,
+}
+
+impl<C1: CurveGroup, C2: CurveGroup> PCDProof<C1, C2> {
+    /// returns true if the proof covers no steps, which is the case for the missing children
+    pub fn is_empty(&self) -> bool {
+        self.i_start == self.i_end
+    }
+}
+
+/// Values of a child proof that are given to the node circuit. For the left child `i_bound` and
+/// `z_bound` are the start of its range, for the right child they are the end of its range.
+#[derive(Debug, Clone)]
+pub struct PCDChildInputs<C1: CurveGroup, C2: CurveGroup> {
+    pub is_empty: bool,
+    pub i_bound: C1::ScalarField,
+    pub z_bound: Vec<C1::ScalarField>,
+    pub U: CommittedInstance<C1>,
+    pub u_cmW: C1,
+    pub cf_U: CommittedInstance<C2>,
+    pub cmT: C1,
+    /// cmE & cmW of the running instance resulting from folding u into U
+    pub U1_cmE: C1,
+    pub U1_cmW: C1,
+}
+
+// allocated values of a child, see PCDChildInputs
+struct PCDChildVar<C1: CurveGroup, C2: CurveGroup, GC2: CurveVar<C2, CF2<C2>>>
+where
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    is_empty: Boolean<CF1<C1>>,
+    i_bound: FpVar<CF1<C1>>,
+    z_bound: Vec<FpVar<CF1<C1>>>,
+    U: CommittedInstanceVar<C1>,
+    u_cmW: NonNativeAffineVar<C1>,
+    cf_U: CycleFoldCommittedInstanceVar<C2, GC2>,
+    cmT: NonNativeAffineVar<C1>,
+    U1_cmE: NonNativeAffineVar<C1>,
+    U1_cmW: NonNativeAffineVar<C1>,
+}
+
+impl<C1, C2, GC2> PCDChildVar<C1, C2, GC2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    fn new_witness(
+        cs: ConstraintSystemRef<CF1<C1>>,
+        child: Option<PCDChildInputs<C1, C2>>,
+        state_len: usize,
+    ) -> Result<Self, SynthesisError> {
+        let child = child.unwrap_or(PCDChildInputs {
+            is_empty: true,
+            i_bound: C1::ScalarField::zero(),
+            z_bound: vec![C1::ScalarField::zero(); state_len],
+            U: CommittedInstance::dummy(2),
+            u_cmW: C1::zero(),
+            cf_U: CommittedInstance::dummy(CF_IO_LEN),
+            cmT: C1::zero(),
+            U1_cmE: C1::zero(),
+            U1_cmW: C1::zero(),
+        });
+        Ok(Self {
+            is_empty: Boolean::new_witness(cs.clone(), || Ok(child.is_empty))?,
+            i_bound: FpVar::new_witness(cs.clone(), || Ok(child.i_bound))?,
+            z_bound: Vec::new_witness(cs.clone(), || Ok(child.z_bound))?,
+            U: CommittedInstanceVar::new_witness(cs.clone(), || Ok(child.U))?,
+            u_cmW: NonNativeAffineVar::new_witness(cs.clone(), || Ok(child.u_cmW))?,
+            cf_U: CycleFoldCommittedInstanceVar::new_witness(cs.clone(), || Ok(child.cf_U))?,
+            cmT: NonNativeAffineVar::new_witness(cs.clone(), || Ok(child.cmT))?,
+            U1_cmE: NonNativeAffineVar::new_witness(cs.clone(), || Ok(child.U1_cmE))?,
+            U1_cmW: NonNativeAffineVar::new_witness(cs, || Ok(child.U1_cmW))?,
+        })
+    }
+}
+
+/// NodeCircuit implements the circuit of a PCD node: it executes the step `i` of the F circuit,
+/// checks that the children proofs are consistent with it, and verifies the folding of the
+/// children instances. Its two public inputs are `H(pp_hash, a, b, z_a, z_b, U)` and `H(cf_U)`,
+/// where `[a, b)` is the range covered by the node, and `U`, `cf_U` the resulting running
+/// instances.
+#[derive(Debug, Clone)]
+pub struct NodeCircuit<
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+> where
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    pub _gc2: PhantomData<GC2>,
+    pub poseidon_config: PoseidonConfig<CF1<C1>>,
+    pub pp_hash: Option<CF1<C1>>,
+    pub i: Option<CF1<C1>>,
+    pub i_usize: Option<usize>,
+    pub z_i: Option<Vec<C1::ScalarField>>,
+    pub external_inputs: Option<Vec<C1::ScalarField>>,
+    pub left: Option<PCDChildInputs<C1, C2>>,
+    pub right: Option<PCDChildInputs<C1, C2>>,
+    /// values of the fold of the left and right running instances
+    pub cmT: Option<C1>,
+    pub U_cmE: Option<C1>,
+    pub U_cmW: Option<C1>,
+    /// `cmT + r * U_R.cmE`, intermediate value for the computation of U.cmE in CycleFold
+    pub cmE_tmp: Option<C1>,
+    pub F: FC,              // F circuit
+    pub x: Option<CF1<C1>>, // public input (u.x[0])
+
+    // cyclefold verifier on C1
+    /// cmT of the fold of the CycleFold running instances of both children
+    pub cf_merge_cmT: Option<C2>,
+    /// cmW of the CycleFold instances of the node, and the cmT of their folds
+    pub cf_u_cmWs: Option<Vec<C2>>,
+    pub cf_cmTs: Option<Vec<C2>>,
+    pub cf_x: Option<CF1<C1>>, // public input (u.x[1])
+}
+
+impl<C1: CurveGroup, C2: CurveGroup, GC2: CurveVar<C2, CF2<C2>>, FC: FCircuit<CF1<C1>>>
+    NodeCircuit<C1, C2, GC2, FC>
+where
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    pub fn empty(poseidon_config: &PoseidonConfig<CF1<C1>>, F_circuit: FC) -> Self {
+        Self {
+            _gc2: PhantomData,
+            poseidon_config: poseidon_config.clone(),
+            pp_hash: None,
+            i: None,
+            i_usize: None,
+            z_i: None,
+            external_inputs: None,
+            left: None,
+            right: None,
+            cmT: None,
+            U_cmE: None,
+            U_cmW: None,
+            cmE_tmp: None,
+            F: F_circuit,
+            x: None,
+            cf_merge_cmT: None,
+            cf_u_cmWs: None,
+            cf_cmTs: None,
+            cf_x: None,
+        }
+    }
+}
+
+impl<C1, C2, GC2, FC> ConstraintSynthesizer<CF1<C1>> for NodeCircuit<C1, C2, GC2, FC>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<CF1<C1>>) -> Result<(), SynthesisError> {
+        let state_len = self.F.state_len();
+        let pp_hash = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self.pp_hash.unwrap_or_else(CF1::<C1>::zero))
+        })?;
+        let i = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self.i.unwrap_or_else(CF1::<C1>::zero))
+        })?;
+        let z_i = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self.z_i.unwrap_or(vec![CF1::<C1>::zero(); state_len]))
+        })?;
+        let external_inputs = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .external_inputs
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.external_inputs_len()]))
+        })?;
+        let left = PCDChildVar::<C1, C2, GC2>::new_witness(cs.clone(), self.left, state_len)?;
+        let right = PCDChildVar::<C1, C2, GC2>::new_witness(cs.clone(), self.right, state_len)?;
+
+        let cmT =
+            NonNativeAffineVar::new_witness(cs.clone(), || Ok(self.cmT.unwrap_or_else(C1::zero)))?;
+        let U_cmE = NonNativeAffineVar::new_witness(cs.clone(), || {
+            Ok(self.U_cmE.unwrap_or_else(C1::zero))
+        })?;
+        let U_cmW = NonNativeAffineVar::new_witness(cs.clone(), || {
+            Ok(self.U_cmW.unwrap_or_else(C1::zero))
+        })?;
+        let cmE_tmp = NonNativeAffineVar::new_witness(cs.clone(), || {
+            Ok(self.cmE_tmp.unwrap_or_else(C1::zero))
+        })?;
+
+        let cf_merge_cmT = GC2::new_witness(cs.clone(), || {
+            Ok(self.cf_merge_cmT.unwrap_or_else(C2::zero))
+        })?;
+        let cf_u_cmWs = Vec::<GC2>::new_witness(cs.clone(), || {
+            Ok(self.cf_u_cmWs.unwrap_or(vec![C2::zero(); PCD_CF_CIRCUITS]))
+        })?;
+        let cf_cmTs = Vec::<GC2>::new_witness(cs.clone(), || {
+            Ok(self.cf_cmTs.unwrap_or(vec![C2::zero(); PCD_CF_CIRCUITS]))
+        })?;
+
+        let crh_params = CRHParametersVar::<C1::ScalarField>::new_constant(
+            cs.clone(),
+            self.poseidon_config.clone(),
+        )?;
+
+        // get z_{i+1} from the F circuit
+        let i_usize = self.i_usize.unwrap_or(0);
+        let z_i1 =
+            self.F
+                .generate_step_constraints(cs.clone(), i_usize, z_i.clone(), external_inputs)?;
+        let i1 = i.clone() + FpVar::<CF1<C1>>::one();
+
+        // an empty child covers no steps, so the node range starts at i (resp. ends at i+1)
+        left.i_bound.conditional_enforce_equal(&i, &left.is_empty)?;
+        left.z_bound
+            .conditional_enforce_equal(&z_i, &left.is_empty)?;
+        right
+            .i_bound
+            .conditional_enforce_equal(&i1, &right.is_empty)?;
+        right
+            .z_bound
+            .conditional_enforce_equal(&z_i1, &right.is_empty)?;
+
+        // 1. fold the incoming instance of each child into its running instance
+        // the left child covers [a, i), with u_L.x[0] = H(pp_hash, a, i, z_a, z_i, U_L), and the
+        // right child covers [i+1, b), with u_R.x[0] = H(pp_hash, i+1, b, z_{i+1}, z_b, U_R)
+        let (u_L_x, U_L_vec) = left.U.clone().hash_pcd(
+            &crh_params,
+            pp_hash.clone(),
+            left.i_bound.clone(),
+            i.clone(),
+            left.z_bound.clone(),
+            z_i.clone(),
+        )?;
+        let (u_R_x, U_R_vec) = right.U.clone().hash_pcd(
+            &crh_params,
+            pp_hash.clone(),
+            i1,
+            right.i_bound.clone(),
+            z_i1,
+            right.z_bound.clone(),
+        )?;
+
+        let mut folded = vec![];
+        let mut cf_U_L_vec = vec![];
+        for (child, u_x, U_vec) in [(&left, u_L_x, U_L_vec), (&right, u_R_x, U_R_vec)] {
+            let (cf_u_x, cf_U_vec) = child.cf_U.clone().hash(&crh_params)?;
+            if cf_U_L_vec.is_empty() {
+                cf_U_L_vec = cf_U_vec;
+            }
+
+            // the incoming instance of an empty child is the dummy instance
+            let zero = FpVar::<CF1<C1>>::zero();
+            let u = CommittedInstanceVar {
+                cmE: NonNativeAffineVar::new_constant(cs.clone(), C1::zero())?,
+                u: FpVar::from(child.is_empty.not()),
+                cmW: child.u_cmW.clone(),
+                x: vec![
+                    child.is_empty.select(&zero, &u_x)?,
+                    child.is_empty.select(&zero, &cf_u_x)?,
+                ],
+            };
+
+            let r_bits = ChallengeGadget::<C1>::get_challenge_gadget(
+                cs.clone(),
+                &self.poseidon_config,
+                pp_hash.clone(),
+                U_vec,
+                u.clone(),
+                child.cmT.clone(),
+            )?;
+            let r = Boolean::le_bits_to_fp_var(&r_bits)?;
+            let mut U1 = NIFSGadget::<C1>::fold_committed_instance(r, child.U.clone(), u.clone())?;
+            U1.cmE = child.U1_cmE.clone();
+            U1.cmW = child.U1_cmW.clone();
+            folded.push((r_bits, u, U1));
+        }
+        let (r_R_bits, u_R, U_R1) = folded.pop().ok_or(SynthesisError::Unsatisfiable)?;
+        let (r_L_bits, u_L, U_L1) = folded.pop().ok_or(SynthesisError::Unsatisfiable)?;
+
+        // 2. fold both running instances, U = U_L1 + r * U_R1
+        let r_bits = ChallengeGadget::<C1>::get_challenge_gadget(
+            cs.clone(),
+            &self.poseidon_config,
+            pp_hash.clone(),
+            to_field_elements_gadget(&U_L1)?,
+            U_R1.clone(),
+            cmT.clone(),
+        )?;
+        let r = Boolean::le_bits_to_fp_var(&r_bits)?;
+        let mut U = NIFSGadget::<C1>::fold_committed_instance(r, U_L1.clone(), U_R1.clone())?;
+        U.cmE = U_cmE;
+        U.cmW = U_cmW;
+
+        // 3. compute and check the first output of the node, u.x[0] = H(pp_hash, a, b, z_a, z_b, U)
+        let (u_x, _) = U.clone().hash_pcd(
+            &crh_params,
+            pp_hash,
+            left.i_bound,
+            right.i_bound,
+            left.z_bound,
+            right.z_bound,
+        )?;
+        let x = FpVar::new_input(cs.clone(), || Ok(self.x.unwrap_or(u_x.value()?)))?;
+        x.enforce_equal(&u_x)?;
+
+        // CycleFold part
+        // C.1. fold the CycleFold running instances of both children
+        let cf_merge_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_gadget(
+            cs.clone(),
+            &self.poseidon_config,
+            cf_U_L_vec,
+            right.cf_U.clone(),
+            cf_merge_cmT.clone(),
+        )?;
+        let cf_merge_r_nonnat = bits_to_nonnative::<C1>(&cf_merge_r_bits);
+        let mut cf_U = fold_relaxed_cyclefold_instances(
+            cf_merge_r_bits,
+            cf_merge_r_nonnat,
+            cf_merge_cmT,
+            left.cf_U,
+            right.cf_U,
+        )?;
+
+        // C.2. fold the CycleFold instances of the elliptic curve operations of the node, each of
+        // them with public inputs [r, p1, p2, p1 + r * p2]
+        let r_L = bits_to_nonnative::<C1>(&r_L_bits);
+        let r_R = bits_to_nonnative::<C1>(&r_R_bits);
+        let r = bits_to_nonnative::<C1>(&r_bits);
+        let cf_ops = [
+            // U_L1.cmW = U_L.cmW + r_L * u_L.cmW
+            (&r_L, &left.U.cmW, &u_L.cmW, &U_L1.cmW),
+            // U_L1.cmE = U_L.cmE + r_L * cmT_L
+            (&r_L, &left.U.cmE, &left.cmT, &U_L1.cmE),
+            // U_R1.cmW = U_R.cmW + r_R * u_R.cmW
+            (&r_R, &right.U.cmW, &u_R.cmW, &U_R1.cmW),
+            // U_R1.cmE = U_R.cmE + r_R * cmT_R
+            (&r_R, &right.U.cmE, &right.cmT, &U_R1.cmE),
+            // U.cmW = U_L1.cmW + r * U_R1.cmW
+            (&r, &U_L1.cmW, &U_R1.cmW, &U.cmW),
+            // cmE_tmp = cmT + r * U_R1.cmE
+            (&r, &cmT, &U_R1.cmE, &cmE_tmp),
+            // U.cmE = U_L1.cmE + r * cmE_tmp = U_L1.cmE + r * cmT + r^2 * U_R1.cmE
+            (&r, &U_L1.cmE, &cmE_tmp, &U.cmE),
+        ];
+        for ((r_k, p1, p2, p3), (cf_u_cmW, cf_cmT)) in
+            cf_ops.into_iter().zip(cf_u_cmWs.into_iter().zip(cf_cmTs))
+        {
+            let cf_u = CycleFoldCommittedInstanceVar {
+                cmE: GC2::zero(),
+                u: NonNativeUintVar::new_constant(cs.clone(), C1::BaseField::one())?,
+                cmW: cf_u_cmW,
+                x: vec![
+                    r_k.clone(),
+                    p1.x.clone(),
+                    p1.y.clone(),
+                    p2.x.clone(),
+                    p2.y.clone(),
+                    p3.x.clone(),
+                    p3.y.clone(),
+                ],
+            };
+            let cf_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_gadget(
+                cs.clone(),
+                &self.poseidon_config,
+                cf_U.to_constraint_field()?,
+                cf_u.clone(),
+                cf_cmT.clone(),
+            )?;
+            let cf_r_nonnat = bits_to_nonnative::<C1>(&cf_r_bits);
+            cf_U = NIFSFullGadget::<C2, GC2>::fold_committed_instance(
+                cf_r_bits,
+                cf_r_nonnat,
+                cf_cmT,
+                cf_U,
+                cf_u,
+            )?;
+        }
+
+        // C.3. compute and check the second output of the node, u.x[1] = H(cf_U)
+        let (cf_u_x, _) = cf_U.hash(&crh_params)?;
+        let cf_x = FpVar::new_input(cs.clone(), || Ok(self.cf_x.unwrap_or(cf_u_x.value()?)))?;
+        cf_x.enforce_equal(&cf_u_x)?;
+
+        Ok(())
+    }
+}
+
+/// NovaPCD contains the parameters to prove and merge the PCD nodes of Nova+CycleFold.
+#[derive(Clone, Debug)]
+pub struct NovaPCD<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    /// R1CS of the NodeCircuit
+    pub r1cs: R1CS<C1::ScalarField>,
+    /// R1CS of the CycleFold circuit
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    /// CommitmentScheme::ProverParams over C1
+    pub cs_params: CS1::ProverParams,
+    /// CycleFold CommitmentScheme::ProverParams, over C2
+    pub cf_cs_params: CS2::ProverParams,
+    /// digest of the public parameters, see `ProverParams::pp_hash`
+    pub pp_hash: C1::ScalarField,
+    /// F circuit, the circuit that is being folded
+    pub F: FC,
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> NovaPCD<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    /// initializes the PCD prover for the given parameters and F circuit
+    pub fn init(pp: &ProverParams<C1, C2, CS1, CS2>, F: FC) -> Result<Self, Error> {
+        let (r1cs, cf_r1cs) = get_r1cs::<C1, GC1, C2, GC2, FC>(&pp.poseidon_config, F.clone())?;
+        let pp_hash = pp.pp_hash(&r1cs, &cf_r1cs)?;
+        Ok(Self {
+            _gc1: PhantomData,
+            _c2: PhantomData,
+            _gc2: PhantomData,
+            r1cs,
+            cf_r1cs,
+            poseidon_config: pp.poseidon_config.clone(),
+            cs_params: pp.cs_params.clone(),
+            cf_cs_params: pp.cf_cs_params.clone(),
+            pp_hash,
+            F,
+        })
+    }
+
+    /// returns the verifier params of the PCD
+    pub fn verifier_params(&self) -> VerifierParams<C1, C2> {
+        VerifierParams {
+            poseidon_config: self.poseidon_config.clone(),
+            r1cs: self.r1cs.clone(),
+            cf_r1cs: self.cf_r1cs.clone(),
+            pp_hash: self.pp_hash,
+        }
+    }
+
+    /// returns the empty proof at step `i` with state `z_i`, used for the missing children
+    pub fn empty_proof(&self, i: usize, z_i: Vec<C1::ScalarField>) -> PCDProof<C1, C2> {
+        let (W, U) = self.r1cs.dummy_instance();
+        let (cf_W, cf_U) = self.cf_r1cs.dummy_instance();
+        PCDProof {
+            i_start: i,
+            i_end: i,
+            z_start: z_i.clone(),
+            z_end: z_i,
+            w: W.clone(),
+            u: U.clone(),
+            W,
+            U,
+            cf_W,
+            cf_U,
+        }
+    }
+
+    /// proves the step `i` of the F circuit (from `z_i`), merging the proofs of the left child,
+    /// which must end at `(i, z_i)`, and of the right child, which must start at
+    /// `(i+1, z_{i+1})`. Missing children are replaced by empty proofs, eg. the leaves are proven
+    /// with `left = right = None`.
+    pub fn prove_node(
+        &self,
+        i: usize,
+        z_i: Vec<C1::ScalarField>,
+        external_inputs: Vec<C1::ScalarField>,
+        left: Option<PCDProof<C1, C2>>,
+        right: Option<PCDProof<C1, C2>>,
+    ) -> Result<PCDProof<C1, C2>, Error> {
+        if z_i.len() != self.F.state_len() {
+            return Err(Error::NotSameLength(
+                "z_i.len()".to_string(),
+                z_i.len(),
+                "F.state_len()".to_string(),
+                self.F.state_len(),
+            ));
+        }
+        if external_inputs.len() != self.F.external_inputs_len() {
+            return Err(Error::NotSameLength(
+                "F.external_inputs_len()".to_string(),
+                self.F.external_inputs_len(),
+                "external_inputs.len()".to_string(),
+                external_inputs.len(),
+            ));
+        }
+
+        let z_i1 = self
+            .F
+            .step_native(i, z_i.clone(), external_inputs.clone())?;
+
+        let left = left.unwrap_or_else(|| self.empty_proof(i, z_i.clone()));
+        let right = right.unwrap_or_else(|| self.empty_proof(i + 1, z_i1.clone()));
+        if left.i_end != i || left.z_end != z_i {
+            return Err(Error::Other(
+                "left child does not end at the node's step".to_string(),
+            ));
+        }
+        if right.i_start != i + 1 || right.z_start != z_i1 {
+            return Err(Error::Other(
+                "right child does not start after the node's step".to_string(),
+            ));
+        }
+
+        // 1. fold the incoming instance of each child into its running instance
+        let mut folded = vec![];
+        for child in [&left, &right] {
+            let (T, cmT) = NIFS::<C1, CS1>::compute_cmT(
+                &self.cs_params,
+                &self.r1cs,
+                &child.w,
+                &child.u,
+                &child.W,
+                &child.U,
+            )?;
+            let r_bits = ChallengeGadget::<C1>::get_challenge_native(
+                &self.poseidon_config,
+                self.pp_hash,
+                child.U.clone(),
+                child.u.clone(),
+                cmT,
+            )?;
+            let r_Fr = C1::ScalarField::from_bigint(BigInteger::from_bits_le(&r_bits))
+                .ok_or(Error::OutOfBounds)?;
+            let W1 = NIFS::<C1, CS1>::fold_witness(
+                r_Fr,
+                &child.W,
+                &child.w,
+                &T,
+                C1::ScalarField::zero(),
+            )?;
+            let U1 = NIFS::<C1, CS1>::fold_committed_instance(r_Fr, &child.U, &child.u, &cmT);
+            folded.push((r_bits, cmT, W1, U1));
+        }
+        let (r_R_bits, cmT_R, W_R1, U_R1) = folded.pop().ok_or(Error::Empty)?;
+        let (r_L_bits, cmT_L, W_L1, U_L1) = folded.pop().ok_or(Error::Empty)?;
+
+        // 2. fold both running instances, U = U_L1 + r * U_R1
+        let (T, cmT) =
+            NIFS::<C1, CS1>::compute_cmT(&self.cs_params, &self.r1cs, &W_R1, &U_R1, &W_L1, &U_L1)?;
+        let r_bits = ChallengeGadget::<C1>::get_challenge_native(
+            &self.poseidon_config,
+            self.pp_hash,
+            U_L1.clone(),
+            U_R1.clone(),
+            cmT,
+        )?;
+        let r_Fr = C1::ScalarField::from_bigint(BigInteger::from_bits_le(&r_bits))
+            .ok_or(Error::OutOfBounds)?;
+        let W = NIFS::<C1, CS1>::fold_witness(r_Fr, &W_L1, &W_R1, &T, C1::ScalarField::zero())?;
+        let U = NIFS::<C1, CS1>::fold_committed_instance(r_Fr, &U_L1, &U_R1, &cmT);
+        let cmE_tmp = cmT + U_R1.cmE * r_Fr;
+
+        // 3. u.x[0] = H(pp_hash, a, b, z_a, z_b, U)
+        let u_x = U.hash_pcd(
+            &self.poseidon_config,
+            self.pp_hash,
+            C1::ScalarField::from(left.i_start as u64),
+            C1::ScalarField::from(right.i_end as u64),
+            left.z_start.clone(),
+            right.z_end.clone(),
+        )?;
+
+        // CycleFold part
+        // C.1. fold the CycleFold running instances of both children
+        let (cf_T, cf_merge_cmT) = NIFS::<C2, CS2>::compute_cyclefold_cmT(
+            &self.cf_cs_params,
+            &self.cf_r1cs,
+            &right.cf_W,
+            &right.cf_U,
+            &left.cf_W,
+            &left.cf_U,
+        )?;
+        let cf_merge_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_native(
+            &self.poseidon_config,
+            left.cf_U.clone(),
+            right.cf_U.clone(),
+            cf_merge_cmT,
+        )?;
+        let cf_merge_r = C1::BaseField::from_bigint(BigInteger::from_bits_le(&cf_merge_r_bits))
+            .ok_or(Error::OutOfBounds)?;
+        let (mut cf_W, mut cf_U) = NIFS::<C2, CS2>::fold_instances(
+            cf_merge_r,
+            &left.cf_W,
+            &left.cf_U,
+            &right.cf_W,
+            &right.cf_U,
+            &cf_T,
+            cf_merge_cmT,
+        )?;
+
+        // C.2. fold the CycleFold instances of the elliptic curve operations of the node, in the
+        // same order as in the NodeCircuit
+        let cf_ops = [
+            (&r_L_bits, left.U.cmW, left.u.cmW, U_L1.cmW),
+            (&r_L_bits, left.U.cmE, cmT_L, U_L1.cmE),
+            (&r_R_bits, right.U.cmW, right.u.cmW, U_R1.cmW),
+            (&r_R_bits, right.U.cmE, cmT_R, U_R1.cmE),
+            (&r_bits, U_L1.cmW, U_R1.cmW, U.cmW),
+            (&r_bits, cmT, U_R1.cmE, cmE_tmp),
+            (&r_bits, U_L1.cmE, cmE_tmp, U.cmE),
+        ];
+        let mut cf_u_cmWs = Vec::with_capacity(PCD_CF_CIRCUITS);
+        let mut cf_cmTs = Vec::with_capacity(PCD_CF_CIRCUITS);
+        for (r_k_bits, p1, p2, p3) in cf_ops {
+            let r_k_Fq = C1::BaseField::from_bigint(BigInteger::from_bits_le(r_k_bits))
+                .ok_or(Error::OutOfBounds)?;
+            let cf_u_x = [
+                vec![r_k_Fq],
+                get_cm_coordinates(&p1),
+                get_cm_coordinates(&p2),
+                get_cm_coordinates(&p3),
+            ]
+            .concat();
+            let cf_circuit = CycleFoldCircuit::<C1, GC1> {
+                _gc: PhantomData,
+                r_bits: Some(r_k_bits.clone()),
+                p1: Some(p1),
+                p2: Some(p2),
+                x: Some(cf_u_x.clone()),
+            };
+            let (_cf_w, cf_u, cf_W_i1, cf_U_i1, cf_cmT, _) =
+                fold_cyclefold_circuit::<C1, GC1, C2, GC2, CS2>(
+                    &self.poseidon_config,
+                    self.cf_r1cs.clone(),
+                    self.cf_cs_params.clone(),
+                    cf_W,
+                    cf_U,
+                    cf_u_x,
+                    cf_circuit,
+                )?;
+            #[cfg(test)]
+            self.cf_r1cs.check_instance_relation(&_cf_w, &cf_u)?;
+
+            cf_u_cmWs.push(cf_u.cmW);
+            cf_cmTs.push(cf_cmT);
+            cf_W = cf_W_i1;
+            cf_U = cf_U_i1;
+        }
+
+        // C.3. u.x[1] = H(cf_U)
+        let cf_u_x = cf_U.hash_cyclefold(&self.poseidon_config)?;
+
+        let child_inputs =
+            |child: &PCDProof<C1, C2>, is_left: bool, cmT: C1, U1: &CommittedInstance<C1>| {
+                PCDChildInputs {
+                    is_empty: child.is_empty(),
+                    i_bound: C1::ScalarField::from(
+                        if is_left { child.i_start } else { child.i_end } as u64,
+                    ),
+                    z_bound: if is_left {
+                        child.z_start.clone()
+                    } else {
+                        child.z_end.clone()
+                    },
+                    U: child.U.clone(),
+                    u_cmW: child.u.cmW,
+                    cf_U: child.cf_U.clone(),
+                    cmT,
+                    U1_cmE: U1.cmE,
+                    U1_cmW: U1.cmW,
+                }
+            };
+        let node_circuit = NodeCircuit::<C1, C2, GC2, FC> {
+            pp_hash: Some(self.pp_hash),
+            i: Some(C1::ScalarField::from(i as u64)),
+            i_usize: Some(i),
+            z_i: Some(z_i),
+            external_inputs: Some(external_inputs),
+            left: Some(child_inputs(&left, true, cmT_L, &U_L1)),
+            right: Some(child_inputs(&right, false, cmT_R, &U_R1)),
+            cmT: Some(cmT),
+            U_cmE: Some(U.cmE),
+            U_cmW: Some(U.cmW),
+            cmE_tmp: Some(cmE_tmp),
+            x: Some(u_x),
+            cf_merge_cmT: Some(cf_merge_cmT),
+            cf_u_cmWs: Some(cf_u_cmWs),
+            cf_cmTs: Some(cf_cmTs),
+            cf_x: Some(cf_u_x),
+            ..NodeCircuit::empty(&self.poseidon_config, self.F.clone())
+        };
+
+        let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+        node_circuit.generate_constraints(cs.clone())?;
+
+        #[cfg(test)]
+        assert!(cs.is_satisfied().unwrap());
+
+        let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+        let (w, x) = extract_w_x::<C1::ScalarField>(&cs);
+        if x[0] != u_x || x[1] != cf_u_x {
+            return Err(Error::NotEqual);
+        }
+
+        let w = Witness::<C1>::new(w, self.r1cs.A.n_rows);
+        let u = w.commit::<CS1, false>(&self.cs_params, x)?;
+
+        #[cfg(test)]
+        {
+            self.r1cs.check_instance_relation(&w, &u)?;
+            self.r1cs.check_relaxed_instance_relation(&W, &U)?;
+            self.cf_r1cs.check_relaxed_instance_relation(&cf_W, &cf_U)?;
+        }
+
+        Ok(PCDProof {
+            i_start: left.i_start,
+            i_end: right.i_end,
+            z_start: left.z_start,
+            z_end: right.z_end,
+            W,
+            U,
+            w,
+            u,
+            cf_W,
+            cf_U,
+        })
+    }
+
+    /// proves the steps `[i_start, i_start + external_inputs.len())` from `z_start` in a balanced
+    /// binary tree, where the subtrees are proven in parallel
+    pub fn prove_tree(
+        &self,
+        i_start: usize,
+        z_start: Vec<C1::ScalarField>,
+        external_inputs: &[Vec<C1::ScalarField>],
+    ) -> Result<PCDProof<C1, C2>, Error>
+    where
+        Self: Sync,
+    {
+        if external_inputs.is_empty() {
+            return Err(Error::Empty);
+        }
+        // compute the intermediate states, so that the subtrees can be proven independently
+        let mut z = vec![z_start];
+        for (j, external_inputs_j) in external_inputs.iter().enumerate() {
+            let z_j1 = self
+                .F
+                .step_native(i_start + j, z[j].clone(), external_inputs_j.clone())?;
+            z.push(z_j1);
+        }
+        self.prove_subtree(i_start, &z, external_inputs)
+    }
+
+    // proves the steps [i_start, i_start + external_inputs.len()), where z contains the states
+    // z_{i_start}, ..., z_{i_start + external_inputs.len()}
+    fn prove_subtree(
+        &self,
+        i_start: usize,
+        z: &[Vec<C1::ScalarField>],
+        external_inputs: &[Vec<C1::ScalarField>],
+    ) -> Result<PCDProof<C1, C2>, Error>
+    where
+        Self: Sync,
+    {
+        let m = external_inputs.len() / 2;
+        let (left, right) = rayon::join(
+            || match m {
+                0 => Ok(None),
+                _ => self
+                    .prove_subtree(i_start, &z[..=m], &external_inputs[..m])
+                    .map(Some),
+            },
+            || match external_inputs.len() - m - 1 {
+                0 => Ok(None),
+                _ => self
+                    .prove_subtree(i_start + m + 1, &z[m + 1..], &external_inputs[m + 1..])
+                    .map(Some),
+            },
+        );
+        self.prove_node(
+            i_start + m,
+            z[m].clone(),
+            external_inputs[m].clone(),
+            left?,
+            right?,
+        )
+    }
+
+    /// verifies a PCD proof, checking that its incoming instance points to its running instances
+    /// and that the instances are satisfied
+    pub fn verify(vp: &VerifierParams<C1, C2>, proof: &PCDProof<C1, C2>) -> Result<(), Error> {
+        let PCDProof {
+            i_start,
+            i_end,
+            z_start,
+            z_end,
+            W,
+            U,
+            w,
+            u,
+            cf_W,
+            cf_U,
+        } = proof;
+
+        if i_start >= i_end || u.x.len() != 2 || U.x.len() != 2 {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // u.x[0] == H(pp_hash, i_start, i_end, z_start, z_end, U)
+        let expected_u_x = U.hash_pcd(
+            &vp.poseidon_config,
+            vp.pp_hash,
+            C1::ScalarField::from(*i_start as u64),
+            C1::ScalarField::from(*i_end as u64),
+            z_start.clone(),
+            z_end.clone(),
+        )?;
+        if expected_u_x != u.x[0] {
+            return Err(Error::IVCVerificationFail);
+        }
+        // u.x[1] == H(cf_U)
+        if cf_U.hash_cyclefold(&vp.poseidon_config)? != u.x[1] {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check u.cmE==0, u.u==1 (=u is a un-relaxed instance)
+        if !u.cmE.is_zero() || !u.u.is_one() {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        vp.r1cs.check_instance_relation(w, u)?;
+        vp.r1cs.check_relaxed_instance_relation(W, U)?;
+        vp.cf_r1cs.check_relaxed_instance_relation(cf_W, cf_U)?;
+
+        Ok(())
+    }
+}
+
+/// helper method to get the R1CS for both the NodeCircuit and the CycleFold circuit
+#[allow(clippy::type_complexity)]
+pub fn get_r1cs<C1, GC1, C2, GC2, FC>(
+    poseidon_config: &PoseidonConfig<C1::ScalarField>,
+    F_circuit: FC,
+) -> Result<(R1CS<C1::ScalarField>, R1CS<C2::ScalarField>), Error>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    let node_circuit = NodeCircuit::<C1, C2, GC2, FC>::empty(poseidon_config, F_circuit);
+    let cf_circuit = CycleFoldCircuit::<C1, GC1>::empty();
+    let r1cs = get_r1cs_from_cs::<C1::ScalarField>(node_circuit)?;
+    let cf_r1cs = get_r1cs_from_cs::<C2::ScalarField>(cf_circuit)?;
+    Ok((r1cs, cf_r1cs))
+}
+
+/// helper method to get the commitment params length for both the NodeCircuit and the CycleFold
+/// circuit
+pub fn get_cs_params_len<C1, GC1, C2, GC2, FC>(
+    poseidon_config: &PoseidonConfig<C1::ScalarField>,
+    F_circuit: FC,
+) -> Result<(usize, usize), Error>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    let (r1cs, cf_r1cs) = get_r1cs::<C1, GC1, C2, GC2, FC>(poseidon_config, F_circuit)?;
+    Ok((r1cs.A.n_rows, cf_r1cs.A.n_rows))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bn254::{constraints::GVar, Fr, G1Projective as Projective};
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+
+    use crate::commitment::pedersen::Pedersen;
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_canonical_config;
+
+    type PCD = NovaPCD<
+        Projective,
+        GVar,
+        Projective2,
+        GVar2,
+        CubicFCircuit<Fr>,
+        Pedersen<Projective>,
+        Pedersen<Projective2>,
+    >;
+
+    #[test]
+    fn test_pcd() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap();
+        let (cs_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_cs_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        let pp =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params,
+                cf_cs_params,
+            };
+        let pcd = PCD::init(&pp, F_circuit).unwrap();
+        let vp = pcd.verifier_params();
+
+        // prove 4 steps in a tree, which merges non-empty children on both sides
+        let z_0 = vec![Fr::from(3_u32)];
+        let num_steps = 4;
+        let proof = pcd
+            .prove_tree(0, z_0.clone(), &vec![vec![]; num_steps])
+            .unwrap();
+        assert_eq!(proof.i_start, 0);
+        assert_eq!(proof.i_end, num_steps);
+
+        let mut z_i = z_0.clone();
+        for i in 0..num_steps {
+            z_i = F_circuit.step_native(i, z_i, vec![]).unwrap();
+        }
+        assert_eq!(proof.z_end, z_i);
+        PCD::verify(&vp, &proof).unwrap();
+
+        // a proof claiming a different final state must not verify
+        let mut bad_proof = proof.clone();
+        bad_proof.z_end = z_0.clone();
+        assert!(PCD::verify(&vp, &bad_proof).is_err());
+
+        // merging children that are not contiguous with the node's step fails
+        let leaf = pcd.prove_node(0, z_0.clone(), vec![], None, None).unwrap();
+        assert!(pcd
+            .prove_node(2, proof.z_end.clone(), vec![], Some(leaf), None)
+            .is_err());
+    }
+}
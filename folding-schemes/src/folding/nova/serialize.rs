@@ -2,8 +2,8 @@ use super::{circuits::AugmentedFCircuit, cyclefold::CycleFoldCircuit, Nova, Prov
 pub use super::{CommittedInstance, Witness};
 pub use crate::folding::circuits::CF2;
 use crate::{
-    ccs::r1cs::extract_r1cs, commitment::CommitmentScheme, folding::circuits::CF1,
-    frontend::FCircuit,
+    ccs::r1cs::extract_r1cs, commitment::CommitmentScheme, folding::checkpoint::Checkpoint,
+    folding::circuits::CF1, frontend::FCircuit, Error,
 };
 use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
 use ark_ec::{CurveGroup, Group};
@@ -14,7 +14,7 @@ use ark_r1cs_std::{
 };
 use ark_relations::r1cs::ConstraintSynthesizer;
 use ark_relations::r1cs::ConstraintSystem;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError, Write};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
 use std::marker::PhantomData;
 
 impl<C1, GC1, C2, GC2, FC, CS1, CS2> CanonicalSerialize for Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
@@ -146,12 +146,17 @@ where
         let cs2 = cs2.into_inner().ok_or(SerializationError::InvalidData)?;
         let cf_r1cs = extract_r1cs::<C1::BaseField>(&cs2);
 
+        let pp_hash = prover_params
+            .pp_hash(&r1cs, &cf_r1cs)
+            .map_err(|_| SerializationError::InvalidData)?;
+
         Ok(Nova {
             _gc1: PhantomData,
             _c2: PhantomData,
             _gc2: PhantomData,
             cs_params: prover_params.cs_params,
             cf_cs_params: prover_params.cf_cs_params,
+            pp_hash,
             i,
             z_0,
             z_i,
@@ -169,6 +174,60 @@ where
     }
 }
 
+impl<C1, GC1, C2, GC2, FC, CS1, CS2, const H: bool> Checkpoint<C1, C2, FC>
+    for Nova<C1, GC1, C2, GC2, FC, CS1, CS2, H>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1, H>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    /// the pp_hash already covers both R1CS, the commitment schemes params and the Poseidon config
+    fn params_hash(&self) -> Result<C1::ScalarField, Error> {
+        Ok(self.pp_hash)
+    }
+
+    fn step(&self) -> C1::ScalarField {
+        self.i
+    }
+
+    fn serialize_state<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        self.i.serialize_compressed(&mut writer)?;
+        self.z_0.serialize_compressed(&mut writer)?;
+        self.z_i.serialize_compressed(&mut writer)?;
+        self.w_i.serialize_compressed(&mut writer)?;
+        self.u_i.serialize_compressed(&mut writer)?;
+        self.W_i.serialize_compressed(&mut writer)?;
+        self.U_i.serialize_compressed(&mut writer)?;
+        self.cf_W_i.serialize_compressed(&mut writer)?;
+        self.cf_U_i.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    fn deserialize_state<R: Read>(&mut self, mut reader: R) -> Result<(), Error> {
+        self.i = C1::ScalarField::deserialize_compressed(&mut reader)?;
+        self.z_0 = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.z_i = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.w_i = Witness::<C1>::deserialize_compressed(&mut reader)?;
+        self.u_i = CommittedInstance::<C1>::deserialize_compressed(&mut reader)?;
+        self.W_i = Witness::<C1>::deserialize_compressed(&mut reader)?;
+        self.U_i = CommittedInstance::<C1>::deserialize_compressed(&mut reader)?;
+        self.cf_W_i = Witness::<C2>::deserialize_compressed(&mut reader)?;
+        self.cf_U_i = CommittedInstance::<C2>::deserialize_compressed(&mut reader)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use crate::{
@@ -177,10 +236,14 @@ pub mod tests {
             pedersen::Pedersen,
             CommitmentScheme,
         },
+        folding::checkpoint::Checkpoint,
         folding::nova::{get_cs_params_len, Nova, ProverParams},
-        frontend::{tests::CubicFCircuit, FCircuit},
+        frontend::{
+            tests::{CubicFCircuit, CustomFCircuit},
+            FCircuit,
+        },
         transcript::poseidon::poseidon_canonical_config,
-        FoldingScheme,
+        Error, FoldingScheme,
     };
     use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as Projective};
     use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
@@ -218,7 +281,7 @@ pub mod tests {
 
         let num_steps: usize = 3;
         for _ in 0..num_steps {
-            nova.prove_step(vec![]).unwrap();
+            nova.prove_step(&mut rng, vec![]).unwrap();
         }
 
         let mut writer = vec![];
@@ -257,10 +320,79 @@ pub mod tests {
 
         let num_steps: usize = 3;
         for _ in 0..num_steps {
-            deserialized_nova.prove_step(vec![]).unwrap();
-            nova.prove_step(vec![]).unwrap();
+            deserialized_nova.prove_step(&mut rng, vec![]).unwrap();
+            nova.prove_step(&mut rng, vec![]).unwrap();
         }
 
         assert_eq!(deserialized_nova.w_i, nova.w_i);
     }
+
+    #[test]
+    fn test_checkpoint_nova() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let F_circuit = CustomFCircuit::<Fr>::new(10).unwrap();
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CustomFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        type NOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CustomFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >;
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config: poseidon_config.clone(),
+                cs_params: pedersen_params.clone(),
+                cf_cs_params: cf_pedersen_params.clone(),
+            };
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(&mut rng, vec![]).unwrap();
+        }
+
+        let mut checkpoint = vec![];
+        nova.write_checkpoint(&mut checkpoint).unwrap();
+
+        // resume from the checkpoint, and check that both instances continue identically
+        let mut resumed_nova =
+            NOVA::resume_from_checkpoint(&prover_params, F_circuit, checkpoint.as_slice()).unwrap();
+        assert_eq!(resumed_nova.i, nova.i);
+        assert_eq!(resumed_nova.z_0, z_0);
+        assert_eq!(resumed_nova.U_i, nova.U_i);
+        for _ in 0..3 {
+            resumed_nova.prove_step(&mut rng, vec![]).unwrap();
+            nova.prove_step(&mut rng, vec![]).unwrap();
+        }
+        assert_eq!(resumed_nova.W_i, nova.W_i);
+        assert_eq!(resumed_nova.z_i, nova.z_i);
+
+        // resuming with a different F circuit is detected
+        let other_F_circuit = CustomFCircuit::<Fr>::new(20).unwrap();
+        let res =
+            NOVA::resume_from_checkpoint(&prover_params, other_F_circuit, checkpoint.as_slice());
+        assert!(matches!(res, Err(Error::CheckpointMismatch(_))));
+
+        // resuming with different params is detected
+        let (other_pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let other_prover_params = ProverParams {
+            cs_params: other_pedersen_params,
+            ..prover_params
+        };
+        let res =
+            NOVA::resume_from_checkpoint(&other_prover_params, F_circuit, checkpoint.as_slice());
+        assert!(matches!(res, Err(Error::CheckpointMismatch(_))));
+    }
 }
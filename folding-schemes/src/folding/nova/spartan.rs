@@ -0,0 +1,349 @@
+/// Implements a [Spartan](https://eprint.iacr.org/2019/550.pdf)-style argument for the Relaxed
+/// R1CS relation of Nova's committed instances, using the sum-check protocol and the IPA as
+/// multilinear polynomial commitment. It does not use SPARK, so the verifier evaluates the
+/// multilinear extensions of the R1CS matrices by itself, in time linear to the number of non-zero
+/// entries.
+///
+/// Given a `CommittedInstance` U=(cmE, u, cmW, x) and its `Witness` (E, W), the argument proves
+/// that Az∘Bz = u⋅Cz + E, with z=(u, x, W):
+/// 1. sum-check of 0 = Σ_x eq(τ, x)⋅(Az~(x)⋅Bz~(x) - u⋅Cz~(x) - E~(x)), obtaining r_x
+/// 2. sum-check of r_A⋅Az~(r_x) + r_B⋅Bz~(r_x) + r_C⋅Cz~(r_x) = Σ_y M~(r_x, y)⋅z~(y), where
+///    M~ = r_A⋅A~ + r_B⋅B~ + r_C⋅C~, obtaining r_y
+/// 3. IPA openings of W~ and E~ at r_y and r_x respectively.
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::{CurveGroup, Group};
+use ark_ff::PrimeField;
+use ark_poly::MultilinearExtension;
+use ark_std::{log2, One, Zero};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use super::{CommittedInstance, Witness};
+use crate::ccs::r1cs::R1CS;
+use crate::commitment::{
+    ipa::{Proof as IPAProof, IPA},
+    pedersen::Params as PedersenParams,
+};
+use crate::transcript::Transcript;
+use crate::utils::mle::dense_vec_to_dense_mle;
+use crate::utils::sum_check::{structs::IOPProof, IOPSumCheck, SumCheck};
+use crate::utils::vec::{mat_vec_mul, SparseMatrix};
+use crate::utils::virtual_polynomial::{build_eq_x_r_vec, eq_eval, VPAuxInfo, VirtualPolynomial};
+use crate::Error;
+
+/// Proof of the Relaxed R1CS relation of a Nova CommittedInstance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpartanProof<C: CurveGroup> {
+    /// sum-check proof of the Relaxed R1CS relation, over the rows
+    pub sc_proof_outer: IOPProof<C::ScalarField>,
+    /// claimed evaluations of Az~, Bz~, Cz~ and E~ at r_x
+    pub v_A: C::ScalarField,
+    pub v_B: C::ScalarField,
+    pub v_C: C::ScalarField,
+    pub v_E: C::ScalarField,
+    /// sum-check proof of the evaluations v_A, v_B and v_C, over the columns
+    pub sc_proof_inner: IOPProof<C::ScalarField>,
+    /// IPA proofs of the evaluations of W~ at r_y and of E~ at r_x
+    pub W_proof: (IPAProof<C>, C::ScalarField, C::ScalarField),
+    pub E_proof: (IPAProof<C>, C::ScalarField, C::ScalarField),
+}
+
+/// Spartan implements the prover and verifier of the Relaxed R1CS argument, using the transcript
+/// T.
+pub struct Spartan<C: CurveGroup, T: Transcript<C>> {
+    _c: PhantomData<C>,
+    _t: PhantomData<T>,
+}
+
+impl<C: CurveGroup, T: Transcript<C>> Spartan<C, T>
+where
+    <C as Group>::ScalarField: Absorb,
+{
+    pub fn prove(
+        params: &PedersenParams<C>,
+        transcript: &mut T,
+        r1cs: &R1CS<C::ScalarField>,
+        W: &Witness<C>,
+        U: &CommittedInstance<C>,
+    ) -> Result<SpartanProof<C>, Error> {
+        let (s_x, s_y) = Self::num_vars(r1cs);
+
+        let z: Vec<C::ScalarField> = [vec![U.u], U.x.to_vec(), W.W.to_vec()].concat();
+        let Az = mat_vec_mul(&r1cs.A, &z)?;
+        let Bz = mat_vec_mul(&r1cs.B, &z)?;
+        let Cz = mat_vec_mul(&r1cs.C, &z)?;
+
+        Self::absorb_instance(transcript, U)?;
+        let tau = transcript.get_challenges(s_x);
+
+        // 1. sum-check over the rows
+        let eq_tau = Arc::new(dense_vec_to_dense_mle(s_x, &build_eq_x_r_vec(&tau)?));
+        let Az_mle = Arc::new(dense_vec_to_dense_mle(s_x, &Az));
+        let Bz_mle = Arc::new(dense_vec_to_dense_mle(s_x, &Bz));
+        let Cz_mle = Arc::new(dense_vec_to_dense_mle(s_x, &Cz));
+        let E_mle = Arc::new(dense_vec_to_dense_mle(s_x, &W.E));
+
+        let mut g = VirtualPolynomial::<C::ScalarField>::new(s_x);
+        g.add_mle_list(
+            vec![eq_tau.clone(), Az_mle.clone(), Bz_mle.clone()],
+            C::ScalarField::one(),
+        )?;
+        g.add_mle_list(vec![eq_tau.clone(), Cz_mle.clone()], -U.u)?;
+        g.add_mle_list(vec![eq_tau, E_mle.clone()], -C::ScalarField::one())?;
+
+        let sc_proof_outer = IOPSumCheck::<C, T>::prove(&g, transcript)
+            .map_err(|err| Error::SumCheckProveError(err.to_string()))?;
+        let r_x = sc_proof_outer.point.clone();
+
+        let v_A = Az_mle.evaluate(&r_x).ok_or(Error::EvaluationFail)?;
+        let v_B = Bz_mle.evaluate(&r_x).ok_or(Error::EvaluationFail)?;
+        let v_C = Cz_mle.evaluate(&r_x).ok_or(Error::EvaluationFail)?;
+        let v_E = E_mle.evaluate(&r_x).ok_or(Error::EvaluationFail)?;
+        transcript.absorb_vec(&[v_A, v_B, v_C, v_E]);
+
+        // 2. sum-check over the columns
+        let c = transcript.get_challenges(3);
+        let eq_rx = build_eq_x_r_vec(&r_x)?;
+        let M_rx = Self::matrices_eval_at_row(r1cs, &c, &eq_rx, 1 << s_y);
+        let M_rx_mle = Arc::new(dense_vec_to_dense_mle(s_y, &M_rx));
+        let z_mle = Arc::new(dense_vec_to_dense_mle(s_y, &z));
+
+        let mut g = VirtualPolynomial::<C::ScalarField>::new(s_y);
+        g.add_mle_list(vec![M_rx_mle, z_mle], C::ScalarField::one())?;
+
+        let sc_proof_inner = IOPSumCheck::<C, T>::prove(&g, transcript)
+            .map_err(|err| Error::SumCheckProveError(err.to_string()))?;
+        let r_y = sc_proof_inner.point.clone();
+
+        // 3. IPA openings of W~(r_y) and E~(r_x)
+        let eq_ry = build_eq_x_r_vec(&r_y)?;
+        let (W_padded, b_W) = Self::W_and_eq_vec(r1cs, &W.W, &eq_ry);
+        let W_proof = IPA::<C>::prove_inner_product(
+            params, transcript, &U.cmW, &W_padded, &b_W, &W.rW, None,
+        )?;
+        let mut E_padded = W.E.clone();
+        E_padded.resize(1 << s_x, C::ScalarField::zero());
+        let E_proof = IPA::<C>::prove_inner_product(
+            params, transcript, &U.cmE, &E_padded, &eq_rx, &W.rE, None,
+        )?;
+
+        Ok(SpartanProof {
+            sc_proof_outer,
+            v_A,
+            v_B,
+            v_C,
+            v_E,
+            sc_proof_inner,
+            W_proof,
+            E_proof,
+        })
+    }
+
+    pub fn verify(
+        params: &PedersenParams<C>,
+        transcript: &mut T,
+        r1cs: &R1CS<C::ScalarField>,
+        U: &CommittedInstance<C>,
+        proof: &SpartanProof<C>,
+    ) -> Result<(), Error> {
+        let (s_x, s_y) = Self::num_vars(r1cs);
+        if U.x.len() != r1cs.l {
+            return Err(Error::NotSameLength(
+                "U.x.len()".to_string(),
+                U.x.len(),
+                "r1cs.l".to_string(),
+                r1cs.l,
+            ));
+        }
+
+        Self::absorb_instance(transcript, U)?;
+        let tau = transcript.get_challenges(s_x);
+
+        // 1. sum-check over the rows
+        let aux_info = VPAuxInfo::<C::ScalarField> {
+            max_degree: 3,
+            num_variables: s_x,
+            phantom: PhantomData::<C::ScalarField>,
+        };
+        let subclaim = IOPSumCheck::<C, T>::verify(
+            C::ScalarField::zero(),
+            &proof.sc_proof_outer,
+            &aux_info,
+            transcript,
+        )
+        .map_err(|err| Error::SumCheckVerifyError(err.to_string()))?;
+        let r_x = subclaim.point.clone();
+        let expected = eq_eval(&tau, &r_x)? * (proof.v_A * proof.v_B - U.u * proof.v_C - proof.v_E);
+        if expected != subclaim.expected_evaluation {
+            return Err(Error::NotSatisfied);
+        }
+        transcript.absorb_vec(&[proof.v_A, proof.v_B, proof.v_C, proof.v_E]);
+
+        // 2. sum-check over the columns
+        let c = transcript.get_challenges(3);
+        let aux_info = VPAuxInfo::<C::ScalarField> {
+            max_degree: 2,
+            num_variables: s_y,
+            phantom: PhantomData::<C::ScalarField>,
+        };
+        let claim = c[0] * proof.v_A + c[1] * proof.v_B + c[2] * proof.v_C;
+        let subclaim =
+            IOPSumCheck::<C, T>::verify(claim, &proof.sc_proof_inner, &aux_info, transcript)
+                .map_err(|err| Error::SumCheckVerifyError(err.to_string()))?;
+        let r_y = subclaim.point.clone();
+
+        // 3. check the IPA openings of W~(r_y) and E~(r_x)
+        let eq_rx = build_eq_x_r_vec(&r_x)?;
+        let eq_ry = build_eq_x_r_vec(&r_y)?;
+        let (_, b_W) = Self::W_and_eq_vec(r1cs, &[], &eq_ry);
+        IPA::<C>::verify_inner_product(params, transcript, &U.cmW, &b_W, &proof.W_proof)?;
+        IPA::<C>::verify_inner_product(params, transcript, &U.cmE, &eq_rx, &proof.E_proof)?;
+        if proof.E_proof.1 != proof.v_E {
+            return Err(Error::NotSatisfied);
+        }
+
+        // z~(r_y) = u⋅eq(r_y, 0) + Σ_k x_k⋅eq(r_y, 1+k) + W~(r_y)
+        let z_ry = U.u * eq_ry[0]
+            + U.x
+                .iter()
+                .enumerate()
+                .map(|(k, x_k)| *x_k * eq_ry[1 + k])
+                .sum::<C::ScalarField>()
+            + proof.W_proof.1;
+        // M~(r_x, r_y), computed by the verifier
+        let M_rx = Self::matrices_eval_at_row(r1cs, &c, &eq_rx, 1 << s_y);
+        let M_rx_ry: C::ScalarField = M_rx.iter().zip(&eq_ry).map(|(m, e)| *m * e).sum();
+
+        if M_rx_ry * z_ry != subclaim.expected_evaluation {
+            return Err(Error::NotSatisfied);
+        }
+        Ok(())
+    }
+
+    // returns the number of variables of the rows and columns multilinear extensions
+    fn num_vars(r1cs: &R1CS<C::ScalarField>) -> (usize, usize) {
+        let s_x = log2(r1cs.A.n_rows).max(1) as usize;
+        let s_y = log2(r1cs.A.n_cols).max(1) as usize;
+        (s_x, s_y)
+    }
+
+    fn absorb_instance(transcript: &mut T, U: &CommittedInstance<C>) -> Result<(), Error> {
+        transcript.absorb_point(&U.cmE)?;
+        transcript.absorb(&U.u);
+        transcript.absorb_point(&U.cmW)?;
+        transcript.absorb_vec(&U.x);
+        Ok(())
+    }
+
+    // computes the vector (r_A⋅A~ + r_B⋅B~ + r_C⋅C~)(r_x, y) for all y in the boolean hypercube,
+    // from the evaluations eq(r_x, i) of all the rows i
+    fn matrices_eval_at_row(
+        r1cs: &R1CS<C::ScalarField>,
+        c: &[C::ScalarField],
+        eq_rx: &[C::ScalarField],
+        n_cols: usize,
+    ) -> Vec<C::ScalarField> {
+        let mut M_rx = vec![C::ScalarField::zero(); n_cols];
+        let matrices: [&SparseMatrix<C::ScalarField>; 3] = [&r1cs.A, &r1cs.B, &r1cs.C];
+        for (M, c_M) in matrices.iter().zip(c) {
+            for (row_i, row) in M.coeffs.iter().enumerate() {
+                let c_eq = *c_M * eq_rx[row_i];
+                for &(value, col_i) in row.iter() {
+                    M_rx[col_i] += c_eq * value;
+                }
+            }
+        }
+        M_rx
+    }
+
+    // returns the witness W and the vector of the evaluations eq(r_y, 1+l+k) for the positions k
+    // of W in z=(u, x, W), both padded to the next power of two, so that <W, b> = W~(r_y)
+    // contribution to z~(r_y)
+    fn W_and_eq_vec(
+        r1cs: &R1CS<C::ScalarField>,
+        W: &[C::ScalarField],
+        eq_ry: &[C::ScalarField],
+    ) -> (Vec<C::ScalarField>, Vec<C::ScalarField>) {
+        let w_len = r1cs.A.n_cols - 1 - r1cs.l;
+        let padded_len = w_len.next_power_of_two();
+        let mut b = eq_ry[1 + r1cs.l..r1cs.A.n_cols].to_vec();
+        b.resize(padded_len, C::ScalarField::zero());
+        let mut W = W.to_vec();
+        W.resize(padded_len, C::ScalarField::zero());
+        (W, b)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_pallas::{Fr, Projective};
+    use ark_std::UniformRand;
+
+    use crate::ccs::r1cs::tests::{get_test_r1cs, get_test_z};
+    use crate::commitment::CommitmentScheme;
+    use crate::folding::nova::{nifs::NIFS, traits::NovaR1CS};
+    use crate::transcript::poseidon::{poseidon_canonical_config, PoseidonTranscript};
+
+    #[test]
+    fn test_spartan_relaxed_r1cs() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let r1cs = get_test_r1cs::<Fr>();
+        let (params, _) = IPA::<Projective>::setup(&mut rng, r1cs.A.n_rows).unwrap();
+
+        // obtain a relaxed instance by folding two R1CS instances
+        let z1 = get_test_z(3);
+        let z2 = get_test_z(4);
+        let (w1, x1) = r1cs.split_z(&z1);
+        let (w2, x2) = r1cs.split_z(&z2);
+        let w1 = Witness::<Projective>::new(w1, r1cs.A.n_rows);
+        let w2 = Witness::<Projective>::new(w2, r1cs.A.n_rows);
+        let u1 = w1.commit::<IPA<Projective>, false>(&params, x1).unwrap();
+        let u2 = w2.commit::<IPA<Projective>, false>(&params, x2).unwrap();
+        let (T, cmT) =
+            NIFS::<Projective, IPA<Projective>>::compute_cmT(&params, &r1cs, &w1, &u1, &w2, &u2)
+                .unwrap();
+        let r = Fr::rand(&mut rng);
+        let (W, U) =
+            NIFS::<Projective, IPA<Projective>>::fold_instances(r, &w1, &u1, &w2, &u2, &T, cmT)
+                .unwrap();
+        r1cs.check_relaxed_instance_relation(&W, &U).unwrap();
+
+        let mut transcript_p = PoseidonTranscript::<Projective>::new(&poseidon_config);
+        let proof = Spartan::<Projective, PoseidonTranscript<Projective>>::prove(
+            &params,
+            &mut transcript_p,
+            &r1cs,
+            &W,
+            &U,
+        )
+        .unwrap();
+
+        let mut transcript_v = PoseidonTranscript::<Projective>::new(&poseidon_config);
+        Spartan::<Projective, PoseidonTranscript<Projective>>::verify(
+            &params,
+            &mut transcript_v,
+            &r1cs,
+            &U,
+            &proof,
+        )
+        .unwrap();
+
+        // the proof does not verify for a different instance
+        let mut U_wrong = U.clone();
+        U_wrong.u += Fr::one();
+        let mut transcript_v = PoseidonTranscript::<Projective>::new(&poseidon_config);
+        assert!(
+            Spartan::<Projective, PoseidonTranscript<Projective>>::verify(
+                &params,
+                &mut transcript_v,
+                &r1cs,
+                &U_wrong,
+                &proof,
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,9 +1,12 @@
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::{CurveGroup, Group};
-use ark_std::{One, Zero};
+use ark_std::rand::RngCore;
+use ark_std::{One, UniformRand, Zero};
 
 use super::{CommittedInstance, Witness};
 use crate::ccs::r1cs::R1CS;
+use crate::commitment::CommitmentScheme;
+use crate::utils::vec::{hadamard, mat_vec_mul, vec_scalar_mul, vec_sub};
 use crate::Error;
 
 /// NovaR1CS extends R1CS methods with Nova specific methods
@@ -11,6 +14,16 @@ pub trait NovaR1CS<C: CurveGroup> {
     /// returns a dummy instance (Witness and CommittedInstance) for the current R1CS structure
     fn dummy_instance(&self) -> (Witness<C>, CommittedInstance<C>);
 
+    /// samples a random Witness and CommittedInstance satisfying the Relaxed R1CS relation of the
+    /// current R1CS structure, committing to them with the given CommitmentScheme (with random
+    /// blinding factors when it is hiding). Used to randomize the running instances in the
+    /// zero-knowledge mode.
+    fn sample_witness_instance<CS: CommitmentScheme<C, H>, const H: bool>(
+        &self,
+        params: &CS::ProverParams,
+        rng: impl RngCore,
+    ) -> Result<(Witness<C>, CommittedInstance<C>), Error>;
+
     /// checks the R1CS relation (un-relaxed) for the given Witness and CommittedInstance.
     fn check_instance_relation(
         &self,
@@ -39,6 +52,41 @@ where
         (w_dummy, u_dummy)
     }
 
+    fn sample_witness_instance<CS: CommitmentScheme<C, H>, const H: bool>(
+        &self,
+        params: &CS::ProverParams,
+        mut rng: impl RngCore,
+    ) -> Result<(Witness<C>, CommittedInstance<C>), Error> {
+        let w_len = self.A.n_cols - 1 - self.l;
+        let W = (0..w_len)
+            .map(|_| C::ScalarField::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let x = (0..self.l)
+            .map(|_| C::ScalarField::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let u = C::ScalarField::rand(&mut rng);
+        let (rE, rW) = if H {
+            (
+                C::ScalarField::rand(&mut rng),
+                C::ScalarField::rand(&mut rng),
+            )
+        } else {
+            (C::ScalarField::zero(), C::ScalarField::zero())
+        };
+
+        // set E such that the relaxed relation Az∘Bz = u*Cz + E holds
+        let Z: Vec<C::ScalarField> = [vec![u], x.clone(), W.clone()].concat();
+        let Az = mat_vec_mul(&self.A, &Z)?;
+        let Bz = mat_vec_mul(&self.B, &Z)?;
+        let Cz = mat_vec_mul(&self.C, &Z)?;
+        let E = vec_sub(&hadamard(&Az, &Bz)?, &vec_scalar_mul(&Cz, &u))?;
+
+        let witness = Witness { E, rE, W, rW };
+        let mut instance = witness.commit::<CS, H>(params, x)?;
+        instance.u = u;
+        Ok((witness, instance))
+    }
+
     fn check_instance_relation(
         &self,
         W: &Witness<C>,
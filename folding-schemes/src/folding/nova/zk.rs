@@ -0,0 +1,426 @@
+/// Implements the zero-knowledge layer for the Nova+CycleFold IVC proofs: before handing out the
+/// IVC proof, the last running instance is folded with the last incoming instance, and the result
+/// is folded again with a random satisfying relaxed instance. The resulting witness is then statistically
+/// independent of the witnesses of the folded steps and can be revealed to the verifier. The same
+/// is done with the CycleFold running instance, which is folded with a random satisfying relaxed
+/// CycleFold instance before revealing its witness.
+///
+/// This requires the Nova instance to use a hiding CommitmentScheme over C1 (ie.
+/// `Nova<..., CS1, CS2, true>` with `CS1=Pedersen<C1, true>`), so that the intermediate
+/// commitments contained in the proof do not leak the private inputs of the steps.
+/// Notice that KZG does not support hiding mode yet.
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::{One, UniformRand, Zero};
+
+use super::{
+    circuits::ChallengeGadget, cyclefold::CycleFoldChallengeGadget, nifs::NIFS, traits::NovaR1CS,
+    CommittedInstance, Nova, VerifierParams, Witness,
+};
+use crate::ccs::r1cs::R1CS;
+use crate::commitment::CommitmentScheme;
+use crate::folding::circuits::CF2;
+use crate::frontend::FCircuit;
+use crate::Error;
+
+/// RandomizedIVCProof contains the values that the prover sends to the verifier in the
+/// zero-knowledge mode. The original running instance `U_i` and incoming instance `u_i` are sent
+/// (they only contain hiding commitments and public values), together with the random relaxed
+/// instance `U_r`, the cross-term commitments of both folds, and the randomized witness
+/// `W_i_prime` which satisfies the instance `U_i' = NIFS.V(NIFS.V(U_i, u_i), U_r)`.
+/// Similarly, for CycleFold it contains the running instance `cf_U_i`, the random relaxed instance
+/// `cf_U_r`, the cross-term commitment of their fold, and the randomized witness `cf_W_i_prime`
+/// which satisfies `cf_U_i' = NIFS.V(cf_U_i, cf_U_r)`.
+///
+/// Notice that the CycleFold part is not hiding: CS2 is used with `H=false`, so the commitments of
+/// `cf_U_i`, `cf_U_r` and `cf_cmT_r` have no blinding factors, and anyone can check a guess of the
+/// CycleFold witness against them. What the CycleFold part reveals is the CycleFold witness of the
+/// past steps, that is, the values of the CycleFold circuits that computed the folds of the C1
+/// commitments:
+/// - the folding challenges `r` of every step (and their bits), which are also public in the Nova
+///   transcript;
+/// - the C1 points that were folded (the `cmW`, `cmE` and `cmT` of the past running and incoming
+///   instances), which are hiding commitments under CS1;
+/// - the intermediate values of the scalar multiplications and additions of those points.
+///
+/// So it reveals the folding transcript of the past steps (the hiding commitments of the past
+/// instances and the challenges), but none of the witnesses of F.
+#[derive(Debug, Clone, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct RandomizedIVCProof<C1: CurveGroup, C2: CurveGroup> {
+    pub U_i: CommittedInstance<C1>,
+    pub u_i: CommittedInstance<C1>,
+    pub cmT: C1,
+    pub U_r: CommittedInstance<C1>,
+    pub cmT_r: C1,
+    pub W_i_prime: Witness<C1>,
+    pub cf_U_i: CommittedInstance<C2>,
+    pub cf_U_r: CommittedInstance<C2>,
+    pub cf_cmT_r: C2,
+    pub cf_W_i_prime: Witness<C2>,
+}
+
+impl<C1: CurveGroup, C2: CurveGroup> RandomizedIVCProof<C1, C2>
+where
+    <C1 as Group>::ScalarField: Absorb,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as Group>::ScalarField: Absorb,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+{
+    /// computes the RandomizedIVCProof from the current state of the given Nova instance, which
+    /// must be using a hiding CommitmentScheme over C1.
+    pub fn new<GC1, GC2, FC, CS1, CS2>(
+        nova: &Nova<C1, GC1, C2, GC2, FC, CS1, CS2, true>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<Self, Error>
+    where
+        GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+        GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+        FC: FCircuit<C1::ScalarField>,
+        CS1: CommitmentScheme<C1, true>,
+        CS2: CommitmentScheme<C2>,
+        for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+        for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+    {
+        // fold the last incoming instance u_i into the running instance U_i
+        let (W_f, U_f, cmT) = Self::fold_hiding::<CS1>(
+            &nova.poseidon_config,
+            nova.pp_hash,
+            &nova.cs_params,
+            &nova.r1cs,
+            &nova.W_i,
+            &nova.U_i,
+            &nova.w_i,
+            &nova.u_i,
+            &mut rng,
+        )?;
+
+        // sample a random satisfying relaxed instance and fold it with the previous result
+        let (W_r, U_r) = nova
+            .r1cs
+            .sample_witness_instance::<CS1, true>(&nova.cs_params, &mut rng)?;
+        let (W_i_prime, _, cmT_r) = Self::fold_hiding::<CS1>(
+            &nova.poseidon_config,
+            nova.pp_hash,
+            &nova.cs_params,
+            &nova.r1cs,
+            &W_f,
+            &U_f,
+            &W_r,
+            &U_r,
+            &mut rng,
+        )?;
+
+        // sample a random satisfying relaxed CycleFold instance and fold it with the CycleFold
+        // running instance, so that the revealed CycleFold witness is randomized too
+        let (cf_W_r, cf_U_r) = nova
+            .cf_r1cs
+            .sample_witness_instance::<CS2, false>(&nova.cf_cs_params, &mut rng)?;
+        let (cf_W_i_prime, _, cf_cmT_r) = Self::fold_cyclefold::<GC2, CS2>(
+            &nova.poseidon_config,
+            &nova.cf_cs_params,
+            &nova.cf_r1cs,
+            &nova.cf_W_i,
+            &nova.cf_U_i,
+            &cf_W_r,
+            &cf_U_r,
+        )?;
+
+        Ok(Self {
+            U_i: nova.U_i.clone(),
+            u_i: nova.u_i.clone(),
+            cmT,
+            U_r,
+            cmT_r,
+            W_i_prime,
+            cf_U_i: nova.cf_U_i.clone(),
+            cf_U_r,
+            cf_cmT_r,
+            cf_W_i_prime,
+        })
+    }
+
+    /// verifies the RandomizedIVCProof for the given number of steps `i`, initial state `z_0` and
+    /// last state `z_i`. Notice that the verifier needs the ProverParams of both
+    /// CommitmentSchemes, since it opens the commitments of the randomized instances.
+    pub fn verify<GC2, CS1, CS2>(
+        &self,
+        vp: &VerifierParams<C1, C2>,
+        cs_params: &CS1::ProverParams,
+        cf_cs_params: &CS2::ProverParams,
+        i: C1::ScalarField,
+        z_0: Vec<C1::ScalarField>,
+        z_i: Vec<C1::ScalarField>,
+    ) -> Result<(), Error>
+    where
+        GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+        CS1: CommitmentScheme<C1, true>,
+        CS2: CommitmentScheme<C2>,
+        for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+    {
+        if self.u_i.x.len() != 2 || self.U_i.x.len() != 2 || self.U_r.x.len() != 2 {
+            return Err(Error::IVCVerificationFail);
+        }
+        if self.cf_U_r.x.len() != self.cf_U_i.x.len() {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check that u_i's output points to the running instance
+        // u_i.X[0] == H(pp_hash, i, z_0, z_i, U_i)
+        let expected_u_i_x = self
+            .U_i
+            .hash(&vp.poseidon_config, vp.pp_hash, i, z_0, z_i)?;
+        if expected_u_i_x != self.u_i.x[0] {
+            return Err(Error::IVCVerificationFail);
+        }
+        // u_i.X[1] == H(cf_U_i)
+        let expected_cf_u_i_x = self.cf_U_i.hash_cyclefold(&vp.poseidon_config)?;
+        if expected_cf_u_i_x != self.u_i.x[1] {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check u_i.cmE==0, u_i.u==1 (=u_i is a un-relaxed instance)
+        if !self.u_i.cmE.is_zero() || !self.u_i.u.is_one() {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // recompute the folded instance U_f = NIFS.V(U_i, u_i), and the randomized instance
+        // U_i' = NIFS.V(U_f, U_r)
+        let r = Self::get_challenge(
+            &vp.poseidon_config,
+            vp.pp_hash,
+            &self.U_i,
+            &self.u_i,
+            self.cmT,
+        )?;
+        let U_f = NIFS::<C1, CS1, true>::verify(r, &self.U_i, &self.u_i, &self.cmT);
+        let r_r =
+            Self::get_challenge(&vp.poseidon_config, vp.pp_hash, &U_f, &self.U_r, self.cmT_r)?;
+        let U_i_prime = NIFS::<C1, CS1, true>::verify(r_r, &U_f, &self.U_r, &self.cmT_r);
+
+        // check that the revealed witness opens the commitments of U_i'
+        let W = &self.W_i_prime;
+        if CS1::commit(cs_params, &W.W, &W.rW)? != U_i_prime.cmW
+            || CS1::commit(cs_params, &W.E, &W.rE)? != U_i_prime.cmE
+        {
+            return Err(Error::CommitmentVerificationFail);
+        }
+
+        // check RelaxedR1CS satisfiability of the randomized instance
+        vp.r1cs
+            .check_relaxed_instance_relation(&self.W_i_prime, &U_i_prime)?;
+
+        // recompute the randomized CycleFold instance cf_U_i' = NIFS.V(cf_U_i, cf_U_r)
+        let cf_r = Self::get_cyclefold_challenge::<GC2>(
+            &vp.poseidon_config,
+            &self.cf_U_i,
+            &self.cf_U_r,
+            self.cf_cmT_r,
+        )?;
+        let cf_U_i_prime =
+            NIFS::<C2, CS2>::verify(cf_r, &self.cf_U_i, &self.cf_U_r, &self.cf_cmT_r);
+
+        // check that the revealed CycleFold witness opens the commitments of cf_U_i'
+        let cf_W = &self.cf_W_i_prime;
+        if CS2::commit(cf_cs_params, &cf_W.W, &cf_W.rW)? != cf_U_i_prime.cmW
+            || CS2::commit(cf_cs_params, &cf_W.E, &cf_W.rE)? != cf_U_i_prime.cmE
+        {
+            return Err(Error::CommitmentVerificationFail);
+        }
+
+        // check CycleFold RelaxedR1CS satisfiability of the randomized CycleFold instance
+        vp.cf_r1cs
+            .check_relaxed_instance_relation(cf_W, &cf_U_i_prime)?;
+
+        Ok(())
+    }
+
+    // folds the given pair of instances committing to the cross-terms with a random blinding
+    // factor. Returns the folded witness and instance, together with cm(T).
+    #[allow(clippy::too_many_arguments)]
+    fn fold_hiding<CS1: CommitmentScheme<C1, true>>(
+        poseidon_config: &PoseidonConfig<C1::ScalarField>,
+        pp_hash: C1::ScalarField,
+        cs_params: &CS1::ProverParams,
+        r1cs: &R1CS<C1::ScalarField>,
+        W1: &Witness<C1>,
+        U1: &CommittedInstance<C1>,
+        W2: &Witness<C1>,
+        U2: &CommittedInstance<C1>,
+        mut rng: impl RngCore,
+    ) -> Result<(Witness<C1>, CommittedInstance<C1>, C1), Error> {
+        let rT = C1::ScalarField::rand(&mut rng);
+        let (T, cmT) =
+            NIFS::<C1, CS1, true>::compute_cmT_with_blind(cs_params, r1cs, W1, U1, W2, U2, rT)?;
+        let r = Self::get_challenge(poseidon_config, pp_hash, U1, U2, cmT)?;
+        let W = NIFS::<C1, CS1, true>::fold_witness(r, W1, W2, &T, rT)?;
+        let U = NIFS::<C1, CS1, true>::fold_committed_instance(r, U1, U2, &cmT);
+        Ok((W, U, cmT))
+    }
+
+    // folds the given pair of CycleFold instances, returning the folded witness and instance,
+    // together with cm(T).
+    fn fold_cyclefold<GC2, CS2>(
+        poseidon_config: &PoseidonConfig<C1::ScalarField>,
+        cf_cs_params: &CS2::ProverParams,
+        cf_r1cs: &R1CS<C2::ScalarField>,
+        W1: &Witness<C2>,
+        U1: &CommittedInstance<C2>,
+        W2: &Witness<C2>,
+        U2: &CommittedInstance<C2>,
+    ) -> Result<(Witness<C2>, CommittedInstance<C2>, C2), Error>
+    where
+        GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+        CS2: CommitmentScheme<C2>,
+        for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+    {
+        let (T, cmT) =
+            NIFS::<C2, CS2>::compute_cyclefold_cmT(cf_cs_params, cf_r1cs, W1, U1, W2, U2)?;
+        let r = Self::get_cyclefold_challenge::<GC2>(poseidon_config, U1, U2, cmT)?;
+        let (W, U) = NIFS::<C2, CS2>::fold_instances(r, W1, U1, W2, U2, &T, cmT)?;
+        Ok((W, U, cmT))
+    }
+
+    fn get_cyclefold_challenge<GC2>(
+        poseidon_config: &PoseidonConfig<C1::ScalarField>,
+        U1: &CommittedInstance<C2>,
+        U2: &CommittedInstance<C2>,
+        cmT: C2,
+    ) -> Result<C2::ScalarField, Error>
+    where
+        GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+        for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+    {
+        let r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_native(
+            poseidon_config,
+            U1.clone(),
+            U2.clone(),
+            cmT,
+        )?;
+        C2::ScalarField::from_bigint(BigInteger::from_bits_le(&r_bits)).ok_or(Error::OutOfBounds)
+    }
+
+    fn get_challenge(
+        poseidon_config: &PoseidonConfig<C1::ScalarField>,
+        pp_hash: C1::ScalarField,
+        U1: &CommittedInstance<C1>,
+        U2: &CommittedInstance<C1>,
+        cmT: C1,
+    ) -> Result<C1::ScalarField, Error> {
+        let r_bits = ChallengeGadget::<C1>::get_challenge_native(
+            poseidon_config,
+            pp_hash,
+            U1.clone(),
+            U2.clone(),
+            cmT,
+        )?;
+        C1::ScalarField::from_bigint(BigInteger::from_bits_le(&r_bits)).ok_or(Error::OutOfBounds)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bn254::{constraints::GVar, Fr, G1Projective as Projective};
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+
+    use crate::commitment::pedersen::Pedersen;
+    use crate::folding::nova::{get_cs_params_len, ProverParams};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_canonical_config;
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_zk_ivc() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective, true>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        type NOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            Pedersen<Projective, true>,
+            Pedersen<Projective2>,
+            true,
+        >;
+        let prover_params = ProverParams::<
+            Projective,
+            Projective2,
+            Pedersen<Projective, true>,
+            Pedersen<Projective2>,
+            true,
+        > {
+            poseidon_config: poseidon_config.clone(),
+            cs_params: pedersen_params.clone(),
+            cf_cs_params: cf_pedersen_params.clone(),
+        };
+        let (_, verifier_params) = NOVA::preprocess(&(prover_params.clone(), F_circuit)).unwrap();
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        let num_steps: usize = 3;
+        for _ in 0..num_steps {
+            nova.prove_step(&mut rng, vec![]).unwrap();
+        }
+        // the incoming instance commitments are blinded
+        assert!(!nova.w_i.rW.is_zero());
+
+        let proof = RandomizedIVCProof::new(&nova, &mut rng).unwrap();
+        proof
+            .verify::<GVar2, Pedersen<Projective, true>, Pedersen<Projective2>>(
+                &verifier_params,
+                &pedersen_params,
+                &cf_pedersen_params,
+                nova.i,
+                z_0.clone(),
+                nova.z_i.clone(),
+            )
+            .unwrap();
+
+        // the randomized witnesses differ from the original running witnesses
+        assert_ne!(proof.W_i_prime.W, nova.W_i.W);
+        assert_ne!(proof.cf_W_i_prime.W, nova.cf_W_i.W);
+
+        // verification fails when revealing the original (non-randomized) CycleFold witness
+        let mut bad_proof = proof.clone();
+        bad_proof.cf_W_i_prime = nova.cf_W_i.clone();
+        assert!(bad_proof
+            .verify::<GVar2, Pedersen<Projective, true>, Pedersen<Projective2>>(
+                &verifier_params,
+                &pedersen_params,
+                &cf_pedersen_params,
+                nova.i,
+                z_0.clone(),
+                nova.z_i.clone(),
+            )
+            .is_err());
+
+        // verification fails for a wrong last state
+        assert!(proof
+            .verify::<GVar2, Pedersen<Projective, true>, Pedersen<Projective2>>(
+                &verifier_params,
+                &pedersen_params,
+                &cf_pedersen_params,
+                nova.i,
+                z_0,
+                vec![Fr::from(4_u32)],
+            )
+            .is_err());
+    }
+}
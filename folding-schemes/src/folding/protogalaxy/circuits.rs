@@ -0,0 +1,754 @@
+/// Implementation of the ProtoGalaxy+CycleFold circuits
+use ark_crypto_primitives::crh::{
+    poseidon::constraints::{CRHGadget, CRHParametersVar},
+    CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::PrimeField;
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use ark_r1cs_std::{
+    alloc::{AllocVar, AllocationMode},
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    groups::GroupOpsBounds,
+    prelude::CurveVar,
+    R1CSVar, ToBitsGadget, ToConstraintFieldGadget,
+};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, Namespace, SynthesisError};
+use ark_std::{fmt::Debug, log2, One, Zero};
+use core::{borrow::Borrow, marker::PhantomData};
+
+use super::{folding::lagrange_polys, CommittedInstance, ProtoGalaxyError};
+use crate::folding::circuits::{
+    nonnative::{affine::NonNativeAffineVar, uint::NonNativeUintVar},
+    CF1, CF2,
+};
+use crate::folding::nova::{
+    cyclefold::{
+        CycleFoldChallengeGadget, CycleFoldCommittedInstanceVar, NIFSFullGadget, CF_IO_LEN,
+    },
+    get_r1cs_from_cs, CommittedInstance as CycleFoldCommittedInstance,
+};
+use crate::frontend::FCircuit;
+use crate::transcript::{poseidon::PoseidonTranscriptVar, TranscriptVar};
+use crate::Error;
+
+/// CommittedInstanceVar contains the ProtoGalaxy CommittedInstance in-circuit representation,
+/// where the commitment `phi` is represented by its non-native coordinates.
+#[derive(Debug, Clone)]
+pub struct CommittedInstanceVar<C: CurveGroup>
+where
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    pub phi: NonNativeAffineVar<C>,
+    pub betas: Vec<FpVar<CF1<C>>>,
+    pub e: FpVar<CF1<C>>,
+    pub u: FpVar<CF1<C>>,
+    pub x: Vec<FpVar<CF1<C>>>,
+}
+
+impl<C> AllocVar<CommittedInstance<C>, CF1<C>> for CommittedInstanceVar<C>
+where
+    C: CurveGroup,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    fn new_variable<T: Borrow<CommittedInstance<C>>>(
+        cs: impl Into<Namespace<CF1<C>>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        f().and_then(|val| {
+            let cs = cs.into();
+
+            let phi =
+                NonNativeAffineVar::<C>::new_variable(cs.clone(), || Ok(val.borrow().phi), mode)?;
+            let betas: Vec<FpVar<CF1<C>>> =
+                Vec::new_variable(cs.clone(), || Ok(val.borrow().betas.clone()), mode)?;
+            let e = FpVar::<CF1<C>>::new_variable(cs.clone(), || Ok(val.borrow().e), mode)?;
+            let u = FpVar::<CF1<C>>::new_variable(cs.clone(), || Ok(val.borrow().u), mode)?;
+            let x: Vec<FpVar<CF1<C>>> =
+                Vec::new_variable(cs.clone(), || Ok(val.borrow().x.clone()), mode)?;
+
+            Ok(Self {
+                phi,
+                betas,
+                e,
+                u,
+                x,
+            })
+        })
+    }
+}
+
+impl<C> ToConstraintFieldGadget<CF1<C>> for CommittedInstanceVar<C>
+where
+    C: CurveGroup,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    // Extract the underlying field elements from `CommittedInstanceVar`, in the order of `phi.x`,
+    // `phi.y`, `betas`, `e`, `u`, `x`, compatible with the native `to_field_elements`.
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<CF1<C>>>, SynthesisError> {
+        Ok([
+            self.phi.to_constraint_field()?,
+            self.betas.clone(),
+            vec![self.e.clone(), self.u.clone()],
+            self.x.clone(),
+        ]
+        .concat())
+    }
+}
+
+impl<C> CommittedInstanceVar<C>
+where
+    C: CurveGroup,
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    /// hash implements the committed instance hash compatible with the native implementation from
+    /// CommittedInstance.hash.
+    /// Returns `H(i, z_0, z_i, U_i)`, where `i` can be `i` but also `i+1`, and `U` is the
+    /// `CommittedInstance`.
+    /// Additionally it returns the vector of the field elements from the self parameters, so they
+    /// can be reused in other gadgets avoiding recalculating (reconstraining) them.
+    #[allow(clippy::type_complexity)]
+    pub fn hash(
+        self,
+        crh_params: &CRHParametersVar<CF1<C>>,
+        i: FpVar<CF1<C>>,
+        z_0: Vec<FpVar<CF1<C>>>,
+        z_i: Vec<FpVar<CF1<C>>>,
+    ) -> Result<(FpVar<CF1<C>>, Vec<FpVar<CF1<C>>>), SynthesisError> {
+        let U_vec = self.to_constraint_field()?;
+        let input = [vec![i], z_0, z_i, U_vec.clone()].concat();
+        Ok((
+            CRHGadget::<C::ScalarField>::evaluate(crh_params, &input)?,
+            U_vec,
+        ))
+    }
+}
+
+/// Implements the in-circuit version of the ProtoGalaxy `Folding::verify` method.
+pub struct FoldingGadget<C: CurveGroup> {
+    _c: PhantomData<C>,
+}
+
+impl<C> FoldingGadget<C>
+where
+    C: CurveGroup,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    /// folds the given committed instances, returning the folded instance together with the
+    /// evaluations of the Lagrange polynomials at the challenge gamma.
+    /// Notice that the commitment `phi` is not folded in-circuit: the returned instance contains
+    /// the `phi` of the running instance as a placeholder, and the Lagrange evaluations are
+    /// returned so that the correct folding of `phi` can be checked in the CycleFold circuits.
+    #[allow(clippy::type_complexity)]
+    pub fn fold_committed_instance(
+        transcript: &mut impl TranscriptVar<CF1<C>>,
+        // running instance
+        instance: &CommittedInstanceVar<C>,
+        // incoming instances
+        vec_instances: &[CommittedInstanceVar<C>],
+        // polys from P
+        F_coeffs: Vec<FpVar<CF1<C>>>,
+        K_coeffs: Vec<FpVar<CF1<C>>>,
+    ) -> Result<(CommittedInstanceVar<C>, Vec<FpVar<CF1<C>>>), SynthesisError> {
+        let t = instance.betas.len();
+
+        // absorb the committed instances
+        transcript.absorb_vec(&instance.to_constraint_field()?)?;
+        for ci in vec_instances.iter() {
+            transcript.absorb_vec(&ci.to_constraint_field()?)?;
+        }
+
+        // deltas = (delta, delta^2, delta^4, ..., delta^{2^{t-1}})
+        let delta = transcript.get_challenge()?;
+        let mut deltas = vec![delta];
+        for _ in 1..t {
+            let delta_i = deltas[deltas.len() - 1].square()?;
+            deltas.push(delta_i);
+        }
+
+        transcript.absorb_vec(&F_coeffs)?;
+
+        let alpha = transcript.get_challenge()?;
+
+        // F(alpha) = e + \sum_t F_i * alpha^i
+        let mut F_alpha = instance.e.clone();
+        let mut alpha_i = FpVar::<CF1<C>>::one();
+        for F_i in F_coeffs.iter().skip(1) {
+            alpha_i *= &alpha;
+            F_alpha += F_i * &alpha_i;
+        }
+
+        // betas* = betas + alpha * deltas
+        let betas_star = instance
+            .betas
+            .iter()
+            .zip(deltas.iter())
+            .map(|(beta_i, delta_i)| beta_i + &alpha * delta_i)
+            .collect::<Vec<_>>();
+
+        let k = vec_instances.len();
+        let H =
+            GeneralEvaluationDomain::<CF1<C>>::new(k + 1).ok_or(SynthesisError::Unsatisfiable)?;
+        let L_X: Vec<DensePolynomial<CF1<C>>> = lagrange_polys(H);
+        let Z_X: DensePolynomial<CF1<C>> = H.vanishing_polynomial().into();
+
+        transcript.absorb_vec(&K_coeffs)?;
+
+        let gamma = transcript.get_challenge()?;
+
+        let L_X_evals = L_X
+            .iter()
+            .map(|L| {
+                let coeffs = L
+                    .coeffs
+                    .iter()
+                    .map(|c| FpVar::<CF1<C>>::Constant(*c))
+                    .collect::<Vec<_>>();
+                evaluate_poly(&coeffs, &gamma)
+            })
+            .collect::<Vec<_>>();
+        let Z_gamma = evaluate_poly(
+            &Z_X.coeffs
+                .iter()
+                .map(|c| FpVar::<CF1<C>>::Constant(*c))
+                .collect::<Vec<_>>(),
+            &gamma,
+        );
+        let K_gamma = evaluate_poly(&K_coeffs, &gamma);
+
+        let e_star = F_alpha * &L_X_evals[0] + Z_gamma * K_gamma;
+
+        let mut u_star = &instance.u * &L_X_evals[0];
+        let mut x_star = instance
+            .x
+            .iter()
+            .map(|x_i| x_i * &L_X_evals[0])
+            .collect::<Vec<_>>();
+        for (ci, L_i) in vec_instances.iter().zip(L_X_evals.iter().skip(1)) {
+            u_star += &ci.u * L_i;
+            x_star = x_star
+                .iter()
+                .zip(ci.x.iter())
+                .map(|(a, b)| a + b * L_i)
+                .collect();
+        }
+
+        Ok((
+            CommittedInstanceVar {
+                phi: instance.phi.clone(),
+                betas: betas_star,
+                e: e_star,
+                u: u_star,
+                x: x_star,
+            },
+            L_X_evals,
+        ))
+    }
+}
+
+// evaluates the polynomial with the given coefficients at x, using Horner's method
+fn evaluate_poly<F: PrimeField>(coeffs: &[FpVar<F>], x: &FpVar<F>) -> FpVar<F> {
+    let mut r = FpVar::<F>::zero();
+    for c in coeffs.iter().rev() {
+        r = r * x + c;
+    }
+    r
+}
+
+/// AugmentedFCircuit implements the F' circuit (augmented F) defined in
+/// [Nova](https://eprint.iacr.org/2021/370.pdf) together with the extra constraints defined in
+/// [CycleFold](https://eprint.iacr.org/2023/1192.pdf), adapted to
+/// [ProtoGalaxy](https://eprint.iacr.org/2023/1106.pdf) folding of `k` incoming instances (the
+/// incoming instance `u_i` and `k-1` extra ones `us`) into the running instance.
+#[derive(Debug, Clone)]
+pub struct AugmentedFCircuit<
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+> where
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    pub _gc2: PhantomData<GC2>,
+    pub poseidon_config: PoseidonConfig<CF1<C1>>,
+    pub t: usize, // log2 of the number of constraints of the AugmentedFCircuit, ie. |betas|
+    pub k: usize, // number of incoming instances folded at each step
+    pub i: Option<CF1<C1>>,
+    pub i_usize: Option<usize>,
+    pub z_0: Option<Vec<C1::ScalarField>>,
+    pub z_i: Option<Vec<C1::ScalarField>>,
+    pub external_inputs: Option<Vec<C1::ScalarField>>,
+    pub u_i_phi: Option<C1>, // u_i.phi
+    pub U_i: Option<CommittedInstance<C1>>,
+    pub us: Option<Vec<CommittedInstance<C1>>>, // other incoming instances, of length k-1
+    pub U_i1_phi: Option<C1>,                   // U_{i+1}.phi
+    pub F_coeffs: Option<Vec<CF1<C1>>>,
+    pub K_coeffs: Option<Vec<CF1<C1>>>,
+    // intermediate values of the phi folding, phi_stars[0] = L_0(gamma) * U_i.phi
+    pub phi_stars: Option<Vec<C1>>,
+    pub F: FC,              // F circuit
+    pub x: Option<CF1<C1>>, // public input (u_{i+1}.x[0])
+
+    // cyclefold verifier on C1
+    // Here cf_u_i_cmW and cf_cmT contain the values of each of the CycleFold circuits, which
+    // compute the random linear combination of the phi commitments
+    pub cf_u_i_cmW: Option<Vec<C2>>,                    // input
+    pub cf_U_i: Option<CycleFoldCommittedInstance<C2>>, // input
+    pub cf_x: Option<CF1<C1>>,                          // public input (u_{i+1}.x[1])
+    pub cf_cmT: Option<Vec<C2>>,
+}
+
+impl<C1, C2, GC2, FC> AugmentedFCircuit<C1, C2, GC2, FC>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    pub fn default(
+        poseidon_config: &PoseidonConfig<CF1<C1>>,
+        F_circuit: FC,
+        k: usize,
+        t: usize,
+    ) -> Self {
+        Self {
+            _gc2: PhantomData,
+            poseidon_config: poseidon_config.clone(),
+            t,
+            k,
+            i: None,
+            i_usize: None,
+            z_0: None,
+            z_i: None,
+            external_inputs: None,
+            u_i_phi: None,
+            U_i: None,
+            us: None,
+            U_i1_phi: None,
+            F_coeffs: None,
+            K_coeffs: None,
+            phi_stars: None,
+            F: F_circuit,
+            x: None,
+            cf_u_i_cmW: None,
+            cf_U_i: None,
+            cf_x: None,
+            cf_cmT: None,
+        }
+    }
+
+    /// Returns an empty AugmentedFCircuit. If `t` is not provided, it is computed through
+    /// `upper_bound_t`.
+    pub fn empty(
+        poseidon_config: &PoseidonConfig<CF1<C1>>,
+        F_circuit: FC,
+        k: usize,
+        t: Option<usize>,
+    ) -> Result<Self, Error> {
+        if k == 0 || !(k + 1).is_power_of_two() {
+            return Err(Error::ProtoGalaxy(ProtoGalaxyError::WrongNumInstances(k)));
+        }
+        let mut augmented_f_circuit = Self::default(poseidon_config, F_circuit, k, 1);
+        augmented_f_circuit.t = match t {
+            Some(t) => t,
+            None => augmented_f_circuit.upper_bound_t()?,
+        };
+        Ok(augmented_f_circuit)
+    }
+
+    /// This method computes `t`, the length of the betas of the running instance, which is the
+    /// log2 of the number of constraints of the AugmentedFCircuit. Since the number of
+    /// constraints at the same time depends on `t`, we iterate until it stabilizes.
+    pub fn upper_bound_t(&self) -> Result<usize, Error> {
+        let mut augmented_f_circuit = self.clone();
+        loop {
+            let r1cs = get_r1cs_from_cs::<CF1<C1>>(augmented_f_circuit.clone())?;
+            let t = log2(r1cs.A.n_rows) as usize;
+            if t == augmented_f_circuit.t {
+                return Ok(t);
+            }
+            augmented_f_circuit.t = t;
+        }
+    }
+}
+
+impl<C1, C2, GC2, FC> ConstraintSynthesizer<CF1<C1>> for AugmentedFCircuit<C1, C2, GC2, FC>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<CF1<C1>>) -> Result<(), SynthesisError> {
+        let k = self.k;
+
+        let i = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self.i.unwrap_or_else(CF1::<C1>::zero))
+        })?;
+        let z_0 = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .z_0
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.state_len()]))
+        })?;
+        let z_i = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .z_i
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.state_len()]))
+        })?;
+        let external_inputs = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .external_inputs
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.external_inputs_len()]))
+        })?;
+
+        let U_dummy = CommittedInstance::<C1>::dummy_running(2, self.t);
+        let U_i = CommittedInstanceVar::<C1>::new_witness(cs.clone(), || {
+            Ok(self.U_i.unwrap_or(U_dummy.clone()))
+        })?;
+        // the other incoming instances have no betas, e = 0 and u = 1, as u_i, so only their phi
+        // and x are allocated
+        let us = self
+            .us
+            .unwrap_or(vec![CommittedInstance::<C1>::dummy_incoming(2); k - 1])
+            .into_iter()
+            .map(|u| {
+                Ok(CommittedInstanceVar {
+                    phi: NonNativeAffineVar::new_witness(cs.clone(), || Ok(u.phi))?,
+                    betas: vec![],
+                    e: FpVar::zero(),
+                    u: FpVar::one(),
+                    x: Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || Ok(u.x))?,
+                })
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+        let U_i1_phi = NonNativeAffineVar::new_witness(cs.clone(), || {
+            Ok(self.U_i1_phi.unwrap_or_else(C1::zero))
+        })?;
+        let phi_stars = Vec::<NonNativeAffineVar<C1>>::new_witness(cs.clone(), || {
+            Ok(self.phi_stars.unwrap_or(vec![C1::zero(); k]))
+        })?;
+        // deg(F) = t, deg(K) = d*k - (k+1), with d = 2
+        let F_coeffs = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self.F_coeffs.unwrap_or(vec![CF1::<C1>::zero(); self.t + 1]))
+        })?;
+        let K_coeffs = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self.K_coeffs.unwrap_or(vec![CF1::<C1>::zero(); k]))
+        })?;
+
+        let cf_u_dummy = CycleFoldCommittedInstance::dummy(CF_IO_LEN);
+        let cf_U_i = CycleFoldCommittedInstanceVar::<C2, GC2>::new_witness(cs.clone(), || {
+            Ok(self.cf_U_i.unwrap_or(cf_u_dummy.clone()))
+        })?;
+        let cf_u_i_cmW = Vec::<GC2>::new_witness(cs.clone(), || {
+            Ok(self.cf_u_i_cmW.unwrap_or(vec![C2::zero(); k + 1]))
+        })?;
+        let cf_cmT = Vec::<GC2>::new_witness(cs.clone(), || {
+            Ok(self.cf_cmT.unwrap_or(vec![C2::zero(); k + 1]))
+        })?;
+
+        let crh_params = CRHParametersVar::<C1::ScalarField>::new_constant(
+            cs.clone(),
+            self.poseidon_config.clone(),
+        )?;
+
+        // get z_{i+1} from the F circuit
+        let i_usize = self.i_usize.unwrap_or(0);
+        let z_i1 =
+            self.F
+                .generate_step_constraints(cs.clone(), i_usize, z_i.clone(), external_inputs)?;
+
+        let is_basecase = i.is_zero()?;
+
+        // Primary Part
+        // P.1. Compute u_i.x
+        // u_i.x[0] = H(i, z_0, z_i, U_i)
+        let (u_i_x, _) = U_i
+            .clone()
+            .hash(&crh_params, i.clone(), z_0.clone(), z_i.clone())?;
+        // u_i.x[1] = H(cf_U_i)
+        let (cf_u_i_x, cf_U_i_vec) = cf_U_i.clone().hash(&crh_params)?;
+
+        // P.2. Construct u_i
+        let u_i = CommittedInstanceVar {
+            // u_i.phi is provided by the prover as witness
+            phi: NonNativeAffineVar::new_witness(cs.clone(), || {
+                Ok(self.u_i_phi.unwrap_or(C1::zero()))
+            })?,
+            // incoming instances have no betas, e = 0 and u = 1
+            betas: vec![],
+            e: FpVar::zero(),
+            u: FpVar::one(),
+            // u_i.x is computed in step 1
+            x: vec![u_i_x, cf_u_i_x],
+        };
+
+        // P.3. Folding.verify, obtains U_{i+1} by folding [U_i] & [u_i, us].
+        // Notice that FoldingGadget::fold_committed_instance does not fold phi. We set
+        // `U_i1.phi` to the unconstrained witness `U_i1_phi`, its correctness is checked on the
+        // other curve.
+        let mut transcript =
+            PoseidonTranscriptVar::<CF1<C1>>::new(cs.clone(), &self.poseidon_config);
+        let all_us = [vec![u_i], us].concat();
+        let (mut U_i1, L_X_evals) = FoldingGadget::<C1>::fold_committed_instance(
+            &mut transcript,
+            &U_i,
+            &all_us,
+            F_coeffs,
+            K_coeffs,
+        )?;
+        U_i1.phi = U_i1_phi;
+
+        // P.4.a compute and check the first output of F'
+        // Base case: u_{i+1}.x[0] == H((1, z_0, z_{i+1}, U_{\bot})
+        // Non-base case: u_{i+1}.x[0] == H((i+1, z_0, z_{i+1}, U_{i+1})
+        let (u_i1_x, _) = U_i1.clone().hash(
+            &crh_params,
+            i + FpVar::<CF1<C1>>::one(),
+            z_0.clone(),
+            z_i1.clone(),
+        )?;
+        let (u_i1_x_base, _) = CommittedInstanceVar::new_constant(cs.clone(), U_dummy)?.hash(
+            &crh_params,
+            FpVar::<CF1<C1>>::one(),
+            z_0.clone(),
+            z_i1.clone(),
+        )?;
+        let x = FpVar::new_input(cs.clone(), || Ok(self.x.unwrap_or(u_i1_x_base.value()?)))?;
+        x.enforce_equal(&is_basecase.select(&u_i1_x_base, &u_i1_x)?)?;
+
+        // CycleFold part
+        // The commitment U_{i+1}.phi = L_0(gamma) * U_i.phi + \sum_j L_j(gamma) * u_j.phi,
+        // where u_j are the instances [u_i, us], is computed through a chain of CycleFold
+        // circuits, where the j-th circuit computes p3 = p1 + L_j(gamma) * p2, being p1 the output
+        // of the previous circuit (or 0 for the first one), and p2 the phi of the j-th instance.
+        let zero_point = NonNativeAffineVar::new_constant(cs.clone(), C1::zero())?;
+        let p1s = [vec![zero_point], phi_stars.clone()].concat();
+        let p2s = [
+            vec![U_i.phi.clone()],
+            all_us.iter().map(|u| u.phi.clone()).collect(),
+        ]
+        .concat();
+        let p3s = [phi_stars, vec![U_i1.phi.clone()]].concat();
+
+        let mut cf_U_i1 = cf_U_i;
+        let mut cf_U_i1_vec = cf_U_i_vec;
+        for j in 0..k + 1 {
+            // convert L_j(gamma) to its bits representation, which is used as the scalar in the
+            // CycleFold circuit
+            let r_nonnat = {
+                let mut bits = L_X_evals[j].to_bits_le()?;
+                bits.resize(C1::BaseField::MODULUS_BIT_SIZE as usize, Boolean::FALSE);
+                NonNativeUintVar::from(&bits)
+            };
+
+            // C.1. Construct `cf_u_i`, ensuring that it has as public inputs the coordinates of
+            // the points p1, p2, p3
+            let cf_u_i = CycleFoldCommittedInstanceVar {
+                // cf_u_i.cmE = 0
+                cmE: GC2::zero(),
+                // cf_u_i.u = 1
+                u: NonNativeUintVar::new_constant(cs.clone(), C1::BaseField::one())?,
+                // cf_u_i.cmW is provided by the prover as witness
+                cmW: cf_u_i_cmW[j].clone(),
+                x: vec![
+                    r_nonnat,
+                    p1s[j].x.clone(),
+                    p1s[j].y.clone(),
+                    p2s[j].x.clone(),
+                    p2s[j].y.clone(),
+                    p3s[j].x.clone(),
+                    p3s[j].y.clone(),
+                ],
+            };
+
+            // C.2. nifs.verify, obtains cf_U_{i+1} by folding cf_u_i & cf_U_i.
+            // compute cf_r = H(cf_u_i, cf_U_i, cf_cmT)
+            let cf_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_gadget(
+                cs.clone(),
+                &self.poseidon_config,
+                cf_U_i1_vec,
+                cf_u_i.clone(),
+                cf_cmT[j].clone(),
+            )?;
+            // Convert cf_r_bits to a `NonNativeFieldVar`
+            let cf_r_nonnat = {
+                let mut bits = cf_r_bits.clone();
+                bits.resize(C1::BaseField::MODULUS_BIT_SIZE as usize, Boolean::FALSE);
+                NonNativeUintVar::from(&bits)
+            };
+            cf_U_i1 = NIFSFullGadget::<C2, GC2>::fold_committed_instance(
+                cf_r_bits,
+                cf_r_nonnat,
+                cf_cmT[j].clone(),
+                cf_U_i1,
+                cf_u_i,
+            )?;
+            cf_U_i1_vec = cf_U_i1.to_constraint_field()?;
+        }
+
+        // Back to Primary Part
+        // P.4.b compute and check the second output of F'
+        // Base case: u_{i+1}.x[1] == H(cf_U_{\bot})
+        // Non-base case: u_{i+1}.x[1] == H(cf_U_{i+1})
+        let (cf_u_i1_x, _) = cf_U_i1.hash(&crh_params)?;
+        let (cf_u_i1_x_base, _) =
+            CycleFoldCommittedInstanceVar::new_constant(cs.clone(), cf_u_dummy)?
+                .hash(&crh_params)?;
+        let cf_x = FpVar::new_input(cs.clone(), || {
+            Ok(self.cf_x.unwrap_or(cf_u_i1_x_base.value()?))
+        })?;
+        cf_x.enforce_equal(&is_basecase.select(&cf_u_i1_x_base, &cf_u_i1_x)?)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_pallas::{Fr, Projective};
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::UniformRand;
+
+    use crate::ccs::r1cs::tests::{get_test_r1cs, get_test_z};
+    use crate::commitment::{pedersen::Pedersen, CommitmentScheme};
+    use crate::folding::protogalaxy::{folding::Folding, utils::exponential_powers, Witness};
+    use crate::transcript::{
+        poseidon::{poseidon_canonical_config, PoseidonTranscript},
+        Transcript,
+    };
+
+    #[test]
+    fn test_committed_instance_hash() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let i = Fr::from(3_u32);
+        let z_0 = vec![Fr::from(3_u32)];
+        let z_i = vec![Fr::from(3_u32)];
+        let ci = CommittedInstance::<Projective> {
+            phi: Projective::rand(&mut rng),
+            betas: vec![Fr::rand(&mut rng); 3],
+            e: Fr::rand(&mut rng),
+            u: Fr::rand(&mut rng),
+            x: vec![Fr::rand(&mut rng); 2],
+        };
+
+        // compute the CommittedInstance hash natively
+        let h = ci
+            .hash(&poseidon_config, i, z_0.clone(), z_i.clone())
+            .unwrap();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+
+        let iVar = FpVar::<Fr>::new_witness(cs.clone(), || Ok(i)).unwrap();
+        let z_0Var = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_0.clone())).unwrap();
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i.clone())).unwrap();
+        let ciVar =
+            CommittedInstanceVar::<Projective>::new_witness(cs.clone(), || Ok(ci.clone())).unwrap();
+
+        let crh_params = CRHParametersVar::<Fr>::new_constant(cs.clone(), poseidon_config).unwrap();
+
+        // compute the CommittedInstance hash in-circuit
+        let (hVar, _) = ciVar.hash(&crh_params, iVar, z_0Var, z_iVar).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // check that the natively computed and in-circuit computed hashes match
+        assert_eq!(hVar.value().unwrap(), h);
+    }
+
+    #[test]
+    fn test_folding_gadget() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, 10).unwrap();
+
+        let r1cs = get_test_r1cs::<Fr>();
+        let t = log2(r1cs.A.n_rows) as usize;
+
+        // running instance
+        let z = get_test_z::<Fr>(3);
+        let (w, x) = r1cs.split_z(&z);
+        let witness = Witness::<Fr>::new(w);
+        let instance = CommittedInstance::<Projective> {
+            betas: exponential_powers(Fr::rand(&mut rng), t),
+            ..witness
+                .commit::<Projective, Pedersen<Projective>>(&pedersen_params, x)
+                .unwrap()
+        };
+        // incoming instance
+        let z_1 = get_test_z::<Fr>(4);
+        let (w_1, x_1) = r1cs.split_z(&z_1);
+        let witness_1 = Witness::<Fr>::new(w_1);
+        let instance_1 = witness_1
+            .commit::<Projective, Pedersen<Projective>>(&pedersen_params, x_1)
+            .unwrap();
+
+        let mut transcript_p = PoseidonTranscript::<Projective>::new(&poseidon_config);
+        let (folded_instance, _, F_coeffs, K_coeffs, L_X_evals) = Folding::<Projective>::prove(
+            &mut transcript_p,
+            &r1cs,
+            &instance,
+            &witness,
+            &[instance_1.clone()],
+            &[witness_1],
+        )
+        .unwrap();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let mut transcriptVar = PoseidonTranscriptVar::<Fr>::new(cs.clone(), &poseidon_config);
+        let instanceVar =
+            CommittedInstanceVar::<Projective>::new_witness(cs.clone(), || Ok(instance.clone()))
+                .unwrap();
+        let instance_1Var =
+            CommittedInstanceVar::<Projective>::new_witness(cs.clone(), || Ok(instance_1.clone()))
+                .unwrap();
+        let F_coeffsVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(F_coeffs)).unwrap();
+        let K_coeffsVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(K_coeffs)).unwrap();
+
+        let (folded_instanceVar, L_X_evalsVar) =
+            FoldingGadget::<Projective>::fold_committed_instance(
+                &mut transcriptVar,
+                &instanceVar,
+                &[instance_1Var],
+                F_coeffsVar,
+                K_coeffsVar,
+            )
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // check that the native and in-circuit folded instances match (except for phi, which is
+        // folded in the CycleFold circuit)
+        assert_eq!(
+            folded_instanceVar.betas.value().unwrap(),
+            folded_instance.betas
+        );
+        assert_eq!(folded_instanceVar.e.value().unwrap(), folded_instance.e);
+        assert_eq!(folded_instanceVar.u.value().unwrap(), folded_instance.u);
+        assert_eq!(folded_instanceVar.x.value().unwrap(), folded_instance.x);
+        assert_eq!(L_X_evalsVar.value().unwrap(), L_X_evals);
+
+        // check that both transcripts are in the same state
+        assert_eq!(
+            transcript_p.get_challenge(),
+            transcriptVar.get_challenge().unwrap().value().unwrap()
+        );
+    }
+}
@@ -0,0 +1,457 @@
+/// This file implements the onchain (Ethereum's EVM) decider circuit for ProtoGalaxy+CycleFold.
+/// For non-ethereum use cases, other more efficient approaches can be used.
+use ark_crypto_primitives::crh::poseidon::constraints::CRHParametersVar;
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::PrimeField;
+use ark_poly::Polynomial;
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    groups::GroupOpsBounds,
+    prelude::CurveVar,
+    ToConstraintFieldGadget,
+};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_std::{log2, Zero};
+use core::marker::PhantomData;
+
+use super::{circuits::CommittedInstanceVar, CommittedInstance, ProtoGalaxy, Witness};
+use crate::ccs::r1cs::R1CS;
+use crate::commitment::{pedersen::Params as PedersenParams, CommitmentScheme};
+use crate::folding::circuits::{nonnative::affine::NonNativeAffineVar, CF1, CF2};
+use crate::folding::hypernova::decider_eth_circuit::{evaluate_gadget, KZGChallengesGadget};
+use crate::folding::nova::{
+    decider_eth_circuit::{R1CSVar, RelaxedR1CSGadget},
+    CommittedInstance as CycleFoldCommittedInstance, Witness as CycleFoldWitness,
+};
+use crate::frontend::FCircuit;
+use crate::utils::{gadgets::MatrixGadget, vec::poly_from_vec};
+use crate::Error;
+
+/// Circuit that implements the in-circuit checks needed for the ProtoGalaxy's onchain (Ethereum's
+/// EVM) verification.
+#[derive(Clone, Debug)]
+pub struct DeciderEthCircuit<C1, GC1, C2, GC2, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    _c1: PhantomData<C1>,
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    _cs1: PhantomData<CS1>,
+    _cs2: PhantomData<CS2>,
+
+    /// E vector's length of the CycleFold instance witness
+    pub cf_E_len: usize,
+    /// R1CS of the Augmented Function circuit
+    pub r1cs: R1CS<C1::ScalarField>,
+    /// R1CS of the CycleFold circuit
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+    /// CycleFold PedersenParams over C2
+    pub cf_pedersen_params: PedersenParams<C2>,
+    pub poseidon_config: PoseidonConfig<CF1<C1>>,
+    pub i: Option<CF1<C1>>,
+    /// initial state
+    pub z_0: Option<Vec<C1::ScalarField>>,
+    /// current i-th state
+    pub z_i: Option<Vec<C1::ScalarField>>,
+    /// ProtoGalaxy instances
+    pub U_i: Option<CommittedInstance<C1>>,
+    pub W_i: Option<Witness<C1::ScalarField>>,
+    pub u_i: Option<CommittedInstance<C1>>,
+    pub w_i: Option<Witness<C1::ScalarField>>,
+    /// CycleFold running instance
+    pub cf_U_i: Option<CycleFoldCommittedInstance<C2>>,
+    pub cf_W_i: Option<CycleFoldWitness<C2>>,
+
+    /// KZG challenges, for the commitments U_i.phi and u_i.phi
+    pub kzg_challenges: Option<Vec<C1::ScalarField>>,
+    /// evaluations of the polynomials of W_i.w and w_i.w at the KZG challenges
+    pub kzg_evaluations: Option<Vec<C1::ScalarField>>,
+}
+
+impl<C1, GC1, C2, GC2, CS1, CS2> DeciderEthCircuit<C1, GC1, C2, GC2, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    CS1: CommitmentScheme<C1>,
+    // enforce that the CS2 is Pedersen commitment scheme, since we're at Ethereum's EVM decider
+    CS2: CommitmentScheme<C2, ProverParams = PedersenParams<C2>>,
+    <C1 as Group>::ScalarField: Absorb,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+{
+    pub fn from_protogalaxy<FC: FCircuit<C1::ScalarField>>(
+        protogalaxy: ProtoGalaxy<C1, GC1, C2, GC2, FC, CS1, CS2>,
+    ) -> Result<Self, Error> {
+        // compute the KZG challenges used as inputs in the circuit
+        let kzg_challenges = KZGChallengesGadget::<C1>::get_challenges_native(
+            &protogalaxy.poseidon_config,
+            &[protogalaxy.U_i.phi, protogalaxy.u_i.phi],
+        )?;
+
+        // get KZG evals
+        let kzg_evaluations = [&protogalaxy.W_i.w, &protogalaxy.w_i.w]
+            .iter()
+            .zip(kzg_challenges.iter())
+            .map(|(w, c)| Ok(poly_from_vec(w.to_vec())?.evaluate(c)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self {
+            _c1: PhantomData,
+            _gc1: PhantomData,
+            _c2: PhantomData,
+            _gc2: PhantomData,
+            _cs1: PhantomData,
+            _cs2: PhantomData,
+
+            cf_E_len: protogalaxy.cf_W_i.E.len(),
+            r1cs: protogalaxy.r1cs,
+            cf_r1cs: protogalaxy.cf_r1cs,
+            cf_pedersen_params: protogalaxy.cf_cs_params,
+            poseidon_config: protogalaxy.poseidon_config,
+            i: Some(protogalaxy.i),
+            z_0: Some(protogalaxy.z_0),
+            z_i: Some(protogalaxy.z_i),
+            U_i: Some(protogalaxy.U_i),
+            W_i: Some(protogalaxy.W_i),
+            u_i: Some(protogalaxy.u_i),
+            w_i: Some(protogalaxy.w_i),
+            cf_U_i: Some(protogalaxy.cf_U_i),
+            cf_W_i: Some(protogalaxy.cf_W_i),
+            kzg_challenges: Some(kzg_challenges),
+            kzg_evaluations: Some(kzg_evaluations),
+        })
+    }
+}
+
+impl<C1, GC1, C2, GC2, CS1, CS2> ConstraintSynthesizer<CF1<C1>>
+    for DeciderEthCircuit<C1, GC1, C2, GC2, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>>,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'b> &'b GC2: GroupOpsBounds<'b, C2, GC2>,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<CF1<C1>>) -> Result<(), SynthesisError> {
+        let t = log2(self.r1cs.A.n_rows) as usize;
+        let U_dummy_native = CommittedInstance::<C1>::dummy_running(self.r1cs.l, t);
+        let u_dummy_native = CommittedInstance::<C1>::dummy_incoming(self.r1cs.l);
+        let w_dummy_native =
+            Witness::<C1::ScalarField>::new(vec![
+                C1::ScalarField::zero();
+                self.r1cs.A.n_cols - self.r1cs.l - 1
+            ]);
+
+        let U_i_native = self.U_i.unwrap_or(U_dummy_native);
+        let u_i_native = self.u_i.unwrap_or(u_dummy_native);
+
+        // public inputs
+        let i =
+            FpVar::<CF1<C1>>::new_input(cs.clone(), || Ok(self.i.unwrap_or_else(CF1::<C1>::zero)))?;
+        let z_0 = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
+            Ok(self.z_0.unwrap_or(vec![CF1::<C1>::zero()]))
+        })?;
+        let z_i = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
+            Ok(self.z_i.unwrap_or(vec![CF1::<C1>::zero()]))
+        })?;
+        // the commitments of U_i & u_i are public inputs, since their openings at the KZG
+        // challenges are verified outside of the circuit
+        let U_i_phi = NonNativeAffineVar::<C1>::new_input(cs.clone(), || Ok(U_i_native.phi))?;
+        let u_i_phi = NonNativeAffineVar::<C1>::new_input(cs.clone(), || Ok(u_i_native.phi))?;
+        let kzg_challenges = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
+            Ok(self.kzg_challenges.unwrap_or(vec![CF1::<C1>::zero(); 2]))
+        })?;
+        let kzg_evaluations = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
+            Ok(self.kzg_evaluations.unwrap_or(vec![CF1::<C1>::zero(); 2]))
+        })?;
+
+        // witnesses
+        let U_i = CommittedInstanceVar::<C1> {
+            phi: U_i_phi,
+            betas: Vec::new_witness(cs.clone(), || Ok(U_i_native.betas.clone()))?,
+            e: FpVar::new_witness(cs.clone(), || Ok(U_i_native.e))?,
+            u: FpVar::new_witness(cs.clone(), || Ok(U_i_native.u))?,
+            x: Vec::new_witness(cs.clone(), || Ok(U_i_native.x.clone()))?,
+        };
+        // the incoming instance has e = 0 and u = 1, so only its public inputs are allocated
+        let u_i_x = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || Ok(u_i_native.x.clone()))?;
+        let W_i = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self.W_i.unwrap_or(w_dummy_native.clone()).w)
+        })?;
+        let w_i = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self.w_i.unwrap_or(w_dummy_native.clone()).w)
+        })?;
+
+        let crh_params = CRHParametersVar::<C1::ScalarField>::new_constant(
+            cs.clone(),
+            self.poseidon_config.clone(),
+        )?;
+
+        let r1cs =
+            R1CSVar::<C1::ScalarField, CF1<C1>, FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+                Ok(self.r1cs.clone())
+            })?;
+
+        // 1. check the ProtoGalaxy relation of U_i: e == \sum_j pow_j(betas) * f_j(z), where
+        // z = (u, x, W_i) and f_j(z) = (Az)_j * (Bz)_j - u * (Cz)_j
+        let z_U: Vec<FpVar<CF1<C1>>> = [vec![U_i.u.clone()], U_i.x.clone(), W_i.clone()].concat();
+        let Az = r1cs.A.mul_vector(&z_U)?;
+        let Bz = r1cs.B.mul_vector(&z_U)?;
+        let Cz = r1cs.C.mul_vector(&z_U)?;
+        let pows = pow_i_evals_gadget(&U_i.betas);
+        let computed_e: FpVar<CF1<C1>> = Az
+            .iter()
+            .zip(Bz.iter())
+            .zip(Cz.iter())
+            .zip(pows.iter())
+            .map(|(((a, b), c), p)| (a * b - &U_i.u * c) * p)
+            .sum();
+        computed_e.enforce_equal(&U_i.e)?;
+
+        // 2. check R1CS of u_i: Az∘Bz == Cz, where z = (1, x, w_i)
+        let z_u: Vec<FpVar<CF1<C1>>> = [vec![FpVar::one()], u_i_x.clone(), w_i.clone()].concat();
+        RelaxedR1CSGadget::check_native(
+            r1cs,
+            vec![FpVar::zero(); self.r1cs.A.n_rows],
+            FpVar::one(),
+            z_u,
+        )?;
+
+        // 3.a u_i.x[0] == H(i, z_0, z_i, U_i)
+        let (expected_u_i_x, _) =
+            U_i.clone()
+                .hash(&crh_params, i.clone(), z_0.clone(), z_i.clone())?;
+        (u_i_x[0]).enforce_equal(&expected_u_i_x)?;
+
+        #[cfg(feature = "light-test")]
+        println!("[WARNING]: Running with the 'light-test' feature, skipping the big part of the DeciderEthCircuit.\n           Only for testing purposes.");
+
+        // The following two checks (and their respective allocations) are disabled for normal
+        // tests since they take several millions of constraints and would take several minutes
+        // (and RAM) to run the test. It is active by default, and not active only when
+        // 'light-test' feature is used.
+        #[cfg(not(feature = "light-test"))]
+        {
+            // imports here instead of at the top of the file, so we avoid having multiple
+            // `#[cfg(not(test))]`
+            use crate::commitment::pedersen::PedersenGadget;
+            use crate::folding::circuits::nonnative::uint::NonNativeUintVar;
+            use crate::folding::nova::{
+                cyclefold::{CycleFoldCommittedInstanceVar, CF_IO_LEN},
+                decider_eth_circuit::CycleFoldWitnessVar,
+            };
+            use ark_r1cs_std::{boolean::Boolean, ToBitsGadget};
+
+            let cf_u_dummy_native = CycleFoldCommittedInstance::<C2>::dummy(CF_IO_LEN);
+            let w_dummy_native = CycleFoldWitness::<C2>::new(
+                vec![C2::ScalarField::zero(); self.cf_r1cs.A.n_cols - 1 - self.cf_r1cs.l],
+                self.cf_E_len,
+            );
+            let cf_U_i = CycleFoldCommittedInstanceVar::<C2, GC2>::new_witness(cs.clone(), || {
+                Ok(self.cf_U_i.unwrap_or_else(|| cf_u_dummy_native.clone()))
+            })?;
+            let cf_W_i = CycleFoldWitnessVar::<C2>::new_witness(cs.clone(), || {
+                Ok(self.cf_W_i.unwrap_or(w_dummy_native.clone()))
+            })?;
+
+            // 3.b u_i.x[1] == H(cf_U_i)
+            let (cf_u_i_x, _) = cf_U_i.clone().hash(&crh_params)?;
+            (u_i_x[1]).enforce_equal(&cf_u_i_x)?;
+
+            // 4. check Pedersen commitments of cf_U_i.{cmE, cmW}
+            let H = GC2::new_constant(cs.clone(), self.cf_pedersen_params.h)?;
+            let G = Vec::<GC2>::new_constant(cs.clone(), self.cf_pedersen_params.generators)?;
+            let cf_W_i_E_bits: Result<Vec<Vec<Boolean<CF1<C1>>>>, SynthesisError> =
+                cf_W_i.E.iter().map(|E_i| E_i.to_bits_le()).collect();
+            let cf_W_i_W_bits: Result<Vec<Vec<Boolean<CF1<C1>>>>, SynthesisError> =
+                cf_W_i.W.iter().map(|W_i| W_i.to_bits_le()).collect();
+
+            let computed_cmE = PedersenGadget::<C2, GC2>::commit(
+                H.clone(),
+                G.clone(),
+                cf_W_i_E_bits?,
+                cf_W_i.rE.to_bits_le()?,
+            )?;
+            cf_U_i.cmE.enforce_equal(&computed_cmE)?;
+            let computed_cmW =
+                PedersenGadget::<C2, GC2>::commit(H, G, cf_W_i_W_bits?, cf_W_i.rW.to_bits_le()?)?;
+            cf_U_i.cmW.enforce_equal(&computed_cmW)?;
+
+            let cf_r1cs =
+                R1CSVar::<C1::BaseField, CF1<C1>, NonNativeUintVar<CF1<C1>>>::new_witness(
+                    cs.clone(),
+                    || Ok(self.cf_r1cs.clone()),
+                )?;
+
+            // 5. check RelaxedR1CS of cf_U_i
+            let cf_z_U = [vec![cf_U_i.u.clone()], cf_U_i.x.to_vec(), cf_W_i.W.to_vec()].concat();
+            RelaxedR1CSGadget::check_nonnative(cf_r1cs, cf_W_i.E, cf_U_i.u.clone(), cf_z_U)?;
+        }
+
+        // 6. check KZG challenges
+        let incircuit_challenges = KZGChallengesGadget::<C1>::get_challenges_gadget(
+            cs.clone(),
+            &self.poseidon_config,
+            &[U_i.phi.clone(), u_i_phi],
+        )?;
+        incircuit_challenges.enforce_equal(&kzg_challenges)?;
+
+        // 7. check that the KZG evaluations match the polynomials of W_i & w_i at the challenges,
+        // so that the witnesses used in the checks 1 & 2 are the ones committed in U_i.phi &
+        // u_i.phi
+        for ((w, challenge), eval) in [W_i, w_i]
+            .into_iter()
+            .zip(incircuit_challenges)
+            .zip(kzg_evaluations.iter())
+        {
+            let incircuit_eval = evaluate_gadget::<CF1<C1>>(w, challenge)?;
+            incircuit_eval.enforce_equal(eval)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the evaluations of pow_j(betas) for all the j \in [0, 2^{|betas|}), where
+/// pow_j(betas) = \prod_{k: j_k = 1} betas_k, with j_k the k-th bit of j in little-endian, matching
+/// the native `pow_i` used in `folding.rs`.
+fn pow_i_evals_gadget<F: PrimeField>(betas: &[FpVar<F>]) -> Vec<FpVar<F>> {
+    let mut evals = vec![FpVar::<F>::one()];
+    for beta_k in betas.iter() {
+        let hi: Vec<FpVar<F>> = evals.iter().map(|e| e * beta_k).collect();
+        evals.extend(hi);
+    }
+    evals
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_pallas::{constraints::GVar, Fr, Projective};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::UniformRand;
+    use ark_vesta::{constraints::GVar as GVar2, Projective as Projective2};
+
+    use crate::commitment::pedersen::Pedersen;
+    use crate::folding::protogalaxy::{
+        folding::pow_i, get_cs_params_len, ProverParams, VerifierParams,
+    };
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_canonical_config;
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_decider_circuit() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let z_0 = vec![Fr::from(3_u32)];
+
+        // get the CS & CF_CS len
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+                1,
+            )
+            .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config: poseidon_config.clone(),
+                cs_params: pedersen_params,
+                cf_cs_params: cf_pedersen_params,
+                k: 1,
+            };
+
+        type PG = ProtoGalaxy<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >;
+
+        // generate a ProtoGalaxy instance and do three steps of it, so the running instance is
+        // not the dummy one
+        let mut protogalaxy = PG::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        protogalaxy.prove_step(&mut rng, vec![]).unwrap();
+        protogalaxy.prove_step(&mut rng, vec![]).unwrap();
+        protogalaxy.prove_step(&mut rng, vec![]).unwrap();
+        let ivc_v = protogalaxy.clone();
+        let verifier_params = VerifierParams::<Projective, Projective2> {
+            poseidon_config: poseidon_config.clone(),
+            r1cs: ivc_v.clone().r1cs,
+            cf_r1cs: ivc_v.clone().cf_r1cs,
+        };
+        let (running_instance, incoming_instance, cyclefold_instance) = ivc_v.instances();
+        PG::verify(
+            verifier_params,
+            z_0,
+            ivc_v.z_i,
+            Fr::from(3_u32),
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+        )
+        .unwrap();
+
+        // load the DeciderEthCircuit from the generated ProtoGalaxy instance
+        let decider_circuit = DeciderEthCircuit::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >::from_protogalaxy(protogalaxy)
+        .unwrap();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+
+        // generate the constraints and check that are satisfied by the inputs
+        decider_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_pow_i_evals_gadget() {
+        let mut rng = ark_std::test_rng();
+        let betas: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(4)
+            .collect();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let betasVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(betas.clone())).unwrap();
+        let evals = pow_i_evals_gadget(&betasVar);
+
+        assert_eq!(evals.len(), 1 << betas.len());
+        for (j, eval) in evals.iter().enumerate() {
+            assert_eq!(eval.value().unwrap(), pow_i(j, &betas));
+        }
+    }
+}
@@ -31,7 +31,7 @@ pub struct Folding<C: CurveGroup> {
 impl<C: CurveGroup> Folding<C>
 where
     <C as Group>::ScalarField: Absorb,
-    <C as CurveGroup>::BaseField: Absorb,
+    <C as CurveGroup>::BaseField: Absorb + PrimeField,
 {
     #![allow(clippy::type_complexity)]
     /// implements the non-interactive Prover from the folding scheme described in section 4
@@ -50,6 +50,7 @@ where
             Witness<C::ScalarField>,
             Vec<C::ScalarField>, // F_X coeffs
             Vec<C::ScalarField>, // K_X coeffs
+            Vec<C::ScalarField>, // L_X evaluations at gamma
         ),
         Error,
     > {
@@ -65,15 +66,18 @@ where
         let k = vec_instances.len();
         let t = instance.betas.len();
         let n = r1cs.A.n_cols;
-        if w.w.len() != n {
+        let m = r1cs.A.n_rows;
+
+        let z = get_z(instance, w);
+        if z.len() != n {
             return Err(Error::NotSameLength(
-                "w.w.len()".to_string(),
-                w.w.len(),
+                "z.len()".to_string(),
+                z.len(),
                 "n".to_string(),
                 n,
             ));
         }
-        if log2(n) as usize != t {
+        if log2(m) as usize != t {
             return Err(Error::NotEqual);
         }
         if !(k + 1).is_power_of_two() {
@@ -89,13 +93,18 @@ where
         let delta = transcript.get_challenge();
         let deltas = exponential_powers(delta, t);
 
-        let f_w = eval_f(r1cs, &w.w)?;
+        let mut f_z = eval_f(r1cs, &z)?;
+        // pad f(z) with zeroes, so that the binary tree is full
+        f_z.resize(1 << t, C::ScalarField::zero());
 
         // F(X)
         let F_X: SparsePolynomial<C::ScalarField> =
-            calc_f_from_btree(&f_w, &instance.betas, &deltas).expect("Error calculating F[x]");
-        let F_X_dense = DensePolynomial::from(F_X.clone());
-        transcript.absorb_vec(&F_X_dense.coeffs);
+            calc_f_from_btree(&f_z, &instance.betas, &deltas).expect("Error calculating F[x]");
+        // pad the coefficients to t+1, since `DensePolynomial` trims the trailing zeroes, and we
+        // want the F(X) coefficients to always have the same length (needed by the circuit)
+        let mut F_coeffs = DensePolynomial::from(F_X.clone()).coeffs;
+        F_coeffs.resize(t + 1, C::ScalarField::zero());
+        transcript.absorb_vec(&F_coeffs);
 
         let alpha = transcript.get_challenge();
 
@@ -108,30 +117,32 @@ where
         // sanity check: check that the new randomized instance (the original instance but with
         // 'refreshed' randomness) satisfies the relation.
         #[cfg(test)]
-        tests::check_instance(
+        check_instance(
             r1cs,
             &CommittedInstance {
-                phi: instance.phi,
                 betas: betas_star.clone(),
                 e: F_alpha,
+                ..instance.clone()
             },
             w,
         )?;
 
-        let ws: Vec<Vec<C::ScalarField>> = std::iter::once(w.w.clone())
+        let zs: Vec<Vec<C::ScalarField>> = std::iter::once(z.clone())
             .chain(
-                vec_w
+                vec_instances
                     .iter()
-                    .map(|wj| {
-                        if wj.w.len() != n {
+                    .zip(vec_w)
+                    .map(|(uj, wj)| {
+                        let zj = get_z(uj, wj);
+                        if zj.len() != n {
                             return Err(Error::NotSameLength(
-                                "wj.w.len()".to_string(),
-                                wj.w.len(),
+                                "zj.len()".to_string(),
+                                zj.len(),
                                 "n".to_string(),
                                 n,
                             ));
                         }
-                        Ok(wj.w.clone())
+                        Ok(zj)
                     })
                     .collect::<Result<Vec<Vec<C::ScalarField>>, Error>>()?,
             )
@@ -148,17 +159,17 @@ where
         let mut G_evals: Vec<C::ScalarField> = vec![C::ScalarField::zero(); G_domain.size()];
         for (hi, h) in G_domain.elements().enumerate() {
             // each iteration evaluates G(h)
-            // inner = L_0(x) * w + \sum_k L_i(x) * w_j
-            let mut inner: Vec<C::ScalarField> = vec![C::ScalarField::zero(); ws[0].len()];
-            for (i, w) in ws.iter().enumerate() {
-                // Li_w_h = (Li(X)*wj)(h) = Li(h) * wj
-                let mut Liw_h: Vec<C::ScalarField> = vec![C::ScalarField::zero(); w.len()];
-                for (j, wj) in w.iter().enumerate() {
-                    Liw_h[j] = (&L_X[i] * *wj).evaluate(&h);
+            // inner = L_0(x) * z + \sum_k L_i(x) * z_j
+            let mut inner: Vec<C::ScalarField> = vec![C::ScalarField::zero(); zs[0].len()];
+            for (i, z) in zs.iter().enumerate() {
+                // Li_z_h = (Li(X)*zj)(h) = Li(h) * zj
+                let mut Liz_h: Vec<C::ScalarField> = vec![C::ScalarField::zero(); z.len()];
+                for (j, zj) in z.iter().enumerate() {
+                    Liz_h[j] = (&L_X[i] * *zj).evaluate(&h);
                 }
 
                 for j in 0..inner.len() {
-                    inner[j] += Liw_h[j];
+                    inner[j] += Liz_h[j];
                 }
             }
             let f_ev = eval_f(r1cs, &inner)?;
@@ -187,21 +198,30 @@ where
             return Err(Error::ProtoGalaxy(ProtoGalaxyError::RemainderNotZero));
         }
 
-        transcript.absorb_vec(&K_X.coeffs);
+        // deg(K) = d*k - (k+1), pad the coefficients for the same reason as with F(X)
+        let mut K_coeffs = K_X.coeffs.clone();
+        K_coeffs.resize(d * k - k, C::ScalarField::zero());
+        transcript.absorb_vec(&K_coeffs);
 
         let gamma = transcript.get_challenge();
 
-        let e_star =
-            F_alpha * L_X[0].evaluate(&gamma) + Z_X.evaluate(&gamma) * K_X.evaluate(&gamma);
+        let L_X_evals = L_X
+            .iter()
+            .map(|L| L.evaluate(&gamma))
+            .collect::<Vec<C::ScalarField>>();
 
-        let mut phi_star: C = instance.phi * L_X[0].evaluate(&gamma);
-        for i in 0..k {
-            phi_star += vec_instances[i].phi * L_X[i + 1].evaluate(&gamma);
-        }
-        let mut w_star: Vec<C::ScalarField> = vec_scalar_mul(&w.w, &L_X[0].evaluate(&gamma));
-        let mut r_w_star: C::ScalarField = w.r_w * L_X[0].evaluate(&gamma);
+        let e_star = F_alpha * L_X_evals[0] + Z_X.evaluate(&gamma) * K_X.evaluate(&gamma);
+
+        let mut phi_star: C = instance.phi * L_X_evals[0];
+        let mut u_star: C::ScalarField = instance.u * L_X_evals[0];
+        let mut x_star: Vec<C::ScalarField> = vec_scalar_mul(&instance.x, &L_X_evals[0]);
+        let mut w_star: Vec<C::ScalarField> = vec_scalar_mul(&w.w, &L_X_evals[0]);
+        let mut r_w_star: C::ScalarField = w.r_w * L_X_evals[0];
         for i in 0..k {
-            let L_X_at_i1 = L_X[i + 1].evaluate(&gamma);
+            let L_X_at_i1 = L_X_evals[i + 1];
+            phi_star += vec_instances[i].phi * L_X_at_i1;
+            u_star += vec_instances[i].u * L_X_at_i1;
+            x_star = vec_add(&x_star, &vec_scalar_mul(&vec_instances[i].x, &L_X_at_i1))?;
             w_star = vec_add(&w_star, &vec_scalar_mul(&vec_w[i].w, &L_X_at_i1))?;
             r_w_star += vec_w[i].r_w * L_X_at_i1;
         }
@@ -211,13 +231,16 @@ where
                 betas: betas_star,
                 phi: phi_star,
                 e: e_star,
+                u: u_star,
+                x: x_star,
             },
             Witness {
                 w: w_star,
                 r_w: r_w_star,
             },
-            F_X_dense.coeffs,
-            K_X.coeffs,
+            F_coeffs,
+            K_coeffs,
+            L_X_evals,
         ))
     }
 
@@ -234,7 +257,9 @@ where
         K_coeffs: Vec<C::ScalarField>,
     ) -> Result<CommittedInstance<C>, Error> {
         let t = instance.betas.len();
-        let n = r1cs.A.n_cols;
+        if log2(r1cs.A.n_rows) as usize != t {
+            return Err(Error::NotEqual);
+        }
 
         // absorb the committed instances
         transcript.absorb_committed_instance(instance)?;
@@ -248,7 +273,7 @@ where
         transcript.absorb_vec(&F_coeffs);
 
         let alpha = transcript.get_challenge();
-        let alphas = all_powers(alpha, n);
+        let alphas = all_powers(alpha, F_coeffs.len());
 
         // F(alpha) = e + \sum_t F_i * alpha^i
         let mut F_alpha = instance.e;
@@ -263,19 +288,30 @@ where
             GeneralEvaluationDomain::<C::ScalarField>::new(k + 1).ok_or(Error::NewDomainFail)?;
         let L_X: Vec<DensePolynomial<C::ScalarField>> = lagrange_polys(H);
         let Z_X: DensePolynomial<C::ScalarField> = H.vanishing_polynomial().into();
+
+        transcript.absorb_vec(&K_coeffs);
         let K_X: DensePolynomial<C::ScalarField> =
             DensePolynomial::<C::ScalarField>::from_coefficients_vec(K_coeffs);
 
-        transcript.absorb_vec(&K_X.coeffs);
-
         let gamma = transcript.get_challenge();
 
-        let e_star =
-            F_alpha * L_X[0].evaluate(&gamma) + Z_X.evaluate(&gamma) * K_X.evaluate(&gamma);
+        let L_X_evals = L_X
+            .iter()
+            .map(|L| L.evaluate(&gamma))
+            .collect::<Vec<C::ScalarField>>();
+
+        let e_star = F_alpha * L_X_evals[0] + Z_X.evaluate(&gamma) * K_X.evaluate(&gamma);
 
-        let mut phi_star: C = instance.phi * L_X[0].evaluate(&gamma);
+        let mut phi_star: C = instance.phi * L_X_evals[0];
+        let mut u_star: C::ScalarField = instance.u * L_X_evals[0];
+        let mut x_star: Vec<C::ScalarField> = vec_scalar_mul(&instance.x, &L_X_evals[0]);
         for i in 0..k {
-            phi_star += vec_instances[i].phi * L_X[i + 1].evaluate(&gamma);
+            phi_star += vec_instances[i].phi * L_X_evals[i + 1];
+            u_star += vec_instances[i].u * L_X_evals[i + 1];
+            x_star = vec_add(
+                &x_star,
+                &vec_scalar_mul(&vec_instances[i].x, &L_X_evals[i + 1]),
+            )?;
         }
 
         // return the folded instance
@@ -283,12 +319,51 @@ where
             betas: betas_star,
             phi: phi_star,
             e: e_star,
+            u: u_star,
+            x: x_star,
         })
     }
 }
 
+// returns z = (u, x, w), where u takes the role of the constant 1 of the R1CS
+fn get_z<C: CurveGroup>(
+    instance: &CommittedInstance<C>,
+    w: &Witness<C::ScalarField>,
+) -> Vec<C::ScalarField> {
+    [vec![instance.u], instance.x.clone(), w.w.clone()].concat()
+}
+
+/// checks the ProtoGalaxy relation for the given running instance and witness, ie. that
+/// e == \sum_i pow_i(betas) * f_i(z), where z = (u, x, w)
+pub(crate) fn check_instance<C: CurveGroup>(
+    r1cs: &R1CS<C::ScalarField>,
+    instance: &CommittedInstance<C>,
+    w: &Witness<C::ScalarField>,
+) -> Result<(), Error> {
+    let t = log2(r1cs.A.n_rows) as usize;
+    if instance.betas.len() != t {
+        return Err(Error::NotSameLength(
+            "instance.betas.len()".to_string(),
+            instance.betas.len(),
+            "log2(r1cs.A.n_rows)".to_string(),
+            t,
+        ));
+    }
+
+    let f_z = eval_f(r1cs, &get_z(instance, w))?; // f(z)
+
+    let mut r = C::ScalarField::zero();
+    for (i, f_z_i) in f_z.iter().enumerate() {
+        r += pow_i(i, &instance.betas) * f_z_i;
+    }
+    if instance.e == r {
+        return Ok(());
+    }
+    Err(Error::NotSatisfied)
+}
+
 // naive impl of pow_i for betas, assuming that betas=(b, b^2, b^4, ..., b^{2^{t-1}})
-fn pow_i<F: PrimeField>(i: usize, betas: &[F]) -> F {
+pub(crate) fn pow_i<F: PrimeField>(i: usize, betas: &[F]) -> F {
     // WIP check if makes more sense to do it with ifs instead of arithmetic
 
     let n = 2_u64.pow(betas.len() as u32);
@@ -356,7 +431,9 @@ fn calc_f_from_btree<F: PrimeField>(
 }
 
 // lagrange_polys method from caulk: https://github.com/caulk-crypto/caulk/tree/8210b51fb8a9eef4335505d1695c44ddc7bf8170/src/multi/setup.rs#L300
-fn lagrange_polys<F: PrimeField>(domain_n: GeneralEvaluationDomain<F>) -> Vec<DensePolynomial<F>> {
+pub(crate) fn lagrange_polys<F: PrimeField>(
+    domain_n: GeneralEvaluationDomain<F>,
+) -> Vec<DensePolynomial<F>> {
     let mut lagrange_polynomials: Vec<DensePolynomial<F>> = Vec::new();
     for i in 0..domain_n.size() {
         let evals: Vec<F> = cfg_into_iter!(0..domain_n.size())
@@ -367,14 +444,17 @@ fn lagrange_polys<F: PrimeField>(domain_n: GeneralEvaluationDomain<F>) -> Vec<De
     lagrange_polynomials
 }
 
-// f(w) in R1CS context. For the moment we use R1CS, in the future we will abstract this with a
-// trait
-fn eval_f<F: PrimeField>(r1cs: &R1CS<F>, w: &[F]) -> Result<Vec<F>, Error> {
-    let Az = mat_vec_mul(&r1cs.A, w)?;
-    let Bz = mat_vec_mul(&r1cs.B, w)?;
-    let Cz = mat_vec_mul(&r1cs.C, w)?;
+// f(z) in R1CS context, where z = (u, x, w). For the moment we use R1CS, in the future we will
+// abstract this with a trait.
+// Notice that we use the homogeneous version of the R1CS relation, Az∘Bz - u*Cz, where u is
+// z[0]. For the incoming instances u=1, so it matches the plain R1CS relation, while it allows the
+// all-zeroes dummy running instance to satisfy the relation.
+fn eval_f<F: PrimeField>(r1cs: &R1CS<F>, z: &[F]) -> Result<Vec<F>, Error> {
+    let Az = mat_vec_mul(&r1cs.A, z)?;
+    let Bz = mat_vec_mul(&r1cs.B, z)?;
+    let Cz = mat_vec_mul(&r1cs.C, z)?;
     let AzBz = hadamard(&Az, &Bz)?;
-    vec_sub(&AzBz, &Cz)
+    vec_sub(&AzBz, &vec_scalar_mul(&Cz, &z[0]))
 }
 
 #[cfg(test)]
@@ -385,34 +465,9 @@ mod tests {
 
     use crate::ccs::r1cs::tests::{get_test_r1cs, get_test_z};
     use crate::commitment::{pedersen::Pedersen, CommitmentScheme};
+    use crate::transcript::keccak::KeccakTranscript;
     use crate::transcript::poseidon::{poseidon_canonical_config, PoseidonTranscript};
 
-    pub(crate) fn check_instance<C: CurveGroup>(
-        r1cs: &R1CS<C::ScalarField>,
-        instance: &CommittedInstance<C>,
-        w: &Witness<C::ScalarField>,
-    ) -> Result<(), Error> {
-        if instance.betas.len() != log2(w.w.len()) as usize {
-            return Err(Error::NotSameLength(
-                "instance.betas.len()".to_string(),
-                instance.betas.len(),
-                "log2(w.w.len())".to_string(),
-                log2(w.w.len()) as usize,
-            ));
-        }
-
-        let f_w = eval_f(r1cs, &w.w)?; // f(w)
-
-        let mut r = C::ScalarField::zero();
-        for (i, f_w_i) in f_w.iter().enumerate() {
-            r += pow_i(i, &instance.betas) * f_w_i;
-        }
-        if instance.e == r {
-            return Ok(());
-        }
-        Err(Error::NotSatisfied)
-    }
-
     #[test]
     fn test_pow_i() {
         let mut rng = ark_std::test_rng();
@@ -454,6 +509,7 @@ mod tests {
         let mut rng = ark_std::test_rng();
         let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, 100).unwrap(); // 100 is wip, will get it from actual vec
 
+        let r1cs = get_test_r1cs::<Fr>();
         let z = get_test_z::<Fr>(3);
         let mut zs: Vec<Vec<Fr>> = Vec::new();
         for i in 0..k {
@@ -461,14 +517,13 @@ mod tests {
             zs.push(z_i);
         }
 
-        let n = z.len();
-        let t = log2(n) as usize;
+        let t = log2(r1cs.A.n_rows) as usize;
 
         let beta = Fr::rand(&mut rng);
         let betas = exponential_powers(beta, t);
 
         let witness = Witness::<Fr> {
-            w: z.clone(),
+            w: z[r1cs.l + 1..].to_vec(),
             r_w: Fr::rand(&mut rng),
         };
         let phi = Pedersen::<Projective, true>::commit(&pedersen_params, &witness.w, &witness.r_w)
@@ -477,6 +532,8 @@ mod tests {
             phi,
             betas: betas.clone(),
             e: Fr::zero(),
+            u: z[0],
+            x: z[1..r1cs.l + 1].to_vec(),
         };
         // same for the other instances
         let mut witnesses: Vec<Witness<Fr>> = Vec::new();
@@ -484,7 +541,7 @@ mod tests {
         #[allow(clippy::needless_range_loop)]
         for i in 0..k {
             let witness_i = Witness::<Fr> {
-                w: zs[i].clone(),
+                w: zs[i][r1cs.l + 1..].to_vec(),
                 r_w: Fr::rand(&mut rng),
             };
             let phi_i = Pedersen::<Projective, true>::commit(
@@ -495,8 +552,10 @@ mod tests {
             .unwrap();
             let instance_i = CommittedInstance::<Projective> {
                 phi: phi_i,
-                betas: betas.clone(),
+                betas: vec![],
                 e: Fr::zero(),
+                u: zs[i][0],
+                x: zs[i][1..r1cs.l + 1].to_vec(),
             };
             witnesses.push(witness_i);
             instances.push(instance_i);
@@ -516,16 +575,61 @@ mod tests {
         let mut transcript_p = PoseidonTranscript::<Projective>::new(&poseidon_config);
         let mut transcript_v = PoseidonTranscript::<Projective>::new(&poseidon_config);
 
-        let (folded_instance, folded_witness, F_coeffs, K_coeffs) = Folding::<Projective>::prove(
-            &mut transcript_p,
+        let (folded_instance, folded_witness, F_coeffs, K_coeffs, _) =
+            Folding::<Projective>::prove(
+                &mut transcript_p,
+                &r1cs,
+                &instance,
+                &witness,
+                &instances,
+                &witnesses,
+            )
+            .unwrap();
+
+        // verifier
+        let folded_instance_v = Folding::<Projective>::verify(
+            &mut transcript_v,
             &r1cs,
             &instance,
-            &witness,
             &instances,
-            &witnesses,
+            F_coeffs,
+            K_coeffs,
         )
         .unwrap();
 
+        // check that prover & verifier folded instances are the same values
+        assert_eq!(folded_instance.phi, folded_instance_v.phi);
+        assert_eq!(folded_instance.betas, folded_instance_v.betas);
+        assert_eq!(folded_instance.e, folded_instance_v.e);
+        assert_eq!(folded_instance.u, folded_instance_v.u);
+        assert_eq!(folded_instance.x, folded_instance_v.x);
+        assert!(!folded_instance.e.is_zero());
+
+        // check that the folded instance satisfies the relation
+        check_instance(&r1cs, &folded_instance, &folded_witness).unwrap();
+    }
+
+    #[test]
+    fn test_fold_native_case_keccak() {
+        let k = 7;
+        let (witness, instance, witnesses, instances) = prepare_inputs(k);
+        let r1cs = get_test_r1cs::<Fr>();
+
+        // init Prover & Verifier's transcript
+        let mut transcript_p = KeccakTranscript::<Projective>::new(&());
+        let mut transcript_v = KeccakTranscript::<Projective>::new(&());
+
+        let (folded_instance, folded_witness, F_coeffs, K_coeffs, _) =
+            Folding::<Projective>::prove(
+                &mut transcript_p,
+                &r1cs,
+                &instance,
+                &witness,
+                &instances,
+                &witnesses,
+            )
+            .unwrap();
+
         // verifier
         let folded_instance_v = Folding::<Projective>::verify(
             &mut transcript_v,
@@ -541,7 +645,8 @@ mod tests {
         assert_eq!(folded_instance.phi, folded_instance_v.phi);
         assert_eq!(folded_instance.betas, folded_instance_v.betas);
         assert_eq!(folded_instance.e, folded_instance_v.e);
-        assert!(!folded_instance.e.is_zero());
+        assert_eq!(folded_instance.u, folded_instance_v.u);
+        assert_eq!(folded_instance.x, folded_instance_v.x);
 
         // check that the folded instance satisfies the relation
         check_instance(&r1cs, &folded_instance, &folded_witness).unwrap();
@@ -565,7 +670,7 @@ mod tests {
             // generate the instances to be fold
             let (_, _, witnesses, instances) = prepare_inputs(k);
 
-            let (folded_instance, folded_witness, F_coeffs, K_coeffs) =
+            let (folded_instance, folded_witness, F_coeffs, K_coeffs, _) =
                 Folding::<Projective>::prove(
                     &mut transcript_p,
                     &r1cs,
@@ -591,6 +696,8 @@ mod tests {
             assert_eq!(folded_instance.phi, folded_instance_v.phi);
             assert_eq!(folded_instance.betas, folded_instance_v.betas);
             assert_eq!(folded_instance.e, folded_instance_v.e);
+            assert_eq!(folded_instance.u, folded_instance_v.u);
+            assert_eq!(folded_instance.x, folded_instance_v.x);
             assert!(!folded_instance.e.is_zero());
 
             // check that the folded instance satisfies the relation
@@ -1,26 +1,149 @@
 /// Implements the scheme described in [ProtoGalaxy](https://eprint.iacr.org/2023/1106.pdf)
-use ark_ec::CurveGroup;
-use ark_ff::PrimeField;
-use thiserror::Error;
+use ark_crypto_primitives::{
+    crh::{poseidon::CRH, CRHScheme},
+    sponge::{poseidon::PoseidonConfig, Absorb},
+};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, Write};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::{log2, One, Zero};
+use core::marker::PhantomData;
 
+pub mod circuits;
+pub mod decider_eth;
+pub mod decider_eth_circuit;
 pub mod folding;
 pub mod traits;
 pub(crate) mod utils;
 
-#[derive(Clone, Debug)]
+use circuits::AugmentedFCircuit;
+use folding::{check_instance, Folding};
+
+use crate::ccs::r1cs::{extract_w_x, R1CS};
+use crate::commitment::CommitmentScheme;
+use crate::folding::checkpoint::Checkpoint;
+use crate::folding::circuits::{nonnative::affine::nonnative_affine_to_field_elements, CF2};
+use crate::folding::nova::{
+    cyclefold::{fold_cyclefold_circuit, CycleFoldCircuit},
+    get_cm_coordinates, get_r1cs_from_cs,
+    traits::NovaR1CS,
+    CommittedInstance as CycleFoldCommittedInstance, Witness as CycleFoldWitness,
+};
+use crate::frontend::FCircuit;
+use crate::transcript::{poseidon::PoseidonTranscript, Transcript};
+use crate::utils::{hash_bytes, serialize_poseidon_config};
+use crate::Error;
+use crate::FoldingScheme;
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct CommittedInstance<C: CurveGroup> {
-    phi: C,
-    betas: Vec<C::ScalarField>,
-    e: C::ScalarField,
+    pub phi: C,
+    pub betas: Vec<C::ScalarField>,
+    pub e: C::ScalarField,
+    // relaxation factor, it plays the role of the constant 1 in z = (u, x, w), so that the dummy
+    // (all zeroes) running instance satisfies the relation. Incoming instances have u = 1.
+    pub u: C::ScalarField,
+    pub x: Vec<C::ScalarField>,
 }
 
-#[derive(Clone, Debug)]
+impl<C: CurveGroup> CommittedInstance<C> {
+    /// returns a dummy running instance (all zeroes), which satisfies the relation for the
+    /// all-zeroes witness
+    pub fn dummy_running(io_len: usize, t: usize) -> Self {
+        Self {
+            phi: C::zero(),
+            betas: vec![C::ScalarField::zero(); t],
+            e: C::ScalarField::zero(),
+            u: C::ScalarField::zero(),
+            x: vec![C::ScalarField::zero(); io_len],
+        }
+    }
+
+    /// returns a dummy incoming instance, which is used as placeholder for the IVC base case
+    pub fn dummy_incoming(io_len: usize) -> Self {
+        Self {
+            phi: C::zero(),
+            betas: vec![],
+            e: C::ScalarField::zero(),
+            u: C::ScalarField::one(),
+            x: vec![C::ScalarField::zero(); io_len],
+        }
+    }
+}
+
+impl<C: CurveGroup> CommittedInstance<C>
+where
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    /// returns the field elements of the instance, in the same order as in
+    /// CommittedInstanceVar.to_constraint_field
+    pub fn to_field_elements(&self) -> Result<Vec<C::ScalarField>, Error> {
+        let (phi_x, phi_y) = nonnative_affine_to_field_elements::<C>(self.phi)?;
+        Ok([
+            phi_x,
+            phi_y,
+            self.betas.clone(),
+            vec![self.e, self.u],
+            self.x.clone(),
+        ]
+        .concat())
+    }
+
+    /// hash implements the committed instance hash compatible with the gadget implemented in
+    /// protogalaxy/circuits.rs::CommittedInstanceVar.hash.
+    /// Returns `H(i, z_0, z_i, U_i)`, where `i` can be `i` but also `i+1`, and `U_i` is the
+    /// `CommittedInstance`.
+    pub fn hash(
+        &self,
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        i: C::ScalarField,
+        z_0: Vec<C::ScalarField>,
+        z_i: Vec<C::ScalarField>,
+    ) -> Result<C::ScalarField, Error> {
+        CRH::<C::ScalarField>::evaluate(
+            poseidon_config,
+            [vec![i], z_0, z_i, self.to_field_elements()?].concat(),
+        )
+        .map_err(|e| Error::Other(e.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Witness<F: PrimeField> {
-    w: Vec<F>,
-    r_w: F,
+    pub w: Vec<F>,
+    pub r_w: F,
 }
 
-#[derive(Debug, Error, PartialEq)]
+impl<F: PrimeField> Witness<F> {
+    pub fn new(w: Vec<F>) -> Self {
+        // note: at the current version, we don't use the blinding factors and we set them to 0
+        // always.
+        Self { w, r_w: F::zero() }
+    }
+
+    /// commits to the witness, returning the incoming CommittedInstance for the given public
+    /// inputs `x`
+    pub fn commit<C: CurveGroup<ScalarField = F>, CS: CommitmentScheme<C>>(
+        &self,
+        params: &CS::ProverParams,
+        x: Vec<F>,
+    ) -> Result<CommittedInstance<C>, Error> {
+        let phi = CS::commit(params, &self.w, &self.r_w)?;
+        Ok(CommittedInstance {
+            phi,
+            betas: vec![],
+            e: F::zero(),
+            u: F::one(),
+            x,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
 pub enum ProtoGalaxyError {
     #[error("The remainder from G(X)-F(α)*L_0(X)) / Z(X) should be zero")]
     RemainderNotZero,
@@ -33,3 +156,922 @@ pub enum ProtoGalaxyError {
     #[error("The lengths of β and δ do not equal: |β| = {0}, |δ|={0}")]
     WrongLenBetas(usize, usize),
 }
+
+#[derive(Debug, Clone)]
+pub struct ProverParams<C1, C2, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    pub cs_params: CS1::ProverParams,
+    pub cf_cs_params: CS2::ProverParams,
+    // number of incoming instances folded at each IVC step, including the incoming instance
+    // u_i. k+1 must be a power of two.
+    pub k: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifierParams<C1: CurveGroup, C2: CurveGroup> {
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    pub r1cs: R1CS<C1::ScalarField>,
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+}
+
+/// Implements ProtoGalaxy+CycleFold's IVC, described in
+/// [ProtoGalaxy](https://eprint.iacr.org/2023/1106.pdf) and
+/// [CycleFold](https://eprint.iacr.org/2023/1192.pdf), following the FoldingScheme trait
+#[derive(Clone, Debug)]
+pub struct ProtoGalaxy<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    /// R1CS of the Augmented Function circuit
+    pub r1cs: R1CS<C1::ScalarField>,
+    /// R1CS of the CycleFold circuit
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    /// CommitmentScheme::ProverParams over C1
+    pub cs_params: CS1::ProverParams,
+    /// CycleFold CommitmentScheme::ProverParams, over C2
+    pub cf_cs_params: CS2::ProverParams,
+    /// F circuit, the circuit that is being folded
+    pub F: FC,
+    /// number of incoming instances folded at each step
+    pub k: usize,
+    pub i: C1::ScalarField,
+    /// initial state
+    pub z_0: Vec<C1::ScalarField>,
+    /// current i-th state
+    pub z_i: Vec<C1::ScalarField>,
+    /// ProtoGalaxy instances
+    pub w_i: Witness<C1::ScalarField>,
+    pub u_i: CommittedInstance<C1>,
+    pub W_i: Witness<C1::ScalarField>,
+    pub U_i: CommittedInstance<C1>,
+
+    /// CycleFold running instance
+    pub cf_W_i: CycleFoldWitness<C2>,
+    pub cf_U_i: CycleFoldCommittedInstance<C2>,
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> ProtoGalaxy<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    /// returns the R1CS of the AugmentedFCircuit and of the CycleFold circuit.
+    /// Notice that since the CycleFold circuits used by ProtoGalaxy take as scalars the Lagrange
+    /// polynomial evaluations, which are elements of the full C1::ScalarField, the r_bits of the
+    /// CycleFold circuit have MODULUS_BIT_SIZE length.
+    #[allow(clippy::type_complexity)]
+    pub fn get_r1cs(
+        poseidon_config: &PoseidonConfig<C1::ScalarField>,
+        F_circuit: FC,
+        k: usize,
+    ) -> Result<(R1CS<C1::ScalarField>, R1CS<C2::ScalarField>), Error> {
+        let augmented_f_circuit =
+            AugmentedFCircuit::<C1, C2, GC2, FC>::empty(poseidon_config, F_circuit, k, None)?;
+        let r1cs = get_r1cs_from_cs::<C1::ScalarField>(augmented_f_circuit)?;
+        let cf_circuit = CycleFoldCircuit::<C1, GC1> {
+            r_bits: Some(vec![false; C1::ScalarField::MODULUS_BIT_SIZE as usize]),
+            ..CycleFoldCircuit::empty()
+        };
+        let cf_r1cs = get_r1cs_from_cs::<C2::ScalarField>(cf_circuit)?;
+        Ok((r1cs, cf_r1cs))
+    }
+
+    /// Returns a new incoming instance of the AugmentedFCircuit, obtained from the base case step
+    /// of an IVC starting at the given `state`. It can be used as one of the `k-1` other incoming
+    /// instances of `prove_step_with_instances`.
+    pub fn new_incoming_instance(
+        &self,
+        state: Vec<C1::ScalarField>,
+        external_inputs: Vec<C1::ScalarField>,
+    ) -> Result<(CommittedInstance<C1>, Witness<C1::ScalarField>), Error> {
+        if state.len() != self.F.state_len() {
+            return Err(Error::NotSameLength(
+                "state.len()".to_string(),
+                state.len(),
+                "F.state_len()".to_string(),
+                self.F.state_len(),
+            ));
+        }
+        if external_inputs.len() != self.F.external_inputs_len() {
+            return Err(Error::NotSameLength(
+                "F.external_inputs_len()".to_string(),
+                self.F.external_inputs_len(),
+                "external_inputs.len()".to_string(),
+                external_inputs.len(),
+            ));
+        }
+
+        // the public inputs x are left unset, so that the circuit computes them for the base case
+        let augmented_f_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
+            i: Some(C1::ScalarField::zero()),
+            i_usize: Some(0),
+            z_0: Some(state.clone()),
+            z_i: Some(state),
+            external_inputs: Some(external_inputs),
+            ..AugmentedFCircuit::default(
+                &self.poseidon_config,
+                self.F.clone(),
+                self.k,
+                self.U_i.betas.len(),
+            )
+        };
+
+        let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+        augmented_f_circuit.generate_constraints(cs.clone())?;
+        let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+        let (w, x) = extract_w_x::<C1::ScalarField>(&cs);
+        let w = Witness::<C1::ScalarField>::new(w);
+        let u = w.commit::<C1, CS1>(&self.cs_params, x)?;
+        Ok((u, w))
+    }
+
+    /// Implements IVC.P of ProtoGalaxy+CycleFold, folding at each step the incoming instance u_i
+    /// together with the given `other_instances`, which are `k-1` incoming instances of the
+    /// AugmentedFCircuit (see `new_incoming_instance`), into the running instance U_i. If
+    /// `other_instances` is `None`, copies of u_i are folded in their place.
+    /// At the base case (i=0) there is no folding, so `other_instances` must be `None`.
+    pub fn prove_step_with_instances(
+        &mut self,
+        _rng: impl RngCore + CryptoRng,
+        external_inputs: Vec<C1::ScalarField>,
+        other_instances: Option<Vec<(CommittedInstance<C1>, Witness<C1::ScalarField>)>>,
+    ) -> Result<(), Error> {
+        let augmented_f_circuit: AugmentedFCircuit<C1, C2, GC2, FC>;
+
+        if self.z_i.len() != self.F.state_len() {
+            return Err(Error::NotSameLength(
+                "z_i.len()".to_string(),
+                self.z_i.len(),
+                "F.state_len()".to_string(),
+                self.F.state_len(),
+            ));
+        }
+        if external_inputs.len() != self.F.external_inputs_len() {
+            return Err(Error::NotSameLength(
+                "F.external_inputs_len()".to_string(),
+                self.F.external_inputs_len(),
+                "external_inputs.len()".to_string(),
+                external_inputs.len(),
+            ));
+        }
+
+        if self.i > C1::ScalarField::from_le_bytes_mod_order(&usize::MAX.to_le_bytes()) {
+            return Err(Error::MaxStep);
+        }
+
+        #[cfg(target_pointer_width = "64")]
+        let i_usize: usize = {
+            let mut i_bytes: [u8; 8] = [0; 8];
+            i_bytes.copy_from_slice(&self.i.into_bigint().to_bytes_le()[..8]);
+            usize::from_le_bytes(i_bytes)
+        };
+        #[cfg(target_pointer_width = "32")]
+        let i_usize: usize = {
+            let mut i_bytes: [u8; 4] = [0; 4];
+            i_bytes.copy_from_slice(&self.i.into_bigint().to_bytes_le()[..4]);
+            usize::from_le_bytes(i_bytes)
+        };
+
+        // other incoming instances to be folded together with u_i
+        let (us, ws) = match other_instances {
+            Some(instances) => {
+                if self.i == C1::ScalarField::zero() {
+                    return Err(Error::NotSupported(
+                        "other instances can not be folded at the base case (i=0)".to_string(),
+                    ));
+                }
+                if instances.len() != self.k - 1 {
+                    return Err(Error::NotSameLength(
+                        "other_instances.len()".to_string(),
+                        instances.len(),
+                        "k - 1".to_string(),
+                        self.k - 1,
+                    ));
+                }
+                instances.into_iter().unzip()
+            }
+            None => (
+                vec![self.u_i.clone(); self.k - 1],
+                vec![self.w_i.clone(); self.k - 1],
+            ),
+        };
+
+        let z_i1 = self
+            .F
+            .step_native(i_usize, self.z_i.clone(), external_inputs.clone())?;
+
+        let t = self.U_i.betas.len();
+
+        // u_{i+1}.x[0] = H(i+1, z_0, z_{i+1}, U_{i+1})
+        let u_i1_x: C1::ScalarField;
+        // u_{i+1}.x[1] = H(cf_U_{i+1})
+        let cf_u_i1_x: C1::ScalarField;
+        let U_i1: CommittedInstance<C1>;
+        let W_i1: Witness<C1::ScalarField>;
+
+        if self.i == C1::ScalarField::zero() {
+            // base case: the running instance U_{i+1} is the dummy running instance, so there is
+            // no folding to be done
+            W_i1 = Witness::<C1::ScalarField>::new(vec![
+                C1::ScalarField::zero();
+                self.r1cs.A.n_cols - self.r1cs.l - 1
+            ]);
+            U_i1 = CommittedInstance::<C1>::dummy_running(self.r1cs.l, t);
+
+            u_i1_x = U_i1.hash(
+                &self.poseidon_config,
+                C1::ScalarField::one(),
+                self.z_0.clone(),
+                z_i1.clone(),
+            )?;
+            cf_u_i1_x = self.cf_U_i.hash_cyclefold(&self.poseidon_config)?;
+
+            augmented_f_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
+                _gc2: PhantomData,
+                poseidon_config: self.poseidon_config.clone(),
+                t,
+                k: self.k,
+                i: Some(C1::ScalarField::zero()),
+                i_usize: Some(0),
+                z_0: Some(self.z_0.clone()),
+                z_i: Some(self.z_i.clone()),
+                external_inputs: Some(external_inputs.clone()),
+                u_i_phi: Some(self.u_i.phi),
+                U_i: Some(self.U_i.clone()),
+                us: Some(us),
+                U_i1_phi: Some(U_i1.phi),
+                F_coeffs: None,
+                K_coeffs: None,
+                phi_stars: None,
+                F: self.F.clone(),
+                x: Some(u_i1_x),
+
+                // cyclefold values
+                cf_u_i_cmW: None,
+                cf_U_i: None,
+                cf_x: Some(cf_u_i1_x),
+                cf_cmT: None,
+            };
+        } else {
+            let mut transcript_p: PoseidonTranscript<C1> =
+                PoseidonTranscript::<C1>::new(&self.poseidon_config);
+            let (F_coeffs, K_coeffs, L_X_evals);
+            (U_i1, W_i1, F_coeffs, K_coeffs, L_X_evals) = Folding::<C1>::prove(
+                &mut transcript_p,
+                &self.r1cs,
+                &self.U_i,
+                &self.W_i,
+                &[vec![self.u_i.clone()], us.clone()].concat(),
+                &[vec![self.w_i.clone()], ws].concat(),
+            )?;
+
+            // sanity check: check the folded instance relation
+            #[cfg(test)]
+            check_instance(&self.r1cs, &U_i1, &W_i1)?;
+
+            u_i1_x = U_i1.hash(
+                &self.poseidon_config,
+                self.i + C1::ScalarField::one(),
+                self.z_0.clone(),
+                z_i1.clone(),
+            )?;
+
+            // CycleFold part:
+            // U_{i+1}.phi = L_0(gamma) * U_i.phi + \sum_j L_j(gamma) * u_j.phi, where u_j are the
+            // instances [u_i, us], is computed by a chain of CycleFold circuits, where the j-th
+            // one computes p3 = p1 + L_j(gamma) * p2
+            let p2s: Vec<C1> = [
+                vec![self.U_i.phi, self.u_i.phi],
+                us.iter().map(|u| u.phi).collect(),
+            ]
+            .concat();
+            let mut phi_stars: Vec<C1> = vec![];
+            let mut phi_star = C1::zero();
+            for (p2, L_j) in p2s[..self.k].iter().zip(L_X_evals.iter()) {
+                phi_star += *p2 * L_j;
+                phi_stars.push(phi_star);
+            }
+            let p1s = [vec![C1::zero()], phi_stars.clone()].concat();
+            let p3s = [phi_stars.clone(), vec![U_i1.phi]].concat();
+
+            let mut cf_u_i_cmW: Vec<C2> = vec![];
+            let mut cf_cmT: Vec<C2> = vec![];
+            let mut cf_W_i1 = self.cf_W_i.clone();
+            let mut cf_U_i1 = self.cf_U_i.clone();
+            for (j, L_j) in L_X_evals.iter().enumerate() {
+                let r_bits = L_j.into_bigint().to_bits_le()
+                    [..C1::ScalarField::MODULUS_BIT_SIZE as usize]
+                    .to_vec();
+                let r_Fq = C1::BaseField::from_bigint(BigInteger::from_bits_le(&r_bits))
+                    .ok_or(Error::OutOfBounds)?;
+
+                // get the vector used as public inputs 'x' in the CycleFold circuit
+                let cf_u_i_x = [
+                    vec![r_Fq],
+                    get_cm_coordinates(&p1s[j]),
+                    get_cm_coordinates(&p2s[j]),
+                    get_cm_coordinates(&p3s[j]),
+                ]
+                .concat();
+
+                let cf_circuit = CycleFoldCircuit::<C1, GC1> {
+                    _gc: PhantomData,
+                    r_bits: Some(r_bits),
+                    p1: Some(p1s[j]),
+                    p2: Some(p2s[j]),
+                    x: Some(cf_u_i_x.clone()),
+                };
+
+                let (_cf_w_i, cf_u_i, cf_W_i1_j, cf_U_i1_j, cf_cmT_j, _) =
+                    fold_cyclefold_circuit::<C1, GC1, C2, GC2, CS2>(
+                        &self.poseidon_config,
+                        self.cf_r1cs.clone(),
+                        self.cf_cs_params.clone(),
+                        cf_W_i1, // CycleFold running instance witness
+                        cf_U_i1, // CycleFold running instance
+                        cf_u_i_x,
+                        cf_circuit,
+                    )?;
+
+                #[cfg(test)]
+                self.cf_r1cs.check_instance_relation(&_cf_w_i, &cf_u_i)?;
+
+                cf_u_i_cmW.push(cf_u_i.cmW);
+                cf_cmT.push(cf_cmT_j);
+                cf_W_i1 = cf_W_i1_j;
+                cf_U_i1 = cf_U_i1_j;
+            }
+
+            cf_u_i1_x = cf_U_i1.hash_cyclefold(&self.poseidon_config)?;
+
+            augmented_f_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
+                _gc2: PhantomData,
+                poseidon_config: self.poseidon_config.clone(),
+                t,
+                k: self.k,
+                i: Some(self.i),
+                i_usize: Some(i_usize),
+                z_0: Some(self.z_0.clone()),
+                z_i: Some(self.z_i.clone()),
+                external_inputs: Some(external_inputs.clone()),
+                u_i_phi: Some(self.u_i.phi),
+                U_i: Some(self.U_i.clone()),
+                us: Some(us),
+                U_i1_phi: Some(U_i1.phi),
+                F_coeffs: Some(F_coeffs),
+                K_coeffs: Some(K_coeffs),
+                phi_stars: Some(phi_stars),
+                F: self.F.clone(),
+                x: Some(u_i1_x),
+
+                // cyclefold values
+                cf_u_i_cmW: Some(cf_u_i_cmW),
+                cf_U_i: Some(self.cf_U_i.clone()),
+                cf_x: Some(cf_u_i1_x),
+                cf_cmT: Some(cf_cmT),
+            };
+
+            self.cf_W_i = cf_W_i1;
+            self.cf_U_i = cf_U_i1;
+
+            #[cfg(test)]
+            self.cf_r1cs
+                .check_relaxed_instance_relation(&self.cf_W_i, &self.cf_U_i)?;
+        }
+
+        let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+
+        augmented_f_circuit.generate_constraints(cs.clone())?;
+
+        #[cfg(test)]
+        assert!(cs.is_satisfied().unwrap());
+
+        let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+        let (w_i1, x_i1) = extract_w_x::<C1::ScalarField>(&cs);
+        if x_i1.len() != 2 {
+            return Err(Error::NotExpectedLength(x_i1.len(), 2));
+        }
+        if x_i1[0] != u_i1_x || x_i1[1] != cf_u_i1_x {
+            return Err(Error::NotEqual);
+        }
+
+        // set values for next iteration
+        self.i += C1::ScalarField::one();
+        self.z_i = z_i1;
+        self.w_i = Witness::<C1::ScalarField>::new(w_i1);
+        self.u_i = self.w_i.commit::<C1, CS1>(&self.cs_params, x_i1)?;
+        self.W_i = W_i1;
+        self.U_i = U_i1;
+
+        #[cfg(test)]
+        {
+            // check the new running instance relation
+            check_instance(&self.r1cs, &self.U_i, &self.W_i)?;
+            // check the new incoming instance relation
+            self.r1cs.check_relation(
+                &[
+                    vec![C1::ScalarField::one()],
+                    self.u_i.x.clone(),
+                    self.w_i.w.clone(),
+                ]
+                .concat(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> FoldingScheme<C1, C2, FC>
+    for ProtoGalaxy<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    type PreprocessorParam = (Self::ProverParam, FC);
+    type ProverParam = ProverParams<C1, C2, CS1, CS2>;
+    type VerifierParam = VerifierParams<C1, C2>;
+    type RunningInstance = (CommittedInstance<C1>, Witness<C1::ScalarField>);
+    type IncomingInstance = (CommittedInstance<C1>, Witness<C1::ScalarField>);
+    type CFInstance = (CycleFoldCommittedInstance<C2>, CycleFoldWitness<C2>);
+
+    fn preprocess(
+        prep_param: &Self::PreprocessorParam,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        let (prover_params, F_circuit) = prep_param;
+
+        let (r1cs, cf_r1cs) = Self::get_r1cs(
+            &prover_params.poseidon_config,
+            F_circuit.clone(),
+            prover_params.k,
+        )?;
+
+        let verifier_params = VerifierParams::<C1, C2> {
+            poseidon_config: prover_params.poseidon_config.clone(),
+            r1cs,
+            cf_r1cs,
+        };
+        Ok((prover_params.clone(), verifier_params))
+    }
+
+    /// Initializes the ProtoGalaxy+CycleFold's IVC for the given parameters and initial state
+    /// `z_0`.
+    fn init(pp: &Self::ProverParam, F: FC, z_0: Vec<C1::ScalarField>) -> Result<Self, Error> {
+        // prepare the circuit to obtain its R1CS
+        let (r1cs, cf_r1cs) = Self::get_r1cs(&pp.poseidon_config, F.clone(), pp.k)?;
+        let t = log2(r1cs.A.n_rows) as usize;
+
+        // setup the dummy instances
+        // W_dummy=W_0 is a 'dummy witness', all zeroes, but with the size corresponding to the
+        // R1CS that we're working with.
+        let W_dummy = Witness::<C1::ScalarField>::new(vec![
+            C1::ScalarField::zero();
+            r1cs.A.n_cols - r1cs.l - 1
+        ]);
+        let U_dummy = CommittedInstance::<C1>::dummy_running(r1cs.l, t);
+        let w_dummy = W_dummy.clone();
+        let u_dummy = CommittedInstance::<C1>::dummy_incoming(r1cs.l);
+        let (cf_w_dummy, cf_u_dummy) = cf_r1cs.dummy_instance();
+
+        Ok(Self {
+            _gc1: PhantomData,
+            _c2: PhantomData,
+            _gc2: PhantomData,
+            r1cs,
+            cf_r1cs,
+            poseidon_config: pp.poseidon_config.clone(),
+            cs_params: pp.cs_params.clone(),
+            cf_cs_params: pp.cf_cs_params.clone(),
+            F,
+            k: pp.k,
+            i: C1::ScalarField::zero(),
+            z_0: z_0.clone(),
+            z_i: z_0,
+            w_i: w_dummy,
+            u_i: u_dummy,
+            W_i: W_dummy,
+            U_i: U_dummy,
+            // cyclefold running instance
+            cf_W_i: cf_w_dummy,
+            cf_U_i: cf_u_dummy,
+        })
+    }
+
+    /// Implements IVC.P of ProtoGalaxy+CycleFold. When k > 1, the incoming instance is folded
+    /// together with copies of itself, use `ProtoGalaxy::prove_step_with_instances` to fold other
+    /// instances at each step.
+    fn prove_step(
+        &mut self,
+        rng: impl RngCore + CryptoRng,
+        external_inputs: Vec<C1::ScalarField>,
+    ) -> Result<(), Error> {
+        self.prove_step_with_instances(rng, external_inputs, None)
+    }
+
+    fn state(&self) -> Vec<C1::ScalarField> {
+        self.z_i.clone()
+    }
+    fn instances(
+        &self,
+    ) -> (
+        Self::RunningInstance,
+        Self::IncomingInstance,
+        Self::CFInstance,
+    ) {
+        (
+            (self.U_i.clone(), self.W_i.clone()),
+            (self.u_i.clone(), self.w_i.clone()),
+            (self.cf_U_i.clone(), self.cf_W_i.clone()),
+        )
+    }
+
+    /// Implements IVC.V of ProtoGalaxy+CycleFold. Notice that this method does not include the
+    /// commitments verification, which is done in the Decider.
+    fn verify(
+        vp: Self::VerifierParam,
+        z_0: Vec<C1::ScalarField>, // initial state
+        z_i: Vec<C1::ScalarField>, // last state
+        num_steps: C1::ScalarField,
+        running_instance: Self::RunningInstance,
+        incoming_instance: Self::IncomingInstance,
+        cyclefold_instance: Self::CFInstance,
+    ) -> Result<(), Error> {
+        let (U_i, W_i) = running_instance;
+        let (u_i, w_i) = incoming_instance;
+        let (cf_U_i, cf_W_i) = cyclefold_instance;
+
+        if u_i.x.len() != 2 || U_i.x.len() != 2 {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check that u_i's output points to the running instance
+        // u_i.X[0] == H(i, z_0, z_i, U_i)
+        let expected_u_i_x = U_i.hash(&vp.poseidon_config, num_steps, z_0, z_i)?;
+        if expected_u_i_x != u_i.x[0] {
+            return Err(Error::IVCVerificationFail);
+        }
+        // u_i.X[1] == H(cf_U_i)
+        let expected_cf_u_i_x = cf_U_i.hash_cyclefold(&vp.poseidon_config)?;
+        if expected_cf_u_i_x != u_i.x[1] {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check the incoming instance R1CS satisfiability
+        vp.r1cs
+            .check_relation(&[vec![C1::ScalarField::one()], u_i.x, w_i.w].concat())?;
+        // check the running instance satisfiability
+        check_instance(&vp.r1cs, &U_i, &W_i)?;
+
+        // check CycleFold RelaxedR1CS satisfiability
+        vp.cf_r1cs
+            .check_relaxed_instance_relation(&cf_W_i, &cf_U_i)?;
+
+        Ok(())
+    }
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> Checkpoint<C1, C2, FC>
+    for ProtoGalaxy<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    fn params_hash(&self) -> Result<C1::ScalarField, Error> {
+        let mut bytes = vec![];
+        self.r1cs.serialize_compressed(&mut bytes)?;
+        self.cf_r1cs.serialize_compressed(&mut bytes)?;
+        self.cs_params.serialize_compressed(&mut bytes)?;
+        self.cf_cs_params.serialize_compressed(&mut bytes)?;
+        serialize_poseidon_config(&self.poseidon_config, &mut bytes)?;
+        hash_bytes(&self.poseidon_config, &bytes)
+    }
+
+    fn step(&self) -> C1::ScalarField {
+        self.i
+    }
+
+    fn serialize_state<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        self.i.serialize_compressed(&mut writer)?;
+        self.z_0.serialize_compressed(&mut writer)?;
+        self.z_i.serialize_compressed(&mut writer)?;
+        self.w_i.serialize_compressed(&mut writer)?;
+        self.u_i.serialize_compressed(&mut writer)?;
+        self.W_i.serialize_compressed(&mut writer)?;
+        self.U_i.serialize_compressed(&mut writer)?;
+        self.cf_W_i.serialize_compressed(&mut writer)?;
+        self.cf_U_i.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    fn deserialize_state<R: Read>(&mut self, mut reader: R) -> Result<(), Error> {
+        self.i = C1::ScalarField::deserialize_compressed(&mut reader)?;
+        self.z_0 = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.z_i = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.w_i = Witness::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.u_i = CommittedInstance::<C1>::deserialize_compressed(&mut reader)?;
+        self.W_i = Witness::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        self.U_i = CommittedInstance::<C1>::deserialize_compressed(&mut reader)?;
+        self.cf_W_i = CycleFoldWitness::<C2>::deserialize_compressed(&mut reader)?;
+        self.cf_U_i = CycleFoldCommittedInstance::<C2>::deserialize_compressed(&mut reader)?;
+        Ok(())
+    }
+}
+
+/// helper method to get the commitment scheme params length for both the AugmentedFCircuit and the
+/// CycleFold circuit
+pub fn get_cs_params_len<C1, GC1, C2, GC2, FC>(
+    poseidon_config: &PoseidonConfig<C1::ScalarField>,
+    F_circuit: FC,
+    k: usize,
+) -> Result<(usize, usize), Error>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    let augmented_f_circuit =
+        AugmentedFCircuit::<C1, C2, GC2, FC>::empty(poseidon_config, F_circuit, k, None)?;
+    let r1cs = get_r1cs_from_cs::<C1::ScalarField>(augmented_f_circuit)?;
+    // same r_bits length as in `ProtoGalaxy::get_r1cs`
+    let cf_circuit = CycleFoldCircuit::<C1, GC1> {
+        r_bits: Some(vec![false; C1::ScalarField::MODULUS_BIT_SIZE as usize]),
+        ..CycleFoldCircuit::empty()
+    };
+    let cf_r1cs = get_r1cs_from_cs::<C2::ScalarField>(cf_circuit)?;
+    // the witness committed in the ProtoGalaxy instances is z without the (u, x) part
+    Ok((r1cs.A.n_cols - r1cs.l - 1, cf_r1cs.A.n_rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::kzg::{ProverKey as KZGProverKey, KZG};
+    use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as Projective};
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+    use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
+
+    use crate::commitment::pedersen::Pedersen;
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_canonical_config;
+
+    /// This test tests the ProtoGalaxy+CycleFold IVC, and by consequence it is also testing the
+    /// AugmentedFCircuit
+    #[test]
+    fn test_ivc() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+
+        // compute the R1CS of the AugmentedFCircuit, to obtain the needed commitment params length
+        let (r1cs, cf_r1cs) = ProtoGalaxy::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >::get_r1cs(&poseidon_config, F_circuit, 1)
+        .unwrap();
+        let cs_len = r1cs.A.n_cols - r1cs.l - 1;
+        let cf_cs_len = cf_r1cs.A.n_rows;
+
+        let (kzg_pk, _): (KZGProverKey<Projective>, KZGVerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        // run the test using Pedersen commitments on both sides of the curve cycle
+        test_ivc_opt::<Pedersen<Projective>, Pedersen<Projective2>>(
+            poseidon_config.clone(),
+            pedersen_params,
+            cf_pedersen_params.clone(),
+            F_circuit,
+            1,
+        );
+        // run the test using KZG for the commitments on the main curve, and Pedersen for the
+        // commitments on the secondary curve
+        test_ivc_opt::<KZG<Bn254>, Pedersen<Projective2>>(
+            poseidon_config,
+            kzg_pk,
+            cf_pedersen_params,
+            F_circuit,
+            1,
+        );
+    }
+
+    /// Tests the ProtoGalaxy+CycleFold IVC folding k > 1 incoming instances at each IVC step
+    #[test]
+    fn test_ivc_multi_instances() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let k = 3;
+
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+                k,
+            )
+            .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        test_ivc_opt::<Pedersen<Projective>, Pedersen<Projective2>>(
+            poseidon_config,
+            pedersen_params,
+            cf_pedersen_params,
+            F_circuit,
+            k,
+        );
+    }
+
+    // test_ivc allowing to choose the CommitmentSchemes
+    fn test_ivc_opt<CS1: CommitmentScheme<Projective>, CS2: CommitmentScheme<Projective2>>(
+        poseidon_config: PoseidonConfig<Fr>,
+        cs_params: CS1::ProverParams,
+        cf_cs_params: CS2::ProverParams,
+        F_circuit: CubicFCircuit<Fr>,
+        k: usize,
+    ) {
+        let mut rng = ark_std::test_rng();
+        type PG<CS1, CS2> =
+            ProtoGalaxy<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>, CS1, CS2>;
+
+        let prover_params = ProverParams::<Projective, Projective2, CS1, CS2> {
+            poseidon_config: poseidon_config.clone(),
+            cs_params,
+            cf_cs_params,
+            k,
+        };
+        let (prover_params, verifier_params) = PG::preprocess(&(prover_params, F_circuit)).unwrap();
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut protogalaxy = PG::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+
+        let num_steps: usize = 4;
+        for i in 0..num_steps {
+            if i % 2 == 0 {
+                // u_i is folded together with copies of itself
+                protogalaxy.prove_step(&mut rng, vec![]).unwrap();
+            } else {
+                // u_i is folded together with other instances, computed from different states
+                let us = (0..k - 1)
+                    .map(|j| {
+                        protogalaxy
+                            .new_incoming_instance(vec![Fr::from(j as u32 + 10)], vec![])
+                            .unwrap()
+                    })
+                    .collect();
+                protogalaxy
+                    .prove_step_with_instances(&mut rng, vec![], Some(us))
+                    .unwrap();
+            }
+        }
+        assert_eq!(Fr::from(num_steps as u32), protogalaxy.i);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = protogalaxy.instances();
+        PG::verify(
+            verifier_params,
+            z_0,
+            protogalaxy.z_i,
+            protogalaxy.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+        )
+        .unwrap();
+    }
+
+    /// Tests that a ProtoGalaxy IVC can be checkpointed and resumed, and that the resumed IVC
+    /// keeps producing valid proofs
+    #[test]
+    fn test_checkpoint() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+
+        type PG = ProtoGalaxy<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >;
+        let (r1cs, cf_r1cs) = PG::get_r1cs(&poseidon_config, F_circuit, 1).unwrap();
+        let (pedersen_params, _) =
+            Pedersen::<Projective>::setup(&mut rng, r1cs.A.n_cols - r1cs.l - 1).unwrap();
+        let (cf_pedersen_params, _) =
+            Pedersen::<Projective2>::setup(&mut rng, cf_r1cs.A.n_rows).unwrap();
+        let prover_params = ProverParams::<Projective, Projective2, _, _> {
+            poseidon_config,
+            cs_params: pedersen_params,
+            cf_cs_params: cf_pedersen_params,
+            k: 1,
+        };
+        let (prover_params, verifier_params) = PG::preprocess(&(prover_params, F_circuit)).unwrap();
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut protogalaxy = PG::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        protogalaxy.prove_step(&mut rng, vec![]).unwrap();
+        protogalaxy.prove_step(&mut rng, vec![]).unwrap();
+
+        let mut checkpoint = vec![];
+        protogalaxy.write_checkpoint(&mut checkpoint).unwrap();
+        let mut protogalaxy =
+            PG::resume_from_checkpoint(&prover_params, F_circuit, checkpoint.as_slice()).unwrap();
+        protogalaxy.prove_step(&mut rng, vec![]).unwrap();
+        assert_eq!(Fr::from(3_u32), protogalaxy.i);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = protogalaxy.instances();
+        PG::verify(
+            verifier_params,
+            z_0,
+            protogalaxy.z_i,
+            protogalaxy.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+        )
+        .unwrap();
+
+        // a checkpoint taken with a different number of folded instances is rejected
+        let (prover_params_multi, _) = PG::preprocess(&(
+            ProverParams {
+                k: 3,
+                ..prover_params
+            },
+            F_circuit,
+        ))
+        .unwrap();
+        let res =
+            PG::resume_from_checkpoint(&prover_params_multi, F_circuit, checkpoint.as_slice());
+        assert!(matches!(res, Err(Error::CheckpointMismatch(_))));
+    }
+}
@@ -1,23 +1,47 @@
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ec::{CurveGroup, Group};
+use ark_ff::PrimeField;
 
 use super::CommittedInstance;
-use crate::transcript::{poseidon::PoseidonTranscript, Transcript};
+use crate::transcript::{keccak::KeccakTranscript, poseidon::PoseidonTranscript, Transcript};
 use crate::Error;
 
 /// ProtoGalaxyTranscript extends [`Transcript`] with the method to absorb ProtoGalaxy's
 /// CommittedInstance.
-pub trait ProtoGalaxyTranscript<C: CurveGroup>: Transcript<C> {
+pub trait ProtoGalaxyTranscript<C: CurveGroup>: Transcript<C>
+where
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    /// absorbs the CommittedInstance, the commitment `phi` is absorbed through its non-native
+    /// limbs so that the same logic can be reproduced in-circuit.
     fn absorb_committed_instance(&mut self, ci: &CommittedInstance<C>) -> Result<(), Error> {
-        self.absorb_point(&ci.phi)?;
-        self.absorb_vec(&ci.betas);
-        self.absorb(&ci.e);
+        self.absorb_vec(&ci.to_field_elements()?);
         Ok(())
     }
 }
 
 // Implements ProtoGalaxyTranscript for PoseidonTranscript
-impl<C: CurveGroup> ProtoGalaxyTranscript<C> for PoseidonTranscript<C> where
-    <C as Group>::ScalarField: Absorb
+impl<C: CurveGroup> ProtoGalaxyTranscript<C> for PoseidonTranscript<C>
+where
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+}
+
+// Implements ProtoGalaxyTranscript for KeccakTranscript. Since there is no in-circuit counterpart
+// of the Keccak transcript, the commitment `phi` is absorbed as a point, as an EVM verifier would
+// do, instead of through its non-native limbs.
+impl<C: CurveGroup> ProtoGalaxyTranscript<C> for KeccakTranscript<C>
+where
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
 {
+    fn absorb_committed_instance(&mut self, ci: &CommittedInstance<C>) -> Result<(), Error> {
+        self.absorb_point(&ci.phi)?;
+        self.absorb_vec(&ci.betas);
+        self.absorb_vec(&[ci.e, ci.u]);
+        self.absorb_vec(&ci.x);
+        Ok(())
+    }
 }
@@ -0,0 +1,522 @@
+/// contains the augmented F' circuit of the SuperNova-style NIVC scheme
+use ark_crypto_primitives::crh::{
+    poseidon::constraints::{CRHGadget, CRHParametersVar},
+    CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    groups::GroupOpsBounds,
+    prelude::CurveVar,
+    select::CondSelectGadget,
+    R1CSVar, ToConstraintFieldGadget,
+};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_std::{One, Zero};
+use core::marker::PhantomData;
+
+use crate::folding::circuits::{
+    nonnative::{affine::NonNativeAffineVar, uint::NonNativeUintVar},
+    CF1, CF2,
+};
+use crate::folding::nova::{
+    circuits::{ChallengeGadget, CommittedInstanceVar, NIFSGadget},
+    cyclefold::{
+        CycleFoldChallengeGadget, CycleFoldCommittedInstanceVar, NIFSFullGadget, CF_IO_LEN,
+    },
+    CommittedInstance,
+};
+use crate::frontend::FCircuit;
+
+/// Gadget counterpart of `supernova::running_instance_digest`. Returns `H(U)` together with the
+/// vector of field elements of `U`, so that they can be reused in the challenge computation.
+#[allow(clippy::type_complexity)]
+pub fn running_instance_digest_gadget<C: CurveGroup>(
+    crh_params: &CRHParametersVar<CF1<C>>,
+    U: &CommittedInstanceVar<C>,
+) -> Result<(FpVar<CF1<C>>, Vec<FpVar<CF1<C>>>), SynthesisError>
+where
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    let U_vec = [
+        vec![U.u.clone()],
+        U.x.clone(),
+        U.cmE.to_constraint_field()?,
+        U.cmW.to_constraint_field()?,
+    ]
+    .concat();
+    Ok((CRHGadget::<CF1<C>>::evaluate(crh_params, &U_vec)?, U_vec))
+}
+
+/// Gadget counterpart of `supernova::ivc_hash`, returns
+/// `H(pp_hash, i, pc, z_0, z_i, H(U[0]), ..., H(U[n-1]))`.
+pub fn ivc_hash_gadget<F: PrimeField + Absorb>(
+    crh_params: &CRHParametersVar<F>,
+    pp_hash: FpVar<F>,
+    i: FpVar<F>,
+    pc: FpVar<F>,
+    z_0: Vec<FpVar<F>>,
+    z_i: Vec<FpVar<F>>,
+    U_digests: Vec<FpVar<F>>,
+) -> Result<FpVar<F>, SynthesisError> {
+    let input = [vec![pp_hash, i, pc], z_0, z_i, U_digests].concat();
+    CRHGadget::<F>::evaluate(crh_params, &input)
+}
+
+/// AugmentedFCircuit implements the F'_j circuit of the NIVC scheme for the step circuit at index
+/// `pc`, which follows the Nova+CycleFold AugmentedFCircuit with the following differences:
+/// - the IVC hash commits to the digests of all the running instances (one per step circuit), and
+///   to `last_pc`, the index of the circuit that produced the incoming instance u_i,
+/// - u_i is folded into the running instance at `last_pc`, which is selected from the digests,
+/// - the first element of the state z_i is the program counter, which must be equal to `pc`.
+#[derive(Debug, Clone)]
+pub struct AugmentedFCircuit<
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+> where
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    pub _gc2: PhantomData<GC2>,
+    pub poseidon_config: PoseidonConfig<CF1<C1>>,
+    /// index of this circuit
+    pub pc: usize,
+    /// number of step circuits
+    pub n_circuits: usize,
+    pub pp_hash: Option<CF1<C1>>,
+    pub i: Option<CF1<C1>>,
+    pub i_usize: Option<usize>,
+    pub last_pc: Option<CF1<C1>>,
+    pub z_0: Option<Vec<C1::ScalarField>>,
+    pub z_i: Option<Vec<C1::ScalarField>>,
+    pub external_inputs: Option<Vec<C1::ScalarField>>,
+    pub u_i_cmW: Option<C1>,
+    /// running instance at `last_pc`, in which u_i is folded
+    pub U_i: Option<CommittedInstance<C1>>,
+    /// digests of all the running instances
+    pub U_i_digests: Option<Vec<CF1<C1>>>,
+    pub U_i1_cmE: Option<C1>,
+    pub U_i1_cmW: Option<C1>,
+    pub cmT: Option<C1>,
+    pub F: FC,              // F circuit
+    pub x: Option<CF1<C1>>, // public input (u_{i+1}.x[0])
+
+    // cyclefold verifier on C1
+    pub cf1_u_i_cmW: Option<C2>,               // input
+    pub cf2_u_i_cmW: Option<C2>,               // input
+    pub cf_U_i: Option<CommittedInstance<C2>>, // input
+    pub cf1_cmT: Option<C2>,
+    pub cf2_cmT: Option<C2>,
+    pub cf_x: Option<CF1<C1>>, // public input (u_{i+1}.x[1])
+}
+
+impl<C1: CurveGroup, C2: CurveGroup, GC2: CurveVar<C2, CF2<C2>>, FC: FCircuit<CF1<C1>>>
+    AugmentedFCircuit<C1, C2, GC2, FC>
+where
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    pub fn empty(
+        poseidon_config: &PoseidonConfig<CF1<C1>>,
+        F_circuit: FC,
+        pc: usize,
+        n_circuits: usize,
+    ) -> Self {
+        Self {
+            _gc2: PhantomData,
+            poseidon_config: poseidon_config.clone(),
+            pc,
+            n_circuits,
+            pp_hash: None,
+            i: None,
+            i_usize: None,
+            last_pc: None,
+            z_0: None,
+            z_i: None,
+            external_inputs: None,
+            u_i_cmW: None,
+            U_i: None,
+            U_i_digests: None,
+            U_i1_cmE: None,
+            U_i1_cmW: None,
+            cmT: None,
+            F: F_circuit,
+            x: None,
+            // cyclefold values
+            cf1_u_i_cmW: None,
+            cf2_u_i_cmW: None,
+            cf_U_i: None,
+            cf1_cmT: None,
+            cf2_cmT: None,
+            cf_x: None,
+        }
+    }
+}
+
+impl<C1, C2, GC2, FC> ConstraintSynthesizer<CF1<C1>> for AugmentedFCircuit<C1, C2, GC2, FC>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<CF1<C1>>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<CF1<C1>>) -> Result<(), SynthesisError> {
+        let pp_hash = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self.pp_hash.unwrap_or_else(CF1::<C1>::zero))
+        })?;
+        let i = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self.i.unwrap_or_else(CF1::<C1>::zero))
+        })?;
+        let last_pc = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self
+                .last_pc
+                .unwrap_or_else(|| CF1::<C1>::from(self.pc as u64)))
+        })?;
+        let z_0 = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .z_0
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.state_len()]))
+        })?;
+        let z_i = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .z_i
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.state_len()]))
+        })?;
+        let external_inputs = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .external_inputs
+                .unwrap_or(vec![CF1::<C1>::zero(); self.F.external_inputs_len()]))
+        })?;
+
+        let u_dummy = CommittedInstance::dummy(2);
+        let U_i = CommittedInstanceVar::<C1>::new_witness(cs.clone(), || {
+            Ok(self.U_i.unwrap_or(u_dummy.clone()))
+        })?;
+        let U_i_digests = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
+            Ok(self
+                .U_i_digests
+                .unwrap_or(vec![CF1::<C1>::zero(); self.n_circuits]))
+        })?;
+        let U_i1_cmE = NonNativeAffineVar::new_witness(cs.clone(), || {
+            Ok(self.U_i1_cmE.unwrap_or_else(C1::zero))
+        })?;
+        let U_i1_cmW = NonNativeAffineVar::new_witness(cs.clone(), || {
+            Ok(self.U_i1_cmW.unwrap_or_else(C1::zero))
+        })?;
+
+        let cmT =
+            NonNativeAffineVar::new_witness(cs.clone(), || Ok(self.cmT.unwrap_or_else(C1::zero)))?;
+
+        let cf_u_dummy = CommittedInstance::dummy(CF_IO_LEN);
+        let cf_U_i = CycleFoldCommittedInstanceVar::<C2, GC2>::new_witness(cs.clone(), || {
+            Ok(self.cf_U_i.unwrap_or(cf_u_dummy.clone()))
+        })?;
+        let cf1_cmT = GC2::new_witness(cs.clone(), || Ok(self.cf1_cmT.unwrap_or_else(C2::zero)))?;
+        let cf2_cmT = GC2::new_witness(cs.clone(), || Ok(self.cf2_cmT.unwrap_or_else(C2::zero)))?;
+
+        let crh_params = CRHParametersVar::<C1::ScalarField>::new_constant(
+            cs.clone(),
+            self.poseidon_config.clone(),
+        )?;
+
+        // the program counter is the first element of the state, and it must point to this circuit
+        let pc = FpVar::<CF1<C1>>::constant(CF1::<C1>::from(self.pc as u64));
+        z_i.first()
+            .ok_or(SynthesisError::Unsatisfiable)?
+            .enforce_equal(&pc)?;
+
+        // get z_{i+1} from the F circuit
+        let i_usize = self.i_usize.unwrap_or(0);
+        let z_i1 =
+            self.F
+                .generate_step_constraints(cs.clone(), i_usize, z_i.clone(), external_inputs)?;
+
+        let is_basecase = i.is_zero()?;
+
+        // selectors of the running instance in which u_i is folded, exactly one of them is set,
+        // which ensures that last_pc < n_circuits
+        let last_pc_bits = (0..self.n_circuits)
+            .map(|j| last_pc.is_eq(&FpVar::constant(CF1::<C1>::from(j as u64))))
+            .collect::<Result<Vec<Boolean<CF1<C1>>>, SynthesisError>>()?;
+        Boolean::kary_or(&last_pc_bits)?.enforce_equal(&Boolean::TRUE)?;
+
+        // check that U_i is the running instance at last_pc
+        let (U_i_digest, U_i_vec) = running_instance_digest_gadget(&crh_params, &U_i)?;
+        let selected_digest = last_pc_bits
+            .iter()
+            .zip(U_i_digests.iter())
+            .try_fold(FpVar::<CF1<C1>>::zero(), |acc, (b, h)| {
+                Ok::<_, SynthesisError>(acc + FpVar::from(b.clone()) * h)
+            })?;
+        U_i_digest.enforce_equal(&selected_digest)?;
+
+        // Primary Part
+        // P.1. Compute u_i.x
+        // u_i.x[0] = H(pp_hash, i, last_pc, z_0, z_i, H(U_i[0]), ..., H(U_i[n-1]))
+        let u_i_x = ivc_hash_gadget(
+            &crh_params,
+            pp_hash.clone(),
+            i.clone(),
+            last_pc,
+            z_0.clone(),
+            z_i.clone(),
+            U_i_digests.clone(),
+        )?;
+        // u_i.x[1] = H(cf_U_i)
+        let (cf_u_i_x, cf_U_i_vec) = cf_U_i.clone().hash(&crh_params)?;
+
+        // P.2. Construct u_i
+        let u_i = CommittedInstanceVar {
+            // u_i.cmE = cm(0)
+            cmE: NonNativeAffineVar::new_constant(cs.clone(), C1::zero())?,
+            // u_i.u = 1
+            u: FpVar::one(),
+            // u_i.cmW is provided by the prover as witness
+            cmW: NonNativeAffineVar::new_witness(cs.clone(), || {
+                Ok(self.u_i_cmW.unwrap_or(C1::zero()))
+            })?,
+            // u_i.x is computed in step 1
+            x: vec![u_i_x, cf_u_i_x],
+        };
+
+        // P.3. nifs.verify, obtains U_{i+1} by folding u_i & U_i[last_pc].
+
+        // compute r = H(pp_hash, u_i, U_i, cmT)
+        let r_bits = ChallengeGadget::<C1>::get_challenge_gadget(
+            cs.clone(),
+            &self.poseidon_config,
+            pp_hash.clone(),
+            U_i_vec,
+            u_i.clone(),
+            cmT.clone(),
+        )?;
+        let r = Boolean::le_bits_to_fp_var(&r_bits)?;
+        // Also convert r_bits to a `NonNativeFieldVar`
+        let r_nonnat = {
+            let mut bits = r_bits;
+            bits.resize(C1::BaseField::MODULUS_BIT_SIZE as usize, Boolean::FALSE);
+            NonNativeUintVar::from(&bits)
+        };
+
+        // cmE & cmW of U_{i+1} are unconstrained witnesses, their correctness is checked on the
+        // other curve.
+        let mut U_i1 = NIFSGadget::<C1>::fold_committed_instance(r, U_i.clone(), u_i.clone())?;
+        U_i1.cmE = U_i1_cmE;
+        U_i1.cmW = U_i1_cmW;
+
+        // replace the digest of the running instance at last_pc by the one of U_{i+1}
+        let (U_i1_digest, _) = running_instance_digest_gadget(&crh_params, &U_i1)?;
+        let U_i1_digests = last_pc_bits
+            .iter()
+            .zip(U_i_digests)
+            .map(|(b, h)| FpVar::conditionally_select(b, &U_i1_digest, &h))
+            .collect::<Result<Vec<FpVar<CF1<C1>>>, SynthesisError>>()?;
+
+        // P.4.a compute and check the first output of F'
+        // Base case: u_{i+1}.x[0] == H(pp_hash, 1, pc, z_0, z_{i+1}, H(U_{\bot}), ..., H(U_{\bot}))
+        // Non-base case: u_{i+1}.x[0] == H(pp_hash, i+1, pc, z_0, z_{i+1}, H(U_{i+1}[0]), ...)
+        let u_i1_x = ivc_hash_gadget(
+            &crh_params,
+            pp_hash.clone(),
+            i + FpVar::<CF1<C1>>::one(),
+            pc.clone(),
+            z_0.clone(),
+            z_i1.clone(),
+            U_i1_digests,
+        )?;
+        let (dummy_digest, _) = running_instance_digest_gadget(
+            &crh_params,
+            &CommittedInstanceVar::new_constant(cs.clone(), u_dummy)?,
+        )?;
+        let u_i1_x_base = ivc_hash_gadget(
+            &crh_params,
+            pp_hash,
+            FpVar::<CF1<C1>>::one(),
+            pc,
+            z_0,
+            z_i1,
+            vec![dummy_digest; self.n_circuits],
+        )?;
+        let x = FpVar::new_input(cs.clone(), || Ok(self.x.unwrap_or(u_i1_x_base.value()?)))?;
+        x.enforce_equal(&is_basecase.select(&u_i1_x_base, &u_i1_x)?)?;
+
+        // CycleFold part
+        // C.1. Compute cf1_u_i.x and cf2_u_i.x
+        let cfW_x = vec![
+            r_nonnat.clone(),
+            U_i.cmW.x,
+            U_i.cmW.y,
+            u_i.cmW.x,
+            u_i.cmW.y,
+            U_i1.cmW.x,
+            U_i1.cmW.y,
+        ];
+        let cfE_x = vec![
+            r_nonnat, U_i.cmE.x, U_i.cmE.y, cmT.x, cmT.y, U_i1.cmE.x, U_i1.cmE.y,
+        ];
+
+        // C.2. Construct `cf1_u_i` and `cf2_u_i`
+        let cf1_u_i = CycleFoldCommittedInstanceVar {
+            // cf1_u_i.cmE = 0
+            cmE: GC2::zero(),
+            // cf1_u_i.u = 1
+            u: NonNativeUintVar::new_constant(cs.clone(), C1::BaseField::one())?,
+            // cf1_u_i.cmW is provided by the prover as witness
+            cmW: GC2::new_witness(cs.clone(), || Ok(self.cf1_u_i_cmW.unwrap_or(C2::zero())))?,
+            // cf1_u_i.x is computed in step 1
+            x: cfW_x,
+        };
+        let cf2_u_i = CycleFoldCommittedInstanceVar {
+            // cf2_u_i.cmE = 0
+            cmE: GC2::zero(),
+            // cf2_u_i.u = 1
+            u: NonNativeUintVar::new_constant(cs.clone(), C1::BaseField::one())?,
+            // cf2_u_i.cmW is provided by the prover as witness
+            cmW: GC2::new_witness(cs.clone(), || Ok(self.cf2_u_i_cmW.unwrap_or(C2::zero())))?,
+            // cf2_u_i.x is computed in step 1
+            x: cfE_x,
+        };
+
+        // C.3. nifs.verify, obtains cf1_U_{i+1} by folding cf1_u_i & cf_U_i, and then cf_U_{i+1}
+        // by folding cf2_u_i & cf1_U_{i+1}.
+        let cf1_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_gadget(
+            cs.clone(),
+            &self.poseidon_config,
+            cf_U_i_vec,
+            cf1_u_i.clone(),
+            cf1_cmT.clone(),
+        )?;
+        let cf1_r_nonnat = {
+            let mut bits = cf1_r_bits.clone();
+            bits.resize(C1::BaseField::MODULUS_BIT_SIZE as usize, Boolean::FALSE);
+            NonNativeUintVar::from(&bits)
+        };
+        let cf1_U_i1 = NIFSFullGadget::<C2, GC2>::fold_committed_instance(
+            cf1_r_bits,
+            cf1_r_nonnat,
+            cf1_cmT,
+            cf_U_i,
+            cf1_u_i,
+        )?;
+
+        let cf2_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_gadget(
+            cs.clone(),
+            &self.poseidon_config,
+            cf1_U_i1.to_constraint_field()?,
+            cf2_u_i.clone(),
+            cf2_cmT.clone(),
+        )?;
+        let cf2_r_nonnat = {
+            let mut bits = cf2_r_bits.clone();
+            bits.resize(C1::BaseField::MODULUS_BIT_SIZE as usize, Boolean::FALSE);
+            NonNativeUintVar::from(&bits)
+        };
+        let cf_U_i1 = NIFSFullGadget::<C2, GC2>::fold_committed_instance(
+            cf2_r_bits,
+            cf2_r_nonnat,
+            cf2_cmT,
+            cf1_U_i1,
+            cf2_u_i,
+        )?;
+
+        // Back to Primary Part
+        // P.4.b compute and check the second output of F'
+        // Base case: u_{i+1}.x[1] == H(cf_U_{\bot})
+        // Non-base case: u_{i+1}.x[1] == H(cf_U_{i+1})
+        let (cf_u_i1_x, _) = cf_U_i1.clone().hash(&crh_params)?;
+        let (cf_u_i1_x_base, _) =
+            CycleFoldCommittedInstanceVar::new_constant(cs.clone(), cf_u_dummy)?
+                .hash(&crh_params)?;
+        let cf_x = FpVar::new_input(cs.clone(), || {
+            Ok(self.cf_x.unwrap_or(cf_u_i1_x_base.value()?))
+        })?;
+        cf_x.enforce_equal(&is_basecase.select(&cf_u_i1_x_base, &cf_u_i1_x)?)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective as Projective};
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::UniformRand;
+
+    use crate::folding::supernova::{ivc_hash, running_instance_digest};
+    use crate::transcript::poseidon::poseidon_canonical_config;
+
+    #[test]
+    fn test_ivc_hash_gadget() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let U: Vec<CommittedInstance<Projective>> = (0..3)
+            .map(|_| CommittedInstance {
+                cmE: Projective::rand(&mut rng),
+                u: Fr::rand(&mut rng),
+                cmW: Projective::rand(&mut rng),
+                x: vec![Fr::rand(&mut rng); 2],
+            })
+            .collect();
+        let pp_hash = Fr::rand(&mut rng);
+        let i = Fr::from(3_u32);
+        let pc = Fr::from(1_u32);
+        let z_0 = vec![Fr::from(1_u32), Fr::from(3_u32)];
+        let z_i = vec![Fr::from(1_u32), Fr::from(5_u32)];
+
+        // native
+        let digests = U
+            .iter()
+            .map(|U_j| running_instance_digest(&poseidon_config, U_j))
+            .collect::<Result<Vec<Fr>, _>>()
+            .unwrap();
+        let h = ivc_hash(
+            &poseidon_config,
+            pp_hash,
+            i,
+            pc,
+            z_0.clone(),
+            z_i.clone(),
+            digests.clone(),
+        )
+        .unwrap();
+
+        // gadget
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let crh_params = CRHParametersVar::<Fr>::new_constant(cs.clone(), poseidon_config).unwrap();
+        let UVar =
+            Vec::<CommittedInstanceVar<Projective>>::new_witness(cs.clone(), || Ok(U)).unwrap();
+        let digestsVar = UVar
+            .iter()
+            .map(|U_j| running_instance_digest_gadget(&crh_params, U_j).map(|(d, _)| d))
+            .collect::<Result<Vec<FpVar<Fr>>, _>>()
+            .unwrap();
+        let hVar = ivc_hash_gadget(
+            &crh_params,
+            FpVar::new_witness(cs.clone(), || Ok(pp_hash)).unwrap(),
+            FpVar::new_witness(cs.clone(), || Ok(i)).unwrap(),
+            FpVar::new_witness(cs.clone(), || Ok(pc)).unwrap(),
+            Vec::new_witness(cs.clone(), || Ok(z_0)).unwrap(),
+            Vec::new_witness(cs.clone(), || Ok(z_i)).unwrap(),
+            digestsVar.clone(),
+        )
+        .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        assert_eq!(digestsVar.value().unwrap(), digests);
+        assert_eq!(hVar.value().unwrap(), h);
+    }
+}
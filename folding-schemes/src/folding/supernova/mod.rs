@@ -0,0 +1,767 @@
+/// Implements a non-uniform IVC (NIVC) scheme following
+/// [SuperNova](https://eprint.iacr.org/2022/1758.pdf), built on top of the Nova+CycleFold
+/// primitives.
+///
+/// Instead of a single F circuit, the scheme is given a list of step circuits
+/// `F[0], ..., F[n-1]`. The first element of the state `z_i` is the program counter, which
+/// selects the circuit executed at step i; the step circuits compute the next program counter as
+/// part of `z_{i+1}`. Each step circuit has its own AugmentedFCircuit (and thus its own R1CS), and
+/// the prover keeps one running instance per step circuit, so that each step only pays for the
+/// circuit being executed, instead of for the union of all the step circuits. The CycleFold
+/// running instance is shared by all the circuits.
+///
+/// The IVC hash commits to the digests of all the running instances, and to the index of the
+/// circuit that produced the last incoming instance, which is the running instance in which it is
+/// folded at the next step.
+use ark_crypto_primitives::{
+    crh::{poseidon::CRH, CRHScheme},
+    sponge::{poseidon::PoseidonConfig, Absorb},
+};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{One, Zero};
+use core::marker::PhantomData;
+
+use crate::ccs::r1cs::{extract_w_x, R1CS};
+use crate::commitment::CommitmentScheme;
+use crate::folding::circuits::{nonnative::affine::nonnative_affine_to_field_elements, CF2};
+use crate::folding::nova::{
+    circuits::ChallengeGadget,
+    cyclefold::{fold_cyclefold_circuit, CycleFoldCircuit},
+    get_cm_coordinates, get_r1cs_from_cs,
+    nifs::NIFS,
+    traits::NovaR1CS,
+    CommittedInstance, ProverParams, Witness,
+};
+use crate::frontend::FCircuit;
+use crate::utils::{hash_bytes, serialize_poseidon_config};
+use crate::Error;
+
+pub mod circuits;
+
+use circuits::AugmentedFCircuit;
+
+/// returns `H(U)`, the digest of a running instance, which is what the IVC hash commits to for
+/// each of the running instances. Compatible with `circuits::running_instance_digest_gadget`.
+pub fn running_instance_digest<C: CurveGroup>(
+    poseidon_config: &PoseidonConfig<C::ScalarField>,
+    U: &CommittedInstance<C>,
+) -> Result<C::ScalarField, Error>
+where
+    <C as Group>::ScalarField: Absorb,
+    <C as CurveGroup>::BaseField: PrimeField,
+{
+    let (cmE_x, cmE_y) = nonnative_affine_to_field_elements::<C>(U.cmE)?;
+    let (cmW_x, cmW_y) = nonnative_affine_to_field_elements::<C>(U.cmW)?;
+    CRH::<C::ScalarField>::evaluate(
+        poseidon_config,
+        [vec![U.u], U.x.clone(), cmE_x, cmE_y, cmW_x, cmW_y].concat(),
+    )
+    .map_err(|e| Error::Other(e.to_string()))
+}
+
+/// returns `H(pp_hash, i, pc, z_0, z_i, H(U[0]), ..., H(U[n-1]))`, where `pc` is the index of the
+/// circuit that produced the instance containing this hash. Compatible with
+/// `circuits::ivc_hash_gadget`.
+pub fn ivc_hash<F: PrimeField + Absorb>(
+    poseidon_config: &PoseidonConfig<F>,
+    pp_hash: F,
+    i: F,
+    pc: F,
+    z_0: Vec<F>,
+    z_i: Vec<F>,
+    U_digests: Vec<F>,
+) -> Result<F, Error> {
+    CRH::<F>::evaluate(
+        poseidon_config,
+        [vec![pp_hash, i, pc], z_0, z_i, U_digests].concat(),
+    )
+    .map_err(|e| Error::Other(e.to_string()))
+}
+
+/// returns the index of the circuit selected by the program counter `z[0]`
+pub fn program_counter<F: PrimeField>(z: &[F], n_circuits: usize) -> Result<usize, Error> {
+    let pc = z.first().ok_or(Error::Empty)?;
+    (0..n_circuits)
+        .find(|j| F::from(*j as u64) == *pc)
+        .ok_or_else(|| Error::InvalidProgramCounter(pc.to_string()))
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifierParams<C1: CurveGroup, C2: CurveGroup> {
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    /// R1CS of the AugmentedFCircuit of each of the step circuits
+    pub r1cs: Vec<R1CS<C1::ScalarField>>,
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+    /// digest of the public parameters, computed by `pp_hash`
+    pub pp_hash: C1::ScalarField,
+}
+
+/// returns the digest of the public parameters: the R1CS of all the AugmentedFCircuits and of the
+/// CycleFold circuit, the commitment schemes parameters and the Poseidon config.
+pub fn pp_hash<C1, C2, CS1, CS2>(
+    pp: &ProverParams<C1, C2, CS1, CS2>,
+    r1cs: &[R1CS<C1::ScalarField>],
+    cf_r1cs: &R1CS<C2::ScalarField>,
+) -> Result<C1::ScalarField, Error>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    C1::ScalarField: Absorb,
+{
+    let mut bytes = vec![];
+    r1cs.serialize_compressed(&mut bytes)?;
+    cf_r1cs.serialize_compressed(&mut bytes)?;
+    pp.cs_params.serialize_compressed(&mut bytes)?;
+    pp.cf_cs_params.serialize_compressed(&mut bytes)?;
+    serialize_poseidon_config(&pp.poseidon_config, &mut bytes)?;
+    hash_bytes(&pp.poseidon_config, &bytes)
+}
+
+/// Implements the SuperNova-style NIVC on top of Nova+CycleFold. All the step circuits share the
+/// type `FC`, so when they are different circuits they are expected to be wrapped in an enum
+/// implementing `FCircuit`.
+#[derive(Clone, Debug)]
+pub struct SuperNova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    /// R1CS of the AugmentedFCircuit of each of the step circuits
+    pub r1cs: Vec<R1CS<C1::ScalarField>>,
+    /// R1CS of the CycleFold circuit
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    /// CommitmentScheme::ProverParams over C1
+    pub cs_params: CS1::ProverParams,
+    /// CycleFold CommitmentScheme::ProverParams, over C2
+    pub cf_cs_params: CS2::ProverParams,
+    /// digest of the public parameters, see `pp_hash`
+    pub pp_hash: C1::ScalarField,
+    /// step circuits, the one at index `z_i[0]` is executed at the step i
+    pub F: Vec<FC>,
+    pub i: C1::ScalarField,
+    /// initial state
+    pub z_0: Vec<C1::ScalarField>,
+    /// current i-th state
+    pub z_i: Vec<C1::ScalarField>,
+    /// index of the circuit that produced the incoming instance u_i
+    pub last_pc: usize,
+    /// incoming instance
+    pub w_i: Witness<C1>,
+    pub u_i: CommittedInstance<C1>,
+    /// running instances, one per step circuit
+    pub W_i: Vec<Witness<C1>>,
+    pub U_i: Vec<CommittedInstance<C1>>,
+
+    /// CycleFold running instance
+    pub cf_W_i: Witness<C2>,
+    pub cf_U_i: CommittedInstance<C2>,
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> SuperNova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    /// computes the R1CS of each of the AugmentedFCircuits and the verifier params
+    pub fn preprocess(
+        pp: &ProverParams<C1, C2, CS1, CS2>,
+        F: &[FC],
+    ) -> Result<VerifierParams<C1, C2>, Error> {
+        let (r1cs, cf_r1cs) = get_r1cs::<C1, GC1, C2, GC2, FC>(&pp.poseidon_config, F)?;
+        let pp_hash = pp_hash(pp, &r1cs, &cf_r1cs)?;
+        Ok(VerifierParams {
+            poseidon_config: pp.poseidon_config.clone(),
+            r1cs,
+            cf_r1cs,
+            pp_hash,
+        })
+    }
+
+    /// Initializes the NIVC for the given parameters, step circuits and initial state `z_0`, whose
+    /// first element is the initial program counter.
+    pub fn init(
+        pp: &ProverParams<C1, C2, CS1, CS2>,
+        F: Vec<FC>,
+        z_0: Vec<C1::ScalarField>,
+    ) -> Result<Self, Error> {
+        let pc = program_counter(&z_0, F.len())?;
+        let (r1cs, cf_r1cs) = get_r1cs::<C1, GC1, C2, GC2, FC>(&pp.poseidon_config, &F)?;
+        let pp_hash = pp_hash(pp, &r1cs, &cf_r1cs)?;
+
+        // setup the dummy instances, the dummy incoming instance is considered to be produced by
+        // the first circuit to be executed, so that it is folded into its (dummy) running instance
+        let (W_i, U_i): (Vec<Witness<C1>>, Vec<CommittedInstance<C1>>) =
+            r1cs.iter().map(|r1cs_j| r1cs_j.dummy_instance()).unzip();
+        let (cf_w_dummy, cf_u_dummy) = cf_r1cs.dummy_instance();
+
+        Ok(Self {
+            _gc1: PhantomData,
+            _c2: PhantomData,
+            _gc2: PhantomData,
+            poseidon_config: pp.poseidon_config.clone(),
+            cs_params: pp.cs_params.clone(),
+            cf_cs_params: pp.cf_cs_params.clone(),
+            pp_hash,
+            F,
+            i: C1::ScalarField::zero(),
+            z_0: z_0.clone(),
+            z_i: z_0,
+            last_pc: pc,
+            w_i: W_i[pc].clone(),
+            u_i: U_i[pc].clone(),
+            W_i,
+            U_i,
+            cf_W_i: cf_w_dummy,
+            cf_U_i: cf_u_dummy,
+            r1cs,
+            cf_r1cs,
+        })
+    }
+
+    /// returns the index of the circuit to be executed at the next step
+    pub fn pc(&self) -> Result<usize, Error> {
+        program_counter(&self.z_i, self.F.len())
+    }
+
+    /// Implements the NIVC prover step: runs the step circuit selected by the program counter,
+    /// folding the incoming instance into the running instance of the circuit that produced it.
+    pub fn prove_step(&mut self, external_inputs: Vec<C1::ScalarField>) -> Result<(), Error> {
+        let pc = self.pc()?;
+        let F = self.F[pc].clone();
+        let last_pc = self.last_pc;
+
+        if external_inputs.len() != F.external_inputs_len() {
+            return Err(Error::NotSameLength(
+                "F.external_inputs_len()".to_string(),
+                F.external_inputs_len(),
+                "external_inputs.len()".to_string(),
+                external_inputs.len(),
+            ));
+        }
+
+        if self.i > C1::ScalarField::from_le_bytes_mod_order(&usize::MAX.to_le_bytes()) {
+            return Err(Error::MaxStep);
+        }
+
+        #[cfg(target_pointer_width = "64")]
+        let i_usize: usize = {
+            let mut i_bytes: [u8; 8] = [0; 8];
+            i_bytes.copy_from_slice(&self.i.into_bigint().to_bytes_le()[..8]);
+            usize::from_le_bytes(i_bytes)
+        };
+        #[cfg(target_pointer_width = "32")]
+        let i_usize: usize = {
+            let mut i_bytes: [u8; 4] = [0; 4];
+            i_bytes.copy_from_slice(&self.i.into_bigint().to_bytes_le()[..4]);
+            usize::from_le_bytes(i_bytes)
+        };
+
+        let z_i1 = F.step_native(i_usize, self.z_i.clone(), external_inputs.clone())?;
+
+        // fold u_i into the running instance of the circuit that produced it
+        let U_i = self.U_i[last_pc].clone();
+        let W_i = self.W_i[last_pc].clone();
+        let (T, cmT) = NIFS::<C1, CS1>::compute_cmT(
+            &self.cs_params,
+            &self.r1cs[last_pc],
+            &self.w_i,
+            &self.u_i,
+            &W_i,
+            &U_i,
+        )?;
+
+        let r_bits = ChallengeGadget::<C1>::get_challenge_native(
+            &self.poseidon_config,
+            self.pp_hash,
+            U_i.clone(),
+            self.u_i.clone(),
+            cmT,
+        )?;
+        let r_Fr = C1::ScalarField::from_bigint(BigInteger::from_bits_le(&r_bits))
+            .ok_or(Error::OutOfBounds)?;
+        let r_Fq = C1::BaseField::from_bigint(BigInteger::from_bits_le(&r_bits))
+            .ok_or(Error::OutOfBounds)?;
+
+        let W_i1 =
+            NIFS::<C1, CS1>::fold_witness(r_Fr, &W_i, &self.w_i, &T, C1::ScalarField::zero())?;
+        let U_i1 = NIFS::<C1, CS1>::fold_committed_instance(r_Fr, &U_i, &self.u_i, &cmT);
+
+        let U_i_digests = self
+            .U_i
+            .iter()
+            .map(|U_j| running_instance_digest(&self.poseidon_config, U_j))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut U_i1_digests = U_i_digests.clone();
+        U_i1_digests[last_pc] = running_instance_digest(&self.poseidon_config, &U_i1)?;
+
+        // u_{i+1}.x[0] = H(pp_hash, i+1, pc, z_0, z_{i+1}, H(U_{i+1}[0]), ..., H(U_{i+1}[n-1]))
+        let u_i1_x = ivc_hash(
+            &self.poseidon_config,
+            self.pp_hash,
+            self.i + C1::ScalarField::one(),
+            C1::ScalarField::from(pc as u64),
+            self.z_0.clone(),
+            z_i1.clone(),
+            U_i1_digests,
+        )?;
+        // u_{i+1}.x[1] = H(cf_U_{i+1})
+        let cf_u_i1_x: C1::ScalarField;
+
+        let mut augmented_F_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
+            pp_hash: Some(self.pp_hash),
+            i: Some(self.i),
+            i_usize: Some(i_usize),
+            last_pc: Some(C1::ScalarField::from(last_pc as u64)),
+            z_0: Some(self.z_0.clone()),
+            z_i: Some(self.z_i.clone()),
+            external_inputs: Some(external_inputs),
+            u_i_cmW: Some(self.u_i.cmW),
+            U_i: Some(U_i.clone()),
+            U_i_digests: Some(U_i_digests),
+            U_i1_cmE: Some(U_i1.cmE),
+            U_i1_cmW: Some(U_i1.cmW),
+            cmT: Some(cmT),
+            x: Some(u_i1_x),
+            ..AugmentedFCircuit::empty(&self.poseidon_config, F, pc, self.F.len())
+        };
+
+        if self.i == C1::ScalarField::zero() {
+            // base case, the CycleFold instances are not folded
+            cf_u_i1_x = self.cf_U_i.hash_cyclefold(&self.poseidon_config)?;
+        } else {
+            // CycleFold part:
+            // get the vector used as public inputs 'x' in the CycleFold circuit
+            // cyclefold circuit for cmW
+            let cfW_u_i_x = [
+                vec![r_Fq],
+                get_cm_coordinates(&U_i.cmW),
+                get_cm_coordinates(&self.u_i.cmW),
+                get_cm_coordinates(&U_i1.cmW),
+            ]
+            .concat();
+            // cyclefold circuit for cmE
+            let cfE_u_i_x = [
+                vec![r_Fq],
+                get_cm_coordinates(&U_i.cmE),
+                get_cm_coordinates(&cmT),
+                get_cm_coordinates(&U_i1.cmE),
+            ]
+            .concat();
+
+            let cfW_circuit = CycleFoldCircuit::<C1, GC1> {
+                _gc: PhantomData,
+                r_bits: Some(r_bits.clone()),
+                p1: Some(U_i.cmW),
+                p2: Some(self.u_i.cmW),
+                x: Some(cfW_u_i_x.clone()),
+            };
+            let cfE_circuit = CycleFoldCircuit::<C1, GC1> {
+                _gc: PhantomData,
+                r_bits: Some(r_bits),
+                p1: Some(U_i.cmE),
+                p2: Some(cmT),
+                x: Some(cfE_u_i_x.clone()),
+            };
+
+            // fold self.cf_U_i + cfW_U -> folded running with cfW
+            let (_cfW_w_i, cfW_u_i, cfW_W_i1, cfW_U_i1, cfW_cmT, _) =
+                fold_cyclefold_circuit::<C1, GC1, C2, GC2, CS2>(
+                    &self.poseidon_config,
+                    self.cf_r1cs.clone(),
+                    self.cf_cs_params.clone(),
+                    self.cf_W_i.clone(),
+                    self.cf_U_i.clone(),
+                    cfW_u_i_x,
+                    cfW_circuit,
+                )?;
+            // fold [the output from folding self.cf_U_i + cfW_U] + cfE_U
+            let (_cfE_w_i, cfE_u_i, cf_W_i1, cf_U_i1, cf_cmT, _) =
+                fold_cyclefold_circuit::<C1, GC1, C2, GC2, CS2>(
+                    &self.poseidon_config,
+                    self.cf_r1cs.clone(),
+                    self.cf_cs_params.clone(),
+                    cfW_W_i1,
+                    cfW_U_i1,
+                    cfE_u_i_x,
+                    cfE_circuit,
+                )?;
+
+            cf_u_i1_x = cf_U_i1.hash_cyclefold(&self.poseidon_config)?;
+
+            augmented_F_circuit.cf1_u_i_cmW = Some(cfW_u_i.cmW);
+            augmented_F_circuit.cf2_u_i_cmW = Some(cfE_u_i.cmW);
+            augmented_F_circuit.cf_U_i = Some(self.cf_U_i.clone());
+            augmented_F_circuit.cf1_cmT = Some(cfW_cmT);
+            augmented_F_circuit.cf2_cmT = Some(cf_cmT);
+
+            self.cf_W_i = cf_W_i1;
+            self.cf_U_i = cf_U_i1;
+
+            #[cfg(test)]
+            {
+                self.cf_r1cs.check_instance_relation(&_cfW_w_i, &cfW_u_i)?;
+                self.cf_r1cs.check_instance_relation(&_cfE_w_i, &cfE_u_i)?;
+                self.cf_r1cs
+                    .check_relaxed_instance_relation(&self.cf_W_i, &self.cf_U_i)?;
+            }
+        }
+        augmented_F_circuit.cf_x = Some(cf_u_i1_x);
+
+        let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+
+        augmented_F_circuit.generate_constraints(cs.clone())?;
+
+        #[cfg(test)]
+        assert!(cs.is_satisfied().unwrap());
+
+        let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+        let (w_i1, x_i1) = extract_w_x::<C1::ScalarField>(&cs);
+        if x_i1[0] != u_i1_x || x_i1[1] != cf_u_i1_x {
+            return Err(Error::NotEqual);
+        }
+
+        // set values for next iteration
+        self.i += C1::ScalarField::one();
+        self.z_i = z_i1;
+        self.last_pc = pc;
+        self.w_i = Witness::<C1>::new(w_i1, self.r1cs[pc].A.n_rows);
+        self.u_i = self.w_i.commit::<CS1, false>(&self.cs_params, x_i1)?;
+        self.W_i[last_pc] = W_i1;
+        self.U_i[last_pc] = U_i1;
+
+        #[cfg(test)]
+        {
+            self.r1cs[pc].check_instance_relation(&self.w_i, &self.u_i)?;
+            self.r1cs[last_pc]
+                .check_relaxed_instance_relation(&self.W_i[last_pc], &self.U_i[last_pc])?;
+        }
+
+        Ok(())
+    }
+
+    pub fn state(&self) -> Vec<C1::ScalarField> {
+        self.z_i.clone()
+    }
+
+    /// returns the running instances (one per step circuit), the incoming instance together with
+    /// the index of the circuit that produced it, and the CycleFold running instance
+    #[allow(clippy::type_complexity)]
+    pub fn instances(
+        &self,
+    ) -> (
+        Vec<(CommittedInstance<C1>, Witness<C1>)>,
+        (usize, CommittedInstance<C1>, Witness<C1>),
+        (CommittedInstance<C2>, Witness<C2>),
+    ) {
+        (
+            self.U_i
+                .iter()
+                .cloned()
+                .zip(self.W_i.iter().cloned())
+                .collect(),
+            (self.last_pc, self.u_i.clone(), self.w_i.clone()),
+            (self.cf_U_i.clone(), self.cf_W_i.clone()),
+        )
+    }
+
+    /// Implements the NIVC verifier
+    #[allow(clippy::type_complexity)]
+    pub fn verify(
+        vp: VerifierParams<C1, C2>,
+        z_0: Vec<C1::ScalarField>, // initial state
+        z_i: Vec<C1::ScalarField>, // last state
+        num_steps: C1::ScalarField,
+        running_instances: Vec<(CommittedInstance<C1>, Witness<C1>)>,
+        incoming_instance: (usize, CommittedInstance<C1>, Witness<C1>),
+        cyclefold_instance: (CommittedInstance<C2>, Witness<C2>),
+    ) -> Result<(), Error> {
+        let (last_pc, u_i, w_i) = incoming_instance;
+        let (cf_U_i, cf_W_i) = cyclefold_instance;
+
+        if running_instances.len() != vp.r1cs.len() || last_pc >= vp.r1cs.len() {
+            return Err(Error::IVCVerificationFail);
+        }
+        if u_i.x.len() != 2 || running_instances.iter().any(|(U, _)| U.x.len() != 2) {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check that u_i's output points to the running instances
+        // u_i.X[0] == H(pp_hash, i, last_pc, z_0, z_i, H(U_i[0]), ..., H(U_i[n-1]))
+        let U_i_digests = running_instances
+            .iter()
+            .map(|(U, _)| running_instance_digest(&vp.poseidon_config, U))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let expected_u_i_x = ivc_hash(
+            &vp.poseidon_config,
+            vp.pp_hash,
+            num_steps,
+            C1::ScalarField::from(last_pc as u64),
+            z_0,
+            z_i,
+            U_i_digests,
+        )?;
+        if expected_u_i_x != u_i.x[0] {
+            return Err(Error::IVCVerificationFail);
+        }
+        // u_i.X[1] == H(cf_U_i)
+        let expected_cf_u_i_x = cf_U_i.hash_cyclefold(&vp.poseidon_config)?;
+        if expected_cf_u_i_x != u_i.x[1] {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check u_i.cmE==0, u_i.u==1 (=u_i is a un-relaxed instance)
+        if !u_i.cmE.is_zero() || !u_i.u.is_one() {
+            return Err(Error::IVCVerificationFail);
+        }
+
+        // check R1CS satisfiability of the incoming instance, for the circuit that produced it
+        vp.r1cs[last_pc].check_instance_relation(&w_i, &u_i)?;
+        // check RelaxedR1CS satisfiability of each running instance
+        for ((U, W), r1cs) in running_instances.iter().zip(vp.r1cs.iter()) {
+            r1cs.check_relaxed_instance_relation(W, U)?;
+        }
+
+        // check CycleFold RelaxedR1CS satisfiability
+        vp.cf_r1cs
+            .check_relaxed_instance_relation(&cf_W_i, &cf_U_i)?;
+
+        Ok(())
+    }
+}
+
+/// helper method to get the R1CS of the AugmentedFCircuit of each of the step circuits, and of the
+/// CycleFold circuit
+#[allow(clippy::type_complexity)]
+pub fn get_r1cs<C1, GC1, C2, GC2, FC>(
+    poseidon_config: &PoseidonConfig<C1::ScalarField>,
+    F: &[FC],
+) -> Result<(Vec<R1CS<C1::ScalarField>>, R1CS<C2::ScalarField>), Error>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    if F.is_empty() {
+        return Err(Error::Empty);
+    }
+    let r1cs = F
+        .iter()
+        .enumerate()
+        .map(|(pc, F_j)| {
+            get_r1cs_from_cs::<C1::ScalarField>(AugmentedFCircuit::<C1, C2, GC2, FC>::empty(
+                poseidon_config,
+                F_j.clone(),
+                pc,
+                F.len(),
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let cf_r1cs = get_r1cs_from_cs::<C2::ScalarField>(CycleFoldCircuit::<C1, GC1>::empty())?;
+    Ok((r1cs, cf_r1cs))
+}
+
+/// helper method to get the commitment params length needed for the biggest of the
+/// AugmentedFCircuits, and for the CycleFold circuit
+pub fn get_cs_params_len<C1, GC1, C2, GC2, FC>(
+    poseidon_config: &PoseidonConfig<C1::ScalarField>,
+    F: &[FC],
+) -> Result<(usize, usize), Error>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    let (r1cs, cf_r1cs) = get_r1cs::<C1, GC1, C2, GC2, FC>(poseidon_config, F)?;
+    let cs_len = r1cs.iter().map(|r1cs_j| r1cs_j.A.n_rows).max().unwrap_or(0);
+    Ok((cs_len, cf_r1cs.A.n_rows))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bn254::{constraints::GVar, Fr, G1Projective as Projective};
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+    use ark_r1cs_std::{
+        alloc::AllocVar,
+        fields::{fp::FpVar, FieldVar},
+    };
+    use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+    use crate::commitment::pedersen::Pedersen;
+    use crate::transcript::poseidon::poseidon_canonical_config;
+
+    /// OpcodeFCircuit is a toy VM with two opcodes over the state `[pc, x]`: opcode 0 computes
+    /// `x + 5` and jumps to opcode 1, and opcode 1 computes `x^3` and jumps back to opcode 0.
+    #[derive(Clone, Copy, Debug)]
+    pub enum OpcodeFCircuit<F: PrimeField> {
+        Add5(PhantomData<F>),
+        Cube(PhantomData<F>),
+    }
+    impl<F: PrimeField> FCircuit<F> for OpcodeFCircuit<F> {
+        type Params = usize;
+        fn new(opcode: Self::Params) -> Result<Self, Error> {
+            match opcode {
+                0 => Ok(Self::Add5(PhantomData)),
+                1 => Ok(Self::Cube(PhantomData)),
+                _ => Err(Error::InvalidProgramCounter(opcode.to_string())),
+            }
+        }
+        fn state_len(&self) -> usize {
+            2
+        }
+        fn external_inputs_len(&self) -> usize {
+            0
+        }
+        fn step_native(
+            &self,
+            _i: usize,
+            z_i: Vec<F>,
+            _external_inputs: Vec<F>,
+        ) -> Result<Vec<F>, Error> {
+            match self {
+                Self::Add5(_) => Ok(vec![F::one(), z_i[1] + F::from(5_u32)]),
+                Self::Cube(_) => Ok(vec![F::zero(), z_i[1] * z_i[1] * z_i[1]]),
+            }
+        }
+        fn generate_step_constraints(
+            &self,
+            cs: ConstraintSystemRef<F>,
+            _i: usize,
+            z_i: Vec<FpVar<F>>,
+            _external_inputs: Vec<FpVar<F>>,
+        ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+            match self {
+                Self::Add5(_) => {
+                    let five = FpVar::<F>::new_constant(cs.clone(), F::from(5_u32))?;
+                    Ok(vec![FpVar::one(), &z_i[1] + five])
+                }
+                Self::Cube(_) => Ok(vec![FpVar::zero(), &z_i[1] * &z_i[1] * &z_i[1]]),
+            }
+        }
+    }
+
+    #[test]
+    fn test_nivc() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+
+        let F = vec![
+            OpcodeFCircuit::<Fr>::new(0).unwrap(),
+            OpcodeFCircuit::<Fr>::new(1).unwrap(),
+        ];
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, OpcodeFCircuit<Fr>>(
+                &poseidon_config,
+                &F,
+            )
+            .unwrap();
+        let (cs_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_cs_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        let pp =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params,
+                cf_cs_params,
+            };
+
+        type NIVC = SuperNova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            OpcodeFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >;
+        let vp = NIVC::preprocess(&pp, &F).unwrap();
+        // each opcode has its own (smaller than the union) R1CS
+        assert_ne!(vp.r1cs[0].A.n_rows, vp.r1cs[1].A.n_rows);
+
+        let z_0 = vec![Fr::zero(), Fr::from(3_u32)];
+        let mut nivc = NIVC::init(&pp, F, z_0.clone()).unwrap();
+
+        let num_steps: usize = 5;
+        let mut expected_x = Fr::from(3_u32);
+        for i in 0..num_steps {
+            assert_eq!(nivc.pc().unwrap(), i % 2);
+            nivc.prove_step(vec![]).unwrap();
+            expected_x = if i % 2 == 0 {
+                expected_x + Fr::from(5_u32)
+            } else {
+                expected_x * expected_x * expected_x
+            };
+        }
+        assert_eq!(nivc.i, Fr::from(num_steps as u32));
+        assert_eq!(nivc.state(), vec![Fr::one(), expected_x]);
+
+        let (running_instances, incoming_instance, cyclefold_instance) = nivc.instances();
+        NIVC::verify(
+            vp.clone(),
+            z_0.clone(),
+            nivc.state(),
+            nivc.i,
+            running_instances.clone(),
+            incoming_instance.clone(),
+            cyclefold_instance.clone(),
+        )
+        .unwrap();
+
+        // claiming that the incoming instance was produced by the other circuit must fail
+        let (last_pc, u_i, w_i) = incoming_instance;
+        assert!(NIVC::verify(
+            vp,
+            z_0,
+            nivc.state(),
+            nivc.i,
+            running_instances,
+            (1 - last_pc, u_i, w_i),
+            cyclefold_instance,
+        )
+        .is_err());
+    }
+}
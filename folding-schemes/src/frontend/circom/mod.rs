@@ -13,8 +13,10 @@ use ark_r1cs_std::R1CSVar;
 use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
 use ark_std::fmt::Debug;
 use num_bigint::BigInt;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::{BufReader, Cursor, Read, Seek};
+use std::io::{BufRead, BufReader, Cursor, Read, Seek};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::{fmt, usize};
@@ -22,10 +24,13 @@ use std::{fmt, usize};
 // #[cfg(not(target_arch = "wasm32"))]
 pub mod utils;
 // #[cfg(not(target_arch = "wasm32"))]
-use utils::CircomWrapper;
+use utils::{read_sym, CircomWrapper};
 
 type ClosurePointer<F> = Rc<dyn Fn(usize, Vec<F>, Vec<F>) -> Result<Vec<F>, Error>>;
 
+/// last computed witness, keyed by the `(i, z_i, external_inputs)` it was computed from
+type WitnessCache<F> = Rc<RefCell<Option<((usize, Vec<F>, Vec<F>), Vec<F>)>>>;
+
 #[derive(Clone)]
 struct CustomStepNative<F: PrimeField> {
     func: ClosurePointer<F>,
@@ -49,9 +54,41 @@ pub struct CircomFCircuit<F: PrimeField> {
     pub external_inputs_len: usize,
     r1cs: CircomR1CS<F>,
     custom_step_native_code: Option<CustomStepNative<F>>,
+    // shared between clones, so that the witness computed in `step_native` is reused when the
+    // folding scheme synthesizes the constraints of the same step from its copy of the circuit
+    witness_cache: WitnessCache<F>,
+    // signal names of each witness index, loaded from the circom `.sym` file for debugging
+    symbols: Option<Rc<BTreeMap<usize, Vec<String>>>>,
 }
 
 impl<F: PrimeField> CircomFCircuit<F> {
+    /// Creates a CircomFCircuit whose witnesses are computed natively from the circuit's
+    /// witness-generation graph instead of through the circom-generated `.wasm`.
+    /// Params: (r1cs_path, graph_path, state_len, external_inputs_len)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new_with_graph(params: (PathBuf, PathBuf, usize, usize)) -> Result<Self, Error> {
+        let (r1cs_path, graph_path, state_len, external_inputs_len) = params;
+        let circom_wrapper = CircomWrapper::from_files_with_graph(r1cs_path, graph_path)?;
+        Self::from_wrapper(circom_wrapper, state_len, external_inputs_len)
+    }
+
+    fn from_wrapper(
+        circom_wrapper: CircomWrapper<F>,
+        state_len: usize,
+        external_inputs_len: usize,
+    ) -> Result<Self, Error> {
+        let r1cs = circom_wrapper.extract_r1cs()?;
+        Ok(Self {
+            circom_wrapper,
+            state_len,
+            external_inputs_len,
+            r1cs,
+            custom_step_native_code: None,
+            witness_cache: Rc::new(RefCell::new(None)),
+            symbols: None,
+        })
+    }
+
     pub fn set_custom_step_native(&mut self, func: ClosurePointer<F>) {
         self.custom_step_native_code = Some(CustomStepNative::<F> { func });
     }
@@ -70,32 +107,132 @@ impl<F: PrimeField> CircomFCircuit<F> {
             #[cfg(test)]
             assert_eq!(external_inputs.len(), self.external_inputs_len());
 
-            let inputs_bi = z_i
+            let witness = self.compute_witness(_i, z_i, external_inputs)?;
+
+            // Extracts the z_i1(next state) from the witness vector.
+            let z_i1 = witness[1..1 + self.state_len].to_vec();
+            Ok(z_i1)
+        }
+    }
+
+    /// Loads the signal names from the circom `.sym` file. Once loaded, each computed witness is
+    /// checked against the circuit's constraints, reporting the failing constraint and the
+    /// signals involved in it.
+    pub fn load_sym<R: BufRead>(&mut self, sym_reader: R) -> Result<(), Error> {
+        self.symbols = Some(Rc::new(read_sym(sym_reader)?));
+        Ok(())
+    }
+
+    /// Loads the signal names from the given `.sym` file path, see `load_sym`.
+    pub fn load_sym_file(&mut self, sym_path: PathBuf) -> Result<(), Error> {
+        let file = File::open(sym_path)?;
+        self.load_sym(BufReader::new(file))
+    }
+
+    /// Returns the names of the signals held by the given witness index, or `wire_{index}` if no
+    /// `.sym` file has been loaded.
+    fn signal_name(&self, index: usize) -> String {
+        self.symbols
+            .as_ref()
+            .and_then(|symbols| symbols.get(&index))
+            .map(|names| names.join(" / "))
+            .unwrap_or_else(|| format!("wire_{}", index))
+    }
+
+    /// Checks that the given witness satisfies the circom R1CS constraints, returning the index of
+    /// the first unsatisfied constraint and the named signals (with their values) involved in it.
+    pub fn check_witness(&self, witness: &[F]) -> Result<(), Error> {
+        let eval_lc = |lc: &[(usize, F)]| -> Result<F, Error> {
+            lc.iter().try_fold(F::zero(), |acc, (index, coeff)| {
+                let value = witness.get(*index).ok_or(Error::OutOfBounds)?;
+                Ok(acc + *coeff * value)
+            })
+        };
+        for (constraint_index, (a, b, c)) in self.r1cs.constraints.iter().enumerate() {
+            if eval_lc(a)? * eval_lc(b)? == eval_lc(c)? {
+                continue;
+            }
+            let mut indexes: Vec<usize> = a.iter().chain(b).chain(c).map(|(i, _)| *i).collect();
+            indexes.sort_unstable();
+            indexes.dedup();
+            let signals = indexes
+                .into_iter()
+                .map(|index| self.format_signal(index, witness[index]))
+                .collect::<Vec<String>>()
+                .join(", ");
+            return Err(Error::CircomConstraintNotSatisfied(
+                constraint_index,
+                signals,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the given witness as lines of `signal_name = value`, using the signal names from
+    /// the `.sym` file if loaded.
+    pub fn dump_witness(&self, witness: &[F]) -> String {
+        witness
+            .iter()
+            .enumerate()
+            .map(|(index, value)| self.format_signal(index, *value))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn format_signal(&self, index: usize, value: F) -> String {
+        format!(
+            "{} = {}",
+            self.signal_name(index),
+            self.circom_wrapper.ark_primefield_to_num_bigint(value)
+        )
+    }
+
+    /// Computes the witness for the step `i` with the given state and external inputs, reusing
+    /// the last computed witness if it was computed from the same values. If a `.sym` file has
+    /// been loaded, the witness is checked against the circuit's constraints.
+    pub fn compute_witness(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        let key = (i, z_i, external_inputs);
+        if let Some((cached_key, witness)) = self.witness_cache.borrow().as_ref() {
+            if *cached_key == key {
+                return Ok(witness.clone());
+            }
+        }
+
+        let inputs_bi = key
+            .1
+            .iter()
+            .map(|val| self.circom_wrapper.ark_primefield_to_num_bigint(*val))
+            .collect::<Vec<BigInt>>();
+        let mut inputs_map = vec![("ivc_input".to_string(), inputs_bi)];
+
+        if self.external_inputs_len > 0 {
+            let external_inputs_bi = key
+                .2
                 .iter()
                 .map(|val| self.circom_wrapper.ark_primefield_to_num_bigint(*val))
                 .collect::<Vec<BigInt>>();
-            let mut inputs_map = vec![("ivc_input".to_string(), inputs_bi)];
-
-            if self.external_inputs_len > 0 {
-                let external_inputs_bi = external_inputs
-                    .iter()
-                    .map(|val| self.circom_wrapper.ark_primefield_to_num_bigint(*val))
-                    .collect::<Vec<BigInt>>();
-                inputs_map.push(("external_inputs".to_string(), external_inputs_bi));
-            }
+            inputs_map.push(("external_inputs".to_string(), external_inputs_bi));
+        }
 
-            // Computes witness
-            let witness = self
-                .circom_wrapper
-                .extract_witness(&inputs_map)
-                .map_err(|e| {
-                    Error::WitnessCalculationError(format!("Failed to calculate witness: {}", e))
-                })?;
+        // Computes witness
+        let witness = self
+            .circom_wrapper
+            .extract_witness(&inputs_map)
+            .map_err(|e| {
+                Error::WitnessCalculationError(format!("Failed to calculate witness: {}", e))
+            })?;
 
-            // Extracts the z_i1(next state) from the witness vector.
-            let z_i1 = witness[1..1 + self.state_len].to_vec();
-            Ok(z_i1)
+        if self.symbols.is_some() {
+            self.check_witness(&witness)?;
         }
+
+        *self.witness_cache.borrow_mut() = Some((key, witness.clone()));
+        Ok(witness)
     }
 }
 
@@ -123,14 +260,7 @@ impl<F: PrimeField> FCircuit<F> for CircomFCircuit<F> {
             CircomWrapper::new(r1cs_reader, &wasm_bytes)?
         };
 
-        let r1cs = circom_wrapper.extract_r1cs()?;
-        Ok(Self {
-            circom_wrapper,
-            state_len,
-            external_inputs_len,
-            r1cs,
-            custom_step_native_code: None,
-        })
+        Self::from_wrapper(circom_wrapper, state_len, external_inputs_len)
     }
 
     fn state_len(&self) -> usize {
@@ -161,17 +291,16 @@ impl<F: PrimeField> FCircuit<F> for CircomFCircuit<F> {
         #[cfg(test)]
         assert_eq!(external_inputs.len(), self.external_inputs_len());
 
-        let input_values = self.fpvars_to_bigints(&z_i)?;
-        let mut inputs_map = vec![("ivc_input".to_string(), input_values)];
-
-        if self.external_inputs_len() > 0 {
-            let external_inputs_bi = self.fpvars_to_bigints(&external_inputs)?;
-            inputs_map.push(("external_inputs".to_string(), external_inputs_bi));
-        }
+        // SynthesisError can not carry the details of the failure, so log them before mapping it
         let witness = self
-            .circom_wrapper
-            .extract_witness(&inputs_map)
-            .map_err(|_| SynthesisError::AssignmentMissing)?;
+            .compute_witness(_i, z_i.value()?, external_inputs.value()?)
+            .map_err(|e| {
+                log::error!("Failed to compute the Circom witness: {}", e);
+                match e {
+                    Error::CircomConstraintNotSatisfied(..) => SynthesisError::Unsatisfiable,
+                    _ => SynthesisError::AssignmentMissing,
+                }
+            })?;
 
         // Since public inputs are already allocated variables, we will tell `circom-compat` to not re-allocate those
         let mut already_allocated_public_inputs = vec![];
@@ -209,23 +338,6 @@ impl<F: PrimeField> FCircuit<F> for CircomFCircuit<F> {
     }
 }
 
-impl<F: PrimeField> CircomFCircuit<F> {
-    fn fpvars_to_bigints(&self, fpVars: &[FpVar<F>]) -> Result<Vec<BigInt>, SynthesisError> {
-        let mut input_values = Vec::new();
-        // converts each FpVar to PrimeField value, then to num_bigint::BigInt.
-        for fp_var in fpVars.iter() {
-            // extracts the PrimeField value from FpVar.
-            let primefield_value = fp_var.value()?;
-            // converts the PrimeField value to num_bigint::BigInt.
-            let num_bigint_value = self
-                .circom_wrapper
-                .ark_primefield_to_num_bigint(primefield_value);
-            input_values.push(num_bigint_value);
-        }
-        Ok(input_values)
-    }
-}
-
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -369,6 +481,107 @@ pub mod tests {
         // assert!(z_i1_var.is_err())
     }
 
+    // Tests the WrapperCircuit with a CircomFCircuit using the native witness-graph backend.
+    #[test]
+    fn test_wrapper_circom_graph_backend() {
+        let r1cs_path = PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit.r1cs");
+        let graph_path = PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit.graph");
+
+        let circom_fcircuit =
+            CircomFCircuit::<Fr>::new_with_graph((r1cs_path, graph_path, 1, 0)).unwrap();
+
+        let z_i = vec![Fr::from(3_u32)];
+        let z_i1 = circom_fcircuit.step_native(0, z_i.clone(), vec![]).unwrap();
+        assert_eq!(z_i1, vec![Fr::from(35u32)]);
+
+        let wrapper_circuit = crate::frontend::tests::WrapperCircuit {
+            FC: circom_fcircuit,
+            z_i: Some(z_i),
+            z_i1: Some(z_i1),
+        };
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        wrapper_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    // Tests that the witness computed by step_native is reused when generating the constraints of
+    // the same step, also from a clone of the circuit.
+    #[test]
+    fn test_circom_witness_cache() {
+        let r1cs_path = PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit.r1cs");
+        let wasm_path =
+            PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit_js/cubic_circuit.wasm");
+
+        let circom_fcircuit = CircomFCircuit::<Fr>::new((r1cs_path, wasm_path, 1, 0)).unwrap(); // state_len:1, external_inputs_len:0
+
+        let z_i = vec![Fr::from(3u32)];
+        let z_i1 = circom_fcircuit.step_native(1, z_i.clone(), vec![]).unwrap();
+        let (key, witness) = circom_fcircuit.witness_cache.borrow().clone().unwrap();
+        assert_eq!(key, (1, z_i.clone(), vec![]));
+
+        // replace the cached witness by a marked copy, to detect whether it gets recomputed
+        let mut marked_witness = witness.clone();
+        let marker = marked_witness.len() - 1;
+        marked_witness[marker] += Fr::from(1u32);
+        *circom_fcircuit.witness_cache.borrow_mut() = Some((key, marked_witness.clone()));
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_i_var = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
+        let z_i1_var = circom_fcircuit
+            .clone()
+            .generate_step_constraints(cs.clone(), 1, z_i_var.clone(), vec![])
+            .unwrap();
+        assert_eq!(z_i1_var.value().unwrap(), z_i1);
+        assert_eq!(
+            circom_fcircuit.witness_cache.borrow().as_ref().unwrap().1,
+            marked_witness
+        );
+
+        // a different step recomputes the witness
+        circom_fcircuit
+            .generate_step_constraints(cs.clone(), 2, z_i_var, vec![])
+            .unwrap();
+        assert_eq!(
+            circom_fcircuit.witness_cache.borrow().as_ref().unwrap().1,
+            witness
+        );
+    }
+
+    // Tests the debugging helpers using the signal names from the .sym file.
+    #[test]
+    fn test_circom_sym_debugging() {
+        let r1cs_path = PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit.r1cs");
+        let wasm_path =
+            PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit_js/cubic_circuit.wasm");
+        let sym_path = PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit.sym");
+
+        let mut circom_fcircuit = CircomFCircuit::<Fr>::new((r1cs_path, wasm_path, 1, 0)).unwrap(); // state_len:1, external_inputs_len:0
+        circom_fcircuit.load_sym_file(sym_path).unwrap();
+
+        let witness = circom_fcircuit
+            .compute_witness(0, vec![Fr::from(3u32)], vec![])
+            .unwrap();
+        let dump = circom_fcircuit.dump_witness(&witness);
+        assert!(dump.contains("main.ivc_input[0] = 3"));
+        assert!(dump.contains("main.ivc_output[0] = 35"));
+        assert!(dump.contains("main.temp = 9"));
+
+        // tamper the `temp` signal, the error reports the constraint `temp <== in * in`
+        let mut bad_witness = witness.clone();
+        let temp_index = dump
+            .lines()
+            .position(|line| line.starts_with("main.temp "))
+            .unwrap();
+        bad_witness[temp_index] = Fr::from(10u32);
+        match circom_fcircuit.check_witness(&bad_witness) {
+            Err(Error::CircomConstraintNotSatisfied(_, signals)) => {
+                assert!(signals.contains("main.temp = 10"));
+                assert!(signals.contains("main.ivc_input[0] = 3"));
+            }
+            _ => panic!("expected an unsatisfied constraint"),
+        }
+    }
+
     #[test]
     fn test_custom_code() {
         let r1cs_path = PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit.r1cs");
@@ -2,3 +2,8 @@
 circom ./folding-schemes/src/frontend/circom/test_folder/cubic_circuit.circom --r1cs --sym --wasm --prime bn128 --output ./folding-schemes/src/frontend/circom/test_folder/
 circom ./folding-schemes/src/frontend/circom/test_folder/with_external_inputs.circom --r1cs --sym --wasm --prime bn128 --output ./folding-schemes/src/frontend/circom/test_folder/
 circom ./folding-schemes/src/frontend/circom/test_folder/no_external_inputs.circom --r1cs --sym --wasm --prime bn128 --output ./folding-schemes/src/frontend/circom/test_folder/
+
+# witness-generation graphs, used by the native (non-wasm) witness calculator backend
+for circuit in cubic_circuit with_external_inputs no_external_inputs; do
+    build-circuit ./folding-schemes/src/frontend/circom/test_folder/${circuit}.circom ./folding-schemes/src/frontend/circom/test_folder/${circuit}.graph
+done
@@ -7,46 +7,160 @@ use ark_circom::{
     WitnessCalculator,
 };
 use ark_ff::{BigInteger, PrimeField};
+#[cfg(not(target_arch = "wasm32"))]
+use circom_witnesscalc::{
+    graph::{evaluate, Node},
+    storage::deserialize_witnesscalc_graph,
+};
 use color_eyre::Result;
 use num_bigint::{BigInt, Sign};
+#[cfg(not(target_arch = "wasm32"))]
+use ruint::aliases::U256;
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
 use std::{
+    collections::BTreeMap,
     fs::File,
-    io::{BufReader, Read, Seek},
+    io::{BufRead, BufReader, Read, Seek},
     marker::PhantomData,
     path::PathBuf,
 };
 use wasmer::{Module, Store};
 
+/// Backend used to compute the witness of a Circom circuit.
+#[derive(Clone, Debug)]
+pub enum CircomWitnessCalculator {
+    /// instantiates the circom-generated `.wasm` witness calculator through wasmer
+    Wasm(WitnessCalculator),
+    /// evaluates natively the witness-generation graph produced by circom-witnesscalc's
+    /// `build-circuit` from the `.circom` sources, avoiding the wasm runtime
+    #[cfg(not(target_arch = "wasm32"))]
+    Graph(WitnessGraph),
+}
+
+/// Witness-generation graph produced by circom-witnesscalc's `build-circuit`, deserialized once so
+/// that each witness computation only evaluates it.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub struct WitnessGraph {
+    nodes: Vec<Node>,
+    signals: Vec<usize>,
+    // maps each input signal name to its (offset, length) in the inputs buffer
+    inputs: HashMap<String, (usize, usize)>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl WitnessGraph {
+    fn new(graph_bytes: &[u8]) -> Result<Self, Error> {
+        let (nodes, signals, inputs) = deserialize_witnesscalc_graph(graph_bytes).map_err(|e| {
+            Error::WitnessCalculationError(format!("Failed to read witness graph: {}", e))
+        })?;
+        Ok(Self {
+            nodes,
+            signals,
+            inputs,
+        })
+    }
+
+    fn calculate_witness(&self, inputs: &[(String, Vec<BigInt>)]) -> Result<Vec<BigInt>, Error> {
+        // the input nodes are at the beginning of the graph, and the buffer slot 0 holds the
+        // constant 1
+        let inputs_len = self
+            .nodes
+            .iter()
+            .take_while(|node| matches!(node, Node::Input(_)))
+            .filter_map(|node| match node {
+                Node::Input(i) => Some(*i),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let mut buffer = vec![U256::ZERO; inputs_len];
+        buffer[0] = U256::from(1u64);
+        for (name, values) in inputs {
+            let (offset, len) = *self.inputs.get(name).ok_or_else(|| {
+                Error::WitnessCalculationError(format!("Unknown input signal: {}", name))
+            })?;
+            if values.len() != len {
+                return Err(Error::NotSameLength(
+                    format!("input signal {}", name),
+                    len,
+                    "values".to_string(),
+                    values.len(),
+                ));
+            }
+            for (i, v) in values.iter().enumerate() {
+                buffer[offset + i] = v
+                    .to_biguint()
+                    .and_then(|v| U256::try_from_le_slice(&v.to_bytes_le()))
+                    .ok_or_else(|| {
+                        Error::BigIntConversionError(format!("Invalid input value: {}", v))
+                    })?;
+            }
+        }
+        Ok(evaluate(&self.nodes, &buffer, &self.signals)
+            .iter()
+            .map(|v| BigInt::from_bytes_le(Sign::Plus, &v.to_le_bytes::<32>()))
+            .collect())
+    }
+}
+
 // A struct that wraps Circom functionalities, allowing for extraction of R1CS and witnesses
-// based on file paths to Circom's .r1cs and .wasm.
+// based on file paths to Circom's .r1cs and .wasm (or witness graph).
 #[derive(Clone, Debug)]
 pub struct CircomWrapper<F: PrimeField> {
     // r1cs_reader: R,
     // wasm_bytes: Vec<u8>,
     pub r1cs: R1CS<F>,
-    pub calculator: WitnessCalculator,
+    pub calculator: CircomWitnessCalculator,
     _f: PhantomData<F>,
 }
 
 impl<F: PrimeField> CircomWrapper<F> {
     // Creates a new instance of the CircomWrapper with the file paths.
     pub fn new<R: Read + Seek>(r1cs_reader: R, wasm_bytes: &[u8]) -> Result<Self, Error> {
-        let r1cs_file = r1cs_reader::R1CSFile::<F>::new(r1cs_reader)?;
-        let r1cs = r1cs_reader::R1CS::<F>::from(r1cs_file);
+        let r1cs = Self::read_r1cs(r1cs_reader)?;
         let store = Store::default();
         let module = Module::new(&store, &wasm_bytes).map_err(|e| {
             Error::WitnessCalculationError(format!("Failed to create Wasm module: {}", e))
         })?;
-        let mut calculator = WitnessCalculator::from_module(module).map_err(|e| {
+        let calculator = WitnessCalculator::from_module(module).map_err(|e| {
             Error::WitnessCalculationError(format!("Failed to create WitnessCalculator: {}", e))
         })?;
         Ok(Self {
             r1cs,
-            calculator,
+            calculator: CircomWitnessCalculator::Wasm(calculator),
             _f: PhantomData,
         })
     }
 
+    /// Creates a new instance of the CircomWrapper which computes the witness natively from the
+    /// witness-generation graph (generated with circom-witnesscalc's `build-circuit`). The graph
+    /// is evaluated over BN254's scalar field, so F must be that field.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new_with_graph<R: Read + Seek>(
+        r1cs_reader: R,
+        graph_bytes: &[u8],
+    ) -> Result<Self, Error> {
+        if F::MODULUS.to_bytes_le() != ark_bn254::Fr::MODULUS.to_bytes_le() {
+            return Err(Error::NotSupported(
+                "the witness graph backend only supports BN254's scalar field".to_string(),
+            ));
+        }
+        let r1cs = Self::read_r1cs(r1cs_reader)?;
+        Ok(Self {
+            r1cs,
+            calculator: CircomWitnessCalculator::Graph(WitnessGraph::new(graph_bytes)?),
+            _f: PhantomData,
+        })
+    }
+
+    fn read_r1cs<R: Read + Seek>(r1cs_reader: R) -> Result<R1CS<F>, Error> {
+        let r1cs_file = r1cs_reader::R1CSFile::<F>::new(r1cs_reader)?;
+        Ok(r1cs_reader::R1CS::<F>::from(r1cs_file))
+    }
+
     /// Creates a new instance of the CircomWrapper with the file paths.
     pub fn from_files(r1cs_filepath: PathBuf, wasm_filepath: PathBuf) -> Result<Self, Error> {
         let file = File::open(&r1cs_filepath)?;
@@ -55,6 +169,19 @@ impl<F: PrimeField> CircomWrapper<F> {
         Self::new(r1cs_reader, &wasm_bytes)
     }
 
+    /// Creates a new instance of the CircomWrapper with the file paths to the .r1cs and to the
+    /// witness-generation graph.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_files_with_graph(
+        r1cs_filepath: PathBuf,
+        graph_filepath: PathBuf,
+    ) -> Result<Self, Error> {
+        let file = File::open(&r1cs_filepath)?;
+        let r1cs_reader = BufReader::new(file);
+        let graph_bytes = std::fs::read(graph_filepath)?;
+        Self::new_with_graph(r1cs_reader, &graph_bytes)
+    }
+
     /// Aggregated function to obtain R1CS and witness from Circom.
     pub fn extract_r1cs_and_witness(
         &self,
@@ -94,17 +221,21 @@ impl<F: PrimeField> CircomWrapper<F> {
             .collect()
     }
 
-    // Calculates the witness given the Wasm filepath and inputs.
+    // Calculates the witness given the inputs, using the wrapper's witness calculator backend.
     pub fn calculate_witness(
         &self,
         inputs: &[(String, Vec<BigInt>)],
     ) -> Result<Vec<BigInt>, Error> {
-        self.calculator
-            .clone()
-            .calculate_witness(inputs.iter().cloned(), true)
-            .map_err(|e| {
-                Error::WitnessCalculationError(format!("Failed to calculate witness: {}", e))
-            })
+        match &self.calculator {
+            CircomWitnessCalculator::Wasm(calculator) => calculator
+                .clone()
+                .calculate_witness(inputs.iter().cloned(), true)
+                .map_err(|e| {
+                    Error::WitnessCalculationError(format!("Failed to calculate witness: {}", e))
+                }),
+            #[cfg(not(target_arch = "wasm32"))]
+            CircomWitnessCalculator::Graph(graph) => graph.calculate_witness(inputs),
+        }
     }
 
     // Converts a num_bigint::BigInt to a PrimeField::BigInt.
@@ -125,6 +256,34 @@ impl<F: PrimeField> CircomWrapper<F> {
     }
 }
 
+/// Reads the `.sym` file generated by circom, returning for each witness index the names of the
+/// signals that it holds. Each line of the file has the form `label,wire,component,name`, where
+/// `wire` is -1 for the signals removed by the circom optimizer.
+pub fn read_sym<R: BufRead>(reader: R) -> Result<BTreeMap<usize, Vec<String>>, Error> {
+    let mut symbols: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.splitn(4, ',').collect();
+        if fields.len() != 4 {
+            return Err(Error::Other(format!("Invalid .sym line: {}", line)));
+        }
+        let wire = fields[1]
+            .parse::<i64>()
+            .map_err(|e| Error::Other(format!("Invalid .sym line: {}, {}", line, e)))?;
+        if wire < 0 {
+            continue;
+        }
+        symbols
+            .entry(wire as usize)
+            .or_default()
+            .push(fields[3].to_string());
+    }
+    Ok(symbols)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +336,57 @@ mod tests {
         circom_circuit.generate_constraints(cs.clone()).unwrap();
         assert!(cs.is_satisfied().unwrap());
     }
+
+    // Test that the native witness-graph backend computes the same witnesses as the wasm one
+    #[test]
+    fn test_graph_witness_matches_wasm() {
+        let test_folder = "./src/frontend/circom/test_folder";
+        for (name, inputs) in [
+            (
+                "cubic_circuit",
+                vec![("ivc_input".to_string(), vec![BigInt::from(3)])],
+            ),
+            (
+                "with_external_inputs",
+                vec![
+                    ("ivc_input".to_string(), vec![BigInt::from(3)]),
+                    (
+                        "external_inputs".to_string(),
+                        vec![BigInt::from(6), BigInt::from(7)],
+                    ),
+                ],
+            ),
+            (
+                "no_external_inputs",
+                vec![(
+                    "ivc_input".to_string(),
+                    vec![BigInt::from(3), BigInt::from(4), BigInt::from(5)],
+                )],
+            ),
+        ] {
+            let r1cs_path = PathBuf::from(format!("{}/{}.r1cs", test_folder, name));
+            let wasm_path = PathBuf::from(format!("{}/{}_js/{}.wasm", test_folder, name, name));
+            let graph_path = PathBuf::from(format!("{}/{}.graph", test_folder, name));
+
+            let wasm_wrapper =
+                CircomWrapper::<Fr>::from_files(r1cs_path.clone(), wasm_path).unwrap();
+            let graph_wrapper =
+                CircomWrapper::<Fr>::from_files_with_graph(r1cs_path, graph_path).unwrap();
+
+            let wasm_witness = wasm_wrapper.extract_witness(&inputs).unwrap();
+            let graph_witness = graph_wrapper.extract_witness(&inputs).unwrap();
+            assert_eq!(wasm_witness, graph_witness);
+        }
+    }
+
+    // The witness graph is evaluated over BN254's scalar field, so it can not be used with any
+    // other field
+    #[test]
+    fn test_graph_rejects_other_field() {
+        let r1cs_path = PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit.r1cs");
+        let graph_path = PathBuf::from("./src/frontend/circom/test_folder/cubic_circuit.graph");
+        assert!(
+            CircomWrapper::<ark_pallas::Fr>::from_files_with_graph(r1cs_path, graph_path).is_err()
+        );
+    }
 }
@@ -0,0 +1,350 @@
+//! Frontend for Noir circuits, which are read from the ACIR artifact produced by `nargo compile`.
+//!
+//! The ACIR program is executed with the `StubbedBlackBoxSolver` of acvm, which does not implement
+//! any black box function. So the Noir programs using blackbox opcodes (ie. sha256, keccak,
+//! poseidon, pedersen, ecdsa, schnorr, range constraints, etc) fail to compute the witness, with an
+//! `Error::WitnessCalculationError`. Only programs built from arithmetic opcodes (and Brillig
+//! unconstrained functions) are supported.
+use crate::frontend::FCircuit;
+use crate::Error;
+
+use acvm::{
+    acir::{
+        acir_field::GenericFieldElement,
+        circuit::{brillig::BrilligBytecode, Circuit, Program},
+        native_types::WitnessMap,
+    },
+    blackbox_solver::StubbedBlackBoxSolver,
+    pwg::{ACVMStatus, ACVM},
+};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, R1CSVar};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use arkworks_backend::{read_program_from_file, sonobe_bridge::AcirCircuitSonobe};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// NoirFCircuit wraps a compiled Noir program (the ACIR artifact produced by `nargo compile`),
+/// whose `main` function takes the state `z_i` as its public parameters, the external inputs as its
+/// private parameters, and returns the next state `z_{i+1}`.
+#[derive(Clone, Debug)]
+pub struct NoirFCircuit<F: PrimeField> {
+    pub circuit: Circuit<GenericFieldElement<F>>,
+    pub unconstrained_functions: Vec<BrilligBytecode<GenericFieldElement<F>>>,
+    pub state_len: usize,
+    pub external_inputs_len: usize,
+}
+
+impl<F: PrimeField> FCircuit<F> for NoirFCircuit<F> {
+    /// (artifact_path, state_len, external_inputs_len)
+    type Params = (PathBuf, usize, usize);
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let (path, state_len, external_inputs_len) = params;
+        let program: Program<GenericFieldElement<F>> = read_program_from_file(path)
+            .map_err(|e| Error::Other(format!("Failed to read the ACIR program: {:?}", e)))?;
+        let circuit = program
+            .functions
+            .first()
+            .ok_or_else(|| Error::Other("The ACIR program has no functions".to_string()))?
+            .clone();
+
+        let n_public_parameters = circuit.public_parameters.0.len();
+        let n_return_values = circuit.return_values.0.len();
+        if n_public_parameters != state_len {
+            return Err(Error::NotSameLength(
+                "public_parameters.len()".to_string(),
+                n_public_parameters,
+                "state_len".to_string(),
+                state_len,
+            ));
+        }
+        if n_return_values != state_len {
+            return Err(Error::NotSameLength(
+                "return_values.len()".to_string(),
+                n_return_values,
+                "state_len".to_string(),
+                state_len,
+            ));
+        }
+        if circuit.private_parameters.len() != external_inputs_len {
+            return Err(Error::NotSameLength(
+                "private_parameters.len()".to_string(),
+                circuit.private_parameters.len(),
+                "external_inputs_len".to_string(),
+                external_inputs_len,
+            ));
+        }
+
+        Ok(Self {
+            circuit,
+            unconstrained_functions: program.unconstrained_functions,
+            state_len,
+            external_inputs_len,
+        })
+    }
+
+    fn state_len(&self) -> usize {
+        self.state_len
+    }
+    fn external_inputs_len(&self) -> usize {
+        self.external_inputs_len
+    }
+
+    fn step_native(
+        &self,
+        _i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        let witness_map = self.solve(&z_i, &external_inputs)?;
+
+        // extracts the z_{i+1} (next state) from the witness map
+        self.circuit
+            .return_values
+            .0
+            .iter()
+            .map(|witness| {
+                witness_map
+                    .get(witness)
+                    .map(|value| value.into_repr())
+                    .ok_or_else(|| {
+                        Error::WitnessCalculationError(format!(
+                            "Missing value for the return witness {}",
+                            witness.witness_index()
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        _i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        #[cfg(test)]
+        assert_eq!(z_i.len(), self.state_len());
+        #[cfg(test)]
+        assert_eq!(external_inputs.len(), self.external_inputs_len());
+
+        let witness_map = self
+            .solve(&z_i.value()?, &external_inputs.value()?)
+            .map_err(|_| SynthesisError::AssignmentMissing)?;
+
+        // allocates z_{i+1} from the computed witness
+        let z_i1 = self
+            .circuit
+            .return_values
+            .0
+            .iter()
+            .map(|witness| {
+                let value = witness_map
+                    .get(witness)
+                    .ok_or(SynthesisError::AssignmentMissing)?;
+                FpVar::<F>::new_witness(cs.clone(), || Ok(value.into_repr()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Since z_i, external_inputs and z_{i+1} are already allocated variables, we tell the ACIR
+        // circuit to reuse them instead of allocating new ones, which binds them to its constraints
+        let mut already_assigned_witnesses = HashMap::new();
+        for (witness, var) in self
+            .circuit
+            .public_parameters
+            .0
+            .iter()
+            .zip(z_i.iter())
+            .chain(
+                self.circuit
+                    .private_parameters
+                    .iter()
+                    .zip(external_inputs.iter()),
+            )
+            .chain(self.circuit.return_values.0.iter().zip(z_i1.iter()))
+        {
+            // a return value can be one of the parameters, in which case it was already assigned
+            already_assigned_witnesses.entry(*witness).or_insert(var);
+        }
+
+        let mut acir_circuit = AcirCircuitSonobe::from((&self.circuit, witness_map));
+        acir_circuit.already_assigned_witnesses = already_assigned_witnesses;
+        acir_circuit.generate_constraints(cs.clone())?;
+
+        Ok(z_i1)
+    }
+}
+
+impl<F: PrimeField> NoirFCircuit<F> {
+    /// runs the ACVM over the circuit with the given state and external inputs, returning the
+    /// resulting witness map.
+    fn solve(
+        &self,
+        z_i: &[F],
+        external_inputs: &[F],
+    ) -> Result<WitnessMap<GenericFieldElement<F>>, Error> {
+        // the parameters of the main function are assigned to the witnesses in order, so the
+        // (sorted) public and private parameters follow the order of z_i and external_inputs
+        let mut initial_witness = WitnessMap::new();
+        for (witness, value) in self
+            .circuit
+            .public_parameters
+            .0
+            .iter()
+            .zip(z_i)
+            .chain(self.circuit.private_parameters.iter().zip(external_inputs))
+        {
+            initial_witness.insert(*witness, GenericFieldElement::from_repr(*value));
+        }
+
+        let mut acvm = ACVM::new(
+            &StubbedBlackBoxSolver,
+            &self.circuit.opcodes,
+            initial_witness,
+            &self.unconstrained_functions,
+            &self.circuit.assert_messages,
+        );
+        match acvm.solve() {
+            ACVMStatus::Solved => Ok(acvm.finalize()),
+            status => Err(Error::WitnessCalculationError(format!(
+                "Failed to solve the ACIR program: {:?}",
+                status
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bn254::{constraints::GVar, Fr, G1Projective as Projective};
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    use crate::commitment::{pedersen::Pedersen, CommitmentScheme};
+    use crate::folding::nova::{get_cs_params_len, Nova, ProverParams};
+    use crate::transcript::poseidon::poseidon_canonical_config;
+    use crate::FoldingScheme;
+
+    // Tests the step_native & generate_step_constraints functions of NoirFCircuit
+    #[test]
+    fn test_noir_step() {
+        let artifact_path =
+            PathBuf::from("./src/frontend/noir/test_folder/test_circuit/target/test_circuit.json");
+        let noirfcircuit = NoirFCircuit::<Fr>::new((artifact_path, 1, 0)).unwrap();
+
+        let z_i = vec![Fr::from(3u32)];
+        let z_i1 = noirfcircuit.step_native(0, z_i.clone(), vec![]).unwrap();
+        assert_eq!(z_i1, vec![Fr::from(35u32)]);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_i_var = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
+        let z_i1_var = noirfcircuit
+            .generate_step_constraints(cs.clone(), 0, z_i_var, vec![])
+            .unwrap();
+        assert_eq!(z_i1_var.value().unwrap(), z_i1);
+        assert!(cs.is_satisfied().unwrap());
+
+        // z_{i+1} is bound to the Noir constraints, so a wrong z_{i+1} must not be satisfiable
+        let wrapper_circuit = crate::frontend::tests::WrapperCircuit {
+            FC: noirfcircuit.clone(),
+            z_i: Some(vec![Fr::from(3u32)]),
+            z_i1: Some(vec![Fr::from(36u32)]),
+        };
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        wrapper_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_noir_external_inputs() {
+        let artifact_path = PathBuf::from(
+            "./src/frontend/noir/test_folder/test_with_external_inputs/target/test_with_external_inputs.json",
+        );
+        let noirfcircuit = NoirFCircuit::<Fr>::new((artifact_path, 1, 2)).unwrap();
+
+        let z_i = vec![Fr::from(3u32)];
+        let external_inputs = vec![Fr::from(6u32), Fr::from(7u32)];
+        let z_i1 = noirfcircuit
+            .step_native(0, z_i.clone(), external_inputs.clone())
+            .unwrap();
+        // 3^3 + 3*6 + 7
+        assert_eq!(z_i1, vec![Fr::from(52u32)]);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_i_var = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
+        let external_inputs_var =
+            Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(external_inputs)).unwrap();
+        let z_i1_var = noirfcircuit
+            .generate_step_constraints(cs.clone(), 0, z_i_var, external_inputs_var)
+            .unwrap();
+        assert_eq!(z_i1_var.value().unwrap(), z_i1);
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    // Folds the Noir circuit with external inputs through several Nova steps
+    #[test]
+    fn test_noir_nova_prove_step() {
+        let mut rng = ark_std::test_rng();
+        let artifact_path = PathBuf::from(
+            "./src/frontend/noir/test_folder/test_with_external_inputs/target/test_with_external_inputs.json",
+        );
+        let noirfcircuit = NoirFCircuit::<Fr>::new((artifact_path, 1, 2)).unwrap();
+
+        type NOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            NoirFCircuit<Fr>,
+            Pedersen<Projective>,
+            Pedersen<Projective2>,
+        >;
+
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, NoirFCircuit<Fr>>(
+                &poseidon_config,
+                noirfcircuit.clone(),
+            )
+            .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params: pedersen_params,
+                cf_cs_params: cf_pedersen_params,
+            };
+        let (prover_params, verifier_params) =
+            NOVA::preprocess(&(prover_params, noirfcircuit.clone())).unwrap();
+
+        let z_0 = vec![Fr::from(3u32)];
+        let mut nova = NOVA::init(&prover_params, noirfcircuit.clone(), z_0.clone()).unwrap();
+        let mut z_i = z_0.clone();
+        for external_inputs in [
+            vec![Fr::from(6u32), Fr::from(7u32)],
+            vec![Fr::from(1u32), Fr::from(2u32)],
+        ] {
+            z_i = noirfcircuit
+                .step_native(0, z_i, external_inputs.clone())
+                .unwrap();
+            nova.prove_step(&mut rng, external_inputs).unwrap();
+        }
+        assert_eq!(nova.z_i, z_i);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+        )
+        .unwrap();
+    }
+}
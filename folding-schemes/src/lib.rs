@@ -78,6 +78,10 @@ pub enum Error {
     BlindingNotZero,
     #[error("Commitment verification failed")]
     CommitmentVerificationFail,
+    #[error("Invalid Powers of Tau file: {0}")]
+    InvalidPtau(String),
+    #[error("Powers of Tau file does not contain enough powers (available={0} < required={1})")]
+    PtauParamsLen(usize, usize),
 
     // Other
     #[error("{0}")]
@@ -92,8 +96,14 @@ pub enum Error {
     NotSupported(String),
     #[error("max i-th step reached (usize limit reached)")]
     MaxStep,
+    #[error("Checkpoint does not match the current scheme: {0}")]
+    CheckpointMismatch(String),
+    #[error("Invalid program counter: {0}")]
+    InvalidProgramCounter(String),
     #[error("Circom Witness calculation error: {0}")]
     WitnessCalculationError(String),
+    #[error("Circom constraint {0} not satisfied, involved signals: {1}")]
+    CircomConstraintNotSatisfied(usize, String),
     #[error("BigInt to PrimeField conversion error: {0}")]
     BigIntConversionError(String),
 }
@@ -113,8 +123,9 @@ where
     type PreprocessorParam: Debug;
     type ProverParam: Debug;
     type VerifierParam: Debug;
-    type CommittedInstanceWithWitness: Debug;
-    type CFCommittedInstanceWithWitness: Debug; // CycleFold CommittedInstance & Witness
+    type RunningInstance: Debug; // contains the CommittedInstance + Witness
+    type IncomingInstance: Debug; // contains the CommittedInstance + Witness
+    type CFInstance: Debug; // CycleFold CommittedInstance & Witness
 
     fn preprocess(
         prep_param: &Self::PreprocessorParam,
@@ -126,7 +137,26 @@ where
         z_0: Vec<C1::ScalarField>, // initial state
     ) -> Result<Self, Error>;
 
-    fn prove_step(&mut self, external_inputs: Vec<C1::ScalarField>) -> Result<(), Error>;
+    // `rng` is used to sample the blinding factors of the hiding commitments (see Nova's
+    // zero-knowledge mode), schemes which do not blind their commitments can ignore it.
+    fn prove_step(
+        &mut self,
+        rng: impl RngCore + CryptoRng,
+        external_inputs: Vec<C1::ScalarField>,
+    ) -> Result<(), Error>;
+
+    // computes several steps of F, one for each of the given external inputs, by calling
+    // `prove_step` for each of them.
+    fn prove_steps(
+        &mut self,
+        mut rng: impl RngCore + CryptoRng,
+        external_inputs: Vec<Vec<C1::ScalarField>>,
+    ) -> Result<(), Error> {
+        for external_inputs_i in external_inputs {
+            self.prove_step(&mut rng, external_inputs_i)?;
+        }
+        Ok(())
+    }
 
     // returns the state at the current step
     fn state(&self) -> Vec<C1::ScalarField>;
@@ -136,9 +166,9 @@ where
     fn instances(
         &self,
     ) -> (
-        Self::CommittedInstanceWithWitness,
-        Self::CommittedInstanceWithWitness,
-        Self::CFCommittedInstanceWithWitness,
+        Self::RunningInstance,
+        Self::IncomingInstance,
+        Self::CFInstance,
     );
 
     fn verify(
@@ -147,9 +177,9 @@ where
         z_i: Vec<C1::ScalarField>, // last state
         // number of steps between the initial state and the last state
         num_steps: C1::ScalarField,
-        running_instance: Self::CommittedInstanceWithWitness,
-        incoming_instance: Self::CommittedInstanceWithWitness,
-        cyclefold_instance: Self::CFCommittedInstanceWithWitness,
+        running_instance: Self::RunningInstance,
+        incoming_instance: Self::IncomingInstance,
+        cyclefold_instance: Self::CFInstance,
     ) -> Result<(), Error>;
 }
 
@@ -0,0 +1,130 @@
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_std::Zero;
+use core::marker::PhantomData;
+use sha3::{Digest, Keccak256};
+
+use crate::transcript::Transcript;
+use crate::Error;
+
+/// KeccakTranscript implements the Transcript trait using the Keccak256 hash, so that the
+/// challenges can be cheaply recomputed by an EVM verifier with the `keccak256` opcode.
+///
+/// Each challenge is computed as `keccak256(prev || data)`, where `prev` is the previously
+/// squeezed digest (empty for the first challenge) and `data` the concatenation of the values
+/// absorbed since then. Field elements are encoded as 32-byte big-endian integers (as `uint256`
+/// in Solidity) and points as their affine `x || y` coordinates, with the point at infinity
+/// encoded as `(0, 0)`. The challenge is the digest interpreted as a big-endian integer, reduced
+/// modulo the scalar field.
+///
+/// There is no in-circuit counterpart, since Keccak is too expensive to be computed in-circuit;
+/// it is meant to be used for the parts of the protocols that are verified outside of a circuit.
+pub struct KeccakTranscript<C: CurveGroup> {
+    buffer: Vec<u8>,
+    _c: PhantomData<C>,
+}
+
+impl<C: CurveGroup> KeccakTranscript<C> {
+    fn squeeze(&mut self) -> [u8; 32] {
+        let digest: [u8; 32] = Keccak256::digest(&self.buffer).into();
+        self.buffer = digest.to_vec();
+        digest
+    }
+}
+
+impl<C: CurveGroup> Transcript<C> for KeccakTranscript<C> {
+    type TranscriptConfig = ();
+
+    fn new(_config: &Self::TranscriptConfig) -> Self {
+        Self {
+            buffer: vec![],
+            _c: PhantomData,
+        }
+    }
+    fn absorb(&mut self, v: &C::ScalarField) {
+        self.buffer.extend(v.into_bigint().to_bytes_be());
+    }
+    fn absorb_vec(&mut self, v: &[C::ScalarField]) {
+        for v_i in v {
+            self.absorb(v_i);
+        }
+    }
+    fn absorb_point(&mut self, p: &C) -> Result<(), Error> {
+        let affine = p.into_affine();
+        let zero_point = (&C::BaseField::zero(), &C::BaseField::zero());
+        let (x, y) = affine.xy().unwrap_or(zero_point);
+        for coord in [x, y] {
+            let coord = coord
+                .to_base_prime_field_elements()
+                .next()
+                .ok_or(Error::Other("point coordinate".to_string()))?;
+            self.buffer.extend(coord.into_bigint().to_bytes_be());
+        }
+        Ok(())
+    }
+    fn get_challenge(&mut self) -> C::ScalarField {
+        C::ScalarField::from_be_bytes_mod_order(&self.squeeze())
+    }
+    /// returns the `nbits` least significant bits (in little-endian) of the digest interpreted as
+    /// a big-endian integer, squeezing more digests if `nbits` > 256
+    fn get_challenge_nbits(&mut self, nbits: usize) -> Vec<bool> {
+        let mut bits = Vec::with_capacity(nbits);
+        while bits.len() < nbits {
+            let digest = self.squeeze();
+            bits.extend(
+                digest
+                    .iter()
+                    .rev()
+                    .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1)),
+            );
+        }
+        bits.truncate(nbits);
+        bits
+    }
+    fn get_challenges(&mut self, n: usize) -> Vec<C::ScalarField> {
+        (0..n).map(|_| self.get_challenge()).collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective as G1};
+    use ark_std::{str::FromStr, UniformRand};
+
+    #[test]
+    fn test_keccak_transcript() {
+        // challenge of the empty transcript: keccak256("") mod r
+        let mut tr = KeccakTranscript::<G1>::new(&());
+        let c = tr.get_challenge();
+        assert_eq!(
+            c,
+            Fr::from_str(
+                "1924180730567573949438414972962865885128629851683618892617351438379423999084"
+            )
+            .unwrap()
+        );
+
+        // same absorbed values lead to the same challenges, different values to different ones
+        let mut rng = ark_std::test_rng();
+        let v = Fr::rand(&mut rng);
+        let p = G1::rand(&mut rng);
+        let mut tr1 = KeccakTranscript::<G1>::new(&());
+        let mut tr2 = KeccakTranscript::<G1>::new(&());
+        tr1.absorb(&v);
+        tr2.absorb(&v);
+        tr1.absorb_point(&p).unwrap();
+        tr2.absorb_point(&p).unwrap();
+        assert_eq!(tr1.get_challenges(3), tr2.get_challenges(3));
+        tr1.absorb(&v);
+        tr2.absorb(&(v + Fr::from(1_u32)));
+        assert_ne!(tr1.get_challenge(), tr2.get_challenge());
+
+        // requesting more than 256 bits squeezes several digests
+        let mut tr1 = KeccakTranscript::<G1>::new(&());
+        let mut tr2 = KeccakTranscript::<G1>::new(&());
+        let c_bits = tr1.get_challenge_nbits(300);
+        assert_eq!(c_bits.len(), 300);
+        assert_eq!(c_bits[..256], tr2.get_challenge_nbits(256));
+    }
+}
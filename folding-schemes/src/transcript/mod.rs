@@ -5,6 +5,7 @@ use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar};
 use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
 use ark_std::fmt::Debug;
 
+pub mod keccak;
 pub mod poseidon;
 
 pub trait Transcript<C: CurveGroup> {
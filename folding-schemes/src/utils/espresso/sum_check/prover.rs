@@ -185,9 +185,11 @@ impl<C: CurveGroup> SumCheckProver<C> for IOPProverState<C> {
             .collect();
 
         let prover_poly = compute_lagrange_interpolated_poly::<C::ScalarField>(&products_sum);
-        Ok(IOPProverMessage {
-            coeffs: prover_poly.coeffs,
-        })
+        // pad the coefficients to max_degree + 1, since `DensePolynomial` trims the trailing
+        // zeroes, and we want the prover messages to always have the same length
+        let mut coeffs = prover_poly.coeffs;
+        coeffs.resize(self.poly.aux_info.max_degree + 1, C::ScalarField::ZERO);
+        Ok(IOPProverMessage { coeffs })
     }
 }
 
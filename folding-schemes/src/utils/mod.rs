@@ -1,4 +1,12 @@
-use ark_ff::PrimeField;
+use ark_crypto_primitives::{
+    crh::{poseidon::CRH, CRHScheme},
+    sponge::{poseidon::PoseidonConfig, Absorb},
+};
+use ark_ff::{PrimeField, ToConstraintField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use sha3::{Digest, Sha3_256};
+
+use crate::Error;
 
 pub mod gadgets;
 pub mod hypercube;
@@ -21,3 +29,54 @@ pub fn powers_of<F: PrimeField>(x: F, n: usize) -> Vec<F> {
     }
     c
 }
+
+/// hashes the given bytes into a field element. Since the bytes can be large (ie. the serialized
+/// R1CS and commitment scheme params), they are first hashed with SHA3-256, and the 32 bytes digest
+/// is then hashed with Poseidon, packing it into field elements.
+pub fn hash_bytes<F: PrimeField + Absorb>(
+    poseidon_config: &PoseidonConfig<F>,
+    bytes: &[u8],
+) -> Result<F, Error> {
+    let digest: [u8; 32] = Sha3_256::digest(bytes).into();
+    let elems: Vec<F> = digest[..].to_field_elements().ok_or(Error::Other(
+        "bytes to field elements conversion".to_string(),
+    ))?;
+    CRH::<F>::evaluate(poseidon_config, elems).map_err(|e| Error::Other(e.to_string()))
+}
+
+/// serializes the Poseidon config, so that it can be included in the params hash
+pub fn serialize_poseidon_config<F: PrimeField, W: Write>(
+    poseidon_config: &PoseidonConfig<F>,
+    mut writer: W,
+) -> Result<(), Error> {
+    (poseidon_config.full_rounds as u64).serialize_compressed(&mut writer)?;
+    (poseidon_config.partial_rounds as u64).serialize_compressed(&mut writer)?;
+    poseidon_config.alpha.serialize_compressed(&mut writer)?;
+    poseidon_config.ark.serialize_compressed(&mut writer)?;
+    poseidon_config.mds.serialize_compressed(&mut writer)?;
+    (poseidon_config.rate as u64).serialize_compressed(&mut writer)?;
+    (poseidon_config.capacity as u64).serialize_compressed(&mut writer)?;
+    Ok(())
+}
+
+/// deserializes a Poseidon config serialized by `serialize_poseidon_config`
+pub fn deserialize_poseidon_config<F: PrimeField, R: Read>(
+    mut reader: R,
+) -> Result<PoseidonConfig<F>, SerializationError> {
+    let full_rounds = u64::deserialize_compressed(&mut reader)? as usize;
+    let partial_rounds = u64::deserialize_compressed(&mut reader)? as usize;
+    let alpha = u64::deserialize_compressed(&mut reader)?;
+    let ark = Vec::<Vec<F>>::deserialize_compressed(&mut reader)?;
+    let mds = Vec::<Vec<F>>::deserialize_compressed(&mut reader)?;
+    let rate = u64::deserialize_compressed(&mut reader)? as usize;
+    let capacity = u64::deserialize_compressed(&mut reader)? as usize;
+    Ok(PoseidonConfig::new(
+        full_rounds,
+        partial_rounds,
+        alpha,
+        mds,
+        ark,
+        rate,
+        capacity,
+    ))
+}
@@ -2,6 +2,12 @@
 pub mod evm;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod utils;
+// the calldata JSON is also used by the wasm-prover
+#[cfg(target_arch = "wasm32")]
+pub mod utils {
+    pub mod calldata;
+    pub use calldata::NovaProofJson;
+}
 #[cfg(not(target_arch = "wasm32"))]
 pub mod verifiers;
 
@@ -9,6 +15,9 @@ pub mod verifiers;
 pub use verifiers::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use verifiers::{
-    get_decider_template_for_cyclefold_decider, Groth16VerifierKey, KZG10VerifierKey,
-    NovaCycleFoldVerifierKey, ProtocolVerifierKey,
+    get_decider_template_for_cyclefold_decider,
+    get_decider_template_for_hypernova_cyclefold_decider,
+    get_decider_template_for_protogalaxy_cyclefold_decider, Groth16VerifierKey,
+    HyperNovaCycleFoldVerifierKey, KZG10VerifierKey, NovaCycleFoldVerifierKey,
+    ProtoGalaxyCycleFoldVerifierKey, ProtocolVerifierKey,
 };
@@ -0,0 +1,70 @@
+#![allow(non_snake_case)]
+/// JSON representation of the calldata of the Nova+CycleFold Solidity verifier.
+use serde::{Deserialize, Serialize};
+
+/// The arguments of the `verifyNovaProof` function of the Solidity verifier, as hex strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NovaProofJson {
+    pub i_z0_zi: Vec<String>,         // [i, z0, zi] where |z0| == |zi|
+    pub U_i_cmW_U_i_cmE: [String; 4], // [U_i_cmW[2], U_i_cmE[2]]
+    pub U_i_u_u_i_u_r: [String; 3],   // [U_i_u, u_i_u, r]
+    pub U_i_x_u_i_cmW: [String; 4],   // [U_i_x[2], u_i_cmW[2]]
+    pub u_i_x_cmT: [String; 4],       // [u_i_x[2], cmT[2]]
+    pub pA: [String; 2],              // groth16
+    pub pB: [[String; 2]; 2],         // groth16
+    pub pC: [String; 2],              // groth16
+    pub challenge_W_challenge_E_kzg_evals: [String; 4], // [challenge_W, challenge_E, eval_W, eval_E]
+    pub kzg_proof: [[String; 2]; 2],                    // [W, W'] (KZG batch proof)
+}
+
+impl NovaProofJson {
+    /// Builds the JSON structure from the calldata returned by Nova's `prepare_calldata`, whose
+    /// 32-byte words (after the function selector) follow the order of the verifier's arguments.
+    /// `z_len` is the length of the IVC state.
+    pub fn from_calldata(calldata: &[u8], z_len: usize) -> Self {
+        let mut words = calldata[4..]
+            .chunks(32)
+            .map(|word| "0x".to_string() + hex::encode(word).as_str());
+        let mut next = || words.next().expect("calldata is too short");
+        Self {
+            i_z0_zi: (0..1 + z_len * 2).map(|_| next()).collect(),
+            U_i_cmW_U_i_cmE: [next(), next(), next(), next()],
+            U_i_u_u_i_u_r: [next(), next(), next()],
+            U_i_x_u_i_cmW: [next(), next(), next(), next()],
+            u_i_x_cmT: [next(), next(), next(), next()],
+            pA: [next(), next()],
+            pB: [[next(), next()], [next(), next()]],
+            pC: [next(), next()],
+            challenge_W_challenge_E_kzg_evals: [next(), next(), next(), next()],
+            kzg_proof: [[next(), next()], [next(), next()]],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nova_proof_json_from_calldata() {
+        let z_len = 2;
+        // selector followed by one 32-byte word per argument, where the last byte is its index
+        let n_words = 1 + 2 * z_len + 4 + 3 + 4 + 4 + 2 + 4 + 2 + 4 + 4;
+        let mut calldata = vec![0xaa; 4];
+        for i in 0..n_words {
+            let mut word = [0u8; 32];
+            word[31] = i as u8;
+            calldata.extend(word);
+        }
+        let word = |i: u8| format!("0x{}{:02x}", "00".repeat(31), i);
+
+        let proof_json = NovaProofJson::from_calldata(&calldata, z_len);
+        assert_eq!(proof_json.i_z0_zi, (0..5).map(word).collect::<Vec<_>>());
+        assert_eq!(proof_json.U_i_cmW_U_i_cmE[0], word(5));
+        assert_eq!(proof_json.pB, [[word(22), word(23)], [word(24), word(25)]]);
+        assert_eq!(
+            proof_json.kzg_proof,
+            [[word(32), word(33)], [word(34), word(35)]]
+        );
+    }
+}
@@ -2,8 +2,11 @@ use crate::{GPL3_SDPX_IDENTIFIER, PRAGMA_GROTH16_VERIFIER};
 use askama::Template;
 use crypto::{digest::Digest, sha3::Sha3};
 use num_bigint::BigUint;
+pub mod calldata;
 pub mod encoding;
 
+pub use calldata::NovaProofJson;
+
 /// Formats call data from a vec of bytes to a hashmap
 /// Useful for debugging directly on the EVM
 /// !! Should follow the contract's function signature, we assuming the order of arguments is correct
@@ -29,6 +32,32 @@ pub fn get_function_selector_for_nova_cyclefold_verifier(
     [hash[0], hash[1], hash[2], hash[3]]
 }
 
+/// Computes the function selector for the hypernova cyclefold verifier
+/// It is computed on the fly since it depends on the length of the first parameter array
+pub fn get_function_selector_for_hypernova_cyclefold_verifier(
+    first_param_array_length: usize,
+) -> [u8; 4] {
+    let mut hasher = Sha3::keccak256();
+    let fn_sig = format!("verifyHyperNovaProof(uint256[{}],uint256[4],uint256[2],uint256[2][2],uint256[2],uint256[4],uint256[2][2])", first_param_array_length);
+    hasher.input_str(&fn_sig);
+    let hash = &mut [0u8; 32];
+    hasher.result(hash);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Computes the function selector for the protogalaxy cyclefold verifier
+/// It is computed on the fly since it depends on the length of the first parameter array
+pub fn get_function_selector_for_protogalaxy_cyclefold_verifier(
+    first_param_array_length: usize,
+) -> [u8; 4] {
+    let mut hasher = Sha3::keccak256();
+    let fn_sig = format!("verifyProtoGalaxyProof(uint256[{}],uint256[4],uint256[2],uint256[2][2],uint256[2],uint256[4],uint256[2][2])", first_param_array_length);
+    hasher.input_str(&fn_sig);
+    let hash = &mut [0u8; 32];
+    hasher.result(hash);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
 #[derive(Template)]
 #[template(path = "header_template.askama.sol", ext = "sol")]
 pub struct HeaderInclusion<T: Template> {
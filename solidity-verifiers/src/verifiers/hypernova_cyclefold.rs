@@ -0,0 +1,375 @@
+#![allow(non_snake_case)]
+#![allow(non_camel_case_types)]
+
+use ark_bn254::{Bn254, Fq, G1Affine};
+use ark_groth16::VerifyingKey;
+use ark_poly_commit::kzg10::VerifierKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use askama::Template;
+
+use folding_schemes::folding::circuits::nonnative::uint::NonNativeUintVar;
+
+use super::g16::Groth16Verifier;
+use super::kzg::KZG10Verifier;
+use crate::utils::HeaderInclusion;
+use crate::{Groth16VerifierKey, KZG10VerifierKey, ProtocolVerifierKey, PRAGMA_GROTH16_VERIFIER};
+
+pub fn get_decider_template_for_hypernova_cyclefold_decider(
+    hypernova_cyclefold_vk: HyperNovaCycleFoldVerifierKey,
+) -> String {
+    HeaderInclusion::<HyperNovaCycleFoldDecider>::builder()
+        .template(hypernova_cyclefold_vk)
+        .build()
+        .render()
+        .unwrap()
+}
+
+#[derive(Template, Default)]
+#[template(path = "hypernova_cyclefold_decider.askama.sol", ext = "sol")]
+pub struct HyperNovaCycleFoldDecider {
+    groth16_verifier: Groth16Verifier,
+    kzg10_verifier: KZG10Verifier,
+    // z_len denotes the FCircuit state (z_i) length
+    z_len: usize,
+    public_inputs_len: usize,
+    num_limbs: usize,
+    bits_per_limb: usize,
+}
+
+impl From<HyperNovaCycleFoldVerifierKey> for HyperNovaCycleFoldDecider {
+    fn from(value: HyperNovaCycleFoldVerifierKey) -> Self {
+        let groth16_verifier = Groth16Verifier::from(value.g16_vk);
+        let public_inputs_len = groth16_verifier.gamma_abc_len;
+        let bits_per_limb = NonNativeUintVar::<Fq>::bits_per_limb();
+        Self {
+            groth16_verifier,
+            kzg10_verifier: KZG10Verifier::from(value.kzg_vk),
+            z_len: value.z_len,
+            public_inputs_len,
+            num_limbs: (250_f32 / (bits_per_limb as f32)).ceil() as usize,
+            bits_per_limb,
+        }
+    }
+}
+
+#[derive(CanonicalDeserialize, CanonicalSerialize, PartialEq, Debug, Clone)]
+pub struct HyperNovaCycleFoldVerifierKey {
+    g16_vk: Groth16VerifierKey,
+    kzg_vk: KZG10VerifierKey,
+    z_len: usize,
+}
+
+impl ProtocolVerifierKey for HyperNovaCycleFoldVerifierKey {
+    const PROTOCOL_NAME: &'static str = "HyperNovaCycleFold";
+
+    fn render_as_template(self, pragma: Option<String>) -> Vec<u8> {
+        HeaderInclusion::<HyperNovaCycleFoldDecider>::builder()
+            .pragma_version(pragma.unwrap_or(PRAGMA_GROTH16_VERIFIER.to_string()))
+            .template(self)
+            .build()
+            .render()
+            .unwrap()
+            .into_bytes()
+    }
+}
+
+impl From<(Groth16VerifierKey, KZG10VerifierKey, usize)> for HyperNovaCycleFoldVerifierKey {
+    fn from(value: (Groth16VerifierKey, KZG10VerifierKey, usize)) -> Self {
+        Self {
+            g16_vk: value.0,
+            kzg_vk: value.1,
+            z_len: value.2,
+        }
+    }
+}
+
+// implements From assuming that the 'batchCheck' method from the KZG10 template will not be used
+// in the HyperNovaCycleFoldDecider verifier contract
+impl From<(VerifyingKey<Bn254>, VerifierKey<Bn254>, usize)> for HyperNovaCycleFoldVerifierKey {
+    fn from(value: (VerifyingKey<Bn254>, VerifierKey<Bn254>, usize)) -> Self {
+        let g16_vk = Groth16VerifierKey::from(value.0);
+        // pass `Vec::new()` since batchCheck will not be used
+        let kzg_vk = KZG10VerifierKey::from((value.1, Vec::new()));
+        Self {
+            g16_vk,
+            kzg_vk,
+            z_len: value.2,
+        }
+    }
+}
+
+impl HyperNovaCycleFoldVerifierKey {
+    pub fn new(
+        vkey_g16: VerifyingKey<Bn254>,
+        vkey_kzg: VerifierKey<Bn254>,
+        crs_points: Vec<G1Affine>,
+        z_len: usize,
+    ) -> Self {
+        Self {
+            g16_vk: Groth16VerifierKey::from(vkey_g16),
+            kzg_vk: KZG10VerifierKey::from((vkey_kzg, crs_points)),
+            z_len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as G1};
+    use ark_crypto_primitives::snark::SNARK;
+    use ark_groth16::VerifyingKey as G16VerifierKey;
+    use ark_groth16::{Groth16, ProvingKey};
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as G2};
+    use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
+    use ark_std::Zero;
+    use askama::Template;
+    use std::time::Instant;
+
+    use folding_schemes::{
+        commitment::{
+            kzg::{ProverKey as KZGProverKey, KZG},
+            pedersen::Pedersen,
+            CommitmentScheme,
+        },
+        folding::hypernova::{
+            decider_eth::{prepare_calldata, Decider as DeciderEth},
+            decider_eth_circuit::DeciderEthCircuit,
+            get_cs_params_len, HyperNova, ProverParams,
+        },
+        frontend::FCircuit,
+        transcript::poseidon::poseidon_canonical_config,
+        Decider, FoldingScheme,
+    };
+
+    use super::HyperNovaCycleFoldDecider;
+    use crate::verifiers::nova_cyclefold::tests::{CubicFCircuit, MultiInputsFCircuit};
+    use crate::verifiers::tests::{setup, DEFAULT_SETUP_LEN};
+    use crate::{
+        evm::{compile_solidity, save_solidity, Evm},
+        utils::{get_function_selector_for_hypernova_cyclefold_verifier, HeaderInclusion},
+        verifiers::hypernova_cyclefold::get_decider_template_for_hypernova_cyclefold_decider,
+        HyperNovaCycleFoldVerifierKey, ProtocolVerifierKey,
+    };
+
+    #[test]
+    fn hypernova_cyclefold_vk_serde_roundtrip() {
+        let (_, kzg_vk, _, g16_vk, _) = setup(DEFAULT_SETUP_LEN);
+
+        let mut bytes = vec![];
+        let hypernova_cyclefold_vk = HyperNovaCycleFoldVerifierKey::from((g16_vk, kzg_vk, 1));
+
+        hypernova_cyclefold_vk
+            .serialize_protocol_verifier_key(&mut bytes)
+            .unwrap();
+        let obtained_hypernova_cyclefold_vk =
+            HyperNovaCycleFoldVerifierKey::deserialize_protocol_verifier_key(bytes.as_slice())
+                .unwrap();
+
+        assert_eq!(hypernova_cyclefold_vk, obtained_hypernova_cyclefold_vk)
+    }
+
+    #[test]
+    fn hypernova_cyclefold_decider_template_renders() {
+        let (_, kzg_vk, _, g16_vk, _) = setup(DEFAULT_SETUP_LEN);
+        let hypernova_cyclefold_vk = HyperNovaCycleFoldVerifierKey::from((g16_vk, kzg_vk, 1));
+
+        let decider_solidity_code = HeaderInclusion::<HyperNovaCycleFoldDecider>::builder()
+            .template(hypernova_cyclefold_vk)
+            .build();
+
+        save_solidity(
+            "HyperNovaDecider.sol",
+            &decider_solidity_code.render().unwrap(),
+        );
+    }
+
+    /// Initializes HyperNova parameters and DeciderEth parameters. Only for test purposes.
+    #[allow(clippy::type_complexity)]
+    fn init_params<FC: FCircuit<Fr, Params = ()>>() -> (
+        ProverParams<G1, G2, KZG<'static, Bn254>, Pedersen<G2>>,
+        KZGVerifierKey<Bn254>,
+        ProvingKey<Bn254>,
+        G16VerifierKey<Bn254>,
+    ) {
+        let mut rng = rand::rngs::OsRng;
+        let start = Instant::now();
+        let poseidon_config = poseidon_canonical_config::<Fr>();
+        let f_circuit = FC::new(()).unwrap();
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<G1, GVar, G2, GVar2, FC>(&poseidon_config, f_circuit, 1, 1)
+                .unwrap();
+        let (kzg_pk, kzg_vk): (KZGProverKey<G1>, KZGVerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<G2>::setup(&mut rng, cf_cs_len).unwrap();
+        let fs_prover_params = ProverParams::<G1, G2, KZG<Bn254>, Pedersen<G2>> {
+            poseidon_config: poseidon_config.clone(),
+            cs_params: kzg_pk.clone(),
+            cf_cs_params: cf_pedersen_params,
+            ccs: None,
+            mu: 1,
+            nu: 1,
+        };
+        println!("generated HyperNova folding params: {:?}", start.elapsed());
+
+        pub type HN_FCircuit<FC> =
+            HyperNova<G1, GVar, G2, GVar2, FC, KZG<'static, Bn254>, Pedersen<G2>>;
+        let z_0 = vec![Fr::zero(); f_circuit.state_len()];
+        let hypernova = HN_FCircuit::init(&fs_prover_params, f_circuit, z_0.clone()).unwrap();
+
+        let decider_circuit =
+            DeciderEthCircuit::<G1, GVar, G2, GVar2, KZG<Bn254>, Pedersen<G2>>::from_hypernova::<
+                FC,
+            >(hypernova.clone())
+            .unwrap();
+        let start = Instant::now();
+        let (g16_pk, g16_vk) =
+            Groth16::<Bn254>::circuit_specific_setup(decider_circuit.clone(), &mut rng).unwrap();
+        println!(
+            "generated G16 (Decider circuit) params: {:?}",
+            start.elapsed()
+        );
+        (fs_prover_params, kzg_vk, g16_pk, g16_vk)
+    }
+
+    /// This function allows to define which FCircuit to use for the test, and how many prove_step
+    /// rounds to perform.
+    /// Actions performed by this test:
+    /// - runs the HyperNovaCycleFold folding scheme for the given FCircuit and n_steps times
+    /// - generates a DeciderEth proof, and executes it through the EVM
+    /// - modifies the calldata and checks that it does not pass the EVM check
+    #[allow(clippy::type_complexity)]
+    fn hypernova_cyclefold_solidity_verifier_opt<FC: FCircuit<Fr, Params = ()>>(
+        params: (
+            ProverParams<G1, G2, KZG<'static, Bn254>, Pedersen<G2>>,
+            KZGVerifierKey<Bn254>,
+            ProvingKey<Bn254>,
+            G16VerifierKey<Bn254>,
+        ),
+        z_0: Vec<Fr>,
+        n_steps: usize,
+    ) {
+        let (fs_prover_params, kzg_vk, g16_pk, g16_vk) = params.clone();
+
+        pub type HN_FCircuit<FC> =
+            HyperNova<G1, GVar, G2, GVar2, FC, KZG<'static, Bn254>, Pedersen<G2>>;
+        pub type DECIDERETH_FCircuit<FC> = DeciderEth<
+            G1,
+            GVar,
+            G2,
+            GVar2,
+            FC,
+            KZG<'static, Bn254>,
+            Pedersen<G2>,
+            Groth16<Bn254>,
+            HN_FCircuit<FC>,
+        >;
+        let f_circuit = FC::new(()).unwrap();
+
+        let hypernova_cyclefold_vk = HyperNovaCycleFoldVerifierKey::from((
+            g16_vk.clone(),
+            kzg_vk.clone(),
+            f_circuit.state_len(),
+        ));
+
+        let mut rng = rand::rngs::OsRng;
+        let mut hypernova = HN_FCircuit::init(&fs_prover_params, f_circuit, z_0).unwrap();
+        for _ in 0..n_steps {
+            hypernova.prove_step(&mut rng, vec![]).unwrap();
+        }
+
+        let start = Instant::now();
+        let proof = DECIDERETH_FCircuit::prove(
+            (g16_pk, fs_prover_params.cs_params.clone()),
+            &mut rng,
+            hypernova.clone(),
+        )
+        .unwrap();
+        println!("generated Decider proof: {:?}", start.elapsed());
+
+        let verified = DECIDERETH_FCircuit::<FC>::verify(
+            (g16_vk, kzg_vk),
+            hypernova.i,
+            hypernova.z_0.clone(),
+            hypernova.z_i.clone(),
+            &hypernova.U_i.C,
+            &hypernova.u_i.C,
+            &proof,
+        )
+        .unwrap();
+        assert!(verified);
+
+        let function_selector =
+            get_function_selector_for_hypernova_cyclefold_verifier(hypernova.z_0.len() * 2 + 1);
+
+        let calldata: Vec<u8> = prepare_calldata(
+            function_selector,
+            hypernova.i,
+            hypernova.z_0,
+            hypernova.z_i,
+            &hypernova.U_i.C,
+            &hypernova.u_i.C,
+            proof,
+        )
+        .unwrap();
+
+        let decider_solidity_code =
+            get_decider_template_for_hypernova_cyclefold_decider(hypernova_cyclefold_vk);
+
+        let hypernova_cyclefold_verifier_bytecode =
+            compile_solidity(decider_solidity_code, "HyperNovaDecider");
+
+        let mut evm = Evm::default();
+        let verifier_address = evm.create(hypernova_cyclefold_verifier_bytecode);
+
+        let (_, output) = evm.call(verifier_address, calldata.clone());
+        assert_eq!(*output.last().unwrap(), 1);
+
+        // change i to make calldata invalid, placed between bytes 4 - 35
+        let mut invalid_calldata = calldata.clone();
+        invalid_calldata[35] += 1;
+        let (_, output) = evm.call(verifier_address, invalid_calldata.clone());
+        assert_eq!(*output.last().unwrap(), 0);
+
+        // change z_0 to make the EVM check fail, placed between bytes 35 - 67
+        let mut invalid_calldata = calldata.clone();
+        invalid_calldata[67] += 1;
+        let (_, output) = evm.call(verifier_address, invalid_calldata.clone());
+        assert_eq!(*output.last().unwrap(), 0);
+
+        // change z_i to make the EVM check fail, placed between bytes 68 - 100
+        let mut invalid_calldata = calldata.clone();
+        invalid_calldata[99] += 1;
+        let (_, output) = evm.call(verifier_address, invalid_calldata.clone());
+        assert_eq!(*output.last().unwrap(), 0);
+    }
+
+    #[test]
+    fn hypernova_cyclefold_solidity_verifier() {
+        let params = init_params::<CubicFCircuit<Fr>>();
+        let z_0 = vec![Fr::from(3_u32)];
+        hypernova_cyclefold_solidity_verifier_opt::<CubicFCircuit<Fr>>(
+            params.clone(),
+            z_0.clone(),
+            2,
+        );
+        hypernova_cyclefold_solidity_verifier_opt::<CubicFCircuit<Fr>>(
+            params.clone(),
+            z_0.clone(),
+            3,
+        );
+
+        let params = init_params::<MultiInputsFCircuit<Fr>>();
+        let z_0 = vec![
+            Fr::from(1_u32),
+            Fr::from(1_u32),
+            Fr::from(1_u32),
+            Fr::from(1_u32),
+            Fr::from(1_u32),
+        ];
+        hypernova_cyclefold_solidity_verifier_opt::<MultiInputsFCircuit<Fr>>(
+            params.clone(),
+            z_0.clone(),
+            2,
+        );
+    }
+}
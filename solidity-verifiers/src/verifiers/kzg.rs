@@ -85,11 +85,13 @@ mod tests {
     use ark_std::Zero;
     use ark_std::{test_rng, UniformRand};
     use askama::Template;
+    use crypto::{digest::Digest, sha3::Sha3};
     use itertools::chain;
 
     use folding_schemes::{
-        commitment::{kzg::KZG, CommitmentScheme},
+        commitment::{kzg::KZG, BatchCommitmentScheme, CommitmentScheme},
         transcript::{
+            keccak::KeccakTranscript,
             poseidon::{poseidon_canonical_config, PoseidonTranscript},
             Transcript,
         },
@@ -183,4 +185,87 @@ mod tests {
         let (_, output) = evm.call(verifier_address, calldata);
         assert_eq!(*output.last().unwrap(), 0);
     }
+
+    #[test]
+    fn kzg_verifier_accepts_and_rejects_batch_proofs() {
+        let mut rng = ark_std::rand::rngs::StdRng::seed_from_u64(test_rng().next_u64());
+        let (kzg_pk, kzg_vk, _, _, _) = setup(DEFAULT_SETUP_LEN);
+        let kzg_vk = KZG10VerifierKey::from((kzg_vk.clone(), kzg_pk.powers_of_g[0..3].to_vec()));
+
+        let vs: Vec<Vec<Fr>> = (0..2)
+            .map(|_| {
+                std::iter::repeat_with(|| Fr::rand(&mut rng))
+                    .take(DEFAULT_SETUP_LEN)
+                    .collect()
+            })
+            .collect();
+        let cms = vs
+            .iter()
+            .map(|v| KZG::<Bn254>::commit(&kzg_pk, v, &Fr::zero()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let points = vec![Fr::rand(&mut rng), Fr::rand(&mut rng)];
+        let proof = KZG::<Bn254>::prove_batch(
+            &kzg_pk,
+            &mut KeccakTranscript::<G1>::new(&()),
+            &cms,
+            &vs,
+            &points,
+        )
+        .unwrap();
+
+        let template = HeaderInclusion::<KZG10Verifier>::builder()
+            .template(kzg_vk)
+            .build()
+            .render()
+            .unwrap();
+        let kzg_verifier_bytecode = compile_solidity(template, "KZG10");
+        let mut evm = Evm::default();
+        let verifier_address = evm.create(kzg_verifier_bytecode);
+
+        let mut hasher = Sha3::keccak256();
+        hasher
+            .input_str("batchOpeningCheck(uint256[2][],uint256[],uint256[],uint256[2],uint256[2])");
+        let hash = &mut [0u8; 32];
+        hasher.result(hash);
+
+        // abi encoding: the three dynamic arrays are referenced by their offsets in the head,
+        // which is made of 3 offsets and 2 static points
+        let word = |n: usize| Fr::from(n as u64).into_bigint().to_bytes_be();
+        let point = |p: G1| {
+            let (x, y) = p.into_affine().xy().unwrap();
+            chain![x.into_bigint().to_bytes_be(), y.into_bigint().to_bytes_be()]
+        };
+        let n = cms.len();
+        let offset_c = 7 * 32;
+        let offset_x = offset_c + (1 + 2 * n) * 32;
+        let offset_y = offset_x + (1 + n) * 32;
+        let mut calldata: Vec<u8> = chain![
+            hash[0..4].to_vec(),
+            word(offset_c),
+            word(offset_x),
+            word(offset_y),
+            point(proof.W),
+            point(proof.W_prime),
+            word(n),
+            cms.iter().flat_map(|cm| point(*cm)),
+            word(n),
+            points.iter().flat_map(|x| x.into_bigint().to_bytes_be()),
+            word(n),
+            proof
+                .evals
+                .iter()
+                .flat_map(|y| y.into_bigint().to_bytes_be()),
+        ]
+        .collect();
+
+        let (_, output) = evm.call(verifier_address, calldata.clone());
+        assert_eq!(*output.last().unwrap(), 1);
+
+        // change calldata to make it invalid
+        let last_calldata_element = calldata.last_mut().unwrap();
+        *last_calldata_element = 0;
+        let (_, output) = evm.call(verifier_address, calldata);
+        assert_eq!(*output.last().unwrap(), 0);
+    }
 }
@@ -11,12 +11,20 @@ pub const MIT_SDPX_IDENTIFIER: &str = "// SPDX-License-Identifier: MIT";
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
 
 pub mod g16;
+pub mod hypernova_cyclefold;
 pub mod kzg;
 pub mod nova_cyclefold;
+pub mod protogalaxy_cyclefold;
 
 pub use g16::Groth16VerifierKey;
+pub use hypernova_cyclefold::{
+    get_decider_template_for_hypernova_cyclefold_decider, HyperNovaCycleFoldVerifierKey,
+};
 pub use kzg::KZG10VerifierKey;
 pub use nova_cyclefold::{get_decider_template_for_cyclefold_decider, NovaCycleFoldVerifierKey};
+pub use protogalaxy_cyclefold::{
+    get_decider_template_for_protogalaxy_cyclefold_decider, ProtoGalaxyCycleFoldVerifierKey,
+};
 
 pub trait ProtocolVerifierKey: CanonicalDeserialize + CanonicalSerialize {
     const PROTOCOL_NAME: &'static str;
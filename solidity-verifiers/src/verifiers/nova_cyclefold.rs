@@ -111,10 +111,21 @@ impl NovaCycleFoldVerifierKey {
             z_len,
         }
     }
+
+    /// Returns the Groth16 and KZG verifier keys, which are the verifier params of the native
+    /// (non-EVM) Decider.
+    pub fn decider_vp(&self) -> (VerifyingKey<Bn254>, VerifierKey<Bn254>) {
+        (self.g16_vk.0.clone(), self.kzg_vk.vk.clone())
+    }
+
+    /// Returns the length of the FCircuit state (z_i).
+    pub fn z_len(&self) -> usize {
+        self.z_len
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as G1};
     use ark_crypto_primitives::snark::SNARK;
     use ark_ff::PrimeField;
@@ -374,16 +385,16 @@ mod tests {
         let nova_cyclefold_vk =
             NovaCycleFoldVerifierKey::from((g16_vk.clone(), kzg_vk.clone(), f_circuit.state_len()));
 
+        let mut rng = rand::rngs::OsRng;
         let mut nova = NOVA_FCircuit::init(&fs_prover_params, f_circuit, z_0).unwrap();
         for _ in 0..n_steps {
-            nova.prove_step(vec![]).unwrap();
+            nova.prove_step(&mut rng, vec![]).unwrap();
         }
 
-        let rng = rand::rngs::OsRng;
         let start = Instant::now();
         let proof = DECIDERETH_FCircuit::prove(
             (g16_pk, fs_prover_params.cs_params.clone()),
-            rng,
+            &mut rng,
             nova.clone(),
         )
         .unwrap();
@@ -185,6 +185,64 @@ contract KZG10Verifier {
         return pairing(pi, VK, rhs_pairing, G_2);
     }
 
+    /**
+     * @notice  Verifies the opening of multiple commitments, each one at its own point, with a single proof.
+     * @dev     Follows the version with two group elements of https://eprint.iacr.org/2020/081.pdf (Section 3).
+     *          The challenges gamma and zeta are recomputed following sonobe's `KeccakTranscript`.
+     * @param   c  G_1 point commitments to the polynomials.
+     * @param   x  Values at which each polynomial is evaluated.
+     * @param   y  Evaluations of each polynomial, poly_i(x_i).
+     * @param   w  G_1 point proof W.
+     * @param   w_prime  G_1 point proof W'.
+     * @return  result  Indicates if the KZG batch proof is correct.
+     */
+    function batchOpeningCheck(
+        uint256[2][] memory c,
+        uint256[] memory x,
+        uint256[] memory y,
+        uint256[2] memory w,
+        uint256[2] memory w_prime
+    ) public view returns (bool result) {
+        require(c.length > 0 && c.length == x.length && c.length == y.length, "KZG: wrong batch lengths");
+        uint256 m = BN254_SCALAR_FIELD;
+
+        // gamma = H(c, x, y), zeta = H(gamma's digest, w)
+        bytes memory transcript;
+        for (uint256 i = 0; i < c.length; i++) {
+            transcript = abi.encodePacked(transcript, c[i][0], c[i][1]);
+        }
+        transcript = abi.encodePacked(transcript, x, y);
+        bytes32 digest = keccak256(transcript);
+        uint256 gamma = uint256(digest) % m;
+        uint256 zeta = uint256(keccak256(abi.encodePacked(digest, w[0], w[1]))) % m;
+
+        //
+        // F = sum_i gamma^i * Z_{T \ x_i}(zeta) * (c_i - y_i * g1) - Z_T(zeta) * W, where Z_T(X) = prod_j (X - x_j)
+        //
+        // and F opens to 0 at zeta, which we check as:
+        //
+        //          e(F + zeta * W', g2) = e(W', vk)
+        //          e(F + zeta * W', g2) * e(-W', vk) = 1
+        //
+        uint256 z_t = 1;
+        for (uint256 j = 0; j < x.length; j++) {
+            z_t = mulmod(z_t, addmod(zeta, m - (x[j] % m), m), m);
+        }
+        uint256[2] memory f = mulScalar(negate(w), z_t);
+        uint256 gamma_i = 1;
+        for (uint256 i = 0; i < c.length; i++) {
+            uint256 s = gamma_i;
+            for (uint256 j = 0; j < x.length; j++) {
+                if (j != i) {
+                    s = mulmod(s, addmod(zeta, m - (x[j] % m), m), m);
+                }
+            }
+            f = add(f, mulScalar(add(c[i], negate(mulScalar(G_1, y[i]))), s));
+            gamma_i = mulmod(gamma_i, gamma, m);
+        }
+        return pairing(add(f, mulScalar(w_prime, zeta)), G_2, negate(w_prime), VK);
+    }
+
     function evalPolyAt(uint256[] memory _coefficients, uint256 _index) public pure returns (uint256) {
         uint256 m = BN254_SCALAR_FIELD;
         uint256 result = 0;
@@ -56,7 +56,7 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
     }
 
     /**
-     * @notice  Verifies a nova cyclefold proof consisting of two KZG proofs and of a groth16 proof.
+     * @notice  Verifies a nova cyclefold proof consisting of a KZG batch proof and of a groth16 proof.
      * @dev     The selector of this function is "dynamic", since it depends on `z_len`.
      */
     function verifyNovaProof(
@@ -70,7 +70,7 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
         uint256[2][2] calldata pB, // groth16
         uint256[2] calldata pC, // groth16
         uint256[4] calldata challenge_W_challenge_E_kzg_evals, // [challenge_W, challenge_E, eval_W, eval_E]
-        uint256[2][2] calldata kzg_proof // [proof_W, proof_E]
+        uint256[2][2] calldata kzg_proof // [W, W'] (KZG batch proof)
     ) public view returns (bool) {
 
         require(i_z0_zi[0] >= 2, "Folding: the number of folded steps should be at least 2");
@@ -84,6 +84,9 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
             public_inputs[1 + i] = i_z0_zi[1 + i];
         }
 
+        // folded commitments [cmW, cmE], opened together by the KZG batch proof
+        uint256[2][] memory cms = new uint256[2][](2);
+
         {
             // U_i.u + r * u_i.u
             uint256 u = rlc(U_i_u_u_i_u_r[0], U_i_u_u_i_u_r[2], U_i_u_u_i_u_r[1]);
@@ -111,7 +114,7 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
                 }
             }
 
-            require(this.check(cmE, kzg_proof[1], challenge_W_challenge_E_kzg_evals[1], challenge_W_challenge_E_kzg_evals[3]), "KZG: verifying proof for challenge E failed");
+            cms[1] = cmE;
         }
 
         {
@@ -129,7 +132,18 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
                 }
             }
         
-            require(this.check(cmW, kzg_proof[0], challenge_W_challenge_E_kzg_evals[0], challenge_W_challenge_E_kzg_evals[2]), "KZG: verifying proof for challenge W failed");
+            cms[0] = cmW;
+        }
+
+        {
+            uint256[] memory points = new uint256[](2);
+            uint256[] memory evals = new uint256[](2);
+            points[0] = challenge_W_challenge_E_kzg_evals[0];
+            points[1] = challenge_W_challenge_E_kzg_evals[1];
+            evals[0] = challenge_W_challenge_E_kzg_evals[2];
+            evals[1] = challenge_W_challenge_E_kzg_evals[3];
+
+            require(this.batchOpeningCheck(cms, points, evals, kzg_proof[0], kzg_proof[1]), "KZG: verifying batch proof for challenges W and E failed");
         }
 
         {